@@ -19,11 +19,10 @@
 use cennznet_primitives::{eth::crypto::AuthorityId as EthBridgeId, types::Block};
 use cennznet_runtime::constants::{asset::*, currency::*};
 use cennznet_runtime::{
-	AssetInfo, AuthorityDiscoveryConfig, BabeConfig, CennzxConfig, Erc20PegConfig, FeeRate, GenericAssetConfig,
-	GrandpaConfig, ImOnlineConfig, PerMillion, PerThousand, RewardsConfig, SessionConfig, SessionKeys, StakerStatus,
-	StakingConfig, SudoConfig, SystemConfig, WASM_BINARY,
+	AssetInfo, AuthorityDiscoveryConfig, BabeConfig, CennzxConfig, Erc20PegConfig, GenericAssetConfig,
+	GovernanceConfig, GrandpaConfig, ImOnlineConfig, ParameterKey, ParameterValue, Permill, RewardsConfig,
+	SessionConfig, SessionKeys, StakerStatus, StakingConfig, SudoConfig, SystemConfig, WASM_BINARY,
 };
-use core::convert::TryFrom;
 use crml_support::H160;
 use pallet_im_online::sr25519::AuthorityId as ImOnlineId;
 use sc_chain_spec::ChainSpecExtension;
@@ -423,15 +422,25 @@ pub fn config_genesis(network_keys: NetworkKeys) -> GenesisConfig {
 			],
 		},
 		cennzx: CennzxConfig {
-			// 0.003%
-			fee_rate: FeeRate::<PerMillion>::try_from(FeeRate::<PerThousand>::from(3u128)).unwrap(),
 			core_asset_id: CPAY_ASSET_ID,
 		},
 		rewards: RewardsConfig {
 			// 20% of all fees
 			development_fund_take: Perbill::from_percent(20),
-			// 80% APY
-			inflation_rate: FixedU128::saturating_from_rational(8, 10),
+		},
+		governance: GovernanceConfig {
+			parameters: vec![
+				// 0.3%
+				(
+					ParameterKey::CennzxDefaultFeeRate,
+					ParameterValue::Permill(Permill::from_parts(3_000)),
+				),
+				// 80% APY
+				(
+					ParameterKey::StakingInflationRate,
+					ParameterValue::FixedU128(FixedU128::saturating_from_rational(8, 10)),
+				),
+			],
 		},
 		erc_20_peg: Erc20PegConfig { erc20s },
 	}
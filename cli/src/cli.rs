@@ -8,11 +8,57 @@ fn parse_uri(uri: &str) -> Result<String> {
 	Ok(uri.into())
 }
 
+/// Parse `<chain id>=<value>`
+fn parse_chain_value(s: &str) -> Result<(u64, String)> {
+	let (chain_id, value) = s
+		.split_once('=')
+		.ok_or_else(|| Error::Input("expected <chain id>=<value>".into()))?;
+	let chain_id = chain_id
+		.parse::<u64>()
+		.map_err(|_| Error::Input("invalid EVM chain id".into()))?;
+	Ok((chain_id, value.into()))
+}
+
 #[derive(Debug, StructOpt)]
 pub struct EthClientOpts {
-	/// Ethereum JSON-RPC client endpoint
-	#[structopt(parse(try_from_str = parse_uri), long = "eth-http", about = "Ethereum client JSON-RPC endpoint")]
-	pub eth_http: Option<String>,
+	/// Ethereum JSON-RPC client endpoints, queried in order with failover
+	#[structopt(
+		parse(try_from_str = parse_uri),
+		long = "eth-http",
+		use_delimiter = true,
+		about = "Ethereum client JSON-RPC endpoints (comma separated)"
+	)]
+	pub eth_http: Vec<String>,
+	/// Number of Ethereum JSON-RPC endpoints that must agree on a response
+	#[structopt(
+		long = "eth-http-quorum",
+		default_value = "1",
+		about = "Number of Ethereum client JSON-RPC endpoints that must agree on a response"
+	)]
+	pub eth_http_quorum: u32,
+	/// JSON-RPC client endpoints of other EVM chains e.g. `137=http://localhost:8545,http://polygon.example.com`
+	#[structopt(
+		parse(try_from_str = parse_chain_value),
+		long = "evm-http",
+		number_of_values = 1,
+		about = "EVM client JSON-RPC endpoints of a chain (<chain id>=<comma separated URIs>), may be repeated"
+	)]
+	pub evm_http: Vec<(u64, String)>,
+	/// Number of JSON-RPC endpoints of other EVM chains that must agree on a response e.g. `137=2`
+	#[structopt(
+		parse(try_from_str = parse_chain_value),
+		long = "evm-http-quorum",
+		number_of_values = 1,
+		about = "Number of EVM client JSON-RPC endpoints of a chain that must agree on a response (<chain id>=<n>)"
+	)]
+	pub evm_http_quorum: Vec<(u64, String)>,
+	/// Port of the mock Ethereum JSON-RPC server started for `--dev` chains without `--eth-http`
+	#[structopt(
+		long = "eth-mock-port",
+		default_value = "8645",
+		about = "Port of the mock Ethereum JSON-RPC server started on --dev chains when no --eth-http is given"
+	)]
+	pub eth_mock_port: u16,
 }
 
 #[derive(Debug, StructOpt)]
@@ -111,18 +111,22 @@ where
 	C::Api: BabeApi<Block>,
 	C::Api: BlockBuilder<Block>,
 	C::Api: crml_cennzx_rpc::CennzxRuntimeApi<Block, AssetId, Balance, AccountId>,
+	C::Api: crml_erc20_peg_rpc::Erc20PegRuntimeApi<Block>,
+	C::Api: crml_eth_bridge_rpc::EthBridgeRuntimeApi<Block, BlockNumber, AccountId>,
 	C::Api: crml_eth_wallet_rpc::EthWalletRuntimeApi<Block>,
 	C::Api: crml_nft_rpc::NftRuntimeApi<Block, AccountId, Runtime>,
 	C::Api: crml_staking_rpc::StakingRuntimeApi<Block, AccountId>,
 	C::Api: crml_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
 	C::Api: crml_generic_asset_rpc::GenericAssetRuntimeApi<Block, AssetId, Balance, AccountId>,
-	C::Api: crml_governance_rpc::GovernanceRuntimeApi<Block, AccountId>,
+	C::Api: crml_governance_rpc::GovernanceRuntimeApi<Block, AccountId, BlockNumber, Hash>,
 	P: TransactionPool + 'static,
 	SC: SelectChain<Block> + 'static,
 	B: sc_client_api::Backend<Block> + Send + Sync + 'static,
 	B::State: sc_client_api::backend::StateBackend<sp_runtime::traits::HashFor<Block>>,
 {
 	use crml_cennzx_rpc::{Cennzx, CennzxApi};
+	use crml_erc20_peg_rpc::{Erc20Peg, Erc20PegApi};
+	use crml_eth_bridge_rpc::{EthBridge, EthBridgeApi};
 	use crml_eth_wallet_rpc::{EthWallet, EthWalletApi};
 	use crml_generic_asset_rpc::{GenericAsset, GenericAssetApi};
 	use crml_governance_rpc::{Governance, GovernanceApi};
@@ -198,6 +202,8 @@ where
 	io.extend_with(StakingApi::to_delegate(Staking::new(client.clone())));
 	io.extend_with(GenericAssetApi::to_delegate(GenericAsset::new(client.clone())));
 	io.extend_with(GovernanceApi::to_delegate(Governance::new(client.clone())));
+	io.extend_with(Erc20PegApi::to_delegate(Erc20Peg::new(client.clone())));
+	io.extend_with(EthBridgeApi::to_delegate(EthBridge::new(client.clone())));
 	io.extend_with(EthWalletApi::to_delegate(EthWallet::new(client)));
 
 	Ok(io)
@@ -20,6 +20,7 @@
 
 //! Service implementation. Specialized wrapper over substrate service.
 
+use crml_eth_bridge_mock_rpc::MockEthereum;
 use ethy_gadget::notification::EthyEventProofSender;
 use futures::prelude::*;
 use sc_client_api::{Backend, ExecutorProvider};
@@ -33,9 +34,13 @@ use sp_core::offchain::OffchainStorage;
 use sp_runtime::traits::Block as BlockT;
 use std::sync::Arc;
 
+use crate::cli::EthClientOpts;
 use crate::rpc as node_rpc;
 use cennznet_primitives::types::Block;
-use cennznet_runtime::{constants::config::ETH_HTTP_URI, RuntimeApi};
+use cennznet_runtime::{
+	constants::config::{ETH_HTTP_QUORUM, ETH_HTTP_URI},
+	RuntimeApi,
+};
 
 // Declare an instance of the native executor named `ExecutorDispatch`. Include the wasm binary as
 // the equivalent wasm code.
@@ -244,6 +249,16 @@ pub fn new_partial(
 	})
 }
 
+/// Genesis timestamp of the `--dev` mock Ethereum chain
+/// lags the wall clock by an hour so blocks can be fast-forwarded with `mock_mineBlocks`
+/// without being timestamped in the future
+fn mock_genesis_timestamp() -> u64 {
+	std::time::SystemTime::now()
+		.duration_since(std::time::UNIX_EPOCH)
+		.map(|now| now.as_secs().saturating_sub(3_600))
+		.unwrap_or_default()
+}
+
 /// Result of [`new_full_base`].
 pub struct NewFullBase {
 	/// The task manager of the node.
@@ -259,7 +274,7 @@ pub struct NewFullBase {
 /// Creates a full service from the configuration.
 pub fn new_full_base(
 	mut config: Configuration,
-	eth_http_config: Option<String>,
+	eth_opts: EthClientOpts,
 	with_startup_data: impl FnOnce(
 		&sc_consensus_babe::BabeBlockImport<Block, FullClient, FullGrandpaBlockImport>,
 		&sc_consensus_babe::BabeLink<Block>,
@@ -276,14 +291,73 @@ pub fn new_full_base(
 		other: (rpc_extensions_builder, import_setup, rpc_setup, event_proof_sender, mut telemetry),
 	} = new_partial(&config)?;
 
+	// `--dev` chains bridge a mock Ethereum chain unless a real client is given
+	let mut eth_http = eth_opts.eth_http.clone();
+	if eth_http.is_empty() && config.chain_spec.id() == "dev" {
+		let mock_addr = std::net::SocketAddr::from(([127, 0, 0, 1], eth_opts.eth_mock_port));
+		let mock_ethereum = MockEthereum::new(1, mock_genesis_timestamp());
+		let mock_server = mock_ethereum
+			.start_server(&mock_addr)
+			.map_err(|err| ServiceError::Other(format!("failed to start mock Ethereum JSON-RPC server: {}", err)))?;
+		mock_ethereum.auto_mine();
+		task_manager.keep_alive(mock_server);
+		log::info!("💎 Serving mock Ethereum JSON-RPC at http://{}", mock_addr);
+		eth_http.push(format!("http://{}", mock_addr));
+	}
+
 	// Set eth http bridge config
 	// the config is stored into the offchain context where it can
 	// be accessed later by the crml-eth-bridge offchain worker.
-	if let Some(eth_http_uri) = eth_http_config {
-		backend.offchain_storage().unwrap().set(
+	if !eth_http.is_empty() {
+		if eth_opts.eth_http_quorum == 0 || eth_opts.eth_http_quorum as usize > eth_http.len() {
+			return Err(ServiceError::Other(format!(
+				"--eth-http-quorum must be between 1 and the number of --eth-http URIs ({})",
+				eth_http.len()
+			)));
+		}
+		let mut offchain_storage = backend.offchain_storage().unwrap();
+		offchain_storage.set(
 			sp_core::offchain::STORAGE_PREFIX,
 			&ETH_HTTP_URI,
-			eth_http_uri.as_bytes(),
+			eth_http.join(",").as_bytes(),
+		);
+		offchain_storage.set(
+			sp_core::offchain::STORAGE_PREFIX,
+			&ETH_HTTP_QUORUM,
+			eth_opts.eth_http_quorum.to_string().as_bytes(),
+		);
+	}
+	// endpoints of other EVM chains are stored under `<key>:<chain id>`
+	for (chain_id, uris) in eth_opts.evm_http.iter() {
+		let endpoints: Vec<&str> = uris.split(',').filter(|uri| !uri.is_empty()).collect();
+		for uri in endpoints.iter() {
+			let _ = url::Url::parse(uri)
+				.map_err(|_| ServiceError::Other(format!("invalid --evm-http URI for chain {}: {}", chain_id, uri)))?;
+		}
+		let quorum = match eth_opts.evm_http_quorum.iter().find(|(id, _)| id == chain_id) {
+			Some((_, quorum)) => quorum
+				.parse::<usize>()
+				.map_err(|_| ServiceError::Other(format!("invalid --evm-http-quorum for chain {}", chain_id)))?,
+			None => 1,
+		};
+		if quorum == 0 || quorum > endpoints.len() {
+			return Err(ServiceError::Other(format!(
+				"--evm-http-quorum for chain {} must be between 1 and the number of --evm-http URIs ({})",
+				chain_id,
+				endpoints.len()
+			)));
+		}
+		let chain_key = |key: &[u8]| [key, b":", chain_id.to_string().as_bytes()].concat();
+		let mut offchain_storage = backend.offchain_storage().unwrap();
+		offchain_storage.set(
+			sp_core::offchain::STORAGE_PREFIX,
+			&chain_key(&ETH_HTTP_URI),
+			endpoints.join(",").as_bytes(),
+		);
+		offchain_storage.set(
+			sp_core::offchain::STORAGE_PREFIX,
+			&chain_key(&ETH_HTTP_QUORUM),
+			quorum.to_string().as_bytes(),
 		);
 	}
 
@@ -494,6 +568,6 @@ pub fn new_full_base(
 }
 
 /// Builds a new service for a full client.
-pub fn new_full(config: Configuration, eth_http_config: Option<String>) -> Result<TaskManager, ServiceError> {
-	new_full_base(config, eth_http_config, |_, _| ()).map(|NewFullBase { task_manager, .. }| task_manager)
+pub fn new_full(config: Configuration, eth_opts: EthClientOpts) -> Result<TaskManager, ServiceError> {
+	new_full_base(config, eth_opts, |_, _| ()).map(|NewFullBase { task_manager, .. }| task_manager)
 }
@@ -87,6 +87,8 @@ pub trait Config: frame_system::Config {
 	type MultiCurrency: MultiCurrency<AccountId = Self::AccountId, CurrencyId = Self::AssetId, Balance = Self::Balance>;
 	/// Something which can generate addresses for exchange pools
 	type ExchangeAddressFor: ExchangeAddressFor<AccountId = Self::AccountId, AssetId = Self::AssetId>;
+	/// Default trading fee rate
+	type DefaultFeeRate: Get<FeeRate<PerMillion>>;
 	/// Provides the public call to weight mapping
 	type WeightInfo: WeightInfo;
 }
@@ -288,14 +290,6 @@ decl_module! {
 			Self::deposit_event(Event::<T>::RemoveLiquidity(from_account, withdraw_value.core, asset_id, withdraw_value.asset));
 			Ok(())
 		}
-
-		/// Set the spot exchange wide fee rate (root only)
-		#[weight = T::WeightInfo::set_fee_rate()]
-		pub fn set_fee_rate(origin, new_fee_rate: FeeRate<PerMillion>) -> DispatchResult {
-			ensure_root(origin)?;
-			DefaultFeeRate::mutate(|fee_rate| *fee_rate = new_fee_rate);
-			Ok(())
-		}
 	}
 }
 
@@ -321,8 +315,6 @@ decl_storage! {
 	{
 		/// Asset Id of the core liquidity asset
 		pub CoreAssetId get(fn core_asset_id) config(): T::AssetId;
-		/// Default trading fee rate
-		pub DefaultFeeRate get(fn fee_rate) config(): FeeRate<PerMillion>;
 		/// Total liquidity holdings of all investors in an exchange.
 		/// ie/ total_liquidity(exchange) == sum(liquidity_balance(exchange, user)) at all times
 		pub TotalLiquidity get(fn total_liquidity): map hasher(twox_64_concat) ExchangeKey<T> => T::Balance;
@@ -334,6 +326,11 @@ decl_storage! {
 
 // The main implementation block for the module.
 impl<T: Config> Module<T> {
+	/// Default trading fee rate
+	pub fn fee_rate() -> FeeRate<PerMillion> {
+		T::DefaultFeeRate::get()
+	}
+
 	//
 	// Liquidity
 	//
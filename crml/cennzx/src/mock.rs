@@ -87,6 +87,7 @@ impl frame_system::Config for Test {
 
 parameter_types! {
 		pub const TreasuryPalletId: PalletId = PalletId(*b"py/trsry");
+		pub static DefaultFeeRate: FeeRate<PerMillion> = FeeRate::<PerMillion>::default();
 }
 impl crml_generic_asset::Config for Test {
 	type AssetId = AssetId;
@@ -101,6 +102,7 @@ impl Config for Test {
 	type Event = Event;
 	type AssetId = AssetId;
 	type ExchangeAddressFor = ExchangeAddressGenerator<Self>;
+	type DefaultFeeRate = DefaultFeeRate;
 	type MultiCurrency = GenericAsset;
 	type WeightInfo = ();
 }
@@ -136,10 +138,10 @@ impl ExtBuilder {
 		.unwrap();
 		crml_cennzx::GenesisConfig::<Test> {
 			core_asset_id: self.core_asset_id,
-			fee_rate: self.fee_rate,
 		}
 		.assimilate_storage(&mut t)
 		.unwrap();
+		DefaultFeeRate::set(&self.fee_rate);
 		let mut ext = sp_io::TestExternalities::new(t);
 
 		// Run in the context of the first block
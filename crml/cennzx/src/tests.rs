@@ -20,13 +20,12 @@
 use crate::{
 	assert_balance_eq, assert_exchange_balance_eq,
 	mock::{
-		last_event, AccountId, Cennzx, Event, ExtBuilder, Origin, Test, CORE_ASSET_ID, TRADE_ASSET_A_ID,
-		TRADE_ASSET_B_ID,
+		last_event, AccountId, Cennzx, DefaultFeeRate, Event, ExtBuilder, Origin, Test, CORE_ASSET_ID,
+		TRADE_ASSET_A_ID, TRADE_ASSET_B_ID,
 	},
-	types::{FeeRate, LowPrecisionUnsigned, PerMillion, PerThousand},
+	types::LowPrecisionUnsigned,
 	with_account, with_exchange, Error, ExchangeAddressFor, RawEvent,
 };
-use core::convert::TryFrom;
 use crml_support::MultiCurrency;
 use frame_support::traits::{LockableCurrency, WithdrawReasons};
 use frame_support::{assert_err, assert_noop, assert_ok};
@@ -1548,21 +1547,12 @@ fn asset_to_asset_transfer_sell() {
 	});
 }
 
-#[test]
-fn set_fee_rate() {
-	ExtBuilder::default().build().execute_with(|| {
-		let new_fee_rate = FeeRate::<PerMillion>::try_from(FeeRate::<PerThousand>::from(5u128)).unwrap();
-		assert_ok!(Cennzx::set_fee_rate(Origin::root(), new_fee_rate), ());
-		assert_eq!(Cennzx::fee_rate(), new_fee_rate);
-	});
-}
-
 #[test]
 fn get_buy_price_simple() {
 	ExtBuilder::default().build().execute_with(|| {
 		with_exchange!(CORE_ASSET_ID => 1000, TRADE_ASSET_A_ID => 1000);
 		with_exchange!(CORE_ASSET_ID => 1000, TRADE_ASSET_B_ID => 1000);
-		let _ = Cennzx::set_fee_rate(Origin::root(), 0.into());
+		DefaultFeeRate::set(&0.into());
 
 		assert_eq!(Cennzx::get_buy_price(TRADE_ASSET_B_ID, 100, TRADE_ASSET_A_ID,), Ok(127));
 	});
@@ -1573,7 +1563,7 @@ fn get_buy_price_with_fee_rate() {
 	ExtBuilder::default().build().execute_with(|| {
 		with_exchange!(CORE_ASSET_ID => 1000, TRADE_ASSET_A_ID => 1000);
 		with_exchange!(CORE_ASSET_ID => 1000, TRADE_ASSET_B_ID => 1000);
-		let _ = Cennzx::set_fee_rate(Origin::root(), 100_000.into());
+		DefaultFeeRate::set(&100_000.into());
 
 		assert_eq!(Cennzx::get_buy_price(TRADE_ASSET_B_ID, 100, TRADE_ASSET_A_ID,), Ok(155));
 	});
@@ -1583,7 +1573,7 @@ fn get_buy_price_with_fee_rate() {
 fn get_buy_price_when_buying_core() {
 	ExtBuilder::default().build().execute_with(|| {
 		with_exchange!(CORE_ASSET_ID => 1000, TRADE_ASSET_A_ID => 1000);
-		let _ = Cennzx::set_fee_rate(Origin::root(), 0.into());
+		DefaultFeeRate::set(&0.into());
 
 		assert_eq!(Cennzx::get_buy_price(CORE_ASSET_ID, 100, TRADE_ASSET_A_ID,), Ok(112));
 	});
@@ -1593,7 +1583,7 @@ fn get_buy_price_when_buying_core() {
 fn get_buy_price_when_selling_core() {
 	ExtBuilder::default().build().execute_with(|| {
 		with_exchange!(CORE_ASSET_ID => 1000, TRADE_ASSET_A_ID => 1000);
-		let _ = Cennzx::set_fee_rate(Origin::root(), 0.into());
+		DefaultFeeRate::set(&0.into());
 
 		assert_eq!(Cennzx::get_buy_price(TRADE_ASSET_A_ID, 100, CORE_ASSET_ID,), Ok(112));
 	});
@@ -1654,7 +1644,7 @@ fn get_sell_price_simple() {
 	ExtBuilder::default().build().execute_with(|| {
 		with_exchange!(CORE_ASSET_ID => 1000, TRADE_ASSET_A_ID => 1000);
 		with_exchange!(CORE_ASSET_ID => 1000, TRADE_ASSET_B_ID => 1000);
-		let _ = Cennzx::set_fee_rate(Origin::root(), 0.into());
+		DefaultFeeRate::set(&0.into());
 
 		assert_eq!(Cennzx::get_sell_price(TRADE_ASSET_B_ID, 100, TRADE_ASSET_A_ID,), Ok(82));
 	});
@@ -1665,7 +1655,7 @@ fn get_sell_price_with_fee_rate() {
 	ExtBuilder::default().build().execute_with(|| {
 		with_exchange!(CORE_ASSET_ID => 1000, TRADE_ASSET_A_ID => 1000);
 		with_exchange!(CORE_ASSET_ID => 1000, TRADE_ASSET_B_ID => 1000);
-		let _ = Cennzx::set_fee_rate(Origin::root(), 100_000.into());
+		DefaultFeeRate::set(&100_000.into());
 
 		assert_eq!(Cennzx::get_sell_price(TRADE_ASSET_B_ID, 100, TRADE_ASSET_A_ID,), Ok(68));
 	});
@@ -1675,7 +1665,7 @@ fn get_sell_price_with_fee_rate() {
 fn get_sell_price_when_selling_core() {
 	ExtBuilder::default().build().execute_with(|| {
 		with_exchange!(CORE_ASSET_ID => 1000, TRADE_ASSET_A_ID => 1000);
-		let _ = Cennzx::set_fee_rate(Origin::root(), 0.into());
+		DefaultFeeRate::set(&0.into());
 
 		assert_eq!(Cennzx::get_sell_price(CORE_ASSET_ID, 100, TRADE_ASSET_A_ID,), Ok(90));
 	});
@@ -1685,7 +1675,7 @@ fn get_sell_price_when_selling_core() {
 fn get_sell_price_when_buying_core() {
 	ExtBuilder::default().build().execute_with(|| {
 		with_exchange!(CORE_ASSET_ID => 1000, TRADE_ASSET_A_ID => 1000);
-		let _ = Cennzx::set_fee_rate(Origin::root(), 0.into());
+		DefaultFeeRate::set(&0.into());
 
 		assert_eq!(Cennzx::get_sell_price(TRADE_ASSET_A_ID, 100, CORE_ASSET_ID,), Ok(90));
 	});
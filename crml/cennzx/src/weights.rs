@@ -25,7 +25,6 @@ pub trait WeightInfo {
 	fn sell_asset() -> Weight;
 	fn add_liquidity() -> Weight;
 	fn remove_liquidity() -> Weight;
-	fn set_fee_rate() -> Weight;
 }
 
 impl WeightInfo for () {
@@ -49,9 +48,4 @@ impl WeightInfo for () {
 			.saturating_add(DbWeight::get().reads(8 as Weight))
 			.saturating_add(DbWeight::get().writes(6 as Weight))
 	}
-	fn set_fee_rate() -> Weight {
-		(14_000_000 as Weight)
-			.saturating_add(DbWeight::get().reads(1 as Weight))
-			.saturating_add(DbWeight::get().writes(1 as Weight))
-	}
 }
@@ -0,0 +1,37 @@
+// Copyright 2019-2021
+//     by  Centrality Investments Ltd.
+//     and Parity Technologies (UK) Ltd.
+// This file is part of Plug-blockchain.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime API definition required by ERC20 peg RPC extensions.
+//!
+//! This API should be imported and implemented by the runtime,
+//! of a node that wants to use the custom RPC extension
+//! adding ERC20 peg access methods.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use cennznet_primitives::types::AssetId;
+use crml_erc20_peg::{PegCapacity, WithdrawalProof};
+
+sp_api::decl_runtime_apis! {
+	pub trait Erc20PegApi {
+		/// Get the remaining deposit & withdrawal capacity of an asset over its current window
+		fn remaining_capacity(asset_id: AssetId) -> PegCapacity;
+		/// Get the inclusion proof of a withdrawal in a proven batch
+		fn withdrawal_proof(batch_id: u64, index: u32) -> Option<WithdrawalProof>;
+	}
+}
@@ -0,0 +1,190 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! RPC interface for the erc20 peg module.
+
+use cennznet_primitives::types::{AssetId, Balance};
+use crml_erc20_peg::{PegCapacity, WithdrawalProof};
+pub use crml_erc20_peg_rpc_runtime_api::Erc20PegApi as Erc20PegRuntimeApi;
+use crml_support::{H160, H256, U256};
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use std::sync::Arc;
+
+/// Remaining capacity of a pegged asset over its current window
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemainingCapacity {
+	/// Amount that may still be deposited, `null` if uncapped
+	#[serde(with = "serde_balance")]
+	pub deposit: Option<Balance>,
+	/// Amount that may still be withdrawn, `null` if uncapped
+	#[serde(with = "serde_balance")]
+	pub withdrawal: Option<Balance>,
+	/// Whether the asset is paused
+	pub paused: bool,
+}
+
+impl From<PegCapacity> for RemainingCapacity {
+	fn from(capacity: PegCapacity) -> Self {
+		Self {
+			deposit: capacity.deposit,
+			withdrawal: capacity.withdrawal,
+			paused: capacity.paused,
+		}
+	}
+}
+
+/// Inclusion proof of a batched withdrawal, submitted with the batch's event proof to redeem ERC20s
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WithdrawalInclusionProof {
+	/// The event proof of the batch root
+	pub event_proof_id: u64,
+	/// The merkle root of the batch
+	pub root: H256,
+	/// The Id of the batch
+	pub batch_id: u64,
+	/// The index of the withdrawal in the batch
+	pub index: u32,
+	/// The ERC20 token address withdrawn
+	pub token_address: H160,
+	/// The amount withdrawn (in 'wei')
+	pub amount: U256,
+	/// The Ethereum beneficiary address
+	pub beneficiary: H160,
+	/// Sibling hashes from the withdrawal's leaf up to the root
+	pub proof: Vec<H256>,
+}
+
+impl From<WithdrawalProof> for WithdrawalInclusionProof {
+	fn from(proof: WithdrawalProof) -> Self {
+		Self {
+			event_proof_id: proof.event_proof_id,
+			root: proof.root,
+			batch_id: proof.batch_id,
+			index: proof.index,
+			token_address: proof.withdrawal.token_address,
+			amount: proof.withdrawal.amount,
+			beneficiary: proof.withdrawal.beneficiary,
+			proof: proof.proof,
+		}
+	}
+}
+
+/// (De)serialize balances as strings, JSON numbers can't represent u128
+mod serde_balance {
+	use super::Balance;
+	use serde::{Deserialize, Deserializer, Serializer};
+
+	pub fn serialize<S: Serializer>(t: &Option<Balance>, serializer: S) -> Result<S::Ok, S::Error> {
+		match t {
+			Some(balance) => serializer.serialize_some(&balance.to_string()),
+			None => serializer.serialize_none(),
+		}
+	}
+
+	pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Balance>, D::Error> {
+		Option::<String>::deserialize(deserializer)?
+			.map(|s| {
+				s.parse::<Balance>()
+					.map_err(|_| serde::de::Error::custom("Parse from string failed"))
+			})
+			.transpose()
+	}
+}
+
+#[rpc]
+pub trait Erc20PegApi<BlockHash> {
+	/// Get the remaining deposit & withdrawal capacity of an asset over its current window
+	#[rpc(name = "erc20Peg_getRemainingCapacity")]
+	fn remaining_capacity(&self, asset_id: AssetId, at: Option<BlockHash>) -> Result<RemainingCapacity>;
+
+	/// Get the merkle inclusion proof of the withdrawal at `index` in a proven withdrawal batch
+	#[rpc(name = "erc20Peg_getWithdrawalProof")]
+	fn withdrawal_proof(
+		&self,
+		batch_id: u64,
+		index: u32,
+		at: Option<BlockHash>,
+	) -> Result<Option<WithdrawalInclusionProof>>;
+}
+
+/// A struct that implements the [`Erc20PegApi`].
+pub struct Erc20Peg<C, P> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<P>,
+}
+
+impl<C, P> Erc20Peg<C, P> {
+	/// Create new `Erc20Peg` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self {
+			client,
+			_marker: Default::default(),
+		}
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl<C, Block> Erc20PegApi<<Block as BlockT>::Hash> for Erc20Peg<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: Erc20PegRuntimeApi<Block>,
+{
+	fn remaining_capacity(&self, asset_id: AssetId, at: Option<<Block as BlockT>::Hash>) -> Result<RemainingCapacity> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		self.client
+			.runtime_api()
+			.remaining_capacity(&at, asset_id)
+			.map(Into::into)
+			.map_err(|e| RpcError {
+				code: ErrorCode::ServerError(Error::RuntimeError as i64),
+				message: "Unable to query remaining capacity.".into(),
+				data: Some(format!("{:?}", e).into()),
+			})
+	}
+
+	fn withdrawal_proof(
+		&self,
+		batch_id: u64,
+		index: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<WithdrawalInclusionProof>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		self.client
+			.runtime_api()
+			.withdrawal_proof(&at, batch_id, index)
+			.map(|proof| proof.map(Into::into))
+			.map_err(|e| RpcError {
+				code: ErrorCode::ServerError(Error::RuntimeError as i64),
+				message: "Unable to query withdrawal proof.".into(),
+				data: Some(format!("{:?}", e).into()),
+			})
+	}
+}
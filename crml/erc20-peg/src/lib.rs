@@ -17,29 +17,56 @@
 
 use cennznet_primitives::types::{AssetId, Balance};
 use codec::Decode;
-use crml_support::{EventClaimSubscriber, EventClaimVerifier, MultiCurrency};
+use crml_support::{EthChainId, EventClaimSubscriber, EventClaimVerifier, MultiCurrency};
 use frame_support::{
 	decl_error, decl_event, decl_module, decl_storage, ensure, log,
-	traits::{ExistenceRequirement, Get, IsType, WithdrawReasons},
-	transactional, PalletId,
+	traits::{ExistenceRequirement, Get, IsType, ValidatorSet, WithdrawReasons},
+	transactional,
+	weights::Weight,
+	PalletId,
 };
 use frame_system::{ensure_root, ensure_signed};
+use sp_io::hashing::keccak_256;
 use sp_runtime::{
-	traits::{AccountIdConversion, Zero},
-	DispatchError,
+	traits::{AccountIdConversion, Saturating, Zero},
+	DispatchError, DispatchResult, Permill,
 };
 use sp_std::prelude::*;
 
+mod merkle;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
 mod types;
 use types::*;
+pub use types::{AssetCap, FeeAsset, PegCapacity, WithdrawMessage, WithdrawalFee, WithdrawalProof};
+
+/// Max. withdrawals collected in a batch
+const MAX_WITHDRAWAL_BATCH_SIZE: usize = 1_000;
+/// Weight of proving a batch, excluding its withdrawals
+const PROVE_WITHDRAWAL_BATCH_WEIGHT: Weight = 50_000_000;
+/// Weight of hashing one withdrawal into a batch's merkle tree
+const WITHDRAWAL_LEAF_WEIGHT: Weight = 1_000_000;
+/// Proven withdrawal batches are retained for this many blocks (~7 days)
+const WITHDRAWAL_BATCH_RETENTION_BLOCKS: u32 = 120_960;
+/// Max. difference between the attested & expected reserves of an asset without a tolerance set
+/// Relative to the expected reserves, covers deposits yet to be claimed on CENNZnet
+const DEFAULT_RESERVES_TOLERANCE: Permill = Permill::from_percent(1);
 
 pub trait Config: frame_system::Config {
 	/// An onchain address for this pallet
 	type PegPalletId: Get<PalletId>;
 	/// The EVM event signature of a deposit
 	type DepositEventSignature: Get<[u8; 32]>;
+	/// The EVM event signature of a reserves attestation
+	type ReservesEventSignature: Get<[u8; 32]>;
+	/// The EVM chain where the peg contract is deployed
+	type ChainId: Get<EthChainId>;
 	/// Submits event claims for Ethereum
-	type EthBridge: EventClaimVerifier;
+	type EthBridge: EventClaimVerifier<AccountId = Self::AccountId>;
+	/// Knows the active authority set (validator stash addresses), who may attest reserves
+	type AuthoritySet: ValidatorSet<Self::AccountId, ValidatorId = Self::AccountId>;
 	/// Currency functions
 	type MultiCurrency: MultiCurrency<AccountId = Self::AccountId, Balance = Balance, CurrencyId = AssetId>;
 	/// The overarching event type.
@@ -62,6 +89,43 @@ decl_storage! {
 		ContractAddress get(fn contract_address): EthAddress;
 		/// Whether CENNZ deposits are active
 		CENNZDepositsActive get(fn cennz_deposit_active): bool;
+		/// Deposit & withdrawal caps of assets
+		AssetCaps get(fn asset_cap): map hasher(twox_64_concat) AssetId => Option<AssetCap<T::BlockNumber>>;
+		/// Amounts bridged of capped assets over their current window
+		AssetCapUsage get(fn asset_cap_usage): map hasher(twox_64_concat) AssetId => CapUsage<T::BlockNumber>;
+		/// Assets paused by reaching a cap, until resumed by governance
+		PausedAssets get(fn asset_paused): map hasher(twox_64_concat) AssetId => bool;
+		/// Deposits of paused assets, held until released (deposit Id => deposit)
+		HeldDeposits get(fn held_deposit): map hasher(twox_64_concat) u64 => Option<Erc20DepositEvent>;
+		/// Fees charged on withdrawals of assets
+		WithdrawalFees get(fn withdrawal_fee): map hasher(twox_64_concat) AssetId => Option<WithdrawalFee<T::AccountId>>;
+		/// Withdrawals are batched over this many blocks, `0` proves each withdrawal immediately
+		WithdrawalBatchPeriod get(fn withdrawal_batch_period): T::BlockNumber;
+		/// Id of the batch collecting withdrawals
+		NextWithdrawalBatchId get(fn next_withdrawal_batch_id): u64;
+		/// Withdrawals collected for the next batch
+		PendingWithdrawals get(fn pending_withdrawals): Vec<WithdrawMessage>;
+		/// Proven withdrawal batches (batch Id => batch), retained for `WITHDRAWAL_BATCH_RETENTION_BLOCKS`
+		WithdrawalBatches get(fn withdrawal_batch): map hasher(twox_64_concat) u64 => Option<WithdrawalBatch>;
+		/// Withdrawal batches to prune at a block (block => batch Id)
+		WithdrawalBatchPruning get(fn withdrawal_batch_pruning): double_map hasher(twox_64_concat) T::BlockNumber, hasher(twox_64_concat) u64 => ();
+		/// Amounts deposited, withdrawn & redeemed per ERC20
+		Erc20Ledgers get(fn erc20_ledger): map hasher(twox_64_concat) EthAddress => Erc20Ledger;
+		/// ERC20s bridged before their ledger was tracked, their next attestation sets the ledger's baseline
+		Erc20LedgerBaselines get(fn erc20_ledger_baseline_pending): map hasher(twox_64_concat) EthAddress => bool;
+		/// The last attested reserves of the peg contract per ERC20
+		Erc20Reserves get(fn erc20_reserves): map hasher(twox_64_concat) EthAddress => Option<ReservesAttestation<T::BlockNumber>>;
+		/// Max. difference between the attested & expected reserves of an asset before it is paused
+		/// `DEFAULT_RESERVES_TOLERANCE` of the expected reserves applies if unset
+		ReservesTolerance get(fn reserves_tolerance): map hasher(twox_64_concat) AssetId => Option<Balance>;
+		/// Reserves of each ERC20 must be attested every this many blocks, `0` disables scheduled attestations
+		ReservesAttestationPeriod get(fn reserves_attestation_period): T::BlockNumber;
+		/// The block the next reserves attestation of an ERC20 is due
+		ReservesAttestationDue get(fn reserves_attestation_due): map hasher(twox_64_concat) EthAddress => Option<T::BlockNumber>;
+		/// ERC20s with a reserves attestation due at a block (block => ERC20)
+		ReservesAttestationDeadlines get(fn reserves_attestation_deadlines): double_map hasher(twox_64_concat) T::BlockNumber, hasher(twox_64_concat) EthAddress => ();
+		/// The current version of the erc20 peg storage, used for migrations
+		StorageVersion build(|_: &GenesisConfig| Releases::V1 as u32): u32;
 	}
 	add_extra_genesis {
 		config(erc20s): Vec<(EthAddress, Vec<u8>, u8)>;
@@ -77,7 +141,7 @@ decl_storage! {
 }
 
 decl_event! {
-	pub enum Event<T> where AccountId = <T as frame_system::Config>::AccountId {
+	pub enum Event<T> where AccountId = <T as frame_system::Config>::AccountId, BlockNumber = <T as frame_system::Config>::BlockNumber {
 		/// An erc20 deposit claim has started. (deposit Id, sender)
 		Erc20Claim(u64, AccountId),
 		/// A bridged erc20 deposit succeeded.(deposit Id, asset, amount, beneficiary)
@@ -90,6 +154,38 @@ decl_event! {
 		SetContractAddress(EthAddress),
 		/// ERC20 CENNZ deposits activated
 		CENNZDepositsActive,
+		/// The deposit & withdrawal caps of an asset have been set
+		SetAssetCap(AssetId),
+		/// An asset reached its cap and was paused
+		AssetPaused(AssetId),
+		/// A paused asset was resumed
+		AssetResumed(AssetId),
+		/// A bridged erc20 deposit is held until its asset is resumed (deposit Id, asset)
+		Erc20DepositHeld(u64, AssetId),
+		/// Tokens were burnt for a batched withdrawal on Ethereum as ERC20s (batch Id, index in batch, asset, amount, beneficiary)
+		Erc20WithdrawBatched(u64, u32, AssetId, Balance, EthAddress),
+		/// A withdrawal batch was proven (batch Id, event proof Id, merkle root)
+		WithdrawalBatchProven(u64, u64, H256),
+		/// The withdrawal fee of an asset has been set
+		SetWithdrawalFee(AssetId),
+		/// The withdrawal batch period has been set, `0` disables batching
+		SetWithdrawalBatchPeriod(BlockNumber),
+		/// A reserves attestation claim has started. (claim Id, sender)
+		ReservesClaim(u64, AccountId),
+		/// The peg contract's reserves of an asset were attested (asset, attested balance, expected balance)
+		ReservesAttested(AssetId, Balance, Balance),
+		/// The attested reserves of an asset differ from the expected reserves beyond tolerance (asset, attested balance, expected balance)
+		ReservesMismatch(AssetId, Balance, Balance),
+		/// A reserves attestation failed. (claim Id)
+		ReservesAttestationFail(u64),
+		/// The reserves tolerance of an asset has been set, `None` applies the default
+		SetReservesTolerance(AssetId, Option<Balance>),
+		/// The reserves attestation period has been set, `0` disables scheduled attestations
+		SetReservesAttestationPeriod(BlockNumber),
+		/// Validators should attest the reserves of an ERC20 before the block due (ERC20, block due)
+		ReservesAttestationRequested(EthAddress, BlockNumber),
+		/// The reserves of an asset were not attested before the block due, the asset is paused
+		ReservesAttestationOverdue(AssetId),
 	}
 }
 
@@ -106,7 +202,21 @@ decl_error! {
 		/// Withdrawals are inactive
 		WithdrawalsPaused,
 		/// Withdrawals of this asset are not supported
-		UnsupportedAsset
+		UnsupportedAsset,
+		/// The asset is paused
+		AssetPaused,
+		/// The amount exceeds the remaining deposit capacity of the asset
+		DepositCapExceeded,
+		/// The amount exceeds the remaining withdrawal capacity of the asset
+		WithdrawalCapExceeded,
+		/// No deposit is held with this Id
+		NoHeldDeposit,
+		/// The withdrawal batch is full, retry once it is proven
+		WithdrawalBatchFull,
+		/// Only active validators may attest reserves
+		NotValidator,
+		/// The reserves attestation is not newer than the last attested
+		StaleReserves,
 	}
 }
 
@@ -115,6 +225,30 @@ decl_module! {
 
 		fn deposit_event() = default;
 
+		fn on_runtime_upgrade() -> Weight {
+			let mut weight = T::DbWeight::get().reads(1);
+			if StorageVersion::get() == Releases::V0 as u32 {
+				StorageVersion::put(Releases::V1 as u32);
+				weight = weight.saturating_add(Self::seed_erc20_ledgers());
+			}
+			weight
+		}
+
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let pruning_weight = Self::prune_withdrawal_batches(now)
+				.saturating_add(Self::expire_reserves_attestations(now));
+			let period = Self::withdrawal_batch_period();
+			let pending = PendingWithdrawals::decode_len().unwrap_or_default();
+			if period.is_zero() || !(now % period).is_zero() || pending == 0 {
+				return pruning_weight.saturating_add(T::DbWeight::get().reads(2));
+			}
+			if let Err(err) = Self::prove_withdrawal_batch() {
+				// withdrawals remain pending until the next period
+				log::error!("📌 ERC20 withdrawal batch proof failed: {:?}", err);
+			}
+			pruning_weight.saturating_add(Self::prove_withdrawal_batch_weight(pending))
+		}
+
 		/// Activate/deactivate deposits (root only)
 		#[weight = 10_000_000]
 		pub fn activate_deposits(origin, activate: bool) {
@@ -144,7 +278,10 @@ decl_module! {
 			// fail a claim if beneficiary is not a valid CENNZnet address
 			ensure!(T::AccountId::decode(&mut &claim.beneficiary.0[..]).is_ok(), Error::<T>::InvalidAddress);
 
+			// the submitter pays the bridge's claim deposit
 			let event_claim_id = T::EthBridge::submit_event_claim(
+					&origin,
+					T::ChainId::get(),
 					&Self::contract_address().into(),
 					&T::DepositEventSignature::get().into(),
 					&tx_hash,
@@ -154,6 +291,34 @@ decl_module! {
 			Self::deposit_event(<Event<T>>::Erc20Claim(event_claim_id, origin));
 		}
 
+		#[weight = 50_000_000]
+		/// Submit a claim attesting the peg contract's reserves of an ERC20 in an ethereum tx (active validators only)
+		/// Validators attest each ERC20 every `ReservesAttestationPeriod` blocks, once verified by notaries the reserves are
+		/// reconciled with the bridged supply and the next attestation is requested
+		/// Attestations must be newer than the last attested i.e. have a greater nonce
+		#[transactional]
+		pub fn attest_reserves(origin, tx_hash: H256, claim: Erc20ReservesEvent) {
+			let origin = ensure_signed(origin)?;
+			ensure!(T::AuthoritySet::validators().contains(&origin), Error::<T>::NotValidator);
+			ensure!(Self::erc20_to_asset(claim.token_address).is_some(), Error::<T>::UnsupportedAsset);
+			ensure!(claim.balance < U256::from(u128::max_value()), Error::<T>::InvalidAmount);
+			ensure!(claim.withdrawn < U256::from(u128::max_value()), Error::<T>::InvalidAmount);
+			ensure!(claim.nonce < U256::from(u64::max_value()), Error::<T>::StaleReserves);
+			ensure!(Self::is_new_attestation(claim.token_address, claim.nonce.as_u64()), Error::<T>::StaleReserves);
+
+			// the submitter pays the bridge's claim deposit
+			let event_claim_id = T::EthBridge::submit_event_claim(
+					&origin,
+					T::ChainId::get(),
+					&Self::contract_address().into(),
+					&T::ReservesEventSignature::get().into(),
+					&tx_hash,
+					&EthAbiCodec::encode(&claim),
+			)?;
+
+			Self::deposit_event(<Event<T>>::ReservesClaim(event_claim_id, origin));
+		}
+
 		#[weight = 50_000_000]
 		/// Withdraw generic assets from CENNZnet in exchange for ERC20s
 		/// Tokens will be burnt and a proof generated to allow redemption of tokens on Ethereum
@@ -166,7 +331,16 @@ decl_module! {
 			// otherwise there may be no liquidity on the Ethereum side of the peg
 			let token_address = Self::asset_to_erc20(asset_id);
 			ensure!(token_address.is_some(), Error::<T>::UnsupportedAsset);
+			ensure!(!Self::asset_paused(asset_id), Error::<T>::AssetPaused);
+			let cap_reached = Self::use_capacity(asset_id, false, amount)?;
 
+			if let Some(fee) = Self::withdrawal_fee(asset_id) {
+				let fee_asset = match fee.asset {
+					FeeAsset::Withdrawn => asset_id,
+					FeeAsset::Spending => T::MultiCurrency::fee_currency(),
+				};
+				T::MultiCurrency::transfer(&origin, &fee.recipient, fee_asset, fee.amount, ExistenceRequirement::KeepAlive)?;
+			}
 			let _imbalance = T::MultiCurrency::withdraw(&origin, asset_id, amount, WithdrawReasons::empty(), frame_support::traits::ExistenceRequirement::KeepAlive)?;
 
 			let message = WithdrawMessage {
@@ -174,9 +348,122 @@ decl_module! {
 				amount: amount.into(),
 				beneficiary
 			};
-			let event_proof_id = T::EthBridge::generate_event_proof(&message)?;
+			Erc20Ledgers::mutate(message.token_address, |ledger| ledger.withdrawn = ledger.withdrawn.saturating_add(amount));
+			Self::request_reserves_attestation(message.token_address);
+			if Self::withdrawal_batch_period().is_zero() {
+				let event_proof_id = T::EthBridge::generate_event_proof(T::ChainId::get(), &message)?;
+				Self::deposit_event(<Event<T>>::Erc20Withdraw(event_proof_id, asset_id, amount, beneficiary));
+			} else {
+				// the withdrawal is proven with the batch at the end of the period
+				let index = PendingWithdrawals::decode_len().unwrap_or_default();
+				ensure!(index < MAX_WITHDRAWAL_BATCH_SIZE, Error::<T>::WithdrawalBatchFull);
+				PendingWithdrawals::append(message);
+				Self::deposit_event(<Event<T>>::Erc20WithdrawBatched(Self::next_withdrawal_batch_id(), index as u32, asset_id, amount, beneficiary));
+			}
+			if cap_reached {
+				Self::pause_asset(asset_id);
+			}
+		}
+
+		#[weight = 50_000_000]
+		/// Release a deposit held while its asset was paused
+		/// Any caller may release the deposit to the intended beneficiary once the asset is resumed
+		#[transactional]
+		pub fn release_held_deposit(origin, deposit_id: u64) {
+			let _ = ensure_signed(origin)?;
+			let deposit = Self::held_deposit(deposit_id).ok_or(Error::<T>::NoHeldDeposit)?;
+			let asset_id = Self::deposit_asset(deposit.token_address)?;
+			ensure!(!Self::asset_paused(asset_id), Error::<T>::AssetPaused);
+			let cap_reached = Self::use_capacity(asset_id, true, deposit.amount.as_u128())?;
+
+			HeldDeposits::remove(deposit_id);
+			let (asset_id, amount, beneficiary) = Self::do_deposit(deposit)?;
+			Self::deposit_event(<Event<T>>::Erc20Deposit(deposit_id, asset_id, amount, beneficiary));
+			if cap_reached {
+				Self::pause_asset(asset_id);
+			}
+		}
+
+		#[weight = 50_000_000]
+		/// Release a held deposit to its beneficiary without counting it against the asset's cap (requires governance)
+		/// e.g. a deposit larger than the deposit cap can never be released otherwise
+		#[transactional]
+		pub fn force_release_held_deposit(origin, deposit_id: u64) {
+			ensure_root(origin)?;
+			let deposit = HeldDeposits::take(deposit_id).ok_or(Error::<T>::NoHeldDeposit)?;
+			let (asset_id, amount, beneficiary) = Self::do_deposit(deposit)?;
+			Self::deposit_event(<Event<T>>::Erc20Deposit(deposit_id, asset_id, amount, beneficiary));
+		}
+
+		#[weight = 1_000_000]
+		#[transactional]
+		/// Set the deposit & withdrawal caps of an asset, `None` removes them (requires governance)
+		/// Usage of the current window is reset
+		pub fn set_asset_cap(origin, asset_id: AssetId, cap: Option<AssetCap<T::BlockNumber>>) {
+			ensure_root(origin)?;
+			match cap {
+				Some(cap) => AssetCaps::<T>::insert(asset_id, cap),
+				None => AssetCaps::<T>::remove(asset_id),
+			}
+			AssetCapUsage::<T>::remove(asset_id);
+			Self::deposit_event(<Event<T>>::SetAssetCap(asset_id));
+		}
+
+		#[weight = 1_000_000]
+		#[transactional]
+		/// Resume deposits & withdrawals of a paused asset (requires governance)
+		/// Usage of the current window is reset, held deposits may then be released
+		pub fn resume_asset(origin, asset_id: AssetId) {
+			ensure_root(origin)?;
+			PausedAssets::remove(asset_id);
+			AssetCapUsage::<T>::remove(asset_id);
+			Self::deposit_event(<Event<T>>::AssetResumed(asset_id));
+		}
+
+		#[weight = 1_000_000]
+		/// Set the max. difference between the attested & expected reserves of an asset (requires governance)
+		/// `None` applies `DEFAULT_RESERVES_TOLERANCE` of the expected reserves
+		pub fn set_reserves_tolerance(origin, asset_id: AssetId, tolerance: Option<Balance>) {
+			ensure_root(origin)?;
+			match tolerance {
+				Some(tolerance) => ReservesTolerance::insert(asset_id, tolerance),
+				None => ReservesTolerance::remove(asset_id),
+			}
+			Self::deposit_event(<Event<T>>::SetReservesTolerance(asset_id, tolerance));
+		}
+
+		#[weight = 1_000_000]
+		/// Require the reserves of each ERC20 be attested every `period` blocks, `0` disables scheduled attestations (requires governance)
+		/// Attestations requested before keep their due block, ERC20s are scheduled on their next attestation, deposit or withdrawal
+		pub fn set_reserves_attestation_period(origin, period: T::BlockNumber) {
+			ensure_root(origin)?;
+			<ReservesAttestationPeriod<T>>::put(period);
+			Self::deposit_event(<Event<T>>::SetReservesAttestationPeriod(period));
+		}
+
+		#[weight = 1_000_000]
+		#[transactional]
+		/// Set the fee charged on withdrawals of an asset, `None` removes it (requires governance)
+		pub fn set_withdrawal_fee(origin, asset_id: AssetId, fee: Option<WithdrawalFee<T::AccountId>>) {
+			ensure_root(origin)?;
+			match fee {
+				Some(fee) => WithdrawalFees::<T>::insert(asset_id, fee),
+				None => WithdrawalFees::<T>::remove(asset_id),
+			}
+			Self::deposit_event(<Event<T>>::SetWithdrawalFee(asset_id));
+		}
 
-			Self::deposit_event(<Event<T>>::Erc20Withdraw(event_proof_id, asset_id, amount, beneficiary));
+		#[weight = Module::<T>::prove_withdrawal_batch_weight(MAX_WITHDRAWAL_BATCH_SIZE)]
+		#[transactional]
+		/// Batch withdrawals over `period` blocks, `0` proves each withdrawal immediately (requires governance)
+		/// Pending withdrawals are proven immediately when batching is disabled
+		pub fn set_withdrawal_batch_period(origin, period: T::BlockNumber) {
+			ensure_root(origin)?;
+			if period.is_zero() {
+				Self::prove_withdrawal_batch()?;
+			}
+			<WithdrawalBatchPeriod<T>>::put(period);
+			Self::deposit_event(<Event<T>>::SetWithdrawalBatchPeriod(period));
 		}
 
 		#[weight = 1_000_000]
@@ -212,15 +499,324 @@ decl_module! {
 }
 
 impl<T: Config> Module<T> {
-	/// fulfil a deposit claim for the given event
-	pub fn do_deposit(verified_event: Erc20DepositEvent) -> Result<(AssetId, Balance, T::AccountId), DispatchError> {
-		let asset_id = match Self::erc20_to_asset(verified_event.token_address) {
+	/// Remaining capacity of `asset_id` over the current window
+	pub fn remaining_capacity(asset_id: AssetId) -> PegCapacity {
+		let paused = Self::asset_paused(asset_id);
+		match Self::asset_cap(asset_id) {
+			Some(cap) => {
+				let usage = Self::current_usage(asset_id, &cap);
+				PegCapacity {
+					deposit: Some(cap.deposit_cap.saturating_sub(usage.deposited)),
+					withdrawal: Some(cap.withdrawal_cap.saturating_sub(usage.withdrawn)),
+					paused,
+				}
+			}
+			None => PegCapacity {
+				deposit: None,
+				withdrawal: None,
+				paused,
+			},
+		}
+	}
+
+	/// Whether an attestation of `token_address` reserves with `nonce` is newer than the last attested
+	fn is_new_attestation(token_address: EthAddress, nonce: u64) -> bool {
+		Self::erc20_reserves(token_address).map_or(true, |last| nonce > last.nonce)
+	}
+
+	/// Reconcile the attested reserves of the peg contract with the bridged supply of the ERC20
+	/// The contract should hold the supply on CENNZnet plus withdrawals not yet redeemed on Ethereum
+	/// A difference beyond tolerance pauses the asset, deposits yet to be claimed on CENNZnet should be covered by the tolerance
+	/// Fails if the attestation is older than the last attested, claims may be verified out of order
+	fn reconcile_reserves(reserves: Erc20ReservesEvent) -> DispatchResult {
+		let asset_id = Self::erc20_to_asset(reserves.token_address).ok_or(Error::<T>::UnsupportedAsset)?;
+		// nonce checked < u64 in `attest_reserves` qed.
+		let nonce = reserves.nonce.as_u64();
+		ensure!(
+			Self::is_new_attestation(reserves.token_address, nonce),
+			Error::<T>::StaleReserves
+		);
+		// amounts checked < u128 in `attest_reserves` qed.
+		let balance = reserves.balance.as_u128();
+		let redeemed = reserves.withdrawn.as_u128();
+		let is_cennz = asset_id == T::MultiCurrency::staking_currency();
+		let mut ledger = Self::erc20_ledger(reserves.token_address);
+		if Erc20LedgerBaselines::take(reserves.token_address) {
+			// withdrawals before the ledger was tracked are assumed redeemed
+			ledger.withdrawn = redeemed;
+			// CENNZ deposits before the ledger was tracked are not known onchain, the attested balance is taken as its supply
+			if is_cennz {
+				ledger.deposited = balance.saturating_add(redeemed);
+			}
+		}
+		ledger.redeemed = redeemed;
+		// CENNZ is not minted by the peg, its supply is tracked by the ledger only
+		let supply = if is_cennz {
+			ledger.deposited.saturating_sub(ledger.withdrawn)
+		} else {
+			T::MultiCurrency::total_issuance(asset_id)
+		};
+		let expected = supply.saturating_add(ledger.in_flight());
+		Erc20Ledgers::insert(reserves.token_address, ledger);
+		<Erc20Reserves<T>>::insert(
+			reserves.token_address,
+			ReservesAttestation {
+				block: <frame_system::Pallet<T>>::block_number(),
+				nonce,
+				balance,
+				expected,
+			},
+		);
+
+		Self::deposit_event(<Event<T>>::ReservesAttested(asset_id, balance, expected));
+		Self::schedule_reserves_attestation(reserves.token_address);
+		let difference = if balance > expected {
+			balance - expected
+		} else {
+			expected - balance
+		};
+		let tolerance = Self::reserves_tolerance(asset_id).unwrap_or_else(|| DEFAULT_RESERVES_TOLERANCE * expected);
+		if difference > tolerance {
+			Self::deposit_event(<Event<T>>::ReservesMismatch(asset_id, balance, expected));
+			if !Self::asset_paused(asset_id) {
+				Self::pause_asset(asset_id);
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Request an attestation of the reserves of `token_address` unless one is due already
+	fn request_reserves_attestation(token_address: EthAddress) {
+		if Self::reserves_attestation_due(token_address).is_none() {
+			Self::schedule_reserves_attestation(token_address);
+		}
+	}
+
+	/// Request an attestation of the reserves of `token_address` due `ReservesAttestationPeriod` blocks from now
+	/// Replaces the attestation due before, nothing is requested while the period is `0`
+	fn schedule_reserves_attestation(token_address: EthAddress) {
+		if let Some(due) = <ReservesAttestationDue<T>>::take(token_address) {
+			<ReservesAttestationDeadlines<T>>::remove(due, token_address);
+		}
+		let period = Self::reserves_attestation_period();
+		if period.is_zero() {
+			return;
+		}
+		let due = <frame_system::Pallet<T>>::block_number().saturating_add(period);
+		<ReservesAttestationDue<T>>::insert(token_address, due);
+		<ReservesAttestationDeadlines<T>>::insert(due, token_address, ());
+		Self::deposit_event(<Event<T>>::ReservesAttestationRequested(token_address, due));
+	}
+
+	/// Pause the assets of ERC20s whose reserves attestation due at `block_number` was not verified and request another
+	fn expire_reserves_attestations(block_number: T::BlockNumber) -> Weight {
+		// scheduled attestations may have been disabled since
+		let scheduled = !Self::reserves_attestation_period().is_zero();
+		let mut expired = 0_u64;
+		for (token_address, _) in <ReservesAttestationDeadlines<T>>::drain_prefix(block_number) {
+			<ReservesAttestationDue<T>>::remove(token_address);
+			expired += 1;
+			if !scheduled {
+				continue;
+			}
+			if let Some(asset_id) = Self::erc20_to_asset(token_address) {
+				Self::deposit_event(<Event<T>>::ReservesAttestationOverdue(asset_id));
+				if !Self::asset_paused(asset_id) {
+					Self::pause_asset(asset_id);
+				}
+			}
+			Self::schedule_reserves_attestation(token_address);
+		}
+
+		T::DbWeight::get().reads_writes(2 + expired * 4, expired * 5)
+	}
+
+	/// Seed the ledgers of ERC20s bridged before ledgers were tracked
+	/// Deposits of peg minted assets are seeded with their supply, the next attestation of each ERC20 sets its
+	/// withdrawn amount (and CENNZ deposits) as a baseline
+	fn seed_erc20_ledgers() -> Weight {
+		let mut seeded = 0_u64;
+		for (token_address, asset_id) in Erc20ToAssetId::iter() {
+			if asset_id != T::MultiCurrency::staking_currency() {
+				let supply = T::MultiCurrency::total_issuance(asset_id);
+				Erc20Ledgers::mutate(token_address, |ledger| {
+					ledger.deposited = ledger.deposited.saturating_add(supply)
+				});
+			}
+			Erc20LedgerBaselines::insert(token_address, true);
+			seeded += 1;
+		}
+
+		T::DbWeight::get().reads_writes(1 + seeded * 3, 1 + seeded * 2)
+	}
+
+	/// Inclusion proof of the withdrawal at `index` in the proven batch `batch_id`
+	pub fn withdrawal_proof(batch_id: u64, index: u32) -> Option<WithdrawalProof> {
+		let batch = Self::withdrawal_batch(batch_id)?;
+		let withdrawal = batch.withdrawals.get(index as usize)?.clone();
+		let proof = merkle::proof(&Self::withdrawal_leaves(batch_id, &batch.withdrawals), index as usize)?;
+
+		Some(WithdrawalProof {
+			event_proof_id: batch.event_proof_id,
+			root: batch.root,
+			batch_id,
+			index,
+			withdrawal,
+			proof,
+		})
+	}
+
+	/// Merkle leaves of the withdrawals in batch `batch_id`
+	/// i.e. `keccak256` of their abi encoded `BatchedWithdrawMessage`s
+	/// The batch Id & index make the leaves of identical withdrawals unique
+	fn withdrawal_leaves(batch_id: u64, withdrawals: &[WithdrawMessage]) -> Vec<H256> {
+		withdrawals
+			.iter()
+			.enumerate()
+			.map(|(index, message)| {
+				let leaf = BatchedWithdrawMessage {
+					batch_id,
+					index: index as u32,
+					message: message.clone(),
+				};
+				H256::from(keccak_256(&EthAbiCodec::encode(&leaf)))
+			})
+			.collect()
+	}
+
+	/// Weight of proving a batch of `withdrawals`
+	fn prove_withdrawal_batch_weight(withdrawals: usize) -> Weight {
+		PROVE_WITHDRAWAL_BATCH_WEIGHT
+			.saturating_add(WITHDRAWAL_LEAF_WEIGHT.saturating_mul(withdrawals as Weight))
+			.saturating_add(T::DbWeight::get().reads_writes(3, 4))
+	}
+
+	/// Prove the pending withdrawals with an event proof of their merkle root
+	/// The batch is retained for `WITHDRAWAL_BATCH_RETENTION_BLOCKS`
+	fn prove_withdrawal_batch() -> DispatchResult {
+		let withdrawals = Self::pending_withdrawals();
+		if withdrawals.is_empty() {
+			return Ok(());
+		}
+		let batch_id = Self::next_withdrawal_batch_id();
+		let root = merkle::root(&Self::withdrawal_leaves(batch_id, &withdrawals));
+		let event_proof_id = T::EthBridge::generate_event_proof(T::ChainId::get(), &WithdrawBatchMessage { root })?;
+
+		let prune_block =
+			<frame_system::Pallet<T>>::block_number() + T::BlockNumber::from(WITHDRAWAL_BATCH_RETENTION_BLOCKS);
+		<WithdrawalBatchPruning<T>>::insert(prune_block, batch_id, ());
+		WithdrawalBatches::insert(
+			batch_id,
+			WithdrawalBatch {
+				event_proof_id,
+				root,
+				withdrawals,
+			},
+		);
+		NextWithdrawalBatchId::put(batch_id.wrapping_add(1));
+		PendingWithdrawals::kill();
+		Self::deposit_event(<Event<T>>::WithdrawalBatchProven(batch_id, event_proof_id, root));
+
+		Ok(())
+	}
+
+	/// Prune withdrawal batches whose retention ends at `block_number`
+	fn prune_withdrawal_batches(block_number: T::BlockNumber) -> Weight {
+		let mut pruned = 0_u64;
+		for (batch_id, _) in <WithdrawalBatchPruning<T>>::drain_prefix(block_number) {
+			WithdrawalBatches::remove(batch_id);
+			pruned += 1;
+		}
+
+		T::DbWeight::get().reads_writes(1 + pruned, pruned * 2)
+	}
+
+	/// Usage of `asset_id` over the current window, a new window starts once `cap.window` has elapsed
+	fn current_usage(asset_id: AssetId, cap: &AssetCap<T::BlockNumber>) -> CapUsage<T::BlockNumber> {
+		let now = <frame_system::Pallet<T>>::block_number();
+		let usage = Self::asset_cap_usage(asset_id);
+		if now >= usage.window_start.saturating_add(cap.window) {
+			CapUsage {
+				window_start: now,
+				..Default::default()
+			}
+		} else {
+			usage
+		}
+	}
+
+	/// Count `amount` deposited (`is_deposit`) or withdrawn against the caps of `asset_id`
+	/// Returns whether the cap has been reached, fails if `amount` exceeds the remaining capacity
+	fn use_capacity(asset_id: AssetId, is_deposit: bool, amount: Balance) -> Result<bool, Error<T>> {
+		let cap = match Self::asset_cap(asset_id) {
+			Some(cap) => cap,
+			None => return Ok(false),
+		};
+		let mut usage = Self::current_usage(asset_id, &cap);
+		let (used, limit, exceeded) = if is_deposit {
+			(&mut usage.deposited, cap.deposit_cap, Error::<T>::DepositCapExceeded)
+		} else {
+			(
+				&mut usage.withdrawn,
+				cap.withdrawal_cap,
+				Error::<T>::WithdrawalCapExceeded,
+			)
+		};
+		let total = used
+			.checked_add(amount)
+			.filter(|total| *total <= limit)
+			.ok_or(exceeded)?;
+		*used = total;
+		AssetCapUsage::<T>::insert(asset_id, usage);
+
+		Ok(total == limit)
+	}
+
+	/// Pause deposits & withdrawals of `asset_id` until resumed by governance
+	fn pause_asset(asset_id: AssetId) {
+		PausedAssets::insert(asset_id, true);
+		Self::deposit_event(<Event<T>>::AssetPaused(asset_id));
+	}
+
+	/// Fulfil a verified deposit, holding it if the asset is paused or the deposit exceeds its remaining capacity
+	/// Exceeding the capacity pauses the asset
+	#[transactional]
+	fn process_deposit(deposit_id: u64, verified_event: Erc20DepositEvent) -> Result<(), DispatchError> {
+		let asset_id = Self::deposit_asset(verified_event.token_address)?;
+		let cap_reached = if Self::asset_paused(asset_id) {
+			None
+		} else {
+			Self::use_capacity(asset_id, true, verified_event.amount.as_u128()).ok()
+		};
+		match cap_reached {
+			Some(cap_reached) => {
+				let (asset_id, amount, beneficiary) = Self::do_deposit(verified_event)?;
+				Self::deposit_event(<Event<T>>::Erc20Deposit(deposit_id, asset_id, amount, beneficiary));
+				if cap_reached {
+					Self::pause_asset(asset_id);
+				}
+			}
+			None => {
+				HeldDeposits::insert(deposit_id, verified_event);
+				Self::deposit_event(<Event<T>>::Erc20DepositHeld(deposit_id, asset_id));
+				if !Self::asset_paused(asset_id) {
+					Self::pause_asset(asset_id);
+				}
+			}
+		}
+
+		Ok(())
+	}
+
+	/// The asset bridged for `token_address`, created on its first deposit
+	fn deposit_asset(token_address: EthAddress) -> Result<AssetId, DispatchError> {
+		match Self::erc20_to_asset(token_address) {
 			None => {
 				// create asset with known values from `Erc20Meta`
 				// asset will be created with `18` decimal places and "" for symbol if the asset is unknown
 				// dapps can also use `AssetToERC20` to retrieve the appropriate decimal places from ethereum
-				let (symbol, decimals) =
-					Erc20Meta::get(verified_event.token_address).unwrap_or((Default::default(), 18));
+				let (symbol, decimals) = Erc20Meta::get(token_address).unwrap_or((Default::default(), 18));
 				let asset_id = T::MultiCurrency::create(
 					&T::PegPalletId::get().into_account(),
 					Zero::zero(), // 0 initial supply
@@ -229,13 +825,18 @@ impl<T: Config> Module<T> {
 					symbol,
 				)
 				.map_err(|_| Error::<T>::CreateAssetFailed)?;
-				Erc20ToAssetId::insert(verified_event.token_address, asset_id);
-				AssetIdToErc20::insert(asset_id, verified_event.token_address);
+				Erc20ToAssetId::insert(token_address, asset_id);
+				AssetIdToErc20::insert(asset_id, token_address);
 
-				asset_id
+				Ok(asset_id)
 			}
-			Some(asset_id) => asset_id,
-		};
+			Some(asset_id) => Ok(asset_id),
+		}
+	}
+
+	/// fulfil a deposit claim for the given event
+	pub fn do_deposit(verified_event: Erc20DepositEvent) -> Result<(AssetId, Balance, T::AccountId), DispatchError> {
+		let asset_id = Self::deposit_asset(verified_event.token_address)?;
 
 		// checked at the time of initiating the verified_event that beneficiary value is valid and this op will not fail qed.
 		let beneficiary: T::AccountId = T::AccountId::decode(&mut &verified_event.beneficiary.0[..]).unwrap();
@@ -244,14 +845,14 @@ impl<T: Config> Module<T> {
 		// it must be transferred from the unclaimed wallet
 		let amount = verified_event.amount.as_u128();
 		if asset_id == T::MultiCurrency::staking_currency() && Self::cennz_deposit_active() {
-			let _result = T::MultiCurrency::transfer(
+			T::MultiCurrency::transfer(
 				// TODO: decide upon: Treasury / Sudo::key() / Bridge,
 				&T::PegPalletId::get().into_account(),
 				&beneficiary,
 				asset_id,
 				amount, // checked amount < u128 in `deposit_claim` qed.
 				ExistenceRequirement::KeepAlive,
-			);
+			)?;
 		} else {
 			// checked amount < u128 on `deposit_claim` qed.
 			let _imbalance = T::MultiCurrency::deposit_creating(
@@ -260,34 +861,67 @@ impl<T: Config> Module<T> {
 				amount, // checked amount < u128 in `deposit_claim` qed.
 			);
 		}
+		// only deposits paid out on CENNZnet are added to the ledger
+		Erc20Ledgers::mutate(verified_event.token_address, |ledger| {
+			ledger.deposited = ledger.deposited.saturating_add(amount)
+		});
+		Self::request_reserves_attestation(verified_event.token_address);
 
 		Ok((asset_id, amount, beneficiary))
 	}
 }
 
 impl<T: Config> EventClaimSubscriber for Module<T> {
-	fn on_success(event_claim_id: u64, contract_address: &EthAddress, event_type: &H256, event_data: &[u8]) {
-		if *contract_address == EthAddress::from(Self::contract_address())
+	fn on_success(
+		event_claim_id: u64,
+		chain_id: EthChainId,
+		contract_address: &EthAddress,
+		event_type: &H256,
+		event_data: &[u8],
+	) {
+		if chain_id == T::ChainId::get()
+			&& *contract_address == EthAddress::from(Self::contract_address())
 			&& *event_type == H256::from(T::DepositEventSignature::get())
 		{
 			if let Some(deposit_event) = EthAbiCodec::decode(event_data) {
-				match Self::do_deposit(deposit_event) {
-					Ok((asset_id, amount, beneficiary)) => {
-						Self::deposit_event(<Event<T>>::Erc20Deposit(event_claim_id, asset_id, amount, beneficiary))
-					}
-					Err(_err) => Self::deposit_event(<Event<T>>::Erc20DepositFail(event_claim_id)),
+				if let Err(_err) = Self::process_deposit(event_claim_id, deposit_event) {
+					Self::deposit_event(<Event<T>>::Erc20DepositFail(event_claim_id));
 				}
 			} else {
 				// input data should be valid, we do not expect to fail here
 				log::error!("📌 ERC20 deposit claim failed unexpectedly: {:?}", event_data);
 			}
+		} else if chain_id == T::ChainId::get()
+			&& *contract_address == EthAddress::from(Self::contract_address())
+			&& *event_type == H256::from(T::ReservesEventSignature::get())
+		{
+			if let Some(reserves_event) = EthAbiCodec::decode(event_data) {
+				if let Err(_err) = Self::reconcile_reserves(reserves_event) {
+					Self::deposit_event(<Event<T>>::ReservesAttestationFail(event_claim_id));
+				}
+			} else {
+				// input data should be valid, we do not expect to fail here
+				log::error!("📌 ERC20 reserves claim failed unexpectedly: {:?}", event_data);
+			}
 		}
 	}
-	fn on_failure(event_claim_id: u64, contract_address: &H160, event_type: &H256, _event_data: &[u8]) {
-		if *contract_address == EthAddress::from(Self::contract_address())
+	fn on_failure(
+		event_claim_id: u64,
+		chain_id: EthChainId,
+		contract_address: &H160,
+		event_type: &H256,
+		_event_data: &[u8],
+	) {
+		if chain_id == T::ChainId::get()
+			&& *contract_address == EthAddress::from(Self::contract_address())
 			&& *event_type == H256::from(T::DepositEventSignature::get())
 		{
 			Self::deposit_event(<Event<T>>::Erc20DepositFail(event_claim_id));
+		} else if chain_id == T::ChainId::get()
+			&& *contract_address == EthAddress::from(Self::contract_address())
+			&& *event_type == H256::from(T::ReservesEventSignature::get())
+		{
+			Self::deposit_event(<Event<T>>::ReservesAttestationFail(event_claim_id));
 		}
 	}
 }
@@ -0,0 +1,107 @@
+/* Copyright 2021 Centrality Investments Limited
+*
+* Licensed under the LGPL, Version 3.0 (the "License");
+* you may not use this file except in compliance with the License.
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific language governing permissions and
+* limitations under the License.
+* You may obtain a copy of the License at the root of this project source code,
+* or at:
+*     https://centrality.ai/licenses/gplv3.txt
+*     https://centrality.ai/licenses/lgplv3.txt
+*/
+
+//! Merkle trees of batched withdrawals
+//! Pairs are hashed in sorted order `keccak256(min ++ max)` so proofs verify with OpenZeppelin's `MerkleProof`
+//! A node without a pair is carried up to the next layer unchanged
+
+use crml_support::H256;
+use sp_io::hashing::keccak_256;
+use sp_std::prelude::*;
+
+/// Hash a pair of nodes in sorted order
+fn hash_pair(a: &H256, b: &H256) -> H256 {
+	let (first, second) = if a <= b { (a, b) } else { (b, a) };
+	H256::from(keccak_256(&[first.as_bytes(), second.as_bytes()].concat()))
+}
+
+/// Hash the nodes of `layer` into the layer above it
+fn next_layer(layer: &[H256]) -> Vec<H256> {
+	layer
+		.chunks(2)
+		.map(|pair| match pair {
+			[a, b] => hash_pair(a, b),
+			_ => pair[0],
+		})
+		.collect()
+}
+
+/// The root of a tree of `leaves`, zero if there are none
+pub fn root(leaves: &[H256]) -> H256 {
+	let mut layer = leaves.to_vec();
+	while layer.len() > 1 {
+		layer = next_layer(&layer);
+	}
+	layer.first().copied().unwrap_or_default()
+}
+
+/// The sibling hashes proving inclusion of the leaf at `index`, ordered from the leaves up
+pub fn proof(leaves: &[H256], index: usize) -> Option<Vec<H256>> {
+	if index >= leaves.len() {
+		return None;
+	}
+	let mut proof = Vec::<H256>::default();
+	let mut layer = leaves.to_vec();
+	let mut index = index;
+	while layer.len() > 1 {
+		if let Some(sibling) = layer.get(index ^ 1) {
+			proof.push(*sibling);
+		}
+		layer = next_layer(&layer);
+		index /= 2;
+	}
+
+	Some(proof)
+}
+
+#[cfg(test)]
+mod test {
+	use super::{hash_pair, proof, root};
+	use crml_support::H256;
+
+	fn verify(leaf: H256, proof: &[H256], root: H256) -> bool {
+		proof.iter().fold(leaf, |node, sibling| hash_pair(&node, sibling)) == root
+	}
+
+	#[test]
+	fn empty_and_single_leaf() {
+		assert_eq!(root(&[]), H256::zero());
+		assert_eq!(proof(&[], 0), None);
+
+		let leaf = H256::from_low_u64_be(1);
+		assert_eq!(root(&[leaf]), leaf);
+		assert_eq!(proof(&[leaf], 0), Some(vec![]));
+	}
+
+	#[test]
+	fn proves_each_leaf() {
+		for count in 2..=9_u64 {
+			let leaves: Vec<H256> = (1..=count).map(H256::from_low_u64_be).collect();
+			let root = root(&leaves);
+			for (index, leaf) in leaves.iter().enumerate() {
+				let proof = proof(&leaves, index).expect("leaf is in the tree");
+				assert!(verify(*leaf, &proof, root));
+				assert!(!verify(H256::from_low_u64_be(count + 1), &proof, root));
+			}
+			assert_eq!(proof(&leaves, leaves.len()), None);
+		}
+	}
+
+	#[test]
+	fn pairs_are_sorted() {
+		let (a, b) = (H256::from_low_u64_be(1), H256::from_low_u64_be(2));
+		assert_eq!(root(&[a, b]), root(&[b, a]));
+	}
+}
@@ -0,0 +1,584 @@
+/* Copyright 2021 Centrality Investments Limited
+*
+* Licensed under the LGPL, Version 3.0 (the "License");
+* you may not use this file except in compliance with the License.
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific language governing permissions and
+* limitations under the License.
+* You may obtain a copy of the License at the root of this project source code,
+* or at:
+*     https://centrality.ai/licenses/gplv3.txt
+*     https://centrality.ai/licenses/lgplv3.txt
+*/
+
+use super::*;
+use crate::mock::{
+	deposit, has_event, reserves, token_address, verify_deposit, verify_reserves, Erc20Peg, ExtBuilder, GenericAsset,
+	MockEthBridge, Origin, PegPalletId, ReservesEventSignature, System, Test, BENEFICIARY, STAKING_ASSET_ID,
+	TOKEN_ASSET_ID, VALIDATORS,
+};
+use frame_support::{
+	assert_noop, assert_ok,
+	storage::{StorageDoubleMap, StorageMap, StorageValue},
+	traits::{OnInitialize, OnRuntimeUpgrade},
+};
+use sp_runtime::DispatchError::BadOrigin;
+
+/// Bridge `TOKEN` with a first deposit of `amount` and cap it
+fn setup_capped_asset(amount: Balance, cap: AssetCap<u64>) {
+	verify_deposit(0, deposit(amount));
+	assert_eq!(Erc20Peg::erc20_to_asset(token_address()), Some(TOKEN_ASSET_ID));
+	assert_ok!(Erc20Peg::set_asset_cap(Origin::root(), TOKEN_ASSET_ID, Some(cap)));
+}
+
+fn cap(deposit_cap: Balance, withdrawal_cap: Balance, window: u64) -> AssetCap<u64> {
+	AssetCap {
+		deposit_cap,
+		withdrawal_cap,
+		window,
+	}
+}
+
+#[test]
+fn deposit_beyond_cap_is_held_and_pauses_asset() {
+	ExtBuilder::default().build().execute_with(|| {
+		setup_capped_asset(10, cap(100, 100, 10));
+
+		verify_deposit(1, deposit(60));
+		assert_eq!(GenericAsset::free_balance(TOKEN_ASSET_ID, &BENEFICIARY), 70);
+		assert_eq!(Erc20Peg::remaining_capacity(TOKEN_ASSET_ID).deposit, Some(40));
+
+		verify_deposit(2, deposit(50));
+		assert!(has_event(RawEvent::Erc20DepositHeld(2, TOKEN_ASSET_ID)));
+		assert!(has_event(RawEvent::AssetPaused(TOKEN_ASSET_ID)));
+		assert_eq!(Erc20Peg::held_deposit(2), Some(deposit(50)));
+		// deposits within capacity are held while the asset is paused
+		verify_deposit(3, deposit(10));
+		assert_eq!(Erc20Peg::held_deposit(3), Some(deposit(10)));
+		assert_eq!(GenericAsset::free_balance(TOKEN_ASSET_ID, &BENEFICIARY), 70);
+		assert_eq!(
+			Erc20Peg::remaining_capacity(TOKEN_ASSET_ID),
+			PegCapacity {
+				deposit: Some(40),
+				withdrawal: Some(100),
+				paused: true,
+			}
+		);
+
+		assert_noop!(
+			Erc20Peg::release_held_deposit(Origin::signed(1), 2),
+			Error::<Test>::AssetPaused
+		);
+		assert_noop!(Erc20Peg::resume_asset(Origin::signed(1), TOKEN_ASSET_ID), BadOrigin);
+		assert_ok!(Erc20Peg::resume_asset(Origin::root(), TOKEN_ASSET_ID));
+		assert!(has_event(RawEvent::AssetResumed(TOKEN_ASSET_ID)));
+
+		// usage is reset on resume
+		assert_ok!(Erc20Peg::release_held_deposit(Origin::signed(1), 2));
+		assert_ok!(Erc20Peg::release_held_deposit(Origin::signed(1), 3));
+		assert_eq!(GenericAsset::free_balance(TOKEN_ASSET_ID, &BENEFICIARY), 130);
+		assert_eq!(Erc20Peg::remaining_capacity(TOKEN_ASSET_ID).deposit, Some(40));
+		assert!(Erc20Peg::held_deposit(2).is_none());
+		assert_noop!(
+			Erc20Peg::release_held_deposit(Origin::signed(1), 2),
+			Error::<Test>::NoHeldDeposit
+		);
+	});
+}
+
+#[test]
+fn deposit_reaching_cap_pauses_asset() {
+	ExtBuilder::default().build().execute_with(|| {
+		setup_capped_asset(10, cap(100, 100, 10));
+
+		verify_deposit(1, deposit(100));
+		assert_eq!(GenericAsset::free_balance(TOKEN_ASSET_ID, &BENEFICIARY), 110);
+		assert!(has_event(RawEvent::Erc20Deposit(1, TOKEN_ASSET_ID, 100, BENEFICIARY)));
+		assert!(Erc20Peg::asset_paused(TOKEN_ASSET_ID));
+		assert_eq!(Erc20Peg::remaining_capacity(TOKEN_ASSET_ID).deposit, Some(0));
+	});
+}
+
+#[test]
+fn cap_usage_resets_each_window() {
+	ExtBuilder::default().build().execute_with(|| {
+		setup_capped_asset(10, cap(100, 100, 10));
+
+		verify_deposit(1, deposit(80));
+		assert_eq!(Erc20Peg::remaining_capacity(TOKEN_ASSET_ID).deposit, Some(20));
+
+		System::set_block_number(9);
+		assert_eq!(Erc20Peg::remaining_capacity(TOKEN_ASSET_ID).deposit, Some(20));
+		System::set_block_number(10);
+		assert_eq!(Erc20Peg::remaining_capacity(TOKEN_ASSET_ID).deposit, Some(100));
+
+		verify_deposit(2, deposit(80));
+		assert_eq!(GenericAsset::free_balance(TOKEN_ASSET_ID, &BENEFICIARY), 170);
+		assert_eq!(
+			Erc20Peg::asset_cap_usage(TOKEN_ASSET_ID),
+			CapUsage {
+				window_start: 10,
+				deposited: 80,
+				withdrawn: 0,
+			}
+		);
+		assert!(!Erc20Peg::asset_paused(TOKEN_ASSET_ID));
+	});
+}
+
+#[test]
+fn withdrawal_cap_pauses_asset() {
+	ExtBuilder::default().build().execute_with(|| {
+		setup_capped_asset(100, cap(1_000, 50, 10));
+		assert_ok!(Erc20Peg::activate_withdrawals(Origin::root(), true));
+		let eth_beneficiary = EthAddress::from_low_u64_be(7);
+
+		assert_noop!(
+			Erc20Peg::withdraw(Origin::signed(BENEFICIARY), TOKEN_ASSET_ID, 51, eth_beneficiary),
+			Error::<Test>::WithdrawalCapExceeded
+		);
+		assert_ok!(Erc20Peg::withdraw(
+			Origin::signed(BENEFICIARY),
+			TOKEN_ASSET_ID,
+			50,
+			eth_beneficiary
+		));
+		assert!(has_event(RawEvent::Erc20Withdraw(
+			0,
+			TOKEN_ASSET_ID,
+			50,
+			eth_beneficiary
+		)));
+		assert!(has_event(RawEvent::AssetPaused(TOKEN_ASSET_ID)));
+		assert_eq!(GenericAsset::free_balance(TOKEN_ASSET_ID, &BENEFICIARY), 50);
+
+		assert_noop!(
+			Erc20Peg::withdraw(Origin::signed(BENEFICIARY), TOKEN_ASSET_ID, 1, eth_beneficiary),
+			Error::<Test>::AssetPaused
+		);
+		assert_ok!(Erc20Peg::resume_asset(Origin::root(), TOKEN_ASSET_ID));
+		assert_ok!(Erc20Peg::withdraw(
+			Origin::signed(BENEFICIARY),
+			TOKEN_ASSET_ID,
+			1,
+			eth_beneficiary
+		));
+	});
+}
+
+#[test]
+fn removing_cap_uncaps_asset() {
+	ExtBuilder::default().build().execute_with(|| {
+		setup_capped_asset(10, cap(100, 100, 10));
+		assert_ok!(Erc20Peg::set_asset_cap(Origin::root(), TOKEN_ASSET_ID, None));
+
+		verify_deposit(1, deposit(1_000));
+		assert_eq!(GenericAsset::free_balance(TOKEN_ASSET_ID, &BENEFICIARY), 1_010);
+		assert_eq!(
+			Erc20Peg::remaining_capacity(TOKEN_ASSET_ID),
+			PegCapacity {
+				deposit: None,
+				withdrawal: None,
+				paused: false,
+			}
+		);
+	});
+}
+
+#[test]
+fn held_deposit_larger_than_cap_released_by_governance() {
+	ExtBuilder::default().build().execute_with(|| {
+		setup_capped_asset(10, cap(100, 100, 10));
+
+		verify_deposit(1, deposit(150));
+		assert_eq!(Erc20Peg::held_deposit(1), Some(deposit(150)));
+		assert_ok!(Erc20Peg::resume_asset(Origin::root(), TOKEN_ASSET_ID));
+
+		// the deposit can never fit the cap
+		assert_noop!(
+			Erc20Peg::release_held_deposit(Origin::signed(1), 1),
+			Error::<Test>::DepositCapExceeded
+		);
+		assert_noop!(Erc20Peg::force_release_held_deposit(Origin::signed(1), 1), BadOrigin);
+		assert_ok!(Erc20Peg::force_release_held_deposit(Origin::root(), 1));
+
+		assert!(has_event(RawEvent::Erc20Deposit(1, TOKEN_ASSET_ID, 150, BENEFICIARY)));
+		assert_eq!(GenericAsset::free_balance(TOKEN_ASSET_ID, &BENEFICIARY), 160);
+		assert!(Erc20Peg::held_deposit(1).is_none());
+		// not counted against the cap
+		assert_eq!(Erc20Peg::remaining_capacity(TOKEN_ASSET_ID).deposit, Some(100));
+		assert!(!Erc20Peg::asset_paused(TOKEN_ASSET_ID));
+		assert_noop!(
+			Erc20Peg::force_release_held_deposit(Origin::root(), 1),
+			Error::<Test>::NoHeldDeposit
+		);
+	});
+}
+
+#[test]
+fn identical_batched_withdrawals_have_unique_leaves() {
+	ExtBuilder::default().build().execute_with(|| {
+		verify_deposit(0, deposit(100));
+		assert_ok!(Erc20Peg::activate_withdrawals(Origin::root(), true));
+		assert_ok!(Erc20Peg::set_withdrawal_batch_period(Origin::root(), 10));
+		let eth_beneficiary = EthAddress::from_low_u64_be(7);
+		for _ in 0..2 {
+			assert_ok!(Erc20Peg::withdraw(
+				Origin::signed(BENEFICIARY),
+				TOKEN_ASSET_ID,
+				10,
+				eth_beneficiary
+			));
+		}
+		assert!(has_event(RawEvent::Erc20WithdrawBatched(
+			0,
+			1,
+			TOKEN_ASSET_ID,
+			10,
+			eth_beneficiary
+		)));
+
+		System::set_block_number(10);
+		assert_eq!(
+			Erc20Peg::on_initialize(10),
+			PROVE_WITHDRAWAL_BATCH_WEIGHT + 2 * WITHDRAWAL_LEAF_WEIGHT
+		);
+		let batch = Erc20Peg::withdrawal_batch(0).expect("batch is proven");
+		let message = WithdrawMessage {
+			token_address: token_address(),
+			amount: U256::from(10),
+			beneficiary: eth_beneficiary,
+		};
+		assert_eq!(batch.withdrawals, vec![message.clone(), message.clone()]);
+		assert_eq!(
+			MockEthBridge::event_proofs(),
+			vec![EthAbiCodec::encode(&WithdrawBatchMessage { root: batch.root })]
+		);
+
+		let leaves = Erc20Peg::withdrawal_leaves(0, &batch.withdrawals);
+		assert_ne!(leaves[0], leaves[1]);
+		let leaf = BatchedWithdrawMessage {
+			batch_id: 0,
+			index: 1,
+			message: message.clone(),
+		};
+		assert_eq!(leaves[1], H256::from(keccak_256(&EthAbiCodec::encode(&leaf))));
+		assert_eq!(
+			Erc20Peg::withdrawal_proof(0, 1),
+			Some(WithdrawalProof {
+				event_proof_id: 0,
+				root: batch.root,
+				batch_id: 0,
+				index: 1,
+				withdrawal: message,
+				proof: vec![leaves[0]],
+			})
+		);
+		assert!(Erc20Peg::withdrawal_proof(0, 2).is_none());
+	});
+}
+
+#[test]
+fn withdrawal_batches_are_pruned() {
+	ExtBuilder::default().build().execute_with(|| {
+		verify_deposit(0, deposit(100));
+		assert_ok!(Erc20Peg::activate_withdrawals(Origin::root(), true));
+		assert_ok!(Erc20Peg::set_withdrawal_batch_period(Origin::root(), 10));
+		assert_ok!(Erc20Peg::withdraw(
+			Origin::signed(BENEFICIARY),
+			TOKEN_ASSET_ID,
+			10,
+			EthAddress::from_low_u64_be(7)
+		));
+		System::set_block_number(10);
+		Erc20Peg::on_initialize(10);
+		assert!(Erc20Peg::withdrawal_proof(0, 0).is_some());
+
+		let prune_block = 10 + WITHDRAWAL_BATCH_RETENTION_BLOCKS as u64;
+		assert!(WithdrawalBatchPruning::<Test>::contains_key(prune_block, 0));
+		Erc20Peg::on_initialize(prune_block - 1);
+		assert!(Erc20Peg::withdrawal_batch(0).is_some());
+		Erc20Peg::on_initialize(prune_block);
+		assert!(Erc20Peg::withdrawal_batch(0).is_none());
+		assert!(Erc20Peg::withdrawal_proof(0, 0).is_none());
+		assert!(!WithdrawalBatchPruning::<Test>::contains_key(prune_block, 0));
+	});
+}
+
+#[test]
+fn attest_reserves_by_validators_only() {
+	ExtBuilder::default().build().execute_with(|| {
+		verify_deposit(0, deposit(1_000));
+		let tx_hash = H256::from_low_u64_be(1);
+
+		assert_noop!(
+			Erc20Peg::attest_reserves(Origin::signed(BENEFICIARY), tx_hash, reserves(1_000, 0, 1)),
+			Error::<Test>::NotValidator
+		);
+		assert_ok!(Erc20Peg::attest_reserves(
+			Origin::signed(VALIDATORS[0]),
+			tx_hash,
+			reserves(1_000, 0, 1)
+		));
+		assert!(has_event(RawEvent::ReservesClaim(0, VALIDATORS[0])));
+		assert_eq!(
+			MockEthBridge::event_claims(),
+			vec![(
+				VALIDATORS[0],
+				H256::from(ReservesEventSignature::get()),
+				EthAbiCodec::encode(&reserves(1_000, 0, 1))
+			)]
+		);
+	});
+}
+
+#[test]
+fn stale_reserves_attestations_are_rejected() {
+	ExtBuilder::default().build().execute_with(|| {
+		verify_deposit(0, deposit(1_000));
+		verify_reserves(1, reserves(1_000, 0, 2));
+		assert_eq!(Erc20Peg::erc20_reserves(token_address()).map(|r| r.nonce), Some(2));
+
+		assert_noop!(
+			Erc20Peg::attest_reserves(
+				Origin::signed(VALIDATORS[0]),
+				H256::from_low_u64_be(1),
+				reserves(1_000, 0, 2)
+			),
+			Error::<Test>::StaleReserves
+		);
+		// an older attestation verified after a newer one
+		verify_reserves(2, reserves(500, 0, 1));
+		assert!(has_event(RawEvent::ReservesAttestationFail(2)));
+		assert_eq!(
+			Erc20Peg::erc20_reserves(token_address()),
+			Some(ReservesAttestation {
+				block: 1,
+				nonce: 2,
+				balance: 1_000,
+				expected: 1_000,
+			})
+		);
+		assert!(!Erc20Peg::asset_paused(TOKEN_ASSET_ID));
+	});
+}
+
+#[test]
+fn reserves_mismatch_beyond_default_tolerance_pauses_asset() {
+	ExtBuilder::default().build().execute_with(|| {
+		verify_deposit(0, deposit(1_000));
+
+		// within 1% of the expected reserves
+		verify_reserves(1, reserves(1_010, 0, 1));
+		assert!(has_event(RawEvent::ReservesAttested(TOKEN_ASSET_ID, 1_010, 1_000)));
+		assert!(!Erc20Peg::asset_paused(TOKEN_ASSET_ID));
+
+		verify_reserves(2, reserves(989, 0, 2));
+		assert!(has_event(RawEvent::ReservesMismatch(TOKEN_ASSET_ID, 989, 1_000)));
+		assert!(Erc20Peg::asset_paused(TOKEN_ASSET_ID));
+	});
+}
+
+#[test]
+fn reserves_tolerance_of_asset() {
+	ExtBuilder::default().build().execute_with(|| {
+		verify_deposit(0, deposit(1_000));
+		assert_noop!(
+			Erc20Peg::set_reserves_tolerance(Origin::signed(1), TOKEN_ASSET_ID, Some(0)),
+			BadOrigin
+		);
+		assert_ok!(Erc20Peg::set_reserves_tolerance(
+			Origin::root(),
+			TOKEN_ASSET_ID,
+			Some(0)
+		));
+		assert!(has_event(RawEvent::SetReservesTolerance(TOKEN_ASSET_ID, Some(0))));
+
+		verify_reserves(1, reserves(1_001, 0, 1));
+		assert!(has_event(RawEvent::ReservesMismatch(TOKEN_ASSET_ID, 1_001, 1_000)));
+		assert!(Erc20Peg::asset_paused(TOKEN_ASSET_ID));
+		assert_ok!(Erc20Peg::resume_asset(Origin::root(), TOKEN_ASSET_ID));
+
+		// the default applies once unset
+		assert_ok!(Erc20Peg::set_reserves_tolerance(Origin::root(), TOKEN_ASSET_ID, None));
+		assert!(Erc20Peg::reserves_tolerance(TOKEN_ASSET_ID).is_none());
+		verify_reserves(2, reserves(1_001, 0, 2));
+		assert!(!Erc20Peg::asset_paused(TOKEN_ASSET_ID));
+	});
+}
+
+#[test]
+fn reserves_include_withdrawals_in_flight() {
+	ExtBuilder::default().build().execute_with(|| {
+		verify_deposit(0, deposit(1_000));
+		assert_ok!(Erc20Peg::set_reserves_tolerance(
+			Origin::root(),
+			TOKEN_ASSET_ID,
+			Some(0)
+		));
+		assert_ok!(Erc20Peg::activate_withdrawals(Origin::root(), true));
+		assert_ok!(Erc20Peg::withdraw(
+			Origin::signed(BENEFICIARY),
+			TOKEN_ASSET_ID,
+			100,
+			EthAddress::from_low_u64_be(7)
+		));
+
+		// the withdrawal is yet to be redeemed
+		verify_reserves(1, reserves(1_000, 0, 1));
+		assert!(has_event(RawEvent::ReservesAttested(TOKEN_ASSET_ID, 1_000, 1_000)));
+		// the withdrawal is redeemed
+		verify_reserves(2, reserves(900, 100, 2));
+		assert!(has_event(RawEvent::ReservesAttested(TOKEN_ASSET_ID, 900, 900)));
+		assert_eq!(
+			Erc20Peg::erc20_ledger(token_address()),
+			Erc20Ledger {
+				deposited: 1_000,
+				withdrawn: 100,
+				redeemed: 100,
+			}
+		);
+		assert!(!Erc20Peg::asset_paused(TOKEN_ASSET_ID));
+	});
+}
+
+#[test]
+fn failed_cennz_deposit_is_not_added_to_ledger() {
+	let peg_account: u64 = PegPalletId::get().into_account();
+	ExtBuilder::default()
+		.endowed_accounts(vec![peg_account])
+		.build()
+		.execute_with(|| {
+			let cennz_address = EthAddress::from_low_u64_be(66);
+			Erc20ToAssetId::insert(cennz_address, STAKING_ASSET_ID);
+			assert_ok!(Erc20Peg::activate_cennz_deposits(Origin::root()));
+			let cennz_deposit = |amount| Erc20DepositEvent {
+				token_address: cennz_address,
+				..deposit(amount)
+			};
+
+			// the peg account holds too little CENNZ
+			verify_deposit(0, cennz_deposit(2_000_000));
+			assert!(has_event(RawEvent::Erc20DepositFail(0)));
+			assert_eq!(Erc20Peg::erc20_ledger(cennz_address).deposited, 0);
+
+			verify_deposit(1, cennz_deposit(1_000));
+			assert!(has_event(RawEvent::Erc20Deposit(
+				1,
+				STAKING_ASSET_ID,
+				1_000,
+				BENEFICIARY
+			)));
+			assert_eq!(GenericAsset::free_balance(STAKING_ASSET_ID, &BENEFICIARY), 1_000);
+			assert_eq!(Erc20Peg::erc20_ledger(cennz_address).deposited, 1_000);
+		});
+}
+
+#[test]
+fn reserves_attestations_are_requested_each_period() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			Erc20Peg::set_reserves_attestation_period(Origin::signed(1), 10),
+			BadOrigin
+		);
+		assert_ok!(Erc20Peg::set_reserves_attestation_period(Origin::root(), 10));
+		assert!(has_event(RawEvent::SetReservesAttestationPeriod(10)));
+
+		// the first deposit requests an attestation
+		verify_deposit(0, deposit(1_000));
+		assert!(has_event(RawEvent::ReservesAttestationRequested(token_address(), 11)));
+		assert_eq!(Erc20Peg::reserves_attestation_due(token_address()), Some(11));
+
+		// an attestation verified in time requests the next
+		System::set_block_number(5);
+		verify_reserves(1, reserves(1_000, 0, 1));
+		assert!(has_event(RawEvent::ReservesAttestationRequested(token_address(), 15)));
+		assert_eq!(Erc20Peg::reserves_attestation_due(token_address()), Some(15));
+		assert!(!ReservesAttestationDeadlines::<Test>::contains_key(11, token_address()));
+		System::set_block_number(11);
+		Erc20Peg::on_initialize(11);
+		assert!(!Erc20Peg::asset_paused(TOKEN_ASSET_ID));
+
+		// the next is overdue
+		System::set_block_number(15);
+		Erc20Peg::on_initialize(15);
+		assert!(has_event(RawEvent::ReservesAttestationOverdue(TOKEN_ASSET_ID)));
+		assert!(Erc20Peg::asset_paused(TOKEN_ASSET_ID));
+		assert!(has_event(RawEvent::ReservesAttestationRequested(token_address(), 25)));
+		assert_eq!(Erc20Peg::reserves_attestation_due(token_address()), Some(25));
+	});
+}
+
+#[test]
+fn disabled_reserves_attestations_expire_without_pausing() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Erc20Peg::set_reserves_attestation_period(Origin::root(), 10));
+		verify_deposit(0, deposit(1_000));
+		assert_eq!(Erc20Peg::reserves_attestation_due(token_address()), Some(11));
+
+		assert_ok!(Erc20Peg::set_reserves_attestation_period(Origin::root(), 0));
+		System::set_block_number(11);
+		Erc20Peg::on_initialize(11);
+		assert!(!has_event(RawEvent::ReservesAttestationOverdue(TOKEN_ASSET_ID)));
+		assert!(!Erc20Peg::asset_paused(TOKEN_ASSET_ID));
+		assert!(Erc20Peg::reserves_attestation_due(token_address()).is_none());
+
+		// nothing is requested while disabled
+		verify_deposit(1, deposit(1_000));
+		assert!(Erc20Peg::reserves_attestation_due(token_address()).is_none());
+	});
+}
+
+#[test]
+fn migration_seeds_ledgers_of_bridged_erc20s() {
+	ExtBuilder::default().build().execute_with(|| {
+		// bridged before ledgers were tracked
+		verify_deposit(0, deposit(1_000));
+		let cennz_address = EthAddress::from_low_u64_be(66);
+		Erc20ToAssetId::insert(cennz_address, STAKING_ASSET_ID);
+		Erc20Ledgers::remove(token_address());
+		StorageVersion::put(Releases::V0 as u32);
+
+		Erc20Peg::on_runtime_upgrade();
+		assert_eq!(StorageVersion::get(), Releases::V1 as u32);
+		assert_eq!(Erc20Peg::erc20_ledger(token_address()).deposited, 1_000);
+		assert_eq!(Erc20Peg::erc20_ledger(cennz_address).deposited, 0);
+		assert!(Erc20Peg::erc20_ledger_baseline_pending(token_address()));
+		assert!(Erc20Peg::erc20_ledger_baseline_pending(cennz_address));
+
+		// withdrawals before the upgrade were redeemed
+		verify_reserves(1, reserves(1_000, 300, 1));
+		assert!(has_event(RawEvent::ReservesAttested(TOKEN_ASSET_ID, 1_000, 1_000)));
+		assert_eq!(
+			Erc20Peg::erc20_ledger(token_address()),
+			Erc20Ledger {
+				deposited: 1_000,
+				withdrawn: 300,
+				redeemed: 300,
+			}
+		);
+		assert!(!Erc20Peg::erc20_ledger_baseline_pending(token_address()));
+
+		// CENNZ deposits are taken from its first attestation
+		verify_reserves(
+			2,
+			Erc20ReservesEvent {
+				token_address: cennz_address,
+				..reserves(5_000, 700, 1)
+			},
+		);
+		assert!(has_event(RawEvent::ReservesAttested(STAKING_ASSET_ID, 5_000, 5_000)));
+		assert_eq!(
+			Erc20Peg::erc20_ledger(cennz_address),
+			Erc20Ledger {
+				deposited: 5_700,
+				withdrawn: 700,
+				redeemed: 700,
+			}
+		);
+		assert!(!Erc20Peg::asset_paused(STAKING_ASSET_ID));
+		assert!(!Erc20Peg::erc20_ledger_baseline_pending(cennz_address));
+	});
+}
@@ -12,6 +12,7 @@
 *     https://centrality.ai/licenses/gplv3.txt
 *     https://centrality.ai/licenses/lgplv3.txt
 */
+use cennznet_primitives::types::Balance;
 use codec::{Decode, Encode};
 pub use crml_support::{EthAbiCodec, H160, H256, U256};
 use scale_info::TypeInfo;
@@ -33,9 +34,179 @@ pub struct Erc20DepositEvent {
 	pub beneficiary: H256,
 }
 
+/// A reserves event made by the ERC20 peg contract on Ethereum, attesting its holdings of a token
+#[derive(Debug, Default, Clone, PartialEq, Decode, Encode, TypeInfo)]
+pub struct Erc20ReservesEvent {
+	/// The ERC20 token address / type held
+	pub token_address: EthAddress,
+	/// The balance (in 'wei') held by the contract
+	pub balance: U256,
+	/// The total amount (in 'wei') ever withdrawn from the contract
+	pub withdrawn: U256,
+	/// The contract's count of reserves attestations, orders attestations of the token
+	pub nonce: U256,
+}
+
+/// Amounts of an ERC20 bridged by the peg
+#[derive(Debug, Default, Clone, PartialEq, Eq, Decode, Encode, TypeInfo)]
+pub struct Erc20Ledger {
+	/// Total amount deposited to CENNZnet
+	pub deposited: Balance,
+	/// Total amount burnt on CENNZnet for withdrawal
+	pub withdrawn: Balance,
+	/// Total amount withdrawn from the contract on Ethereum, as last attested
+	pub redeemed: Balance,
+}
+
+impl Erc20Ledger {
+	/// Amount burnt for withdrawal on CENNZnet but not yet withdrawn from the contract
+	pub fn in_flight(&self) -> Balance {
+		self.withdrawn.saturating_sub(self.redeemed)
+	}
+}
+
+/// The last attestation of the peg contract's reserves of an ERC20
+#[derive(Debug, Default, Clone, PartialEq, Eq, Decode, Encode, TypeInfo)]
+pub struct ReservesAttestation<BlockNumber> {
+	/// The block the attestation was verified
+	pub block: BlockNumber,
+	/// The contract's nonce of the attestation
+	pub nonce: u64,
+	/// The balance held by the contract
+	pub balance: Balance,
+	/// The balance the contract was expected to hold i.e. the bridged supply plus in-flight withdrawals
+	pub expected: Balance,
+}
+
+/// Deposit and withdrawal caps of an asset over a window of blocks
+/// Reaching either cap pauses the asset until it is resumed by governance
+#[derive(Debug, Default, Clone, PartialEq, Eq, Decode, Encode, TypeInfo)]
+pub struct AssetCap<BlockNumber> {
+	/// Max. amount deposited per window
+	pub deposit_cap: Balance,
+	/// Max. amount withdrawn per window
+	pub withdrawal_cap: Balance,
+	/// Length of the window (blocks)
+	pub window: BlockNumber,
+}
+
+/// Amounts of an asset bridged over the current window
+#[derive(Debug, Default, Clone, PartialEq, Eq, Decode, Encode, TypeInfo)]
+pub struct CapUsage<BlockNumber> {
+	/// The block the window started
+	pub window_start: BlockNumber,
+	/// Amount deposited in the window
+	pub deposited: Balance,
+	/// Amount withdrawn in the window
+	pub withdrawn: Balance,
+}
+
+/// Remaining capacity of an asset over the current window
+#[derive(Debug, Default, Clone, PartialEq, Eq, Decode, Encode, TypeInfo)]
+pub struct PegCapacity {
+	/// Amount that may still be deposited, `None` if uncapped
+	pub deposit: Option<Balance>,
+	/// Amount that may still be withdrawn, `None` if uncapped
+	pub withdrawal: Option<Balance>,
+	/// Whether the asset is paused
+	pub paused: bool,
+}
+
+/// The asset a withdrawal fee is paid in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Decode, Encode, TypeInfo)]
+pub enum FeeAsset {
+	/// The asset being withdrawn
+	Withdrawn,
+	/// The network fee currency i.e. CPAY
+	Spending,
+}
+
+/// A fee charged on withdrawals of an asset
+#[derive(Debug, Clone, PartialEq, Eq, Decode, Encode, TypeInfo)]
+pub struct WithdrawalFee<AccountId> {
+	/// The asset the fee is paid in
+	pub asset: FeeAsset,
+	/// The fee amount
+	pub amount: Balance,
+	/// The account paid the fee e.g. a relayer or the treasury
+	pub recipient: AccountId,
+}
+
+/// A batch of withdrawals proven by the merkle root of their messages
+#[derive(Debug, Default, Clone, PartialEq, Decode, Encode, TypeInfo)]
+pub struct WithdrawalBatch {
+	/// The event proof of the batch root
+	pub event_proof_id: u64,
+	/// The merkle root of the batch
+	pub root: H256,
+	/// The batched withdraw messages, in leaf order
+	pub withdrawals: Vec<WithdrawMessage>,
+}
+
+/// Inclusion proof of a batched withdrawal, for redemption on Ethereum
+#[derive(Debug, Default, Clone, PartialEq, Decode, Encode, TypeInfo)]
+pub struct WithdrawalProof {
+	/// The event proof of the batch root
+	pub event_proof_id: u64,
+	/// The merkle root of the batch
+	pub root: H256,
+	/// The Id of the batch
+	pub batch_id: u64,
+	/// The index of the withdrawal in the batch
+	pub index: u32,
+	/// The withdraw message
+	pub withdrawal: WithdrawMessage,
+	/// Sibling hashes from the withdrawal's leaf up to the root
+	pub proof: Vec<H256>,
+}
+
+/// The merkle root of a withdrawal batch to prove and submit to Ethereum
+/// Allowing redemption of each batched withdrawal with its inclusion proof
+#[derive(Debug, Default, Clone, PartialEq, Decode, Encode)]
+pub struct WithdrawBatchMessage {
+	/// The merkle root of `keccak256` hashed `BatchedWithdrawMessage`s
+	pub root: H256,
+}
+
+impl EthAbiCodec for WithdrawBatchMessage {
+	fn encode(&self) -> Vec<u8> {
+		self.root.to_fixed_bytes().to_vec()
+	}
+
+	fn decode(_data: &[u8]) -> Option<Self> {
+		None
+	}
+}
+
+/// A withdraw message at its position in a batch, hashed into a leaf of the batch's merkle tree
+#[derive(Debug, Default, Clone, PartialEq, Decode, Encode)]
+pub struct BatchedWithdrawMessage {
+	/// The Id of the batch
+	pub batch_id: u64,
+	/// The index of the withdrawal in the batch
+	pub index: u32,
+	/// The withdraw message
+	pub message: WithdrawMessage,
+}
+
+impl EthAbiCodec for BatchedWithdrawMessage {
+	/// Encode `BatchedWithdrawMessage` into 32-byte words i.e. `(batch_id, index, token_address, amount, beneficiary)`
+	/// https://docs.soliditylang.org/en/v0.5.3/abi-spec.html#formal-specification-of-the-encoding
+	fn encode(&self) -> Vec<u8> {
+		let mut buf = [0_u8; 32 * 2];
+		buf[24..32].copy_from_slice(&self.batch_id.to_be_bytes());
+		buf[60..64].copy_from_slice(&self.index.to_be_bytes());
+		[&buf[..], &EthAbiCodec::encode(&self.message)].concat()
+	}
+
+	fn decode(_data: &[u8]) -> Option<Self> {
+		None
+	}
+}
+
 /// A withdraw message to prove and submit to Ethereum
 /// Allowing redemption of ERC20s
-#[derive(Debug, Default, Clone, PartialEq, Decode, Encode)]
+#[derive(Debug, Default, Clone, PartialEq, Decode, Encode, TypeInfo)]
 pub struct WithdrawMessage {
 	/// The ERC20 token address / type deposited
 	pub token_address: EthAddress,
@@ -57,7 +228,7 @@ impl EthAbiCodec for WithdrawMessage {
 	}
 
 	fn decode(_data: &[u8]) -> Option<Self> {
-		unimplemented!();
+		None
 	}
 }
 
@@ -89,9 +260,50 @@ impl EthAbiCodec for Erc20DepositEvent {
 	}
 }
 
+impl EthAbiCodec for Erc20ReservesEvent {
+	/// Encode `Erc20ReservesEvent` into 32-byte words
+	/// https://docs.soliditylang.org/en/v0.5.3/abi-spec.html#formal-specification-of-the-encoding
+	fn encode(&self) -> Vec<u8> {
+		let mut buf = [0_u8; 32 * 4];
+		buf[12..32].copy_from_slice(&self.token_address.to_fixed_bytes());
+		buf[32..64].copy_from_slice(&Into::<[u8; 32]>::into(self.balance));
+		buf[64..96].copy_from_slice(&Into::<[u8; 32]>::into(self.withdrawn));
+		buf[96..128].copy_from_slice(&Into::<[u8; 32]>::into(self.nonce));
+		buf.to_vec()
+	}
+	/// Receives Ethereum log 'data' and decodes it
+	fn decode(data: &[u8]) -> Option<Self> {
+		// Expect 4 words of data
+		if data.len() != 4 * 32 {
+			return None;
+		}
+		let token_address = H160::from(&data[12..32].try_into().expect("20 bytes decode"));
+		let balance = data[32..64].into();
+		let withdrawn = data[64..96].into();
+		let nonce = data[96..128].into();
+
+		Some(Self {
+			token_address,
+			balance,
+			withdrawn,
+			nonce,
+		})
+	}
+}
+
+/// A value placed in storage that represents the current version of the erc20 peg storage
+/// Used by the `on_runtime_upgrade` logic to determine whether we run storage migration logic
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, TypeInfo)]
+pub enum Releases {
+	/// ERC20 ledgers untracked
+	V0 = 0,
+	/// ERC20 ledgers seeded for reserves reconciliation
+	V1 = 1,
+}
+
 #[cfg(test)]
 mod test {
-	use super::{Erc20DepositEvent, EthAbiCodec};
+	use super::{BatchedWithdrawMessage, Erc20DepositEvent, Erc20ReservesEvent, EthAbiCodec, WithdrawMessage};
 	use crml_support::{H160, H256, U256};
 
 	#[test]
@@ -111,6 +323,28 @@ mod test {
 		);
 	}
 
+	#[test]
+	fn batched_withdraw_message_encode() {
+		let message = BatchedWithdrawMessage {
+			batch_id: 3,
+			index: 9,
+			message: WithdrawMessage {
+				token_address: H160::from_low_u64_be(55),
+				amount: U256::from(123),
+				beneficiary: H160::from_low_u64_be(77),
+			},
+		};
+		let mut expected = vec![0_u8; 5 * 32];
+		expected[31] = 3;
+		expected[63] = 9;
+		expected[95] = 55;
+		expected[127] = 123;
+		expected[159] = 77;
+		assert_eq!(EthAbiCodec::encode(&message), expected);
+		assert!(<BatchedWithdrawMessage as EthAbiCodec>::decode(&expected).is_none());
+		assert!(<WithdrawMessage as EthAbiCodec>::decode(&expected[64..]).is_none());
+	}
+
 	#[test]
 	fn deposit_event_decode() {
 		let raw = vec![
@@ -127,4 +361,21 @@ mod test {
 			}
 		);
 	}
+
+	#[test]
+	fn reserves_event_decode() {
+		let event = Erc20ReservesEvent {
+			token_address: H160::from_low_u64_be(55),
+			balance: U256::from(123),
+			withdrawn: U256::from(77),
+			nonce: U256::from(3),
+		};
+		let raw = EthAbiCodec::encode(&event);
+		assert_eq!(raw[31], 55);
+		assert_eq!(raw[63], 123);
+		assert_eq!(raw[95], 77);
+		assert_eq!(raw[127], 3);
+		assert_eq!(Erc20ReservesEvent::decode(&raw).expect("it decodes"), event);
+		assert!(Erc20ReservesEvent::decode(&raw[..96]).is_none());
+	}
 }
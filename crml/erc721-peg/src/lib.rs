@@ -0,0 +1,345 @@
+/* Copyright 2021 Centrality Investments Limited
+*
+* Licensed under the LGPL, Version 3.0 (the "License");
+* you may not use this file except in compliance with the License.
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific language governing permissions and
+* limitations under the License.
+* You may obtain a copy of the License at the root of this project source code,
+* or at:
+*     https://centrality.ai/licenses/gplv3.txt
+*     https://centrality.ai/licenses/lgplv3.txt
+*/
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Decode;
+use crml_nft::{CollectionId, SerialNumber, SeriesId, TokenId};
+use crml_support::{EthChainId, EventClaimSubscriber, EventClaimVerifier, NonFungibleTokens};
+use frame_support::{
+	decl_error, decl_event, decl_module, decl_storage, ensure, log,
+	traits::{Get, IsType},
+	transactional, PalletId,
+};
+use frame_system::{ensure_root, ensure_signed};
+use sp_runtime::{traits::AccountIdConversion, DispatchError};
+use sp_std::prelude::*;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+mod types;
+use types::*;
+
+/// Name of collections created for ERC721 contracts without metadata
+const DEFAULT_COLLECTION_NAME: &[u8] = b"bridged-erc721";
+
+pub trait Config: frame_system::Config {
+	/// An onchain address for this pallet
+	type PegPalletId: Get<PalletId>;
+	/// The EVM event signature of an ERC721 deposit
+	type DepositEventSignature: Get<[u8; 32]>;
+	/// The EVM chain where the peg contract is deployed
+	type ChainId: Get<EthChainId>;
+	/// Submits event claims for Ethereum
+	type EthBridge: EventClaimVerifier<AccountId = Self::AccountId>;
+	/// Mints & burns the mirrored tokens
+	type Nft: NonFungibleTokens<
+		AccountId = Self::AccountId,
+		CollectionId = CollectionId,
+		SeriesId = SeriesId,
+		SerialNumber = SerialNumber,
+	>;
+	/// The overarching event type.
+	type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+}
+
+decl_storage! {
+	trait Store for Module<T: Config> as Erc721Peg {
+		/// Whether deposit are active
+		DepositsActive get(fn deposits_active): bool;
+		/// Whether withdrawals are active
+		WithdrawalsActive get(fn withdrawals_active): bool;
+		/// The peg contract address on Ethereum
+		ContractAddress get(fn contract_address): EthAddress;
+		/// Map ERC721 address to the NFT collection & series of its mirrored tokens
+		Erc721ToSeries get(fn erc721_to_series): map hasher(twox_64_concat) EthAddress => Option<(CollectionId, SeriesId)>;
+		/// Map NFT collection & series to the ERC721 address of its tokens
+		SeriesToErc721 get(fn series_to_erc721): map hasher(twox_64_concat) (CollectionId, SeriesId) => Option<EthAddress>;
+		/// Map ERC721 address & token Id to the serial number of its mirrored token
+		EthTokenToSerial get(fn eth_token_to_serial):
+			double_map hasher(twox_64_concat) EthAddress, hasher(blake2_128_concat) U256 => Option<SerialNumber>;
+		/// Map NFT collection & series, serial number to the ERC721 token Id it mirrors
+		SerialToEthToken get(fn serial_to_eth_token):
+			double_map hasher(twox_64_concat) (CollectionId, SeriesId), hasher(twox_64_concat) SerialNumber => Option<U256>;
+		/// Collection names for well-known ERC721 contracts
+		Erc721Meta get(fn erc721_meta): map hasher(twox_64_concat) EthAddress => Option<Vec<u8>>;
+		/// Verified deposits which could not be minted, kept for retry or refund (deposit Id => deposit)
+		FailedDeposits get(fn failed_deposit): map hasher(twox_64_concat) u64 => Option<Erc721DepositEvent>;
+	}
+}
+
+decl_event! {
+	pub enum Event<T> where AccountId = <T as frame_system::Config>::AccountId {
+		/// An erc721 deposit claim has started. (deposit Id, sender)
+		Erc721Claim(u64, AccountId),
+		/// A bridged erc721 deposit succeeded. (deposit Id, ERC721 address, ERC721 token Id, token, beneficiary)
+		Erc721Deposit(u64, EthAddress, U256, TokenId, AccountId),
+		/// A token was burnt for withdrawal on Ethereum as an ERC721 (withdrawal Id, token, ERC721 token Id, beneficiary)
+		Erc721Withdraw(u64, TokenId, U256, EthAddress),
+		/// A bridged erc721 deposit failed. (deposit Id)
+		Erc721DepositFail(u64),
+		/// A failed deposit was refunded on Ethereum (deposit Id, withdrawal Id, ERC721 address, ERC721 token Id, beneficiary)
+		Erc721DepositRefund(u64, u64, EthAddress, U256, EthAddress),
+		/// The peg contract address has been set
+		SetContractAddress(EthAddress),
+	}
+}
+
+decl_error! {
+	pub enum Error for Module<T: Config> {
+		/// Claim has bad account
+		InvalidAddress,
+		/// Deposits are inactive
+		DepositsPaused,
+		/// Withdrawals are inactive
+		WithdrawalsPaused,
+		/// The ERC721 token has already been bridged
+		TokenAlreadyBridged,
+		/// Withdrawals of this token are not supported
+		UnsupportedToken,
+		/// There is no failed deposit with the given Id
+		NoFailedDeposit,
+	}
+}
+
+decl_module! {
+	pub struct Module<T: Config> for enum Call where origin: T::Origin {
+
+		fn deposit_event() = default;
+
+		/// Activate/deactivate deposits (root only)
+		#[weight = 10_000_000]
+		pub fn activate_deposits(origin, activate: bool) {
+			ensure_root(origin)?;
+			DepositsActive::put(activate);
+		}
+
+		/// Activate/deactivate withdrawals (root only)
+		#[weight = 10_000_000]
+		pub fn activate_withdrawals(origin, activate: bool) {
+			ensure_root(origin)?;
+			WithdrawalsActive::put(activate);
+		}
+
+		#[weight = 50_000_000]
+		/// Submit deposit claim for an ethereum tx hash
+		/// The deposit details must be provided for cross-checking by notaries
+		/// Any caller may initiate a claim while only the intended beneficiary will receive the token.
+		#[transactional]
+		pub fn deposit_claim(origin, tx_hash: H256, claim: Erc721DepositEvent) {
+			let origin = ensure_signed(origin)?;
+			ensure!(Self::deposits_active(), Error::<T>::DepositsPaused);
+			// fail a claim early for a token that is already on CENNZnet
+			ensure!(
+				!EthTokenToSerial::contains_key(claim.token_address, claim.token_id),
+				Error::<T>::TokenAlreadyBridged
+			);
+			// fail a claim if beneficiary is not a valid CENNZnet address
+			ensure!(T::AccountId::decode(&mut &claim.beneficiary.0[..]).is_ok(), Error::<T>::InvalidAddress);
+
+			// the submitter pays the bridge's claim deposit
+			let event_claim_id = T::EthBridge::submit_event_claim(
+					&origin,
+					T::ChainId::get(),
+					&Self::contract_address().into(),
+					&T::DepositEventSignature::get().into(),
+					&tx_hash,
+					&EthAbiCodec::encode(&claim),
+			)?;
+
+			Self::deposit_event(<Event<T>>::Erc721Claim(event_claim_id, origin));
+		}
+
+		#[weight = 50_000_000]
+		/// Withdraw a bridged token from CENNZnet in exchange for its ERC721
+		/// The token will be burnt and a proof generated to allow redemption of the ERC721 on Ethereum
+		#[transactional]
+		pub fn withdraw(origin, token_id: TokenId, beneficiary: EthAddress) {
+			let origin = ensure_signed(origin)?;
+			ensure!(Self::withdrawals_active(), Error::<T>::WithdrawalsPaused);
+
+			// only tokens minted by the peg may be withdrawn
+			let (collection_id, series_id, serial_number) = token_id;
+			let token_address = Self::series_to_erc721((collection_id, series_id)).ok_or(Error::<T>::UnsupportedToken)?;
+			let eth_token_id = Self::serial_to_eth_token((collection_id, series_id), serial_number).ok_or(Error::<T>::UnsupportedToken)?;
+
+			T::Nft::burn(&origin, collection_id, series_id, serial_number)?;
+			EthTokenToSerial::remove(token_address, eth_token_id);
+			SerialToEthToken::remove((collection_id, series_id), serial_number);
+
+			let message = Erc721WithdrawMessage {
+				token_address,
+				token_id: eth_token_id,
+				beneficiary
+			};
+			let event_proof_id = T::EthBridge::generate_event_proof(T::ChainId::get(), &message)?;
+
+			Self::deposit_event(<Event<T>>::Erc721Withdraw(event_proof_id, token_id, eth_token_id, beneficiary));
+		}
+
+		#[weight = 50_000_000]
+		/// Retry minting a failed deposit to its beneficiary
+		/// e.g. once the mirror of an already bridged token has been withdrawn
+		#[transactional]
+		pub fn retry_failed_deposit(origin, deposit_id: u64) {
+			let _ = ensure_signed(origin)?;
+			let deposit = FailedDeposits::take(deposit_id).ok_or(Error::<T>::NoFailedDeposit)?;
+			let (token_address, eth_token_id) = (deposit.token_address, deposit.token_id);
+			let (token_id, beneficiary) = Self::do_deposit(deposit)?;
+			Self::deposit_event(<Event<T>>::Erc721Deposit(deposit_id, token_address, eth_token_id, token_id, beneficiary));
+		}
+
+		#[weight = 50_000_000]
+		/// Refund a failed deposit, generating a proof to release its ERC721 to `beneficiary` on Ethereum (requires governance)
+		/// Governance should check the ERC721 is held by the peg contract on behalf of the depositor
+		#[transactional]
+		pub fn refund_failed_deposit(origin, deposit_id: u64, beneficiary: EthAddress) {
+			ensure_root(origin)?;
+			let deposit = FailedDeposits::take(deposit_id).ok_or(Error::<T>::NoFailedDeposit)?;
+
+			let message = Erc721WithdrawMessage {
+				token_address: deposit.token_address,
+				token_id: deposit.token_id,
+				beneficiary
+			};
+			let event_proof_id = T::EthBridge::generate_event_proof(T::ChainId::get(), &message)?;
+
+			Self::deposit_event(<Event<T>>::Erc721DepositRefund(
+				deposit_id,
+				event_proof_id,
+				deposit.token_address,
+				deposit.token_id,
+				beneficiary,
+			));
+		}
+
+		#[weight = 1_000_000]
+		#[transactional]
+		/// Set the peg contract address on Ethereum (requires governance)
+		pub fn set_contract_address(origin, eth_address: EthAddress) {
+			ensure_root(origin)?;
+			ContractAddress::put(eth_address);
+			Self::deposit_event(<Event<T>>::SetContractAddress(eth_address));
+		}
+
+		#[weight = {
+			1_000_000 * details.len() as u64
+		}]
+		/// Set the collection name for given ERC721 addresses (requires governance)
+		/// Applies to collections created on the next first deposit of the contract
+		/// details: `[(contract address, collection name)]`
+		pub fn set_erc721_meta(origin, details: Vec<(EthAddress, Vec<u8>)>) {
+			ensure_root(origin)?;
+			for (address, name) in details {
+				Erc721Meta::insert(address, name);
+			}
+		}
+	}
+}
+
+impl<T: Config> Module<T> {
+	/// The NFT collection & series mirroring `token_address`, created on its first deposit
+	fn deposit_series(token_address: EthAddress) -> Result<(CollectionId, SeriesId), DispatchError> {
+		match Self::erc721_to_series(token_address) {
+			None => {
+				// the collection is owned by the peg so that no one else may mint into it
+				let name = Self::erc721_meta(token_address).unwrap_or_else(|| DEFAULT_COLLECTION_NAME.to_vec());
+				let collection_id = T::Nft::create_collection(&T::PegPalletId::get().into_account(), name)?;
+				let series_id = T::Nft::create_series(collection_id)?;
+				Erc721ToSeries::insert(token_address, (collection_id, series_id));
+				SeriesToErc721::insert((collection_id, series_id), token_address);
+
+				Ok((collection_id, series_id))
+			}
+			Some(series) => Ok(series),
+		}
+	}
+
+	/// fulfil a deposit claim for the given event, minting the mirrored token to its beneficiary
+	#[transactional]
+	fn do_deposit(verified_event: Erc721DepositEvent) -> Result<(TokenId, T::AccountId), DispatchError> {
+		let Erc721DepositEvent {
+			token_address,
+			token_id: eth_token_id,
+			beneficiary,
+		} = verified_event;
+		// the token may have been deposited by another claim since this one was submitted
+		ensure!(
+			!EthTokenToSerial::contains_key(token_address, eth_token_id),
+			Error::<T>::TokenAlreadyBridged
+		);
+		let (collection_id, series_id) = Self::deposit_series(token_address)?;
+
+		// checked at the time of initiating the verified_event that beneficiary value is valid and this op will not fail qed.
+		let beneficiary: T::AccountId = T::AccountId::decode(&mut &beneficiary.0[..]).unwrap();
+		let serial_number = T::Nft::mint(&beneficiary, collection_id, series_id)?;
+		EthTokenToSerial::insert(token_address, eth_token_id, serial_number);
+		SerialToEthToken::insert((collection_id, series_id), serial_number, eth_token_id);
+
+		Ok(((collection_id, series_id, serial_number), beneficiary))
+	}
+}
+
+impl<T: Config> EventClaimSubscriber for Module<T> {
+	fn on_success(
+		event_claim_id: u64,
+		chain_id: EthChainId,
+		contract_address: &EthAddress,
+		event_type: &H256,
+		event_data: &[u8],
+	) {
+		if chain_id == T::ChainId::get()
+			&& *contract_address == Self::contract_address()
+			&& *event_type == H256::from(T::DepositEventSignature::get())
+		{
+			if let Some(deposit_event) = <Erc721DepositEvent as EthAbiCodec>::decode(event_data) {
+				let (token_address, eth_token_id) = (deposit_event.token_address, deposit_event.token_id);
+				match Self::do_deposit(deposit_event.clone()) {
+					Ok((token_id, beneficiary)) => Self::deposit_event(<Event<T>>::Erc721Deposit(
+						event_claim_id,
+						token_address,
+						eth_token_id,
+						token_id,
+						beneficiary,
+					)),
+					Err(_err) => {
+						// the ERC721 is locked in the peg contract, keep the deposit for retry or refund
+						FailedDeposits::insert(event_claim_id, deposit_event);
+						Self::deposit_event(<Event<T>>::Erc721DepositFail(event_claim_id));
+					}
+				}
+			} else {
+				// input data should be valid, we do not expect to fail here
+				log::error!("📌 ERC721 deposit claim failed unexpectedly: {:?}", event_data);
+			}
+		}
+	}
+	fn on_failure(
+		event_claim_id: u64,
+		chain_id: EthChainId,
+		contract_address: &H160,
+		event_type: &H256,
+		_event_data: &[u8],
+	) {
+		if chain_id == T::ChainId::get()
+			&& *contract_address == Self::contract_address()
+			&& *event_type == H256::from(T::DepositEventSignature::get())
+		{
+			Self::deposit_event(<Event<T>>::Erc721DepositFail(event_claim_id));
+		}
+	}
+}
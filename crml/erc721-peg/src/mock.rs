@@ -0,0 +1,231 @@
+/* Copyright 2021 Centrality Investments Limited
+*
+* Licensed under the LGPL, Version 3.0 (the "License");
+* you may not use this file except in compliance with the License.
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific language governing permissions and
+* limitations under the License.
+* You may obtain a copy of the License at the root of this project source code,
+* or at:
+*     https://centrality.ai/licenses/gplv3.txt
+*     https://centrality.ai/licenses/lgplv3.txt
+*/
+
+use crate as crml_erc721_peg;
+use crate::types::{Erc721DepositEvent, EthAddress};
+use cennznet_primitives::types::{AssetId, Balance};
+use crml_generic_asset::impls::TransferDustImbalance;
+use crml_support::{EthAbiCodec, EthChainId, EventClaimSubscriber, EventClaimVerifier, H160};
+use frame_support::{parameter_types, PalletId};
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+	DispatchError,
+};
+use std::cell::RefCell;
+
+pub type AccountId = u64;
+
+/// The EVM chain of the peg contract
+pub const CHAIN_ID: EthChainId = 1;
+/// The ERC721 contract bridged in these tests
+pub const TOKEN: u64 = 55;
+/// The beneficiary of deposits in these tests
+pub const BENEFICIARY: AccountId = 5;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		GenericAsset: crml_generic_asset::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Nft: crml_nft::{Pallet, Call, Storage, Event<T>},
+		Erc721Peg: crml_erc721_peg::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+impl frame_system::Config for Test {
+	type BlockWeights = ();
+	type BlockLength = ();
+	type BaseCallFilter = frame_support::traits::Everything;
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Call = Call;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type BlockHashCount = BlockHashCount;
+	type Event = Event;
+	type DbWeight = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+}
+
+parameter_types! {
+	pub const TreasuryPalletId: PalletId = PalletId(*b"py/trsry");
+}
+impl crml_generic_asset::Config for Test {
+	type AssetId = AssetId;
+	type Balance = Balance;
+	type Event = Event;
+	type OnDustImbalance = TransferDustImbalance<TreasuryPalletId>;
+	type WeightInfo = ();
+}
+
+thread_local! {
+	static NEXT_EVENT_CLAIM_ID: RefCell<u64> = RefCell::new(0);
+	static NEXT_EVENT_PROOF_ID: RefCell<u64> = RefCell::new(0);
+	/// Claims submitted to the bridge (submitter, event signature, event data)
+	static EVENT_CLAIMS: RefCell<Vec<(AccountId, H256, Vec<u8>)>> = RefCell::new(vec![]);
+	/// Messages proven by the bridge (abi encoded)
+	static EVENT_PROOFS: RefCell<Vec<Vec<u8>>> = RefCell::new(vec![]);
+}
+
+/// Records event claims & proofs in place of the eth bridge
+pub struct MockEthBridge;
+
+impl MockEthBridge {
+	/// Claims submitted to the bridge (submitter, event signature, event data)
+	pub fn event_claims() -> Vec<(AccountId, H256, Vec<u8>)> {
+		EVENT_CLAIMS.with(|claims| claims.borrow().clone())
+	}
+	/// Messages proven by the bridge (abi encoded)
+	pub fn event_proofs() -> Vec<Vec<u8>> {
+		EVENT_PROOFS.with(|proofs| proofs.borrow().clone())
+	}
+}
+
+impl EventClaimVerifier for MockEthBridge {
+	type AccountId = AccountId;
+
+	fn submit_event_claim(
+		submitter: &Self::AccountId,
+		_chain_id: EthChainId,
+		_contract_address: &H160,
+		event_signature: &H256,
+		_tx_hash: &H256,
+		event_data: &[u8],
+	) -> Result<u64, DispatchError> {
+		EVENT_CLAIMS.with(|claims| {
+			claims
+				.borrow_mut()
+				.push((*submitter, *event_signature, event_data.to_vec()))
+		});
+		Ok(NEXT_EVENT_CLAIM_ID.with(|id| {
+			let event_claim_id = *id.borrow();
+			*id.borrow_mut() += 1;
+			event_claim_id
+		}))
+	}
+
+	fn generate_event_proof<M: EthAbiCodec>(_chain_id: EthChainId, message: &M) -> Result<u64, DispatchError> {
+		EVENT_PROOFS.with(|proofs| proofs.borrow_mut().push(message.encode()));
+		Ok(NEXT_EVENT_PROOF_ID.with(|id| {
+			let event_proof_id = *id.borrow();
+			*id.borrow_mut() += 1;
+			event_proof_id
+		}))
+	}
+}
+
+parameter_types! {
+	pub const DefaultListingDuration: u64 = 5;
+	pub const MaxAttributeLength: u8 = 140;
+}
+impl crml_nft::Config for Test {
+	type Event = Event;
+	type MultiCurrency = GenericAsset;
+	type MaxAttributeLength = MaxAttributeLength;
+	type DefaultListingDuration = DefaultListingDuration;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const PegPalletId: PalletId = PalletId(*b"erc721pg");
+	pub const DepositEventSignature: [u8; 32] = [1_u8; 32];
+	pub const ChainId: EthChainId = CHAIN_ID;
+}
+impl crate::Config for Test {
+	type PegPalletId = PegPalletId;
+	type DepositEventSignature = DepositEventSignature;
+	type ChainId = ChainId;
+	type EthBridge = MockEthBridge;
+	type Nft = Nft;
+	type Event = Event;
+}
+
+#[derive(Default)]
+pub struct ExtBuilder;
+
+impl ExtBuilder {
+	pub fn build(self) -> sp_io::TestExternalities {
+		let mut ext: sp_io::TestExternalities = frame_system::GenesisConfig::default()
+			.build_storage::<Test>()
+			.unwrap()
+			.into();
+
+		ext.execute_with(|| {
+			System::initialize(&1, &[0u8; 32].into(), &Default::default(), frame_system::InitKind::Full);
+		});
+		NEXT_EVENT_CLAIM_ID.with(|id| *id.borrow_mut() = 0);
+		NEXT_EVENT_PROOF_ID.with(|id| *id.borrow_mut() = 0);
+		EVENT_CLAIMS.with(|claims| claims.borrow_mut().clear());
+		EVENT_PROOFS.with(|proofs| proofs.borrow_mut().clear());
+
+		ext
+	}
+}
+
+/// The Ethereum address of `TOKEN`
+pub fn token_address() -> EthAddress {
+	EthAddress::from_low_u64_be(TOKEN)
+}
+
+/// A deposit of ERC721 `token_id` to `BENEFICIARY`
+pub fn deposit(token_id: u64) -> Erc721DepositEvent {
+	let mut beneficiary = H256::zero();
+	beneficiary.0[..8].copy_from_slice(&BENEFICIARY.to_le_bytes());
+	Erc721DepositEvent {
+		token_address: token_address(),
+		token_id: token_id.into(),
+		beneficiary,
+	}
+}
+
+/// Notify the peg of a verified deposit claim
+pub fn verify_deposit(event_claim_id: u64, deposit: Erc721DepositEvent) {
+	<Erc721Peg as EventClaimSubscriber>::on_success(
+		event_claim_id,
+		CHAIN_ID,
+		&Erc721Peg::contract_address(),
+		&H256::from(DepositEventSignature::get()),
+		&EthAbiCodec::encode(&deposit),
+	);
+}
+
+/// Whether `event` of the peg was deposited
+pub fn has_event(event: crate::Event<Test>) -> bool {
+	System::events()
+		.iter()
+		.any(|record| record.event == Event::Erc721Peg(event.clone()))
+}
@@ -0,0 +1,279 @@
+/* Copyright 2021 Centrality Investments Limited
+*
+* Licensed under the LGPL, Version 3.0 (the "License");
+* you may not use this file except in compliance with the License.
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific language governing permissions and
+* limitations under the License.
+* You may obtain a copy of the License at the root of this project source code,
+* or at:
+*     https://centrality.ai/licenses/gplv3.txt
+*     https://centrality.ai/licenses/lgplv3.txt
+*/
+
+use super::*;
+use crate::mock::{
+	deposit, has_event, token_address, verify_deposit, DepositEventSignature, Erc721Peg, ExtBuilder, MockEthBridge,
+	Nft, Origin, Test, BENEFICIARY, CHAIN_ID,
+};
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::DispatchError::BadOrigin;
+
+#[test]
+fn deposit_claim() {
+	ExtBuilder::default().build().execute_with(|| {
+		let tx_hash = H256::from_low_u64_be(1);
+		assert_noop!(
+			Erc721Peg::deposit_claim(Origin::signed(1), tx_hash, deposit(123)),
+			Error::<Test>::DepositsPaused
+		);
+		assert_ok!(Erc721Peg::activate_deposits(Origin::root(), true));
+
+		assert_ok!(Erc721Peg::deposit_claim(Origin::signed(1), tx_hash, deposit(123)));
+		assert!(has_event(RawEvent::Erc721Claim(0, 1)));
+		assert_eq!(
+			MockEthBridge::event_claims(),
+			vec![(
+				1,
+				H256::from(DepositEventSignature::get()),
+				EthAbiCodec::encode(&deposit(123))
+			)]
+		);
+	});
+}
+
+#[test]
+fn deposit_claim_for_bridged_token_fails() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Erc721Peg::activate_deposits(Origin::root(), true));
+		verify_deposit(0, deposit(123));
+
+		assert_noop!(
+			Erc721Peg::deposit_claim(Origin::signed(1), H256::from_low_u64_be(1), deposit(123)),
+			Error::<Test>::TokenAlreadyBridged
+		);
+	});
+}
+
+#[test]
+fn deposit_mints_token() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Erc721Peg::set_erc721_meta(
+			Origin::root(),
+			vec![(token_address(), b"bridged-kitties".to_vec())]
+		));
+		verify_deposit(0, deposit(123));
+
+		let (collection_id, series_id) = Erc721Peg::erc721_to_series(token_address()).expect("series is created");
+		let token_id = (collection_id, series_id, 0);
+		assert!(has_event(RawEvent::Erc721Deposit(
+			0,
+			token_address(),
+			U256::from(123),
+			token_id,
+			BENEFICIARY
+		)));
+		assert_eq!(Nft::token_owner((collection_id, series_id), 0), BENEFICIARY);
+		assert_eq!(Nft::collection_name(collection_id), b"bridged-kitties".to_vec());
+		// the peg owns the collection
+		assert_eq!(
+			Nft::collection_owner(collection_id),
+			Some(<Test as Config>::PegPalletId::get().into_account())
+		);
+	});
+}
+
+#[test]
+fn deposits_map_token_ids() {
+	ExtBuilder::default().build().execute_with(|| {
+		verify_deposit(0, deposit(123));
+		verify_deposit(1, deposit(7));
+
+		// tokens of the same contract share a series
+		let series = Erc721Peg::erc721_to_series(token_address()).expect("series is created");
+		assert_eq!(Erc721Peg::series_to_erc721(series), Some(token_address()));
+		assert_eq!(
+			Erc721Peg::eth_token_to_serial(token_address(), U256::from(123)),
+			Some(0)
+		);
+		assert_eq!(Erc721Peg::eth_token_to_serial(token_address(), U256::from(7)), Some(1));
+		assert_eq!(Erc721Peg::serial_to_eth_token(series, 0), Some(U256::from(123)));
+		assert_eq!(Erc721Peg::serial_to_eth_token(series, 1), Some(U256::from(7)));
+		assert_eq!(Nft::token_owner(series, 1), BENEFICIARY);
+
+		// tokens of another contract get their own collection
+		let other_token_address = EthAddress::from_low_u64_be(66);
+		verify_deposit(
+			2,
+			Erc721DepositEvent {
+				token_address: other_token_address,
+				..deposit(123)
+			},
+		);
+		let other_series = Erc721Peg::erc721_to_series(other_token_address).expect("series is created");
+		assert_ne!(other_series.0, series.0);
+		assert_eq!(Nft::collection_name(other_series.0), DEFAULT_COLLECTION_NAME.to_vec());
+		assert_eq!(
+			Erc721Peg::eth_token_to_serial(other_token_address, U256::from(123)),
+			Some(0)
+		);
+	});
+}
+
+#[test]
+fn deposit_of_bridged_token_is_kept_for_retry() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Erc721Peg::activate_withdrawals(Origin::root(), true));
+		verify_deposit(0, deposit(123));
+		verify_deposit(1, deposit(123));
+
+		assert!(has_event(RawEvent::Erc721DepositFail(1)));
+		assert_eq!(Erc721Peg::failed_deposit(1), Some(deposit(123)));
+		assert_noop!(
+			Erc721Peg::retry_failed_deposit(Origin::signed(1), 1),
+			Error::<Test>::TokenAlreadyBridged
+		);
+
+		// the mirrored token is withdrawn
+		let (collection_id, series_id) = Erc721Peg::erc721_to_series(token_address()).unwrap();
+		assert_ok!(Erc721Peg::withdraw(
+			Origin::signed(BENEFICIARY),
+			(collection_id, series_id, 0),
+			EthAddress::from_low_u64_be(7)
+		));
+
+		assert_ok!(Erc721Peg::retry_failed_deposit(Origin::signed(1), 1));
+		assert!(has_event(RawEvent::Erc721Deposit(
+			1,
+			token_address(),
+			U256::from(123),
+			(collection_id, series_id, 1),
+			BENEFICIARY
+		)));
+		assert_eq!(Nft::token_owner((collection_id, series_id), 1), BENEFICIARY);
+		assert!(Erc721Peg::failed_deposit(1).is_none());
+		assert_noop!(
+			Erc721Peg::retry_failed_deposit(Origin::signed(1), 1),
+			Error::<Test>::NoFailedDeposit
+		);
+	});
+}
+
+#[test]
+fn failed_deposit_refund() {
+	ExtBuilder::default().build().execute_with(|| {
+		verify_deposit(0, deposit(123));
+		verify_deposit(1, deposit(123));
+		let refund_beneficiary = EthAddress::from_low_u64_be(7);
+
+		assert_noop!(
+			Erc721Peg::refund_failed_deposit(Origin::signed(1), 1, refund_beneficiary),
+			BadOrigin
+		);
+		assert_ok!(Erc721Peg::refund_failed_deposit(Origin::root(), 1, refund_beneficiary));
+
+		assert!(has_event(RawEvent::Erc721DepositRefund(
+			1,
+			0,
+			token_address(),
+			U256::from(123),
+			refund_beneficiary
+		)));
+		let message = Erc721WithdrawMessage {
+			token_address: token_address(),
+			token_id: U256::from(123),
+			beneficiary: refund_beneficiary,
+		};
+		assert_eq!(MockEthBridge::event_proofs(), vec![EthAbiCodec::encode(&message)]);
+		assert!(Erc721Peg::failed_deposit(1).is_none());
+		assert_noop!(
+			Erc721Peg::refund_failed_deposit(Origin::root(), 1, refund_beneficiary),
+			Error::<Test>::NoFailedDeposit
+		);
+	});
+}
+
+#[test]
+fn failed_claim() {
+	ExtBuilder::default().build().execute_with(|| {
+		<Erc721Peg as EventClaimSubscriber>::on_failure(
+			0,
+			CHAIN_ID,
+			&Erc721Peg::contract_address(),
+			&H256::from(DepositEventSignature::get()),
+			&EthAbiCodec::encode(&deposit(123)),
+		);
+		assert!(has_event(RawEvent::Erc721DepositFail(0)));
+		assert!(Erc721Peg::erc721_to_series(token_address()).is_none());
+	});
+}
+
+#[test]
+fn withdraw_burns_token_and_generates_proof() {
+	ExtBuilder::default().build().execute_with(|| {
+		verify_deposit(0, deposit(123));
+		let (collection_id, series_id) = Erc721Peg::erc721_to_series(token_address()).unwrap();
+		let token_id = (collection_id, series_id, 0);
+		let eth_beneficiary = EthAddress::from_low_u64_be(7);
+
+		assert_noop!(
+			Erc721Peg::withdraw(Origin::signed(BENEFICIARY), token_id, eth_beneficiary),
+			Error::<Test>::WithdrawalsPaused
+		);
+		assert_ok!(Erc721Peg::activate_withdrawals(Origin::root(), true));
+		assert_noop!(
+			Erc721Peg::withdraw(Origin::signed(1), token_id, eth_beneficiary),
+			crml_nft::Error::<Test>::NoPermission
+		);
+
+		assert_ok!(Erc721Peg::withdraw(
+			Origin::signed(BENEFICIARY),
+			token_id,
+			eth_beneficiary
+		));
+		assert!(has_event(RawEvent::Erc721Withdraw(
+			0,
+			token_id,
+			U256::from(123),
+			eth_beneficiary
+		)));
+		assert_ne!(Nft::token_owner((collection_id, series_id), 0), BENEFICIARY);
+		assert!(Erc721Peg::eth_token_to_serial(token_address(), U256::from(123)).is_none());
+		assert!(Erc721Peg::serial_to_eth_token((collection_id, series_id), 0).is_none());
+		let message = Erc721WithdrawMessage {
+			token_address: token_address(),
+			token_id: U256::from(123),
+			beneficiary: eth_beneficiary,
+		};
+		assert_eq!(MockEthBridge::event_proofs(), vec![EthAbiCodec::encode(&message)]);
+
+		// the token can be deposited again
+		verify_deposit(1, deposit(123));
+		assert_eq!(
+			Erc721Peg::eth_token_to_serial(token_address(), U256::from(123)),
+			Some(1)
+		);
+	});
+}
+
+#[test]
+fn withdraw_unsupported_token_fails() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Erc721Peg::activate_withdrawals(Origin::root(), true));
+		// a token not minted by the peg
+		let collection_id = <Nft as NonFungibleTokens>::create_collection(&BENEFICIARY, b"native".to_vec()).unwrap();
+		let series_id = <Nft as NonFungibleTokens>::create_series(collection_id).unwrap();
+		let serial_number = <Nft as NonFungibleTokens>::mint(&BENEFICIARY, collection_id, series_id).unwrap();
+
+		assert_noop!(
+			Erc721Peg::withdraw(
+				Origin::signed(BENEFICIARY),
+				(collection_id, series_id, serial_number),
+				EthAddress::from_low_u64_be(7)
+			),
+			Error::<Test>::UnsupportedToken
+		);
+	});
+}
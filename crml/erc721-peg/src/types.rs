@@ -0,0 +1,126 @@
+/* Copyright 2021 Centrality Investments Limited
+*
+* Licensed under the LGPL, Version 3.0 (the "License");
+* you may not use this file except in compliance with the License.
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific language governing permissions and
+* limitations under the License.
+* You may obtain a copy of the License at the root of this project source code,
+* or at:
+*     https://centrality.ai/licenses/gplv3.txt
+*     https://centrality.ai/licenses/lgplv3.txt
+*/
+use codec::{Decode, Encode};
+pub use crml_support::{EthAbiCodec, H160, H256, U256};
+use scale_info::TypeInfo;
+use sp_std::convert::TryInto;
+use sp_std::prelude::*;
+
+/// Ethereum address type
+pub type EthAddress = H160;
+
+/// A deposit event made by the ERC721 peg contract on Ethereum
+#[derive(Debug, Default, Clone, PartialEq, Decode, Encode, TypeInfo)]
+pub struct Erc721DepositEvent {
+	/// The ERC721 token address / contract deposited
+	pub token_address: EthAddress,
+	/// The Id of the deposited token within its contract
+	pub token_id: U256,
+	/// The CENNZnet beneficiary address
+	pub beneficiary: H256,
+}
+
+/// A withdraw message to prove and submit to Ethereum
+/// Allowing redemption of ERC721s
+#[derive(Debug, Default, Clone, PartialEq, Decode, Encode)]
+pub struct Erc721WithdrawMessage {
+	/// The ERC721 token address / contract withdrawn
+	pub token_address: EthAddress,
+	/// The Id of the withdrawn token within its contract
+	pub token_id: U256,
+	/// The Ethereum beneficiary address
+	pub beneficiary: EthAddress,
+}
+
+impl EthAbiCodec for Erc721WithdrawMessage {
+	/// Encode `Erc721WithdrawMessage` into 32-byte words
+	/// https://docs.soliditylang.org/en/v0.5.3/abi-spec.html#formal-specification-of-the-encoding
+	fn encode(&self) -> Vec<u8> {
+		let mut buf = [0_u8; 32 * 3];
+		buf[12..32].copy_from_slice(&self.token_address.to_fixed_bytes());
+		buf[32..64].copy_from_slice(&Into::<[u8; 32]>::into(self.token_id));
+		buf[76..96].copy_from_slice(&self.beneficiary.to_fixed_bytes());
+		buf.to_vec()
+	}
+
+	fn decode(_data: &[u8]) -> Option<Self> {
+		None
+	}
+}
+
+impl EthAbiCodec for Erc721DepositEvent {
+	/// Encode `Erc721DepositEvent` into 32-byte words
+	/// https://docs.soliditylang.org/en/v0.5.3/abi-spec.html#formal-specification-of-the-encoding
+	fn encode(&self) -> Vec<u8> {
+		let mut buf = [0_u8; 32 * 3];
+		buf[12..32].copy_from_slice(&self.token_address.to_fixed_bytes());
+		buf[32..64].copy_from_slice(&Into::<[u8; 32]>::into(self.token_id));
+		buf[64..96].copy_from_slice(&self.beneficiary.to_fixed_bytes());
+		buf.to_vec()
+	}
+	/// Receives Ethereum log 'data' and decodes it
+	fn decode(data: &[u8]) -> Option<Self> {
+		// Expect 3 words of data
+		if data.len() != 3 * 32 {
+			return None;
+		}
+		let token_address = H160::from(&data[12..32].try_into().expect("20 bytes decode"));
+		let token_id = data[32..64].into();
+		let beneficiary = H256::from(&data[64..96].try_into().expect("32 bytes decode"));
+
+		Some(Self {
+			token_address,
+			token_id,
+			beneficiary,
+		})
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::{Erc721DepositEvent, Erc721WithdrawMessage, EthAbiCodec};
+	use crml_support::{H160, H256, U256};
+
+	#[test]
+	fn deposit_event_decode() {
+		let event = Erc721DepositEvent {
+			token_address: H160::from_low_u64_be(55),
+			token_id: U256::from(123),
+			beneficiary: H256::from_low_u64_be(77),
+		};
+		let raw = EthAbiCodec::encode(&event);
+		assert_eq!(raw.len(), 3 * 32);
+		assert_eq!(raw[31], 55);
+		assert_eq!(raw[63], 123);
+		assert_eq!(raw[95], 77);
+		assert_eq!(Erc721DepositEvent::decode(&raw).expect("it decodes"), event);
+		assert!(Erc721DepositEvent::decode(&raw[..64]).is_none());
+	}
+
+	#[test]
+	fn withdraw_message_encode() {
+		let message = Erc721WithdrawMessage {
+			token_address: H160::from_low_u64_be(55),
+			token_id: U256::from(123),
+			beneficiary: H160::from_low_u64_be(77),
+		};
+		let mut expected = vec![0_u8; 3 * 32];
+		expected[31] = 55;
+		expected[63] = 123;
+		expected[95] = 77;
+		assert_eq!(EthAbiCodec::encode(&message), expected);
+		assert!(<Erc721WithdrawMessage as EthAbiCodec>::decode(&expected).is_none());
+	}
+}
@@ -0,0 +1,368 @@
+/* Copyright 2019-2021 Centrality Investments Limited
+*
+* Licensed under the LGPL, Version 3.0 (the "License");
+* you may not use this file except in compliance with the License.
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific language governing permissions and
+* limitations under the License.
+* You may obtain a copy of the License at the root of this project source code,
+* or at:
+*     https://centrality.ai/licenses/gplv3.txt
+*     https://centrality.ai/licenses/lgplv3.txt
+*/
+
+//! A scriptable mock Ethereum JSON-RPC server
+//!
+//! Serves the subset of the Ethereum JSON-RPC API queried by the eth bridge offchain worker:
+//! `eth_getTransactionReceipt`, `eth_getBlockByNumber`, `eth_blockNumber` & `eth_chainId`.
+//! The mock chain is scripted directly via [`MockEthereum`] or over RPC with:
+//! - `mock_addReceipt(receipt)` include a tx receipt in the next block
+//! - `mock_mineBlocks(count)` mine `count` empty blocks, returning the latest block number
+
+use ethereum_types::{H160, H256, U64};
+use jsonrpc_core::{Error, IoHandler, Params, Value};
+use jsonrpc_http_server::{Server, ServerBuilder};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::{collections::BTreeMap, io, net::SocketAddr, sync::Arc, thread, time::Duration};
+
+/// Default port of the mock server for `--dev` chains
+pub const DEFAULT_MOCK_PORT: u16 = 8_645;
+/// Seconds between mined blocks
+pub const BLOCK_TIME_SECONDS: u64 = 12;
+
+/// An event log in a mock tx receipt
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MockLog {
+	/// The contract emitting the event
+	pub address: H160,
+	/// Indexed event topics, the event signature first
+	pub topics: Vec<H256>,
+	/// ABI encoded event data
+	#[serde(default, with = "hex_bytes")]
+	pub data: Vec<u8>,
+}
+
+/// A tx receipt to be included in the mock chain
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MockReceipt {
+	/// Hash of the tx
+	pub transaction_hash: H256,
+	/// Sender of the tx
+	#[serde(default)]
+	pub from: H160,
+	/// Recipient of the tx
+	pub to: Option<H160>,
+	/// Whether the tx succeeded
+	#[serde(default = "succeeded")]
+	pub status: bool,
+	/// Event logs of the tx
+	#[serde(default)]
+	pub logs: Vec<MockLog>,
+}
+
+fn succeeded() -> bool {
+	true
+}
+
+/// Mock chain state
+struct MockChain {
+	/// The EVM chain id
+	chain_id: u64,
+	/// Timestamp of the genesis block (seconds)
+	genesis_timestamp: u64,
+	/// Number of the latest mined block
+	latest_block: u64,
+	/// Timestamp of mined blocks (seconds), where it differs from the genesis schedule
+	timestamps: BTreeMap<u64, u64>,
+	/// Known tx receipts by tx hash, with their block number
+	receipts: BTreeMap<H256, (u64, MockReceipt)>,
+}
+
+impl MockChain {
+	fn timestamp(&self, number: u64) -> u64 {
+		match self.timestamps.range(..=number).next_back() {
+			Some((from, timestamp)) => timestamp + (number - from) * BLOCK_TIME_SECONDS,
+			None => self.genesis_timestamp + number * BLOCK_TIME_SECONDS,
+		}
+	}
+}
+
+/// A scriptable mock Ethereum chain, cheaply cloneable
+#[derive(Clone)]
+pub struct MockEthereum(Arc<RwLock<MockChain>>);
+
+impl MockEthereum {
+	/// Create a mock chain `chain_id` with a genesis block at `genesis_timestamp` (seconds)
+	pub fn new(chain_id: u64, genesis_timestamp: u64) -> Self {
+		Self(Arc::new(RwLock::new(MockChain {
+			chain_id,
+			genesis_timestamp,
+			latest_block: 0,
+			timestamps: Default::default(),
+			receipts: Default::default(),
+		})))
+	}
+	/// Number of the latest mined block
+	pub fn latest_block_number(&self) -> u64 {
+		self.0.read().latest_block
+	}
+	/// Mine `count` empty blocks, returning the latest block number
+	pub fn mine_blocks(&self, count: u64) -> u64 {
+		let mut chain = self.0.write();
+		chain.latest_block += count;
+		chain.latest_block
+	}
+	/// Mine a block at `timestamp` (seconds), following blocks are spaced `BLOCK_TIME_SECONDS` after it
+	pub fn mine_block_at(&self, timestamp: u64) -> u64 {
+		let mut chain = self.0.write();
+		chain.latest_block += 1;
+		let number = chain.latest_block;
+		chain.timestamps.insert(number, timestamp);
+		number
+	}
+	/// Mine a block every `BLOCK_TIME_SECONDS` on a background thread
+	pub fn auto_mine(&self) -> thread::JoinHandle<()> {
+		let chain = self.clone();
+		thread::spawn(move || loop {
+			thread::sleep(Duration::from_secs(BLOCK_TIME_SECONDS));
+			chain.mine_blocks(1);
+		})
+	}
+	/// Include `receipt` in the next block, returning its block number
+	/// The receipt is not visible until the block is mined
+	pub fn add_receipt(&self, receipt: MockReceipt) -> u64 {
+		let mut chain = self.0.write();
+		let block_number = chain.latest_block + 1;
+		chain.receipts.insert(receipt.transaction_hash, (block_number, receipt));
+		block_number
+	}
+	/// Handle a raw JSON-RPC `request`, returning the raw response
+	pub fn handle_request(&self, request: &str) -> Option<String> {
+		self.io_handler().handle_request_sync(request)
+	}
+	/// Start serving the mock chain over HTTP at `addr`
+	pub fn start_server(&self, addr: &SocketAddr) -> io::Result<Server> {
+		ServerBuilder::new(self.io_handler()).threads(1).start_http(addr)
+	}
+	/// A JSON-RPC handler serving the mock chain
+	pub fn io_handler(&self) -> IoHandler {
+		let mut io = IoHandler::new();
+
+		let chain = self.clone();
+		io.add_sync_method("eth_getTransactionReceipt", move |params: Params| {
+			let (tx_hash,): (H256,) = params.parse()?;
+			Ok(chain.transaction_receipt(tx_hash))
+		});
+		let chain = self.clone();
+		io.add_sync_method("eth_getBlockByNumber", move |params: Params| {
+			let (block, _full_txs): (String, bool) = params.parse()?;
+			let number = match block.as_str() {
+				"latest" | "safe" | "finalized" => chain.latest_block_number(),
+				"earliest" => 0,
+				number => parse_quantity(number)?,
+			};
+			Ok(chain.block(number))
+		});
+		let chain = self.clone();
+		io.add_sync_method("eth_blockNumber", move |_params: Params| {
+			Ok(json!(U64::from(chain.latest_block_number())))
+		});
+		let chain = self.clone();
+		io.add_sync_method("eth_chainId", move |_params: Params| {
+			Ok(json!(U64::from(chain.0.read().chain_id)))
+		});
+		let chain = self.clone();
+		io.add_sync_method("mock_mineBlocks", move |params: Params| {
+			let (count,): (u64,) = params.parse()?;
+			Ok(json!(chain.mine_blocks(count)))
+		});
+		let chain = self.clone();
+		io.add_sync_method("mock_addReceipt", move |params: Params| {
+			let (receipt,): (MockReceipt,) = params.parse()?;
+			Ok(json!(chain.add_receipt(receipt)))
+		});
+
+		io
+	}
+	/// `eth_getBlockByNumber` result for block `number`, `null` if it is not mined
+	fn block(&self, number: u64) -> Value {
+		let chain = self.0.read();
+		if number > chain.latest_block {
+			return Value::Null;
+		}
+		json!({
+			"number": U64::from(number),
+			"hash": block_hash(number),
+			"parentHash": number.checked_sub(1).map(block_hash).unwrap_or_default(),
+			"timestamp": U64::from(chain.timestamp(number)),
+			"transactions": chain
+				.receipts
+				.iter()
+				.filter(|(_, (block_number, _))| *block_number == number)
+				.map(|(tx_hash, _)| tx_hash)
+				.collect::<Vec<_>>(),
+			"uncles": [],
+		})
+	}
+	/// `eth_getTransactionReceipt` result for `tx_hash`, `null` if it is unknown or pending
+	fn transaction_receipt(&self, tx_hash: H256) -> Value {
+		let chain = self.0.read();
+		let (block_number, receipt) = match chain.receipts.get(&tx_hash) {
+			Some((block_number, receipt)) if *block_number <= chain.latest_block => (*block_number, receipt),
+			_ => return Value::Null,
+		};
+		let logs: Vec<Value> = receipt
+			.logs
+			.iter()
+			.enumerate()
+			.map(|(log_index, log)| {
+				json!({
+					"address": log.address,
+					"topics": log.topics,
+					"data": format!("0x{}", hex::encode(&log.data)),
+					"blockHash": block_hash(block_number),
+					"blockNumber": U64::from(block_number),
+					"transactionHash": tx_hash,
+					"transactionIndex": U64::zero(),
+					"logIndex": U64::from(log_index),
+					"removed": false,
+				})
+			})
+			.collect();
+		json!({
+			"blockHash": block_hash(block_number),
+			"blockNumber": U64::from(block_number),
+			"contractAddress": null,
+			"cumulativeGasUsed": "0x0",
+			"effectiveGasPrice": "0x0",
+			"from": receipt.from,
+			"gasUsed": "0x0",
+			"logs": logs,
+			"logsBloom": format!("0x{}", "00".repeat(256)),
+			"status": U64::from(receipt.status as u64),
+			"to": receipt.to,
+			"transactionHash": tx_hash,
+			"transactionIndex": U64::zero(),
+			"type": "0x2",
+		})
+	}
+}
+
+/// Deterministic hash of block `number`
+fn block_hash(number: u64) -> H256 {
+	H256::from_low_u64_be(number + 1)
+}
+
+/// Parse a hex encoded JSON-RPC quantity e.g. `0x1f`
+fn parse_quantity(quantity: &str) -> Result<u64, Error> {
+	quantity
+		.strip_prefix("0x")
+		.and_then(|hex| u64::from_str_radix(hex, 16).ok())
+		.ok_or_else(|| Error::invalid_params(format!("invalid block number: {}", quantity)))
+}
+
+/// (De)serialize bytes as a `0x` prefixed hex string
+mod hex_bytes {
+	use serde::{Deserialize, Deserializer, Serializer};
+
+	pub fn serialize<S: Serializer>(bytes: &[u8], s: S) -> Result<S::Ok, S::Error> {
+		s.serialize_str(&format!("0x{}", hex::encode(bytes)))
+	}
+
+	pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Vec<u8>, D::Error> {
+		let s = String::deserialize(d)?;
+		hex::decode(s.strip_prefix("0x").unwrap_or(&s)).map_err(serde::de::Error::custom)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn request(method: &str, params: Value) -> String {
+		json!({ "jsonrpc": "2.0", "method": method, "params": params, "id": 1 }).to_string()
+	}
+
+	fn result(mock: &MockEthereum, method: &str, params: Value) -> Value {
+		let response: Value = serde_json::from_str(&mock.handle_request(&request(method, params)).unwrap()).unwrap();
+		response["result"].clone()
+	}
+
+	#[test]
+	fn receipts_visible_once_mined() {
+		let mock = MockEthereum::new(1, 1_000);
+		let tx_hash = H256::repeat_byte(1);
+		let receipt = MockReceipt {
+			transaction_hash: tx_hash,
+			from: H160::repeat_byte(2),
+			to: Some(H160::repeat_byte(3)),
+			status: true,
+			logs: vec![MockLog {
+				address: H160::repeat_byte(3),
+				topics: vec![H256::repeat_byte(4)],
+				data: vec![1, 2, 3],
+			}],
+		};
+		assert_eq!(mock.add_receipt(receipt), 1);
+		assert_eq!(
+			result(&mock, "eth_getTransactionReceipt", json!([tx_hash])),
+			Value::Null
+		);
+
+		assert_eq!(mock.mine_blocks(3), 3);
+		let receipt = result(&mock, "eth_getTransactionReceipt", json!([tx_hash]));
+		assert_eq!(receipt["blockNumber"], json!("0x1"));
+		assert_eq!(receipt["status"], json!("0x1"));
+		assert_eq!(receipt["logs"][0]["data"], json!("0x010203"));
+		assert_eq!(receipt["logs"][0]["transactionHash"], json!(tx_hash));
+	}
+
+	#[test]
+	fn blocks_by_number() {
+		let mock = MockEthereum::new(1, 1_000);
+		mock.mine_blocks(2);
+		mock.mine_block_at(5_000);
+		mock.mine_blocks(1);
+
+		let latest = result(&mock, "eth_getBlockByNumber", json!(["latest", false]));
+		assert_eq!(latest["number"], json!("0x4"));
+		assert_eq!(latest["timestamp"], json!(format!("{:#x}", 5_000 + BLOCK_TIME_SECONDS)));
+		let block = result(&mock, "eth_getBlockByNumber", json!(["0x2", false]));
+		assert_eq!(
+			block["timestamp"],
+			json!(format!("{:#x}", 1_000 + 2 * BLOCK_TIME_SECONDS))
+		);
+		assert_eq!(block["hash"], json!(block_hash(2)));
+		assert_eq!(
+			result(&mock, "eth_getBlockByNumber", json!(["0x5", false])),
+			Value::Null
+		);
+		assert_eq!(result(&mock, "eth_blockNumber", json!([])), json!("0x4"));
+	}
+
+	#[test]
+	fn scripted_over_rpc() {
+		let mock = MockEthereum::new(5, 1_000);
+		let tx_hash = H256::repeat_byte(1);
+		assert_eq!(
+			result(
+				&mock,
+				"mock_addReceipt",
+				json!([{ "transactionHash": tx_hash, "to": H160::repeat_byte(3), "logs": [] }])
+			),
+			json!(1)
+		);
+		assert_eq!(result(&mock, "mock_mineBlocks", json!([1])), json!(1));
+		assert_eq!(
+			result(&mock, "eth_getTransactionReceipt", json!([tx_hash]))["transactionHash"],
+			json!(tx_hash)
+		);
+		assert_eq!(result(&mock, "eth_chainId", json!([])), json!("0x5"));
+	}
+}
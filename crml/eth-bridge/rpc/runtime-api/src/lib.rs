@@ -0,0 +1,45 @@
+// Copyright 2019-2021
+//     by  Centrality Investments Ltd.
+//     and Parity Technologies (UK) Ltd.
+// This file is part of Plug-blockchain.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime API definition required by Eth bridge RPC extensions.
+//!
+//! This API should be imported and implemented by the runtime,
+//! of a node that wants to use the custom RPC extension
+//! adding Eth bridge access methods.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use crml_eth_bridge::{ClaimRecord, EthChainId, EthHash, EventClaimId, EventProofId, Message, NotaryPerformance};
+use sp_std::prelude::*;
+
+sp_api::decl_runtime_apis! {
+	pub trait EthBridgeApi<BlockNumber, AccountId> where
+		BlockNumber: Codec,
+		AccountId: Codec,
+	{
+		/// Get the status of an event claim
+		fn claim_status(event_claim_id: EventClaimId) -> Option<ClaimRecord<BlockNumber>>;
+		/// Get the status of the latest event claim of a tx hash on an EVM chain
+		fn claim_status_by_tx_hash(chain_id: EthChainId, tx_hash: EthHash) -> Option<ClaimRecord<BlockNumber>>;
+		/// Get the bridge performance of each active validator over the current session
+		fn notary_performance() -> Vec<(AccountId, NotaryPerformance)>;
+		/// Get a message sent to an EVM chain by its proof id
+		fn message(event_proof_id: EventProofId) -> Option<(EthChainId, Message)>;
+	}
+}
@@ -0,0 +1,222 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! RPC interface for the eth bridge module.
+
+use cennznet_primitives::eth::{VersionedEventProof, ETHY_ENGINE_ID};
+use codec::{Codec, Decode};
+use crml_eth_bridge::{ClaimRecord, EthAddress, EthChainId, EthHash, EventClaimId, EventProofId, NotaryPerformance};
+pub use crml_eth_bridge_rpc_runtime_api::EthBridgeApi as EthBridgeRuntimeApi;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use sc_client_api::backend::AuxStore;
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use std::sync::Arc;
+
+/// A message sent to an EVM chain with the notaries' signatures, ready to submit to the destination contract
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MessageProof {
+	/// The proof id of the message
+	pub event_id: EventProofId,
+	/// The notary set that signed the message
+	pub validator_set_id: u64,
+	/// The EVM chain the message is sent to
+	pub chain_id: EthChainId,
+	/// The CENNZnet account that sent the message
+	pub sender: EthHash,
+	/// The contract the message is sent to
+	pub destination: EthAddress,
+	/// Unique nonce of the message
+	pub nonce: u64,
+	/// Opaque data for the destination contract
+	pub payload: Bytes,
+	/// Signature `v` of each notary, `0` if the notary did not sign
+	pub v: Vec<u8>,
+	/// Signature `r` of each notary
+	pub r: Vec<EthHash>,
+	/// Signature `s` of each notary
+	pub s: Vec<EthHash>,
+}
+
+#[rpc]
+pub trait EthBridgeApi<BlockNumber, AccountId, BlockHash> {
+	/// Get the status of an event claim
+	#[rpc(name = "ethBridge_getClaimStatus")]
+	fn claim_status(
+		&self,
+		event_claim_id: EventClaimId,
+		at: Option<BlockHash>,
+	) -> Result<Option<ClaimRecord<BlockNumber>>>;
+
+	/// Get the status of the latest event claim of a tx hash on an EVM chain
+	#[rpc(name = "ethBridge_getClaimStatusByTxHash")]
+	fn claim_status_by_tx_hash(
+		&self,
+		chain_id: EthChainId,
+		tx_hash: EthHash,
+		at: Option<BlockHash>,
+	) -> Result<Option<ClaimRecord<BlockNumber>>>;
+
+	/// Get the bridge performance of each active validator over the current session
+	#[rpc(name = "ethBridge_getNotaryPerformance")]
+	fn notary_performance(&self, at: Option<BlockHash>) -> Result<Vec<(AccountId, NotaryPerformance)>>;
+
+	/// Get a sent message with the notaries' signatures. Returns `null` if missing or not yet signed
+	#[rpc(name = "ethBridge_getMessageProof")]
+	fn message_proof(&self, event_proof_id: EventProofId, at: Option<BlockHash>) -> Result<Option<MessageProof>>;
+}
+
+/// A struct that implements the [`EthBridgeApi`].
+pub struct EthBridge<C, P> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<P>,
+}
+
+impl<C, P> EthBridge<C, P> {
+	/// Create new `EthBridge` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self {
+			client,
+			_marker: Default::default(),
+		}
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl<C, Block, BlockNumber, AccountId> EthBridgeApi<BlockNumber, AccountId, <Block as BlockT>::Hash>
+	for EthBridge<C, (Block, BlockNumber, AccountId)>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block> + AuxStore,
+	C::Api: EthBridgeRuntimeApi<Block, BlockNumber, AccountId>,
+	BlockNumber: Codec + Send + Sync + 'static,
+	AccountId: Codec + Send + Sync + 'static,
+{
+	fn claim_status(
+		&self,
+		event_claim_id: EventClaimId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<ClaimRecord<BlockNumber>>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		self.client
+			.runtime_api()
+			.claim_status(&at, event_claim_id)
+			.map_err(|e| RpcError {
+				code: ErrorCode::ServerError(Error::RuntimeError as i64),
+				message: "Unable to query claim status.".into(),
+				data: Some(format!("{:?}", e).into()),
+			})
+	}
+
+	fn claim_status_by_tx_hash(
+		&self,
+		chain_id: EthChainId,
+		tx_hash: EthHash,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<ClaimRecord<BlockNumber>>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		self.client
+			.runtime_api()
+			.claim_status_by_tx_hash(&at, chain_id, tx_hash)
+			.map_err(|e| RpcError {
+				code: ErrorCode::ServerError(Error::RuntimeError as i64),
+				message: "Unable to query claim status.".into(),
+				data: Some(format!("{:?}", e).into()),
+			})
+	}
+
+	fn notary_performance(&self, at: Option<<Block as BlockT>::Hash>) -> Result<Vec<(AccountId, NotaryPerformance)>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		self.client.runtime_api().notary_performance(&at).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError as i64),
+			message: "Unable to query notary performance.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+
+	fn message_proof(
+		&self,
+		event_proof_id: EventProofId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<MessageProof>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let message = self
+			.client
+			.runtime_api()
+			.message(&at, event_proof_id)
+			.map_err(|e| RpcError {
+				code: ErrorCode::ServerError(Error::RuntimeError as i64),
+				message: "Unable to query message.".into(),
+				data: Some(format!("{:?}", e).into()),
+			})?;
+		let (chain_id, message) = match message {
+			Some(message) => message,
+			None => return Ok(None),
+		};
+
+		// proofs are stored by ethy once a threshold of notaries signed
+		let event_proof = match self
+			.client
+			.get_aux(&[&ETHY_ENGINE_ID[..], &event_proof_id.to_be_bytes()[..]].concat())
+			.ok()
+			.flatten()
+			.and_then(|encoded_proof| VersionedEventProof::decode(&mut &encoded_proof[..]).ok())
+		{
+			Some(VersionedEventProof::V1(event_proof)) => event_proof,
+			None => return Ok(None),
+		};
+
+		let mut proof = MessageProof {
+			event_id: event_proof.event_id,
+			validator_set_id: event_proof.validator_set_id,
+			chain_id,
+			sender: message.sender,
+			destination: message.destination,
+			nonce: message.nonce,
+			payload: message.payload.into(),
+			v: Vec::with_capacity(event_proof.signatures.len()),
+			r: Vec::with_capacity(event_proof.signatures.len()),
+			s: Vec::with_capacity(event_proof.signatures.len()),
+		};
+		for signature in event_proof.signatures.iter() {
+			// 65 byte (r, s, recovery id) ECDSA signature, unsigned is all zero
+			let signature: &[u8] = signature.as_ref();
+			proof.r.push(EthHash::from_slice(&signature[0..32]));
+			proof.s.push(EthHash::from_slice(&signature[32..64]));
+			proof.v.push(if signature.iter().all(|b| *b == 0) {
+				0
+			} else {
+				signature[64] + 27
+			});
+		}
+
+		Ok(Some(proof))
+	}
+}
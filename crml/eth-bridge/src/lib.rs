@@ -22,20 +22,44 @@
 //! Once a threshold of validators sign a notarization having witnessed the event it is considered verified.
 //!
 //! Events are opaque to this module, other modules handle submitting "event claims" and "callbacks" to handle success
+//!
+//! Claims may also be proven without trusting notaries' Ethereum nodes by submitting a Merkle-Patricia proof of the
+//! tx receipt. Notaries attest to the Ethereum block header of the proof and once a threshold agree the header is
+//! relayed on-chain and the proof is verified against its `receiptsRoot`
+//!
+//! Events may be bridged from Ethereum and other EVM chains (e.g. Polygon) through the same notary set.
+//! Event types, processed txs and headers are keyed by EIP-155 chain id. Chains other than Ethereum are
+//! registered by governance with their own block confirmations and deadline, and notaries configure an
+//! endpoint for each chain
+//!
+//! Submitting a claim reserves a deposit from the submitter, returned once the claim is verified or expires and
+//! slashed if notaries find it invalid. Pending claims per account and claims per block are limited and notaries
+//! check the oldest claims first, interleaving submitters, so a single account cannot stall other claims
+//!
+//! Any account may send a message to a contract on an EVM chain with `send_message` for a fee. Notaries sign a proof
+//! of the message (sender, destination, nonce and payload) which may be relayed to the contract
+//!
+//! Notary votes are tracked each session against the result of each claim. Notaries missing or contradicting the
+//! result of too many claims are reported as an offence at the end of the session
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
 #[cfg(test)]
 mod tests;
 
+mod proof;
 mod types;
 use types::*;
+pub use types::{
+	ClaimRecord, ClaimStatus, EthAddress, EthChainId, EthHash, EventClaimId, EventClaimResult, EventProofId,
+	EvmChainConfig, Message, NotaryPerformance, ReceiptProof, RelayedHeader,
+};
 
 use cennznet_primitives::{
 	eth::{ConsensusLog, ValidatorSet, ETHY_ENGINE_ID},
 	types::BlockNumber,
 };
-use codec::Encode;
+use codec::{Decode, Encode};
 use crml_support::{
 	EthAbiCodec, EventClaimSubscriber, EventClaimVerifier, FinalSessionTracker as FinalSessionTrackerT,
 	NotarizationRewardHandler,
@@ -43,7 +67,10 @@ use crml_support::{
 use frame_support::{
 	decl_error, decl_event, decl_module, decl_storage, log,
 	pallet_prelude::*,
-	traits::{OneSessionHandler, UnixTime, ValidatorSet as ValidatorSetT},
+	traits::{
+		Currency, ExistenceRequirement, OnUnbalanced, OneSessionHandler, ReservableCurrency, UnixTime,
+		ValidatorSet as ValidatorSetT, ValidatorSetWithIdentification, WithdrawReasons,
+	},
 	transactional, Parameter,
 };
 use frame_system::{
@@ -54,13 +81,17 @@ use sp_runtime::{
 	generic::DigestItem,
 	offchain as rt_offchain,
 	offchain::StorageKind,
-	traits::{MaybeSerializeDeserialize, Member, SaturatedConversion, Zero},
+	traits::{Convert, MaybeSerializeDeserialize, Member, SaturatedConversion, Zero},
 	transaction_validity::{InvalidTransaction, TransactionSource, TransactionValidity, ValidTransaction},
-	DispatchError, Percent, RuntimeAppPublic,
+	DispatchError, Perbill, Percent, RuntimeAppPublic,
+};
+use sp_staking::{
+	offence::{DisableStrategy, Kind, Offence, ReportOffence},
+	SessionIndex,
 };
 #[cfg(not(feature = "std"))]
 use sp_std::alloc::string::ToString;
-use sp_std::{convert::TryInto, prelude::*};
+use sp_std::{collections::btree_map::BTreeMap, convert::TryInto, prelude::*};
 #[cfg(std)]
 use std::string::ToString;
 
@@ -74,6 +105,48 @@ const REQUEST_TTL_MS: u64 = 2_500;
 const BUCKET_FACTOR_S: u64 = 3_600; // 1 hour
 /// Number of blocks between claim pruning
 const CLAIM_PRUNING_INTERVAL: BlockNumber = BUCKET_FACTOR_S as u32 / 5_u32;
+/// Pending claims expire without a result after this many blocks (~24 hours)
+const CLAIM_EXPIRY_BLOCKS: BlockNumber = 17_280;
+/// Claim statuses are retained for this many blocks after a result (~7 days)
+const CLAIM_STATUS_RETENTION_BLOCKS: BlockNumber = 120_960;
+/// Blocks to wait before re-checking a claim after a transient result, doubles on each attempt
+const CLAIM_RETRY_BACKOFF: BlockNumber = 5;
+/// Maximum blocks to wait before re-checking a claim after a transient result (~1 hour)
+const CLAIM_RETRY_MAX_BACKOFF: BlockNumber = 720;
+/// Offchain storage key prefix for claim retry schedules
+const CLAIM_RETRY_PREFIX: &[u8] = b"ETH_RTRY";
+/// Offchain storage key for the ids of claims with a retry schedule
+const CLAIM_RETRY_IDS_KEY: &[u8] = b"ETH_RIDS";
+/// Max Ethereum headers awaiting notary attestation
+const MAX_REQUESTED_HEADERS: usize = 32;
+/// Max receipt proofs awaiting the same Ethereum header
+const MAX_PROOFS_PER_HEADER: usize = 16;
+/// Max trie nodes in a receipt proof (receipts and transactions tries combined)
+const MAX_PROOF_NODES: usize = 32;
+/// Max total size of the trie nodes in a receipt proof (bytes)
+const MAX_PROOF_SIZE: usize = 65_536;
+/// Max header attestations to attempt per block/OCW invocation
+const HEADERS_PER_BLOCK: usize = 3;
+/// Relayed Ethereum headers are retained for this many blocks (~24 hours)
+const HEADER_RETENTION_BLOCKS: BlockNumber = 17_280;
+/// Upper bound on the block rate of supported EVM chains (blocks per second)
+const MAX_EVM_BLOCKS_PER_SECOND: u64 = 4;
+/// Receipt proofs may reference blocks this far past the expected head of their EVM chain
+const MAX_RECEIPT_PROOF_BLOCK_LEAD: u64 = 1_000;
+/// Max size of a `send_message` payload (bytes)
+const MAX_MESSAGE_PAYLOAD: usize = 1_024;
+/// Sent messages are retained for this many blocks (~7 days)
+const MESSAGE_RETENTION_BLOCKS: BlockNumber = 120_960;
+/// Notaries are not reported in sessions with fewer claim results than this
+const MIN_SESSION_CLAIMS: u32 = 10;
+/// Offchain storage key for the Eth JSON-RPC endpoints (comma separated URIs)
+/// Endpoints of other EVM chains are keyed `ETH_HTTP:<chain id>`
+const ETH_HTTP_KEY: &[u8] = b"ETH_HTTP";
+/// Offchain storage key for the number of Eth JSON-RPC endpoints that must agree on a response
+/// The quorum of other EVM chains is keyed `ETH_QRUM:<chain id>`
+const ETH_HTTP_QUORUM_KEY: &[u8] = b"ETH_QRUM";
+/// Offchain storage key prefix for Eth JSON-RPC endpoint health records
+const ETH_HTTP_HEALTH_PREFIX: &[u8] = b"ETH_HLTH";
 
 pub(crate) const LOG_TARGET: &str = "eth-bridge";
 
@@ -93,14 +166,44 @@ pub trait Config: frame_system::Config + CreateSignedTransaction<Call<Self>> {
 	/// The identifier type for an authority in this module (i.e. active validator session key)
 	/// 33 byte ECDSA public key
 	type EthyId: Member + Parameter + AsRef<[u8]> + RuntimeAppPublic + Default + Ord + MaybeSerializeDeserialize;
-	/// Knows the active authority set (validator stash addresses)
-	type AuthoritySet: ValidatorSetT<Self::AccountId, ValidatorId = Self::AccountId>;
+	/// Knows the active authority set (validator stash addresses) and their full identification
+	type AuthoritySet: ValidatorSetWithIdentification<Self::AccountId, ValidatorId = Self::AccountId>;
 	/// The threshold of notarizations required to approve an Ethereum
 	type NotarizationThreshold: Get<Percent>;
+	/// The EIP-155 chain id of Ethereum, other EVM chains are registered in `EvmChains`
+	type EthereumChainId: Get<EthChainId>;
+	/// The minimum number of block confirmations needed to notarize an Ethereum event
+	type EventConfirmations: Get<u64>;
+	/// Ethereum events cannot be claimed after this time (seconds)
+	type EventDeadlineSeconds: Get<u64>;
 	/// Rewards notaries for participating in claims
 	type RewardHandler: NotarizationRewardHandler<AccountId = Self::AccountId>;
+	/// Reports notaries missing or contradicting the result of too many claims in a session
+	type ReportNotaryOffence: ReportOffence<
+		Self::AccountId,
+		IdentificationTuple<Self>,
+		NotaryOffence<IdentificationTuple<Self>>,
+	>;
+	/// Notaries missing votes on more than this proportion of claims in a session are reported
+	type MaxMissedNotarizations: Get<Percent>;
+	/// Notaries contradicting the result of more than this proportion of claims in a session are reported
+	type MaxNotaryDisagreements: Get<Percent>;
 	/// Things subscribing to event claims
 	type Subscribers: EventClaimSubscriber;
+	/// Currency for claim deposits
+	type Currency: ReservableCurrency<Self::AccountId>;
+	/// Deposit reserved from the submitter of a claim, returned unless the claim is invalid
+	type ClaimDeposit: Get<BalanceOf<Self>>;
+	/// Handler for the deposits of invalid claims
+	type OnClaimSlash: OnUnbalanced<NegativeImbalanceOf<Self>>;
+	/// Max pending claims of an account
+	type MaxClaimsPerAccount: Get<u64>;
+	/// Max claims submitted per block
+	type MaxClaimsPerBlock: Get<u64>;
+	/// Fee charged to send a message to an EVM chain
+	type MessageFee: Get<BalanceOf<Self>>;
+	/// Handler for message fees
+	type OnMessageFee: OnUnbalanced<NegativeImbalanceOf<Self>>;
 	/// Returns the block timestamp
 	type UnixTime: UnixTime;
 	/// The overarching call type.
@@ -111,6 +214,64 @@ pub trait Config: frame_system::Config + CreateSignedTransaction<Call<Self>> {
 	type FinalSessionTracker: FinalSessionTrackerT;
 }
 
+pub type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+pub type NegativeImbalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::NegativeImbalance;
+
+/// A validator and its full identification
+pub type IdentificationTuple<T> = (
+	<T as frame_system::Config>::AccountId,
+	<<T as Config>::AuthoritySet as ValidatorSetWithIdentification<<T as frame_system::Config>::AccountId>>::Identification,
+);
+
+/// Notaries missed or contradicted the result of too many claims in a session
+#[derive(RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Clone, PartialEq, Eq))]
+pub struct NotaryOffence<Offender> {
+	/// The session the notaries were reported in
+	pub session_index: SessionIndex,
+	/// The size of the validator set in the session
+	pub validator_set_count: u32,
+	/// The reported notaries
+	pub offenders: Vec<Offender>,
+}
+
+impl<Offender: Clone> Offence<Offender> for NotaryOffence<Offender> {
+	const ID: Kind = *b"eth-bridge:notar";
+	type TimeSlot = SessionIndex;
+
+	fn offenders(&self) -> Vec<Offender> {
+		self.offenders.clone()
+	}
+
+	fn session_index(&self) -> SessionIndex {
+		self.session_index
+	}
+
+	fn validator_set_count(&self) -> u32 {
+		self.validator_set_count
+	}
+
+	fn time_slot(&self) -> Self::TimeSlot {
+		self.session_index
+	}
+
+	fn disable_strategy(&self) -> DisableStrategy {
+		DisableStrategy::Never
+	}
+
+	// Same as im-online, a few notaries without an Ethereum node are chilled but not slashed
+	// i.e. min((3 * (k - (n / 10 + 1))) / n, 1) * 0.07
+	fn slash_fraction(offenders: u32, validator_set_count: u32) -> Perbill {
+		if let Some(threshold) = offenders.checked_sub(validator_set_count / 10 + 1) {
+			let x = Perbill::from_rational(3 * threshold, validator_set_count);
+			x.saturating_mul(Perbill::from_percent(7))
+		} else {
+			Perbill::default()
+		}
+	}
+}
+
 decl_storage! {
 	trait Store for Module<T: Config> as EthBridge {
 		/// Required % of validator support to signal readiness (default: 66%)
@@ -119,13 +280,27 @@ decl_storage! {
 		EventClaims get(fn event_claims): map hasher(twox_64_concat) EventClaimId => (EthHash, EventTypeId);
 		/// Event data for a given claim
 		EventData get(fn event_data): map hasher(twox_64_concat) EventClaimId => Option<Vec<u8>>;
+		/// The submitter of a pending claim and the deposit reserved from them
+		ClaimDeposits get(fn claim_deposit): map hasher(twox_64_concat) EventClaimId => Option<(T::AccountId, BalanceOf<T>)>;
+		/// The number of pending claims of an account
+		AccountClaimCount get(fn account_claim_count): map hasher(blake2_128_concat) T::AccountId => u64;
+		/// The number of claims submitted this block
+		BlockClaimCount get(fn block_claim_count): u64;
+		/// Claims to expire at a block if they are still pending
+		ClaimExpiries get(fn claim_expiries): double_map hasher(twox_64_concat) T::BlockNumber, hasher(twox_64_concat) EventClaimId => ();
+		/// Status of event claims, retained for `CLAIM_STATUS_RETENTION_BLOCKS` after a result
+		ClaimStatuses get(fn claim_status): map hasher(twox_64_concat) EventClaimId => Option<ClaimRecord<T::BlockNumber>>;
+		/// The latest claim of a tx hash on an EVM chain, retained with its status
+		TxHashClaims get(fn tx_hash_claim): double_map hasher(twox_64_concat) EthChainId, hasher(twox_64_concat) EthHash => Option<EventClaimId>;
+		/// Claim statuses to prune at a block
+		ClaimStatusPruning get(fn claim_status_pruning): double_map hasher(twox_64_concat) T::BlockNumber, hasher(twox_64_concat) EventClaimId => ();
 		/// Notarizations for queued messages
 		/// Either: None = no notarization exists OR Some(yay/nay)
 		EventNotarizations get(fn event_notarizations): double_map hasher(twox_64_concat) EventClaimId, hasher(twox_64_concat) T::EthyId => Option<EventClaimResult>;
-		/// Maps event types seen by the bridge ((contract address, event signature)) to unique type Ids
-		EventTypeToTypeId get(fn event_type_to_type_id): map hasher(blake2_128_concat) (EthAddress, EthHash) => EventTypeId;
-		/// Maps event type ids to ((contract address, event signature))
-		TypeIdToEventType get(fn type_id_to_event_type): map hasher(blake2_128_concat) EventTypeId => (EthAddress, EthHash);
+		/// Maps event types seen by the bridge ((chain id, contract address, event signature)) to unique type Ids
+		EventTypeToTypeId get(fn event_type_to_type_id): map hasher(blake2_128_concat) (EthChainId, EthAddress, EthHash) => EventTypeId;
+		/// Maps event type ids to ((chain id, contract address, event signature))
+		TypeIdToEventType get(fn type_id_to_event_type): map hasher(blake2_128_concat) EventTypeId => (EthChainId, EthAddress, EthHash);
 		/// Id of the next Eth bridge event claim
 		NextEventClaimId get(fn next_event_claim_id): EventClaimId;
 		/// Id of the next event type (internal)
@@ -136,14 +311,14 @@ decl_storage! {
 		NotaryKeys get(fn notary_keys): Vec<T::EthyId>;
 		/// Scheduled notary (validator) public keys for the next session
 		NextNotaryKeys get(fn next_notary_keys): Vec<T::EthyId>;
-		/// Processed tx hashes bucketed by unix timestamp (`BUCKET_FACTOR_S`)
-		// Used in conjunction with `EventDeadlineSeconds` to prevent "double spends".
+		/// Processed tx hashes of each chain bucketed by unix timestamp (`BUCKET_FACTOR_S`)
+		// Used in conjunction with the chain's event deadline to prevent "double spends".
 		// After a bucket is older than the deadline, any events prior are considered expired.
 		// This allows the record of processed events to be pruned from state regularly
-		ProcessedTxBuckets get(fn processed_tx_buckets): double_map hasher(twox_64_concat) u64, hasher(identity) EthHash => ();
-		/// Map from processed tx hash to status
-		/// Periodically cleared after `EventDeadlineSeconds` expires
-		ProcessedTxHashes get(fn processed_tx_hashes): map hasher(twox_64_concat) EthHash => ();
+		ProcessedTxBuckets get(fn processed_tx_buckets): double_map hasher(twox_64_concat) (EthChainId, u64), hasher(identity) EthHash => ();
+		/// Map from processed tx hash of each chain to status
+		/// Periodically cleared after the chain's event deadline expires
+		ProcessedTxHashes get(fn processed_tx_hashes): double_map hasher(twox_64_concat) EthChainId, hasher(twox_64_concat) EthHash => ();
 		/// The current validator set id
 		NotarySetId get(fn notary_set_id): u64;
 		/// The event proof Id generated by the previous validator set to notarize the current set.
@@ -151,10 +326,38 @@ decl_storage! {
 		NotarySetProofId get(fn notary_set_proof_id): EventProofId;
 		/// Whether the bridge is paused (for validator transitions)
 		BridgePaused get(fn bridge_paused): bool;
-		/// The minimum number of block confirmations needed to notarize an Ethereum event
-		EventConfirmations get(fn event_confirmations): u64 = 3;
-		/// Events cannot be claimed after this time (seconds)
-		EventDeadlineSeconds get(fn event_deadline_seconds): u64 = 604_800; // 1 week
+		/// EVM chain headers notarized by a threshold of notaries, by chain id and block number
+		/// Retained for `HEADER_RETENTION_BLOCKS`
+		RelayedHeaders get(fn relayed_header): double_map hasher(twox_64_concat) EthChainId, hasher(twox_64_concat) u64 => Option<RelayedHeader>;
+		/// Block number and timestamp of the highest header relayed from each EVM chain
+		LatestRelayedHeader get(fn latest_relayed_header): map hasher(twox_64_concat) EthChainId => Option<(u64, u64)>;
+		/// Relayed headers to prune at a block
+		RelayedHeaderPruning get(fn relayed_header_pruning): double_map hasher(twox_64_concat) T::BlockNumber, hasher(twox_64_concat) (EthChainId, u64) => ();
+		/// EVM chain block numbers awaiting header attestations from notaries, with the block they were requested at
+		RequestedHeaders get(fn requested_headers): Vec<(EthChainId, u64, T::BlockNumber)>;
+		/// The header hash attested to by each notary for a requested EVM chain block number
+		HeaderAttestations get(fn header_attestations): double_map hasher(twox_64_concat) (EthChainId, u64), hasher(twox_64_concat) T::EthyId => Option<EthHash>;
+		/// Receipt proofs awaiting the header of their EVM chain block
+		PendingReceiptProofs get(fn pending_receipt_proof): map hasher(twox_64_concat) EventClaimId => Option<ReceiptProof>;
+		/// Claims with receipt proofs awaiting the header of an EVM chain block number
+		HeaderProofClaims get(fn header_proof_claims): map hasher(twox_64_concat) (EthChainId, u64) => Vec<EventClaimId>;
+		/// Claims notaries reached a result on this session
+		SessionClaims get(fn session_claims): u32;
+		/// Results of claims notaries reached this session and the authority indices of the notaries counted on them
+		/// Notarizations arriving after a result are counted towards notary votes until the session ends
+		SessionClaimResults get(fn session_claim_result): map hasher(twox_64_concat) EventClaimId => Option<(bool, Vec<u16>)>;
+		/// Votes of each validator on claims notaries reached a result on this session
+		SessionNotaryVotes get(fn session_notary_votes): map hasher(twox_64_concat) T::AccountId => NotaryVotes;
+		/// EVM chains other than Ethereum events may be bridged from
+		EvmChains get(fn evm_chain): map hasher(twox_64_concat) EthChainId => Option<EvmChainConfig>;
+		/// Nonce of the next message sent to an EVM chain
+		NextMessageNonce get(fn next_message_nonce): u64;
+		/// Messages sent to EVM chains by proof id, retained for `MESSAGE_RETENTION_BLOCKS`
+		Messages get(fn message): map hasher(twox_64_concat) EventProofId => Option<(EthChainId, Message)>;
+		/// Messages to prune at a block
+		MessagePruning get(fn message_pruning): double_map hasher(twox_64_concat) T::BlockNumber, hasher(twox_64_concat) EventProofId => ();
+		/// Version of this module's storage schema
+		StorageVersion build(|_: &GenesisConfig| Releases::V2 as u32): u32;
 	}
 }
 
@@ -164,6 +367,22 @@ decl_event! {
 		Verified(EventClaimId),
 		/// Verifying an event failed
 		Invalid(EventClaimId),
+		/// A claim expired before it could be verified, the tx hash may be claimed again
+		ClaimExpired(EventClaimId),
+		/// A receipt proof for a claim is awaiting the header of a block (claim, chain id, block number)
+		ReceiptProofPending(EventClaimId, EthChainId, u64),
+		/// A receipt proof failed verification against its relayed header, the claim remains pending
+		ReceiptProofInvalid(EventClaimId),
+		/// Notaries relayed a block header (chain id, block number, block hash)
+		HeaderRelayed(EthChainId, u64, EthHash),
+		/// Events may be bridged from an EVM chain (chain id)
+		EvmChainSet(EthChainId),
+		/// Events may no longer be bridged from an EVM chain (chain id)
+		EvmChainRemoved(EthChainId),
+		/// The deposit of an invalid claim was slashed
+		ClaimDepositSlashed(EventClaimId),
+		/// A message was sent to a contract on an EVM chain (proof id, chain id, destination, nonce)
+		MessageSent(EventProofId, EthChainId, EthAddress, u64),
 		/// A notary (validator) set change is in motion (event_id, new_validator_set_id)
 		/// A proof for the change will be generated with the given `event_id`
 		AuthoritySetChange(EventProofId, u64),
@@ -189,6 +408,34 @@ decl_error! {
 		/// The bridge is paused pending validator set changes (once every era / 24 hours)
 		/// It will reactive after ~10 minutes
 		BridgePaused,
+		/// Not enough Eth JSON-RPC endpoints responded in agreement
+		EthRpcNoQuorum,
+		/// Transient claim results are retried offchain and may not be notarized
+		TransientNotarization,
+		/// The receipt proof exceeds the trie node count or size limits
+		ReceiptProofTooLarge,
+		/// Only the submitter of a claim may prove it
+		NotClaimSubmitter,
+		/// The receipt proof's block is past the head of its EVM chain or older than the event deadline
+		ImplausibleReceiptProofBlock,
+		/// Too many Ethereum headers or receipt proofs are awaiting attestation
+		TooManyRequestedHeaders,
+		/// The receipt proof does not prove the claimed event
+		InvalidReceiptProof,
+		/// The proven event is past the expiration deadline
+		ReceiptProofExpired,
+		/// The header was not requested or has been relayed already
+		UnexpectedHeader,
+		/// Events are not bridged from the EVM chain
+		UnsupportedChain,
+		/// Ethereum is configured by the runtime
+		InvalidChain,
+		/// The account has too many pending claims
+		TooManyAccountClaims,
+		/// Too many claims were submitted this block
+		TooManyBlockClaims,
+		/// The message payload exceeds `MAX_MESSAGE_PAYLOAD`
+		MessageTooLarge,
 	}
 }
 
@@ -196,36 +443,45 @@ decl_module! {
 	pub struct Module<T: Config> for enum Call where origin: T::Origin {
 		fn deposit_event() = default;
 
+		fn on_runtime_upgrade() -> Weight {
+			let mut weight = Zero::zero();
+			if StorageVersion::get() == Releases::V0 as u32 {
+				StorageVersion::put(Releases::V1 as u32);
+				weight = Self::migrate_to_multi_chain();
+			}
+			if StorageVersion::get() == Releases::V1 as u32 {
+				StorageVersion::put(Releases::V2 as u32);
+				weight = weight.saturating_add(Self::migrate_processed_tx_buckets());
+			}
+			weight
+		}
+
 		fn on_initialize(block_number: T::BlockNumber) -> Weight {
+			BlockClaimCount::kill();
+			let expiry_weight = Self::expire_claims(block_number)
+				.saturating_add(Self::prune_claim_statuses(block_number))
+				.saturating_add(Self::prune_headers(block_number))
+				.saturating_add(Self::prune_messages(block_number));
+
 			// Prune claim storage every hour on CENNZnet (BUCKET_FACTOR_S / 5 seconds = 720 blocks)
-			if (block_number % T::BlockNumber::from(CLAIM_PRUNING_INTERVAL)).is_zero() {
-				// Find the bucket to expire
+			let pruning_weight = if (block_number % T::BlockNumber::from(CLAIM_PRUNING_INTERVAL)).is_zero() {
 				let now = T::UnixTime::now().as_secs().saturated_into::<u64>();
-				let expired_bucket_index = (now - Self::event_deadline_seconds()) % BUCKET_FACTOR_S;
-				for (expired_tx_hash, _empty_value) in ProcessedTxBuckets::iter_prefix(expired_bucket_index) {
-					ProcessedTxHashes::remove(expired_tx_hash);
+				for (chain_id, config) in Self::evm_chain_configs() {
+					// Find the bucket to expire
+					let expired_bucket_index = now.saturating_sub(config.event_deadline_seconds) / BUCKET_FACTOR_S;
+					for (expired_tx_hash, _empty_value) in ProcessedTxBuckets::iter_prefix((chain_id, expired_bucket_index)) {
+						ProcessedTxHashes::remove(chain_id, expired_tx_hash);
+					}
+					ProcessedTxBuckets::remove_prefix((chain_id, expired_bucket_index), None);
 				}
-				ProcessedTxBuckets::remove_prefix(expired_bucket_index, None);
 
 				// TODO: better estimate
 				50_000_000_u64
 			} else {
 				Zero::zero()
-			}
-		}
+			};
 
-		#[weight = 100_000]
-		/// Set event confirmations (blocks). Required block confirmations for an Ethereum event to be notarized by CENNZnet
-		pub fn set_event_confirmations(origin, confirmations: u64) {
-			ensure_root(origin)?;
-			EventConfirmations::put(confirmations)
-		}
-
-		#[weight = 100_000]
-		/// Set event deadline (seconds). Events cannot be notarized after this time has elapsed
-		pub fn set_event_deadline(origin, seconds: u64) {
-			ensure_root(origin)?;
-			EventDeadlineSeconds::put(seconds);
+			expiry_weight.saturating_add(pruning_weight).saturating_add(T::DbWeight::get().writes(1))
 		}
 
 		#[weight = 1_000_000]
@@ -234,6 +490,7 @@ decl_module! {
 		/// Validators will submit inherents with their notarization vote for a given claim
 		pub fn submit_notarization(origin, payload: NotarizationPayload, _signature: <<T as Config>::EthyId as RuntimeAppPublic>::Signature) {
 			let _ = ensure_none(origin)?;
+			ensure!(!payload.result.is_transient(), Error::<T>::TransientNotarization);
 
 			// we don't need to verify the signature here because it has been verified in
 			// `validate_unsigned` function when sending out the unsigned tx.
@@ -242,6 +499,10 @@ decl_module! {
 				Some(id) => id,
 				None => return Err(Error::<T>::InvalidNotarization.into()),
 			};
+			// the claim has a result already, the vote only counts towards the notary's performance
+			if !EventData::contains_key(payload.event_claim_id) {
+				return Self::note_late_notarization(&payload);
+			}
 			<EventNotarizations<T>>::insert::<EventClaimId, T::EthyId, EventClaimResult>(payload.event_claim_id, notary_public_key.clone(), payload.result);
 
 			T::AuthoritySet::validators().get(payload.authority_index as usize)
@@ -251,12 +512,21 @@ decl_module! {
 			let notary_count = T::AuthoritySet::validators().len() as u32;
 			let mut yay_count = 0_u32;
 			let mut nay_count = 0_u32;
+			// nay votes by result
+			let mut reasons = Vec::<(EventClaimResult, u32)>::new();
 			for (_id, result) in <EventNotarizations<T>>::iter_prefix(payload.event_claim_id) {
 				match result {
 					EventClaimResult::Valid => yay_count += 1,
-					_ => nay_count += 1,
+					reason => {
+						nay_count += 1;
+						match reasons.iter_mut().find(|(r, _)| *r == reason) {
+							Some((_, count)) => *count += 1,
+							None => reasons.push((reason, 1)),
+						}
+					}
 				}
 			}
+			let block_number = <frame_system::Pallet<T>>::block_number();
 
 			// Claim is invalid (nays > (100% - NotarizationThreshold))
 			if Percent::from_rational(nay_count, notary_count) > (Percent::from_parts(100_u8 - T::NotarizationThreshold::get().deconstruct())) {
@@ -267,40 +537,153 @@ decl_module! {
 					log!(error, "💎 unexpected empty claim");
 					return Err(Error::<T>::InvalidClaim.into())
 				}
+				Self::note_claim_result(payload.event_claim_id, false);
+				Self::settle_claim_deposit(payload.event_claim_id, true);
 				<EventNotarizations<T>>::remove_prefix(payload.event_claim_id, None);
 				let (_eth_tx_hash, event_type_id) = EventClaims::take(payload.event_claim_id);
-				let (contract_address, event_signature) = TypeIdToEventType::get(event_type_id);
+				let (chain_id, contract_address, event_signature) = TypeIdToEventType::get(event_type_id);
 				let event_data = event_data.unwrap();
+				Self::finalize_claim_status(payload.event_claim_id, ClaimStatus::Rejected { block_number, reasons });
 				Self::deposit_event(Event::Invalid(payload.event_claim_id));
 
-				T::Subscribers::on_failure(payload.event_claim_id, &contract_address, &event_signature, &event_data);
+				T::Subscribers::on_failure(payload.event_claim_id, chain_id, &contract_address, &event_signature, &event_data);
 				return Ok(());
 			}
 
 			// Claim is valid
 			if Percent::from_rational(yay_count, notary_count) >= T::NotarizationThreshold::get() {
-				let event_data = EventData::take(payload.event_claim_id);
-				if event_data.is_none() {
-					// this should never happen
-					log!(error, "💎 unexpected empty claim");
-					return Err(Error::<T>::InvalidClaim.into())
+				Self::note_claim_result(payload.event_claim_id, true);
+				Self::approve_claim(payload.event_claim_id)?;
+			} else {
+				<ClaimStatuses<T>>::mutate(payload.event_claim_id, |record| {
+					if let Some(record) = record {
+						record.status = ClaimStatus::Pending { yay: yay_count, nay: nay_count };
+					}
+				});
+			}
+		}
+
+		#[weight = {
+			5_000_000 + 1_000 * proof.size() as Weight
+		}]
+		#[transactional]
+		/// Prove an event claim with a Merkle-Patricia proof of its Ethereum tx and receipt
+		/// The proof is verified immediately if the header of its Ethereum block has been relayed,
+		/// otherwise it is verified once notaries have attested to the header
+		/// Only the claim submitter may submit a proof, replacing any proof of theirs still awaiting a header
+		pub fn submit_receipt_proof(origin, event_claim_id: EventClaimId, proof: ReceiptProof) {
+			let origin = ensure_signed(origin)?;
+			ensure!(
+				proof.node_count() <= MAX_PROOF_NODES && proof.size() <= MAX_PROOF_SIZE,
+				Error::<T>::ReceiptProofTooLarge
+			);
+			ensure!(EventData::contains_key(event_claim_id), Error::<T>::InvalidClaim);
+			ensure!(
+				Self::claim_deposit(event_claim_id).map(|(submitter, _deposit)| submitter) == Some(origin),
+				Error::<T>::NotClaimSubmitter
+			);
+
+			let (_tx_hash, event_type_id) = EventClaims::get(event_claim_id);
+			let (chain_id, _contract_address, _event_signature) = TypeIdToEventType::get(event_type_id);
+			ensure!(Self::is_plausible_block(chain_id, proof.block_number), Error::<T>::ImplausibleReceiptProofBlock);
+			if let Some(pending_proof) = PendingReceiptProofs::take(event_claim_id) {
+				Self::withdraw_header_request(chain_id, pending_proof.block_number, event_claim_id);
+			}
+			if let Some(header) = Self::relayed_header(chain_id, proof.block_number) {
+				Self::verify_receipt_proof(event_claim_id, &header, &proof)?;
+				return Self::approve_claim(event_claim_id);
+			}
+
+			// request the header from notaries
+			HeaderProofClaims::try_mutate((chain_id, proof.block_number), |claims| -> DispatchResult {
+				ensure!(claims.len() < MAX_PROOFS_PER_HEADER, Error::<T>::TooManyRequestedHeaders);
+				if claims.is_empty() {
+					<RequestedHeaders<T>>::try_mutate(|requested| -> DispatchResult {
+						ensure!(requested.len() < MAX_REQUESTED_HEADERS, Error::<T>::TooManyRequestedHeaders);
+						requested.push((chain_id, proof.block_number, <frame_system::Pallet<T>>::block_number()));
+						Ok(())
+					})?;
 				}
-				// no need to track info on this claim any more since it's approved
-				<EventNotarizations<T>>::remove_prefix(payload.event_claim_id, None);
-				let (eth_tx_hash, event_type_id) = EventClaims::take(payload.event_claim_id);
-				let (contract_address, event_signature) = TypeIdToEventType::get(event_type_id);
-				let event_data = event_data.unwrap();
+				claims.push(event_claim_id);
+				Ok(())
+			})?;
+			Self::deposit_event(Event::ReceiptProofPending(event_claim_id, chain_id, proof.block_number));
+			PendingReceiptProofs::insert(event_claim_id, proof);
+		}
 
-				// note this tx as completed
-				let bucket_index = T::UnixTime::now().as_secs().saturated_into::<u64>() % BUCKET_FACTOR_S;
-				ProcessedTxBuckets::insert(bucket_index, eth_tx_hash, ());
-				ProcessedTxHashes::insert(eth_tx_hash, ());
-				Self::deposit_event(Event::Verified(payload.event_claim_id));
+		#[weight = 1_000_000 + 5_000_000 * MAX_PROOFS_PER_HEADER as Weight]
+		#[transactional]
+		/// Internal only
+		/// Validators will submit inherents attesting to the header of a requested Ethereum block
+		pub fn submit_header_attestation(origin, payload: HeaderAttestationPayload, _signature: <<T as Config>::EthyId as RuntimeAppPublic>::Signature) {
+			let _ = ensure_none(origin)?;
+			let header_key = (payload.chain_id, payload.header.number);
+			ensure!(Self::is_requested_header(payload.chain_id, payload.header.number), Error::<T>::UnexpectedHeader);
+
+			// we don't need to verify the signature here because it has been verified in
+			// `validate_unsigned` function when sending out the unsigned tx.
+			let notary_keys = Self::notary_keys();
+			let notary_public_key = match notary_keys.get(payload.authority_index as usize) {
+				Some(id) => id,
+				None => return Err(Error::<T>::InvalidNotarization.into()),
+			};
+			let header_hash = payload.header.hash();
+			<HeaderAttestations<T>>::insert(header_key, notary_public_key, header_hash);
+
+			// Count attestations to this header, notaries may disagree while they are near the head of the chain
+			let attestations = <HeaderAttestations<T>>::iter_prefix(header_key)
+				.filter(|(_id, attested_hash)| *attested_hash == header_hash)
+				.count();
+			if Percent::from_rational(attestations, notary_keys.len()) >= T::NotarizationThreshold::get() {
+				Self::relay_header(payload.chain_id, &payload.header);
+			}
+		}
 
-				T::Subscribers::on_success(payload.event_claim_id, &contract_address, &event_signature, &event_data);
+		#[weight = 1_000_000]
+		/// Set the config of an EVM chain events may be bridged from or `None` to stop bridging it (requires governance)
+		/// Pending claims of a removed chain expire
+		pub fn set_evm_chain(origin, chain_id: EthChainId, config: Option<EvmChainConfig>) {
+			ensure_root(origin)?;
+			ensure!(chain_id != T::EthereumChainId::get(), Error::<T>::InvalidChain);
+			match config {
+				Some(config) => {
+					EvmChains::insert(chain_id, config);
+					Self::deposit_event(Event::EvmChainSet(chain_id));
+				}
+				None => {
+					EvmChains::remove(chain_id);
+					Self::deposit_event(Event::EvmChainRemoved(chain_id));
+				}
 			}
 		}
 
+		#[weight = 50_000_000]
+		#[transactional]
+		/// Send a message to the `destination` contract on EVM chain `chain_id`, charging `MessageFee`
+		/// Notaries sign a proof of the message (sender, destination, nonce, payload) for relaying to the contract
+		pub fn send_message(origin, chain_id: EthChainId, destination: EthAddress, payload: Vec<u8>) {
+			let origin = ensure_signed(origin)?;
+			ensure!(payload.len() <= MAX_MESSAGE_PAYLOAD, Error::<T>::MessageTooLarge);
+
+			let fee = T::Currency::withdraw(&origin, T::MessageFee::get(), WithdrawReasons::FEE, ExistenceRequirement::KeepAlive)?;
+			T::OnMessageFee::on_unbalanced(fee);
+
+			let nonce = Self::next_message_nonce();
+			let message = Message {
+				sender: Self::account_to_bytes32(&origin),
+				destination,
+				nonce,
+				payload,
+			};
+			let event_proof_id = <Self as EventClaimVerifier>::generate_event_proof(chain_id, &message)?;
+			NextMessageNonce::put(nonce.wrapping_add(1));
+			Messages::insert(event_proof_id, (chain_id, message));
+			let prune_block = <frame_system::Pallet<T>>::block_number() + T::BlockNumber::from(MESSAGE_RETENTION_BLOCKS);
+			<MessagePruning<T>>::insert(prune_block, event_proof_id, ());
+
+			Self::deposit_event(Event::MessageSent(event_proof_id, chain_id, destination, nonce));
+		}
+
 		fn offchain_worker(block_number: T::BlockNumber) {
 			log!(trace, "💎 entering off-chain worker: {:?}", block_number);
 			log!(trace, "💎 active notaries: {:?}", Self::notary_keys());
@@ -349,11 +732,14 @@ decl_module! {
 			}
 			let (active_key, authority_index) = maybe_active_key.map(|(key, idx)| (key, idx as u16)).unwrap();
 
+			Self::offchain_attest_headers(&active_key, authority_index);
+
 			// check all pending claims we have _yet_ to notarize and try to notarize them
 			// this will be invoked once every block
 			// we limit the total claims per invocation using `CLAIMS_PER_BLOCK` so we don't stall block production
 			let mut budget = CLAIMS_PER_BLOCK;
-			for (event_claim_id, (tx_hash, event_type_id)) in EventClaims::iter() {
+			Self::prune_claim_retries();
+			for (event_claim_id, (tx_hash, event_type_id)) in Self::prioritised_claims() {
 				if budget.is_zero() {
 					log!(info, "💎 claims budget exceeded, exiting...");
 					return
@@ -362,13 +748,64 @@ decl_module! {
 				// check we haven't notarized this already
 				if <EventNotarizations<T>>::contains_key::<EventClaimId, T::EthyId>(event_claim_id, active_key.clone()) {
 					log!(trace, "💎 already cast notarization for claim: {:?}, ignoring...", event_claim_id);
+					continue;
+				}
+
+				// wait out the backoff after a transient result
+				let retry_key = [CLAIM_RETRY_PREFIX, &event_claim_id.to_be_bytes()].concat();
+				let retry = sp_io::offchain::local_storage_get(StorageKind::PERSISTENT, &retry_key)
+					.and_then(|retry| <(u32, T::BlockNumber)>::decode(&mut &retry[..]).ok());
+				if let Some((_attempts, retry_at)) = retry {
+					if block_number < retry_at {
+						log!(trace, "💎 claim: {:?} retry scheduled at: {:?}, ignoring...", event_claim_id, retry_at);
+						continue;
+					}
 				}
 
 				if let Some(event_data) = Self::event_data(event_claim_id) {
-					let (contract_address, event_signature) = TypeIdToEventType::get(event_type_id);
-					let event_claim = EventClaim { tx_hash, data: event_data, contract_address, event_signature };
-					let result = Self::offchain_try_notarize_event(event_claim);
+					let (chain_id, contract_address, event_signature) = TypeIdToEventType::get(event_type_id);
+					// the chain was removed, the claim will expire
+					let config = match Self::evm_chain_config(chain_id) {
+						Some(config) => config,
+						None => {
+							log!(trace, "💎 claim: {:?} chain: {:?} unsupported, ignoring...", event_claim_id, chain_id);
+							continue;
+						}
+					};
+					let event_claim = EventClaim { chain_id, tx_hash, data: event_data, contract_address, event_signature };
+					let result = Self::offchain_try_notarize_event(event_claim, &config);
 					log!(trace, "💎 claim verification status: {:?}", &result);
+					budget = budget.saturating_sub(1);
+
+					// transient results are not notarized, check the claim again after a backoff
+					if result.is_transient() {
+						let attempts = retry.map(|(attempts, _)| attempts).unwrap_or_default().saturating_add(1);
+						let retry_at = block_number + T::BlockNumber::from(claim_retry_backoff(attempts));
+						sp_io::offchain::local_storage_set(
+							StorageKind::PERSISTENT,
+							&retry_key,
+							&(attempts, retry_at).encode(),
+						);
+						if attempts == 1 {
+							let mut retry_ids = Self::claim_retry_ids();
+							retry_ids.push(event_claim_id);
+							sp_io::offchain::local_storage_set(
+								StorageKind::PERSISTENT,
+								CLAIM_RETRY_IDS_KEY,
+								&retry_ids.encode(),
+							);
+						}
+						log!(
+							info,
+							"💎 claim: {:?} result: {:?}, retrying at: {:?}",
+							event_claim_id,
+							result,
+							retry_at
+						);
+						continue;
+					}
+					sp_io::offchain::local_storage_clear(StorageKind::PERSISTENT, &retry_key);
+
 					let payload = NotarizationPayload {
 						event_claim_id,
 						authority_index,
@@ -381,10 +818,10 @@ decl_module! {
 						.map(|_| {
 							log!(info, "💎 sent notarization: '{:?}' for claim: {:?}", result, event_claim_id);
 						});
-					budget = budget.saturating_sub(1);
 				} else {
 					// should not happen, defensive only
 					log!(error, "💎 empty claim data for: {:?}", event_claim_id);
+					sp_io::offchain::local_storage_clear(StorageKind::PERSISTENT, &retry_key);
 				}
 			}
 
@@ -395,51 +832,96 @@ decl_module! {
 }
 
 impl<T: Config> EventClaimVerifier for Module<T> {
-	/// Submit an event claim against an ethereum tx hash
+	type AccountId = T::AccountId;
+
+	/// Submit an event claim against an ethereum tx hash, reserving the claim deposit from `submitter`
 	// tx hashes may only be claimed once
 	fn submit_event_claim(
+		submitter: &T::AccountId,
+		chain_id: EthChainId,
 		contract_address: &H160,
 		event_signature: &H256,
 		tx_hash: &H256,
 		event_data: &[u8],
 	) -> Result<EventClaimId, DispatchError> {
-		ensure!(!ProcessedTxHashes::contains_key(tx_hash), Error::<T>::AlreadyNotarized);
+		ensure!(Self::evm_chain_config(chain_id).is_some(), Error::<T>::UnsupportedChain);
+		ensure!(
+			!ProcessedTxHashes::contains_key(chain_id, tx_hash),
+			Error::<T>::AlreadyNotarized
+		);
+		ensure!(
+			Self::block_claim_count() < T::MaxClaimsPerBlock::get(),
+			Error::<T>::TooManyBlockClaims
+		);
+		let account_claim_count = Self::account_claim_count(submitter);
+		ensure!(
+			account_claim_count < T::MaxClaimsPerAccount::get(),
+			Error::<T>::TooManyAccountClaims
+		);
+		let deposit = T::ClaimDeposit::get();
+		T::Currency::reserve(submitter, deposit)?;
 
 		// check if we've seen this event type before
-		// if not we assign it a type Id (saves us storing the (contract address, event signature) each time)
-		let event_type_id = if !EventTypeToTypeId::contains_key((contract_address, event_signature)) {
+		// if not we assign it a type Id (saves us storing the (chain id, contract address, event signature) each time)
+		let event_type = (chain_id, *contract_address, *event_signature);
+		let event_type_id = if !EventTypeToTypeId::contains_key(event_type) {
 			let next_event_type_id = Self::next_event_type_id();
-			EventTypeToTypeId::insert((contract_address, event_signature), next_event_type_id);
-			TypeIdToEventType::insert(next_event_type_id, (contract_address, event_signature));
+			EventTypeToTypeId::insert(event_type, next_event_type_id);
+			TypeIdToEventType::insert(next_event_type_id, event_type);
 			NextEventTypeId::put(next_event_type_id.wrapping_add(1));
 			next_event_type_id
 		} else {
-			EventTypeToTypeId::get((contract_address, event_signature))
+			EventTypeToTypeId::get(event_type)
 		};
 
 		let event_claim_id = Self::next_event_claim_id();
 		EventData::insert(event_claim_id, event_data);
 		EventClaims::insert(event_claim_id, (tx_hash, event_type_id));
+		let expiry_block = <frame_system::Pallet<T>>::block_number() + T::BlockNumber::from(CLAIM_EXPIRY_BLOCKS);
+		<ClaimExpiries<T>>::insert(expiry_block, event_claim_id, ());
+		<ClaimStatuses<T>>::insert(
+			event_claim_id,
+			ClaimRecord {
+				event_claim_id,
+				chain_id,
+				tx_hash: *tx_hash,
+				status: ClaimStatus::Pending { yay: 0, nay: 0 },
+			},
+		);
+		TxHashClaims::insert(chain_id, tx_hash, event_claim_id);
+		<ClaimDeposits<T>>::insert(event_claim_id, (submitter.clone(), deposit));
+		<AccountClaimCount<T>>::insert(submitter, account_claim_count.saturating_add(1));
+		BlockClaimCount::mutate(|count| *count = count.saturating_add(1));
 		NextEventClaimId::put(event_claim_id.wrapping_add(1));
 
 		Ok(event_claim_id)
 	}
 
-	fn generate_event_proof<E: EthAbiCodec>(event: &E) -> Result<u64, DispatchError> {
+	fn generate_event_proof<E: EthAbiCodec>(chain_id: EthChainId, event: &E) -> Result<u64, DispatchError> {
 		ensure!(!Self::bridge_paused(), Error::<T>::BridgePaused);
+		ensure!(Self::evm_chain_config(chain_id).is_some(), Error::<T>::UnsupportedChain);
 		let event_proof_id = Self::next_proof_id();
 
+		// Proofs for chains other than Ethereum commit to the chain id, so they can't be replayed on another chain
+		let chain_id_param = if chain_id == T::EthereumChainId::get() {
+			vec![]
+		} else {
+			EthAbiCodec::encode(&chain_id)
+		};
 		// TODO: does this support multiple consensus logs in a block?
 		// save this for `on_finalize` and insert many
 		let packed_event_with_id = [
 			&event.encode()[..],
+			&chain_id_param[..],
 			&EthAbiCodec::encode(&Self::validator_set().id)[..],
 			&EthAbiCodec::encode(&event_proof_id)[..],
 		]
 		.concat();
+		// tag the proof with its destination chain for relayers
+		let tag = [&b"chain:"[..], chain_id.to_string().as_bytes()].concat();
 		let log: DigestItem = DigestItem::Consensus(
 			ETHY_ENGINE_ID,
-			ConsensusLog::<T::AccountId>::OpaqueSigningRequest((packed_event_with_id, event_proof_id)).encode(),
+			ConsensusLog::<T::AccountId>::TaggedSigningRequest((packed_event_with_id, event_proof_id, tag)).encode(),
 		);
 		<frame_system::Pallet<T>>::deposit_log(log);
 
@@ -450,6 +932,624 @@ impl<T: Config> EventClaimVerifier for Module<T> {
 }
 
 impl<T: Config> Module<T> {
+	/// Migrate storage keyed by Ethereum tx hash or event type to be keyed by chain, existing entries are Ethereum's
+	/// Relayed and requested headers are cleared, pending receipt proofs may be submitted again
+	fn migrate_to_multi_chain() -> Weight {
+		#[allow(dead_code)]
+		mod v0_storage {
+			use super::{ClaimStatus, Config, EthAddress, EthHash, EventClaimId, EventTypeId};
+			use codec::{Decode, Encode};
+			use scale_info::TypeInfo;
+
+			#[derive(Decode, Encode, Debug, PartialEq, Clone, TypeInfo)]
+			pub struct ClaimRecord<BlockNumber> {
+				pub event_claim_id: EventClaimId,
+				pub tx_hash: EthHash,
+				pub status: ClaimStatus<BlockNumber>,
+			}
+
+			pub struct Module<T>(sp_std::marker::PhantomData<T>);
+			frame_support::decl_storage! {
+				trait Store for Module<T: Config> as EthBridge {
+					pub ClaimStatuses: map hasher(twox_64_concat) EventClaimId => Option<ClaimRecord<T::BlockNumber>>;
+					pub TxHashClaims: map hasher(twox_64_concat) EthHash => Option<EventClaimId>;
+					pub EventTypeToTypeId: map hasher(blake2_128_concat) (EthAddress, EthHash) => EventTypeId;
+					pub TypeIdToEventType: map hasher(blake2_128_concat) EventTypeId => (EthAddress, EthHash);
+					pub ProcessedTxBuckets: double_map hasher(twox_64_concat) u64, hasher(identity) EthHash => ();
+					pub ProcessedTxHashes: map hasher(twox_64_concat) EthHash => ();
+				}
+			}
+		}
+
+		let chain_id = T::EthereumChainId::get();
+		let claim_statuses: Vec<_> = <v0_storage::ClaimStatuses<T>>::drain().collect();
+		let tx_hash_claims: Vec<_> = v0_storage::TxHashClaims::drain().collect();
+		let event_types: Vec<_> = v0_storage::TypeIdToEventType::drain().collect();
+		v0_storage::EventTypeToTypeId::remove_all(None);
+		let processed_tx_buckets: Vec<_> = v0_storage::ProcessedTxBuckets::drain().collect();
+		let processed_tx_hashes: Vec<_> = v0_storage::ProcessedTxHashes::drain().collect();
+		let migrated = (claim_statuses.len()
+			+ tx_hash_claims.len()
+			+ event_types.len()
+			+ processed_tx_buckets.len()
+			+ processed_tx_hashes.len()) as u64;
+
+		for (event_claim_id, record) in claim_statuses {
+			<ClaimStatuses<T>>::insert(
+				event_claim_id,
+				ClaimRecord {
+					event_claim_id: record.event_claim_id,
+					chain_id,
+					tx_hash: record.tx_hash,
+					status: record.status,
+				},
+			);
+		}
+		for (tx_hash, event_claim_id) in tx_hash_claims {
+			TxHashClaims::insert(chain_id, tx_hash, event_claim_id);
+		}
+		for (event_type_id, (contract_address, event_signature)) in event_types {
+			EventTypeToTypeId::insert((chain_id, contract_address, event_signature), event_type_id);
+			TypeIdToEventType::insert(event_type_id, (chain_id, contract_address, event_signature));
+		}
+		for (bucket_index, tx_hash, _) in processed_tx_buckets {
+			ProcessedTxBuckets::insert((chain_id, bucket_index), tx_hash, ());
+		}
+		for (tx_hash, _) in processed_tx_hashes {
+			ProcessedTxHashes::insert(chain_id, tx_hash, ());
+		}
+
+		RelayedHeaders::remove_all(None);
+		<RelayedHeaderPruning<T>>::remove_all(None);
+		<RequestedHeaders<T>>::kill();
+		<HeaderAttestations<T>>::remove_all(None);
+		PendingReceiptProofs::remove_all(None);
+		HeaderProofClaims::remove_all(None);
+
+		T::DbWeight::get().reads_writes(migrated + 6, migrated * 3 + 6)
+	}
+
+	/// Move processed txs out of buckets indexed by the second of the hour (`now % BUCKET_FACTOR_S`) into the current
+	/// hour's bucket, the time they were processed is unknown so they are kept for a full event deadline from now
+	fn migrate_processed_tx_buckets() -> Weight {
+		let current_bucket_index = T::UnixTime::now().as_secs().saturated_into::<u64>() / BUCKET_FACTOR_S;
+		let legacy_txs: Vec<_> = ProcessedTxBuckets::iter()
+			.filter(|((_chain_id, bucket_index), _tx_hash, _)| *bucket_index < BUCKET_FACTOR_S)
+			.map(|(key, tx_hash, _)| (key, tx_hash))
+			.collect();
+		let migrated = legacy_txs.len() as u64;
+
+		for ((chain_id, bucket_index), tx_hash) in legacy_txs {
+			ProcessedTxBuckets::remove((chain_id, bucket_index), tx_hash);
+			ProcessedTxBuckets::insert((chain_id, current_bucket_index), tx_hash, ());
+		}
+
+		T::DbWeight::get().reads_writes(migrated + 1, migrated * 2 + 1)
+	}
+
+	/// Expire claims scheduled to expire at `block_number` that are still pending
+	/// Their tx hashes are not marked processed so they may be claimed again
+	fn expire_claims(block_number: T::BlockNumber) -> Weight {
+		let mut scheduled = 0_u64;
+		let mut expired = 0_u64;
+		for (event_claim_id, _) in <ClaimExpiries<T>>::drain_prefix(block_number) {
+			scheduled += 1;
+			// the claim was verified or invalidated already
+			let event_data = match EventData::take(event_claim_id) {
+				Some(event_data) => event_data,
+				None => continue,
+			};
+			<EventNotarizations<T>>::remove_prefix(event_claim_id, None);
+			Self::settle_claim_deposit(event_claim_id, false);
+			let (_eth_tx_hash, event_type_id) = EventClaims::take(event_claim_id);
+			let (chain_id, contract_address, event_signature) = TypeIdToEventType::get(event_type_id);
+			Self::finalize_claim_status(event_claim_id, ClaimStatus::Expired(block_number));
+			Self::deposit_event(Event::ClaimExpired(event_claim_id));
+
+			T::Subscribers::on_failure(
+				event_claim_id,
+				chain_id,
+				&contract_address,
+				&event_signature,
+				&event_data,
+			);
+			expired += 1;
+		}
+
+		T::DbWeight::get().reads_writes(1 + scheduled + expired * 5, scheduled + expired * 8)
+	}
+
+	/// Prune messages whose retention ends at `block_number`
+	fn prune_messages(block_number: T::BlockNumber) -> Weight {
+		let mut pruned = 0_u64;
+		for (event_proof_id, _) in <MessagePruning<T>>::drain_prefix(block_number) {
+			Messages::remove(event_proof_id);
+			pruned += 1;
+		}
+
+		T::DbWeight::get().reads_writes(1 + pruned, pruned * 2)
+	}
+
+	/// The 32 byte representation of `who` for EVM contracts
+	fn account_to_bytes32(who: &T::AccountId) -> H256 {
+		let mut bytes = [0_u8; 32];
+		who.using_encoded(|encoded| {
+			let len = encoded.len().min(32);
+			bytes[..len].copy_from_slice(&encoded[..len]);
+		});
+		H256::from(bytes)
+	}
+
+	/// Return the deposit of a claim to its submitter, or slash it if `slash`
+	fn settle_claim_deposit(event_claim_id: EventClaimId, slash: bool) {
+		if let Some((submitter, deposit)) = <ClaimDeposits<T>>::take(event_claim_id) {
+			let account_claim_count = Self::account_claim_count(&submitter).saturating_sub(1);
+			if account_claim_count.is_zero() {
+				<AccountClaimCount<T>>::remove(&submitter);
+			} else {
+				<AccountClaimCount<T>>::insert(&submitter, account_claim_count);
+			}
+			if slash {
+				let (imbalance, _remaining) = T::Currency::slash_reserved(&submitter, deposit);
+				T::OnClaimSlash::on_unbalanced(imbalance);
+				Self::deposit_event(Event::ClaimDepositSlashed(event_claim_id));
+			} else {
+				let _ = T::Currency::unreserve(&submitter, deposit);
+			}
+		}
+	}
+
+	/// Pending claims in the order notaries should check them
+	/// Oldest first, taking one claim from each submitter in turn so no account can use up the `CLAIMS_PER_BLOCK` budget
+	fn prioritised_claims() -> Vec<(EventClaimId, (EthHash, EventTypeId))> {
+		let mut claims: Vec<(EventClaimId, (EthHash, EventTypeId))> = EventClaims::iter().collect();
+		claims.sort_unstable_by_key(|(event_claim_id, _)| *event_claim_id);
+
+		// the nth pending claim of a submitter is ranked n
+		let mut submitter_claims = BTreeMap::<T::AccountId, u64>::new();
+		let mut ranked_claims: Vec<(u64, (EventClaimId, (EthHash, EventTypeId)))> = claims
+			.into_iter()
+			.map(|claim| {
+				let rank = match Self::claim_deposit(claim.0) {
+					Some((submitter, _deposit)) => {
+						let count = submitter_claims.entry(submitter).or_default();
+						*count += 1;
+						*count - 1
+					}
+					None => 0,
+				};
+				(rank, claim)
+			})
+			.collect();
+		// stable sort keeps the oldest claims first within a rank
+		ranked_claims.sort_by_key(|(rank, _)| *rank);
+
+		ranked_claims.into_iter().map(|(_rank, claim)| claim).collect()
+	}
+
+	/// Record the result of a claim, its status is retained for `CLAIM_STATUS_RETENTION_BLOCKS`
+	fn finalize_claim_status(event_claim_id: EventClaimId, status: ClaimStatus<T::BlockNumber>) {
+		<ClaimStatuses<T>>::mutate(event_claim_id, |record| {
+			if let Some(record) = record {
+				record.status = status;
+			}
+		});
+		let prune_block =
+			<frame_system::Pallet<T>>::block_number() + T::BlockNumber::from(CLAIM_STATUS_RETENTION_BLOCKS);
+		<ClaimStatusPruning<T>>::insert(prune_block, event_claim_id, ());
+	}
+
+	/// Prune claim statuses whose retention ends at `block_number`
+	fn prune_claim_statuses(block_number: T::BlockNumber) -> Weight {
+		let mut pruned = 0_u64;
+		for (event_claim_id, _) in <ClaimStatusPruning<T>>::drain_prefix(block_number) {
+			if let Some(record) = <ClaimStatuses<T>>::take(event_claim_id) {
+				// the tx hash may have been claimed again since
+				if Self::tx_hash_claim(record.chain_id, record.tx_hash) == Some(event_claim_id) {
+					TxHashClaims::remove(record.chain_id, record.tx_hash);
+				}
+			}
+			pruned += 1;
+		}
+
+		T::DbWeight::get().reads_writes(1 + pruned * 2, pruned * 3)
+	}
+
+	/// Approve a claim, marking its tx hash processed and notifying subscribers
+	fn approve_claim(event_claim_id: EventClaimId) -> DispatchResult {
+		let event_data = EventData::take(event_claim_id);
+		if event_data.is_none() {
+			// this should never happen
+			log!(error, "💎 unexpected empty claim");
+			return Err(Error::<T>::InvalidClaim.into());
+		}
+		// no need to track info on this claim any more since it's approved
+		<EventNotarizations<T>>::remove_prefix(event_claim_id, None);
+		Self::settle_claim_deposit(event_claim_id, false);
+		let (eth_tx_hash, event_type_id) = EventClaims::take(event_claim_id);
+		let (chain_id, contract_address, event_signature) = TypeIdToEventType::get(event_type_id);
+		let event_data = event_data.unwrap();
+
+		// note this tx as completed
+		let bucket_index = T::UnixTime::now().as_secs().saturated_into::<u64>() / BUCKET_FACTOR_S;
+		ProcessedTxBuckets::insert((chain_id, bucket_index), eth_tx_hash, ());
+		ProcessedTxHashes::insert(chain_id, eth_tx_hash, ());
+		Self::finalize_claim_status(
+			event_claim_id,
+			ClaimStatus::Verified(<frame_system::Pallet<T>>::block_number()),
+		);
+		Self::deposit_event(Event::Verified(event_claim_id));
+
+		T::Subscribers::on_success(
+			event_claim_id,
+			chain_id,
+			&contract_address,
+			&event_signature,
+			&event_data,
+		);
+		Ok(())
+	}
+
+	/// Record the votes of each notary on a claim against its result (`valid`)
+	/// Claims proven by receipt proof or expired are not counted, notaries may not have had a chance to vote
+	fn note_claim_result(event_claim_id: EventClaimId, valid: bool) {
+		let notary_keys = Self::notary_keys();
+		let validators = T::AuthoritySet::validators();
+		let mut notaries = Vec::<u16>::new();
+		for (notary_public_key, result) in <EventNotarizations<T>>::iter_prefix(event_claim_id) {
+			let index = match notary_keys.iter().position(|k| k == &notary_public_key) {
+				Some(index) => index,
+				None => continue,
+			};
+			notaries.push(index as u16);
+			if let Some(validator) = validators.get(index) {
+				Self::note_notary_vote(validator, result == EventClaimResult::Valid, valid);
+			}
+		}
+		SessionClaimResults::insert(event_claim_id, (valid, notaries));
+		SessionClaims::mutate(|claims| *claims = claims.saturating_add(1));
+	}
+
+	/// Count a notarization arriving after its claim's result towards the notary's votes this session
+	/// With a notarization threshold below 100%, honest notaries often vote after the result
+	fn note_late_notarization(payload: &NotarizationPayload) -> DispatchResult {
+		SessionClaimResults::try_mutate(payload.event_claim_id, |record| -> DispatchResult {
+			let (valid, notaries) = record.as_mut().ok_or(Error::<T>::InvalidClaim)?;
+			ensure!(
+				!notaries.contains(&payload.authority_index),
+				Error::<T>::AlreadyNotarized
+			);
+			notaries.push(payload.authority_index);
+			if let Some(validator) = T::AuthoritySet::validators().get(payload.authority_index as usize) {
+				T::RewardHandler::reward_notary(validator);
+				Self::note_notary_vote(validator, payload.result == EventClaimResult::Valid, *valid);
+			}
+			Ok(())
+		})
+	}
+
+	/// Count a vote of `validator` on a claim, disagreeing when `voted_valid` differs from its result (`valid`)
+	fn note_notary_vote(validator: &T::AccountId, voted_valid: bool, valid: bool) {
+		<SessionNotaryVotes<T>>::mutate(validator, |notary_votes| {
+			notary_votes.votes = notary_votes.votes.saturating_add(1);
+			if voted_valid != valid {
+				notary_votes.disagreements = notary_votes.disagreements.saturating_add(1);
+			}
+		});
+	}
+
+	/// The bridge performance of each active validator over the current session
+	pub fn notary_performance() -> Vec<(T::AccountId, NotaryPerformance)> {
+		let claims = Self::session_claims();
+		T::AuthoritySet::validators()
+			.into_iter()
+			.map(|validator| {
+				let NotaryVotes { votes, disagreements } = Self::session_notary_votes(&validator);
+				let performance = NotaryPerformance {
+					claims,
+					votes,
+					missed: claims.saturating_sub(votes),
+					disagreements,
+				};
+				(validator, performance)
+			})
+			.collect()
+	}
+
+	/// Report notaries missing or contradicting the result of too many claims this session and reset the counts
+	fn report_notary_offences() {
+		let claims = Self::session_claims();
+		let performance = Self::notary_performance();
+		SessionClaims::kill();
+		SessionClaimResults::remove_all(None);
+		<SessionNotaryVotes<T>>::remove_all(None);
+		if claims < MIN_SESSION_CLAIMS {
+			return;
+		}
+
+		let validator_set_count = performance.len() as u32;
+		let offenders = performance
+			.into_iter()
+			.filter(|(_, p)| {
+				Percent::from_rational(p.missed, p.claims) > T::MaxMissedNotarizations::get()
+					|| Percent::from_rational(p.disagreements, p.claims) > T::MaxNotaryDisagreements::get()
+			})
+			.filter_map(|(validator, _)| {
+				<T::AuthoritySet as ValidatorSetWithIdentification<T::AccountId>>::IdentificationOf::convert(
+					validator.clone(),
+				)
+				.map(|full_id| (validator, full_id))
+			})
+			.collect::<Vec<IdentificationTuple<T>>>();
+		if offenders.is_empty() {
+			return;
+		}
+
+		log!(
+			info,
+			"💎 reporting {:?} notaries for session claim performance",
+			offenders.len()
+		);
+		let offence = NotaryOffence {
+			session_index: T::AuthoritySet::session_index(),
+			validator_set_count,
+			offenders,
+		};
+		if let Err(err) = T::ReportNotaryOffence::report_offence(vec![], offence) {
+			log!(error, "💎 reporting notary offence failed: {:?}", err);
+		}
+	}
+
+	/// Verify `proof` shows the tx of a claim emitted the claimed event in the Ethereum block of `header`
+	/// Checks:
+	/// - the tx is in the block's transactions trie at the proven index
+	/// - the tx receipt is in the block's receipts trie at the same index
+	/// - tx success
+	/// - the receipt has a log from the claimed contract address with the claimed event signature and data
+	/// - the block is not older than the event deadline of its chain
+	fn verify_receipt_proof(
+		event_claim_id: EventClaimId,
+		header: &RelayedHeader,
+		proof: &ReceiptProof,
+	) -> Result<(), Error<T>> {
+		let event_data = Self::event_data(event_claim_id).ok_or(Error::<T>::InvalidClaim)?;
+		let (tx_hash, event_type_id) = EventClaims::get(event_claim_id);
+		let (chain_id, contract_address, event_signature) = TypeIdToEventType::get(event_type_id);
+		let config = Self::evm_chain_config(chain_id).ok_or(Error::<T>::UnsupportedChain)?;
+		ensure!(
+			!ProcessedTxHashes::contains_key(chain_id, tx_hash),
+			Error::<T>::AlreadyNotarized
+		);
+
+		let key = proof::tx_index_key(proof.tx_index);
+		let transaction =
+			proof::verify_proof(&header.transactions_root, &key, &proof.transaction_proof).map_err(|err| {
+				log!(
+					debug,
+					"💎 invalid transaction proof for claim: {:?}, {:?}",
+					event_claim_id,
+					err
+				);
+				Error::<T>::InvalidReceiptProof
+			})?;
+		ensure!(
+			sp_io::hashing::keccak_256(&transaction) == tx_hash.0,
+			Error::<T>::InvalidReceiptProof
+		);
+
+		let receipt = proof::verify_proof(&header.receipts_root, &key, &proof.receipt_proof).map_err(|err| {
+			log!(
+				debug,
+				"💎 invalid receipt proof for claim: {:?}, {:?}",
+				event_claim_id,
+				err
+			);
+			Error::<T>::InvalidReceiptProof
+		})?;
+		let receipt = proof::decode_receipt(&receipt).map_err(|err| {
+			log!(debug, "💎 invalid receipt for claim: {:?}, {:?}", event_claim_id, err);
+			Error::<T>::InvalidReceiptProof
+		})?;
+		ensure!(receipt.status, Error::<T>::InvalidReceiptProof);
+		ensure!(
+			receipt.logs.iter().any(|log| log.address == contract_address
+				&& log.topics.first() == Some(&event_signature)
+				&& log.data == event_data),
+			Error::<T>::InvalidReceiptProof
+		);
+
+		let now = T::UnixTime::now().as_secs().saturated_into::<u64>();
+		ensure!(
+			now.saturating_sub(header.timestamp) <= config.event_deadline_seconds,
+			Error::<T>::ReceiptProofExpired
+		);
+
+		Ok(())
+	}
+
+	/// Whether a receipt proof may reference `eth_block_number` on `chain_id`
+	/// Bounded above by the latest relayed header plus the blocks the chain could have produced since,
+	/// and below by the latest relayed header once it is older than the chain's event deadline
+	/// Any block is plausible until a header of the chain has been relayed
+	fn is_plausible_block(chain_id: EthChainId, eth_block_number: u64) -> bool {
+		let (latest_number, latest_timestamp) = match Self::latest_relayed_header(chain_id) {
+			Some(latest) => latest,
+			None => return true,
+		};
+		let event_deadline_seconds = match Self::evm_chain_config(chain_id) {
+			Some(config) => config.event_deadline_seconds,
+			None => return false,
+		};
+		let elapsed = T::UnixTime::now()
+			.as_secs()
+			.saturated_into::<u64>()
+			.saturating_sub(latest_timestamp);
+		let max_block_number = latest_number
+			.saturating_add(elapsed.saturating_mul(MAX_EVM_BLOCKS_PER_SECOND))
+			.saturating_add(MAX_RECEIPT_PROOF_BLOCK_LEAD);
+		// events at or before an expired header are expired too
+		let min_block_number = if elapsed > event_deadline_seconds {
+			latest_number.saturating_add(1)
+		} else {
+			0
+		};
+
+		(min_block_number..=max_block_number).contains(&eth_block_number)
+	}
+
+	/// Remove `event_claim_id` from the claims awaiting the header of `eth_block_number` on `chain_id`
+	/// The header request is withdrawn when no other claims await it
+	fn withdraw_header_request(chain_id: EthChainId, eth_block_number: u64, event_claim_id: EventClaimId) {
+		let header_key = (chain_id, eth_block_number);
+		let mut claims = HeaderProofClaims::get(header_key);
+		claims.retain(|id| *id != event_claim_id);
+		if !claims.is_empty() {
+			HeaderProofClaims::insert(header_key, claims);
+			return;
+		}
+		HeaderProofClaims::remove(header_key);
+		<HeaderAttestations<T>>::remove_prefix(header_key, None);
+		<RequestedHeaders<T>>::mutate(|requested| {
+			requested.retain(|(requested_chain_id, requested, _)| {
+				*requested_chain_id != chain_id || *requested != eth_block_number
+			})
+		});
+	}
+
+	/// Whether notaries have been asked to attest to the header of `eth_block_number` on `chain_id`
+	fn is_requested_header(chain_id: EthChainId, eth_block_number: u64) -> bool {
+		Self::requested_headers()
+			.iter()
+			.any(|(requested_chain_id, requested, _)| *requested_chain_id == chain_id && *requested == eth_block_number)
+	}
+
+	/// Store a header of `chain_id` a threshold of notaries have attested to and verify receipt proofs awaiting it
+	fn relay_header(chain_id: EthChainId, header: &EthHeader) {
+		let eth_block_number = header.number;
+		let relayed_header = RelayedHeader::from(header);
+		<HeaderAttestations<T>>::remove_prefix((chain_id, eth_block_number), None);
+		<RequestedHeaders<T>>::mutate(|requested| {
+			requested.retain(|(requested_chain_id, requested, _)| {
+				*requested_chain_id != chain_id || *requested != eth_block_number
+			})
+		});
+		RelayedHeaders::insert(chain_id, eth_block_number, &relayed_header);
+		LatestRelayedHeader::mutate(chain_id, |latest| {
+			if latest.map_or(true, |(latest_number, _)| latest_number < eth_block_number) {
+				*latest = Some((eth_block_number, header.timestamp));
+			}
+		});
+		let prune_block = <frame_system::Pallet<T>>::block_number() + T::BlockNumber::from(HEADER_RETENTION_BLOCKS);
+		<RelayedHeaderPruning<T>>::insert(prune_block, (chain_id, eth_block_number), ());
+		Self::deposit_event(Event::HeaderRelayed(chain_id, eth_block_number, relayed_header.hash));
+
+		for event_claim_id in HeaderProofClaims::take((chain_id, eth_block_number)) {
+			let proof = match PendingReceiptProofs::take(event_claim_id) {
+				Some(proof) => proof,
+				None => continue,
+			};
+			// the claim was verified, invalidated or expired while waiting
+			if !EventData::contains_key(event_claim_id) {
+				continue;
+			}
+			let result = Self::verify_receipt_proof(event_claim_id, &relayed_header, &proof)
+				.map_err(Into::into)
+				.and_then(|_| Self::approve_claim(event_claim_id));
+			if let Err(err) = result {
+				log!(
+					info,
+					"💎 receipt proof for claim: {:?} failed: {:?}",
+					event_claim_id,
+					err
+				);
+				Self::deposit_event(Event::ReceiptProofInvalid(event_claim_id));
+			}
+		}
+	}
+
+	/// Prune relayed headers whose retention ends at `block_number`
+	/// and header requests not relayed within `CLAIM_EXPIRY_BLOCKS`, along with their receipt proofs
+	fn prune_headers(block_number: T::BlockNumber) -> Weight {
+		let mut pruned = 0_u64;
+		for ((chain_id, eth_block_number), _) in <RelayedHeaderPruning<T>>::drain_prefix(block_number) {
+			RelayedHeaders::remove(chain_id, eth_block_number);
+			pruned += 1;
+		}
+
+		let expiry = T::BlockNumber::from(CLAIM_EXPIRY_BLOCKS);
+		let (expired, requested): (Vec<_>, Vec<_>) = Self::requested_headers()
+			.into_iter()
+			.partition(|(_, _, requested_at)| *requested_at + expiry <= block_number);
+		if !expired.is_empty() {
+			<RequestedHeaders<T>>::put(requested);
+		}
+		for (chain_id, eth_block_number, _) in &expired {
+			<HeaderAttestations<T>>::remove_prefix((chain_id, eth_block_number), None);
+			for event_claim_id in HeaderProofClaims::take((chain_id, eth_block_number)) {
+				PendingReceiptProofs::remove(event_claim_id);
+			}
+		}
+		let expired = expired.len() as u64;
+
+		T::DbWeight::get().reads_writes(
+			2 + pruned + expired,
+			pruned * 2 + expired * (3 + MAX_PROOFS_PER_HEADER as u64),
+		)
+	}
+
+	/// Get the status of the latest claim of a tx hash on `chain_id`
+	pub fn claim_status_by_tx_hash(chain_id: EthChainId, tx_hash: &EthHash) -> Option<ClaimRecord<T::BlockNumber>> {
+		Self::tx_hash_claim(chain_id, tx_hash).and_then(Self::claim_status)
+	}
+
+	/// The config of an EVM chain events may be bridged from
+	pub fn evm_chain_config(chain_id: EthChainId) -> Option<EvmChainConfig> {
+		if chain_id == T::EthereumChainId::get() {
+			Some(EvmChainConfig {
+				event_confirmations: T::EventConfirmations::get(),
+				event_deadline_seconds: T::EventDeadlineSeconds::get(),
+			})
+		} else {
+			Self::evm_chain(chain_id)
+		}
+	}
+
+	/// The configs of all EVM chains events may be bridged from, Ethereum first
+	fn evm_chain_configs() -> Vec<(EthChainId, EvmChainConfig)> {
+		let ethereum_chain_id = T::EthereumChainId::get();
+		let mut configs = vec![(
+			ethereum_chain_id,
+			Self::evm_chain_config(ethereum_chain_id).unwrap_or_default(),
+		)];
+		configs.extend(EvmChains::iter());
+		configs
+	}
+
+	/// Ids of claims with a retry schedule in offchain storage
+	fn claim_retry_ids() -> Vec<EventClaimId> {
+		sp_io::offchain::local_storage_get(StorageKind::PERSISTENT, CLAIM_RETRY_IDS_KEY)
+			.and_then(|ids| Vec::<EventClaimId>::decode(&mut &ids[..]).ok())
+			.unwrap_or_default()
+	}
+
+	/// Clear the retry schedules of claims which have concluded or expired
+	/// Their event data is removed on-chain so they would never be checked again
+	fn prune_claim_retries() {
+		let retry_ids = Self::claim_retry_ids();
+		let (pending, concluded): (Vec<EventClaimId>, Vec<EventClaimId>) =
+			retry_ids.into_iter().partition(|id| EventData::contains_key(id));
+		if concluded.is_empty() {
+			return;
+		}
+		for event_claim_id in concluded {
+			sp_io::offchain::local_storage_clear(
+				StorageKind::PERSISTENT,
+				&[CLAIM_RETRY_PREFIX, &event_claim_id.to_be_bytes()].concat(),
+			);
+		}
+		sp_io::offchain::local_storage_set(StorageKind::PERSISTENT, CLAIM_RETRY_IDS_KEY, &pending.encode());
+	}
+
 	/// Verify a message
 	/// `tx_hash` - The ethereum tx hash
 	/// `event_data` - The claimed message data
@@ -459,16 +1559,17 @@ impl<T: Config> Module<T> {
 	/// - tx success
 	/// - tx sent to deposit contract address
 	/// - check for log with deposited amount and token type
-	/// - confirmations `>= T::EventConfirmations`
-	/// - message has not expired older than `T::EventDeadline`
-	fn offchain_try_notarize_event(event_claim: EventClaim) -> EventClaimResult {
+	/// - confirmations `>= config.event_confirmations`
+	/// - message has not expired older than `config.event_deadline_seconds`
+	fn offchain_try_notarize_event(event_claim: EventClaim, config: &EvmChainConfig) -> EventClaimResult {
 		let EventClaim {
+			chain_id,
 			tx_hash,
 			data,
 			contract_address,
 			event_signature,
 		} = event_claim;
-		let result = Self::get_transaction_receipt(tx_hash);
+		let result = Self::get_transaction_receipt(chain_id, tx_hash);
 		if let Err(err) = result {
 			log!(error, "💎 eth_getTransactionReceipt({:?}) failed: {:?}", tx_hash, err);
 			return EventClaimResult::DataProviderErr;
@@ -514,7 +1615,7 @@ impl<T: Config> Module<T> {
 		//  have we got enough block confirmations to be re-org safe?
 		let observed_block_number: u64 = tx_receipt.block_number.saturated_into();
 
-		let latest_block: EthBlock = match Self::get_block(LatestOrNumber::Latest) {
+		let latest_block: EthBlock = match Self::get_block(chain_id, LatestOrNumber::Latest) {
 			Ok(None) => return EventClaimResult::DataProviderErr,
 			Ok(Some(block)) => block,
 			Err(err) => {
@@ -525,32 +1626,34 @@ impl<T: Config> Module<T> {
 
 		let latest_block_number = latest_block.number.unwrap_or_default().as_u64();
 		let block_confirmations = latest_block_number.saturating_sub(observed_block_number);
-		if block_confirmations < Self::event_confirmations() {
+		if block_confirmations < config.event_confirmations {
 			return EventClaimResult::NotEnoughConfirmations;
 		}
 
-		// we can calculate if the block is expired w some high degree of confidence
+		// we can calculate if the block is expired w some high degree of confidence on Ethereum
 		// time since the event = block_confirmations * ~16 seconds avg
 		// `20` arbitrarily chosen by adding a few seconds to the average block time
-		if block_confirmations * 20 > Self::event_deadline_seconds() {
+		// other EVM chains have faster blocks, rely on the block timestamp below
+		if chain_id == T::EthereumChainId::get() && block_confirmations * 20 > config.event_deadline_seconds {
 			return EventClaimResult::Expired;
 		}
 
 		//  check the block this tx is in if the timestamp > deadline
-		let observed_block: EthBlock = match Self::get_block(LatestOrNumber::Number(observed_block_number as u32)) {
-			Ok(None) => return EventClaimResult::DataProviderErr,
-			Ok(Some(block)) => block,
-			Err(err) => {
-				log!(error, "💎 eth_getBlockByNumber observed failed: {:?}", err);
-				return EventClaimResult::DataProviderErr;
-			}
-		};
+		let observed_block: EthBlock =
+			match Self::get_block(chain_id, LatestOrNumber::Number(observed_block_number as u32)) {
+				Ok(None) => return EventClaimResult::DataProviderErr,
+				Ok(Some(block)) => block,
+				Err(err) => {
+					log!(error, "💎 eth_getBlockByNumber observed failed: {:?}", err);
+					return EventClaimResult::DataProviderErr;
+				}
+			};
 
 		// claim is past the expiration deadline
 		// eth. block timestamp (seconds)
 		// deadline (seconds)
 		if T::UnixTime::now().as_secs().saturated_into::<u64>() - observed_block.timestamp.saturated_into::<u64>()
-			> Self::event_deadline_seconds()
+			> config.event_deadline_seconds
 		{
 			return EventClaimResult::Expired;
 		}
@@ -558,36 +1661,84 @@ impl<T: Config> Module<T> {
 		EventClaimResult::Valid
 	}
 
-	/// Get transaction receipt from eth client
-	fn get_transaction_receipt(tx_hash: EthHash) -> Result<Option<TransactionReceipt>, Error<T>> {
+	/// Get transaction receipt from the eth client of `chain_id`
+	fn get_transaction_receipt(chain_id: EthChainId, tx_hash: EthHash) -> Result<Option<TransactionReceipt>, Error<T>> {
 		let random_request_id = u32::from_be_bytes(sp_io::offchain::random_seed()[..4].try_into().unwrap());
 		let request = GetTxReceiptRequest::new(tx_hash, random_request_id as usize);
-		let resp_bytes = Self::query_eth_client(Some(request)).map_err(|e| {
-			log!(error, "💎 read eth-rpc API error: {:?}", e);
-			<Error<T>>::HttpFetch
-		})?;
+		Self::query_eth_endpoints(chain_id, &request, matching_responses)
+	}
 
-		let resp_str = core::str::from_utf8(&resp_bytes).map_err(|_| {
-			log!(error, "💎 response invalid utf8: {:?}", resp_bytes);
-			<Error<T>>::HttpFetch
-		})?;
+	/// Get latest block number from the eth client of `chain_id`
+	fn get_block(chain_id: EthChainId, req: LatestOrNumber) -> Result<Option<EthBlock>, Error<T>> {
+		match req {
+			// endpoints can be a few blocks apart at the head of the chain
+			LatestOrNumber::Latest => {
+				Self::query_eth_endpoints(chain_id, &GetBlockRequest::latest(1_usize), lowest_block)
+			}
+			LatestOrNumber::Number(n) => {
+				Self::query_eth_endpoints(chain_id, &GetBlockRequest::for_number(1_usize, n), matching_responses)
+			}
+		}
+	}
 
-		// Deserialize JSON to struct
-		serde_json::from_str::<EthResponse<TransactionReceipt>>(resp_str)
-			.map(|resp| resp.result)
-			.map_err(|err| {
-				log!(error, "💎 deserialize json response error: {:?}", err);
-				<Error<T>>::HttpFetch
-			})
+	/// Send `request` to the configured JSON-RPC endpoints of `chain_id`, healthiest first
+	/// Endpoints are queried until `resolve` finds a result among a quorum of responses,
+	/// failing over to the next endpoint on any error
+	fn query_eth_endpoints<R, D>(
+		chain_id: EthChainId,
+		request: &R,
+		resolve: fn(&[Option<D>], usize) -> Option<Option<D>>,
+	) -> Result<Option<D>, Error<T>>
+	where
+		R: serde::Serialize,
+		D: serde::de::DeserializeOwned,
+	{
+		let endpoints = Self::eth_http_endpoints(chain_id)?;
+		let quorum = Self::eth_http_quorum(chain_id, endpoints.len());
+
+		let mut responses = Vec::<Option<D>>::with_capacity(quorum);
+		for (endpoint, mut health) in Self::endpoints_by_health(endpoints) {
+			match Self::query_eth_endpoint::<R, D>(&endpoint, request) {
+				Ok(response) => {
+					health.record_success();
+					responses.push(response);
+				}
+				Err(err) => {
+					log!(
+						warn,
+						"💎 eth endpoint {} failed: {:?}, trying next endpoint",
+						core::str::from_utf8(&endpoint).unwrap_or_default(),
+						err
+					);
+					health.record_failure(sp_io::offchain::timestamp().unix_millis());
+				}
+			}
+			sp_io::offchain::local_storage_set(
+				StorageKind::PERSISTENT,
+				&[ETH_HTTP_HEALTH_PREFIX, &endpoint].concat(),
+				&health.encode(),
+			);
+			if let Some(result) = resolve(&responses, quorum) {
+				return Ok(result);
+			}
+		}
+
+		log!(
+			error,
+			"💎 eth endpoints did not reach quorum: {:?} responses, {:?} required",
+			responses.len(),
+			quorum
+		);
+		Err(Error::<T>::EthRpcNoQuorum)
 	}
 
-	/// Get latest block number from eth client
-	fn get_block(req: LatestOrNumber) -> Result<Option<EthBlock>, Error<T>> {
-		let request = match req {
-			LatestOrNumber::Latest => GetBlockRequest::latest(1_usize),
-			LatestOrNumber::Number(n) => GetBlockRequest::for_number(1_usize, n),
-		};
-		let resp_bytes = Self::query_eth_client(request).map_err(|e| {
+	/// Send `request` to a single Eth JSON-RPC endpoint and deserialize the result
+	fn query_eth_endpoint<R: serde::Serialize, D: serde::de::DeserializeOwned>(
+		endpoint: &[u8],
+		request: &R,
+	) -> Result<Option<D>, Error<T>> {
+		let eth_http_uri = core::str::from_utf8(endpoint).map_err(|_| Error::<T>::OcwConfig)?;
+		let resp_bytes = Self::query_eth_client(eth_http_uri, request).map_err(|e| {
 			log!(error, "💎 read eth-rpc API error: {:?}", e);
 			<Error<T>>::HttpFetch
 		})?;
@@ -598,7 +1749,7 @@ impl<T: Config> Module<T> {
 		})?;
 
 		// Deserialize JSON to struct
-		serde_json::from_str::<EthResponse<EthBlock>>(resp_str)
+		serde_json::from_str::<EthResponse<D>>(resp_str)
 			.map(|resp| resp.result)
 			.map_err(|err| {
 				log!(error, "💎 deserialize json response error: {:?}", err);
@@ -606,27 +1757,79 @@ impl<T: Config> Module<T> {
 			})
 	}
 
-	/// This function uses the `offchain::http` API to query the remote ethereum information,
-	/// and returns the JSON response as vector of bytes.
-	fn query_eth_client<R: serde::Serialize>(request_body: R) -> Result<Vec<u8>, Error<T>> {
-		// Load eth http URI from offchain storage
-		// this should have been configured on start up by passing e.g. `--eth-http`
-		// e.g. `--eth-http=http://localhost:8545`
-		let eth_http_uri = if let Some(value) = sp_io::offchain::local_storage_get(StorageKind::PERSISTENT, b"ETH_HTTP")
-		{
-			value
+	/// Offchain storage key of `key` for `chain_id`, Ethereum uses `key` as is
+	fn chain_storage_key(key: &[u8], chain_id: EthChainId) -> Vec<u8> {
+		if chain_id == T::EthereumChainId::get() {
+			key.to_vec()
 		} else {
+			[key, b":", chain_id.to_string().as_bytes()].concat()
+		}
+	}
+
+	/// Load the JSON-RPC endpoints of `chain_id` from offchain storage
+	fn eth_http_endpoints(chain_id: EthChainId) -> Result<Vec<Vec<u8>>, Error<T>> {
+		// this should have been configured on start up by passing e.g. `--eth-http`
+		// e.g. `--eth-http=http://localhost:8545,http://eth.example.com`
+		// or `--evm-http=137=http://polygon.example.com` for other EVM chains
+		let endpoints: Vec<Vec<u8>> = sp_io::offchain::local_storage_get(
+			StorageKind::PERSISTENT,
+			&Self::chain_storage_key(ETH_HTTP_KEY, chain_id),
+		)
+		.unwrap_or_default()
+		.split(|b| *b == b',')
+		.map(|endpoint| endpoint.to_vec())
+		.filter(|endpoint| !endpoint.is_empty())
+		.collect();
+		if endpoints.is_empty() {
 			log!(
 				error,
-				"💎 Eth http uri is not configured! set --eth-http=<value> on start up"
+				"💎 Eth http uri is not configured for chain: {:?}! set --eth-http=<value> or --evm-http=<chain id>=<value> on start up",
+				chain_id
 			);
 			return Err(Error::<T>::OcwConfig);
-		};
-		let eth_http_uri = core::str::from_utf8(&eth_http_uri).map_err(|_| Error::<T>::OcwConfig)?;
+		}
+
+		Ok(endpoints)
+	}
+
+	/// The number of agreeing responses required from `endpoint_count` endpoints of `chain_id` (default: 1)
+	fn eth_http_quorum(chain_id: EthChainId, endpoint_count: usize) -> usize {
+		sp_io::offchain::local_storage_get(
+			StorageKind::PERSISTENT,
+			&Self::chain_storage_key(ETH_HTTP_QUORUM_KEY, chain_id),
+		)
+		.and_then(|quorum| core::str::from_utf8(&quorum).ok()?.parse::<usize>().ok())
+		.unwrap_or(1)
+		.max(1)
+		.min(endpoint_count)
+	}
+
+	/// Pair `endpoints` with their health records, ordered by fewest recent failures
+	/// Ties keep the configured order
+	fn endpoints_by_health(endpoints: Vec<Vec<u8>>) -> Vec<(Vec<u8>, EndpointHealth)> {
+		let mut endpoints: Vec<(Vec<u8>, EndpointHealth)> = endpoints
+			.into_iter()
+			.map(|endpoint| {
+				let health = sp_io::offchain::local_storage_get(
+					StorageKind::PERSISTENT,
+					&[ETH_HTTP_HEALTH_PREFIX, &endpoint].concat(),
+				)
+				.and_then(|health| EndpointHealth::decode(&mut &health[..]).ok())
+				.unwrap_or_default();
+				(endpoint, health)
+			})
+			.collect();
+		endpoints.sort_by_key(|(_, health)| health.consecutive_failures);
+
+		endpoints
+	}
 
+	/// This function uses the `offchain::http` API to query the remote ethereum information,
+	/// and returns the JSON response as vector of bytes.
+	fn query_eth_client<R: serde::Serialize>(eth_http_uri: &str, request_body: &R) -> Result<Vec<u8>, Error<T>> {
 		const HEADER_CONTENT_TYPE: &str = "application/json";
 		log!(info, "💎 sending request to: {}", eth_http_uri);
-		let body = serde_json::to_string::<R>(&request_body).unwrap();
+		let body = serde_json::to_string::<R>(request_body).unwrap();
 		let body_raw = body.as_bytes();
 		// Initiate an external HTTP GET request. This is using high-level wrappers from `sp_runtime`.
 		let request = rt_offchain::http::Request::post(eth_http_uri, vec![body_raw]);
@@ -669,6 +1872,117 @@ impl<T: Config> Module<T> {
 		Ok(response.body().collect::<Vec<u8>>())
 	}
 
+	/// Attest to the headers of requested Ethereum blocks with enough block confirmations
+	/// we limit the total attestations per invocation using `HEADERS_PER_BLOCK` so we don't stall block production
+	fn offchain_attest_headers(active_key: &T::EthyId, authority_index: u16) {
+		let unattested: Vec<(EthChainId, u64)> = Self::requested_headers()
+			.into_iter()
+			.map(|(chain_id, eth_block_number, _)| (chain_id, eth_block_number))
+			.filter(|header_key| !<HeaderAttestations<T>>::contains_key(header_key, active_key))
+			.take(HEADERS_PER_BLOCK)
+			.collect();
+
+		// latest block number of each chain, fetched once per invocation
+		let mut latest_block_numbers = Vec::<(EthChainId, u64)>::new();
+		for (chain_id, eth_block_number) in unattested {
+			let config = match Self::evm_chain_config(chain_id) {
+				Some(config) => config,
+				None => continue,
+			};
+			let latest_block_number = match latest_block_numbers.iter().find(|(id, _)| *id == chain_id) {
+				Some((_, number)) => *number,
+				None => match Self::get_block(chain_id, LatestOrNumber::Latest) {
+					Ok(Some(block)) => {
+						let number = block.number.unwrap_or_default().as_u64();
+						latest_block_numbers.push((chain_id, number));
+						number
+					}
+					Ok(None) => continue,
+					Err(err) => {
+						log!(
+							error,
+							"💎 eth_getBlockByNumber latest failed for chain: {:?}, {:?}",
+							chain_id,
+							err
+						);
+						continue;
+					}
+				},
+			};
+
+			//  have we got enough block confirmations to be re-org safe?
+			if latest_block_number.saturating_sub(eth_block_number) < config.event_confirmations {
+				log!(
+					trace,
+					"💎 header: {:?} of chain: {:?} not enough confirmations, ignoring...",
+					eth_block_number,
+					chain_id
+				);
+				continue;
+			}
+			let block = match Self::get_block(chain_id, LatestOrNumber::Number(eth_block_number.saturated_into())) {
+				Ok(Some(block)) => block,
+				Ok(None) => continue,
+				Err(err) => {
+					log!(
+						error,
+						"💎 eth_getBlockByNumber {:?} failed for chain: {:?}, {:?}",
+						eth_block_number,
+						chain_id,
+						err
+					);
+					continue;
+				}
+			};
+			// the header must be complete and match the block hash
+			let header = block.header();
+			if block.hash != Some(header.hash()) || header.number != eth_block_number {
+				log!(
+					error,
+					"💎 eth block: {:?} of chain: {:?} header does not match its hash",
+					eth_block_number,
+					chain_id
+				);
+				continue;
+			}
+
+			let payload = HeaderAttestationPayload {
+				chain_id,
+				header,
+				authority_index,
+			};
+			let _ = Self::offchain_send_header_attestation(active_key, payload)
+				.map_err(|err| {
+					log!(error, "💎 sending header attestation failed 🙈, {:?}", err);
+				})
+				.map(|_| {
+					log!(
+						info,
+						"💎 sent header attestation for block: {:?} of chain: {:?}",
+						eth_block_number,
+						chain_id
+					);
+				});
+		}
+	}
+
+	/// Send an attestation to a requested Ethereum block header
+	fn offchain_send_header_attestation(key: &T::EthyId, payload: HeaderAttestationPayload) -> Result<(), Error<T>> {
+		let signature = key
+			.sign(&payload.encode())
+			.ok_or(<Error<T>>::OffchainUnsignedTxSignedPayload)?;
+
+		let call = Call::submit_header_attestation {
+			payload,
+			_signature: signature,
+		};
+
+		SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into())
+			.map_err(|_| <Error<T>>::OffchainUnsignedTxSignedPayload)?;
+
+		Ok(())
+	}
+
 	/// Send a notarization for the given claim
 	fn offchain_send_notarization(key: &T::EthyId, payload: NotarizationPayload) -> Result<(), Error<T>> {
 		let signature = key
@@ -756,6 +2070,37 @@ impl<T: Config> Module<T> {
 	}
 }
 
+/// Blocks to wait before the next check of a claim with a transient result, after `attempts` checks
+fn claim_retry_backoff(attempts: u32) -> BlockNumber {
+	CLAIM_RETRY_BACKOFF
+		.saturating_mul(2_u32.saturating_pow(attempts.saturating_sub(1)))
+		.min(CLAIM_RETRY_MAX_BACKOFF)
+}
+
+/// Resolves to the first response returned by `quorum` endpoints
+fn matching_responses<D: PartialEq + Clone>(responses: &[Option<D>], quorum: usize) -> Option<Option<D>> {
+	responses
+		.iter()
+		.find(|response| responses.iter().filter(|r| r == response).count() >= quorum)
+		.cloned()
+}
+
+/// Resolves to the lowest block once `quorum` endpoints have responded
+/// Underestimating the latest block means confirmations are never overstated
+/// Endpoints without a block are ignored, resolving to `None` only when no endpoint returned a block
+fn lowest_block(responses: &[Option<EthBlock>], quorum: usize) -> Option<Option<EthBlock>> {
+	if responses.len() < quorum {
+		return None;
+	}
+	Some(
+		responses
+			.iter()
+			.flatten()
+			.min_by_key(|block| block.number.unwrap_or_default())
+			.cloned(),
+	)
+}
+
 impl<T: Config> frame_support::unsigned::ValidateUnsigned for Module<T> {
 	type Call = Call<T>;
 
@@ -765,6 +2110,17 @@ impl<T: Config> frame_support::unsigned::ValidateUnsigned for Module<T> {
 			_signature: ref signature,
 		} = call
 		{
+			// transient results are retried, not notarized
+			if payload.result.is_transient() {
+				return InvalidTransaction::Call.into();
+			}
+			// votes after a claim's result are counted once towards notary performance, until the session ends
+			if !EventData::contains_key(payload.event_claim_id) {
+				match Self::session_claim_result(payload.event_claim_id) {
+					Some((_valid, notaries)) if !notaries.contains(&payload.authority_index) => (),
+					_ => return InvalidTransaction::Stale.into(),
+				}
+			}
 			// notarization must be from an active notary
 			let notary_keys = Self::notary_keys();
 			let notary_public_key = match notary_keys.get(payload.authority_index as usize) {
@@ -796,6 +2152,41 @@ impl<T: Config> frame_support::unsigned::ValidateUnsigned for Module<T> {
 				.longevity(3)
 				.propagate(true)
 				.build()
+		} else if let Call::submit_header_attestation {
+			ref payload,
+			_signature: ref signature,
+		} = call
+		{
+			// header must be requested and not yet relayed
+			if !Self::is_requested_header(payload.chain_id, payload.header.number) {
+				return InvalidTransaction::Stale.into();
+			}
+			// attestation must be from an active notary
+			let notary_keys = Self::notary_keys();
+			let notary_public_key = match notary_keys.get(payload.authority_index as usize) {
+				Some(id) => id,
+				None => return InvalidTransaction::BadProof.into(),
+			};
+			// attestation must not be a duplicate/equivocation
+			if <HeaderAttestations<T>>::contains_key((payload.chain_id, payload.header.number), &notary_public_key) {
+				return InvalidTransaction::BadProof.into();
+			}
+			// attestation is signed correctly
+			if !(notary_public_key.verify(&payload.encode(), signature)) {
+				return InvalidTransaction::BadProof.into();
+			}
+			ValidTransaction::with_tag_prefix("eth-bridge")
+				.priority(UNSIGNED_TXS_PRIORITY)
+				// 'provides' must be unique for each submission on the network (i.e. unique for each header and validator)
+				.and_provides((
+					b"attest",
+					payload.chain_id,
+					payload.header.number,
+					payload.authority_index,
+				))
+				.longevity(3)
+				.propagate(true)
+				.build()
 		} else {
 			InvalidTransaction::Call.into()
 		}
@@ -842,6 +2233,8 @@ impl<T: Config> OneSessionHandler<T::AccountId> for Module<T> {
 	/// Note it is triggered before any [`SessionManager::end_session`] handlers,
 	/// so we can still affect the validator set.
 	fn on_before_session_ending() {
+		// Judge notaries before the keys may change
+		Self::report_notary_offences();
 		// Re-activate the bridge, allowing claims & proofs again
 		if T::FinalSessionTracker::is_active_session_final() {
 			log!(trace, "💎 session & era ending, set new validator keys");
@@ -0,0 +1,320 @@
+/* Copyright 2021 Centrality Investments Limited
+*
+* Licensed under the LGPL, Version 3.0 (the "License");
+* you may not use this file except in compliance with the License.
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific language governing permissions and
+* limitations under the License.
+* You may obtain a copy of the License at the root of this project source code,
+* or at:
+*     https://centrality.ai/licenses/gplv3.txt
+*     https://centrality.ai/licenses/lgplv3.txt
+*/
+
+//! Ethereum Merkle-Patricia trie proofs
+//!
+//! Verifies a receipt or transaction is included in a block given the block's `receiptsRoot`/`transactionsRoot`
+//! and the trie nodes on the path from the root to the item, keyed by `rlp(tx_index)`
+
+use crate::types::{EthAddress, EthHash};
+use rlp::Rlp;
+use sp_io::hashing::keccak_256;
+use sp_std::prelude::*;
+
+/// Reasons a trie proof may fail to verify
+#[derive(Debug, PartialEq)]
+pub enum ProofError {
+	/// The proof ended before reaching the key
+	MissingNode,
+	/// A proof node did not hash to the value referenced by its parent
+	HashMismatch,
+	/// A proof node was not a valid trie node
+	InvalidNode,
+	/// The trie does not contain the key
+	KeyNotFound,
+}
+
+/// A log entry from a receipt
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReceiptLog {
+	/// The contract which emitted the log
+	pub address: EthAddress,
+	/// Log topics, the first is the event signature
+	pub topics: Vec<EthHash>,
+	/// Unindexed log data
+	pub data: Vec<u8>,
+}
+
+/// The consensus fields of a (post-Byzantium) transaction receipt, as committed to by the `receiptsRoot`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConsensusReceipt {
+	/// Whether the transaction succeeded
+	pub status: bool,
+	/// Logs emitted by the transaction
+	pub logs: Vec<ReceiptLog>,
+}
+
+/// The trie key of the receipt/transaction at `tx_index` in a block
+pub fn tx_index_key(tx_index: u32) -> Vec<u8> {
+	rlp::encode(&tx_index).to_vec()
+}
+
+/// Verify `proof` for `key` against the trie `root`, returning the value at `key`
+/// `proof` is the list of trie nodes from the root node down to the node containing the value
+pub fn verify_proof(root: &EthHash, key: &[u8], proof: &[Vec<u8>]) -> Result<Vec<u8>, ProofError> {
+	let key = to_nibbles(key);
+	let mut path = &key[..];
+	let mut proof = proof.iter();
+	// the next node is referenced by hash, or embedded in its parent when its encoding is < 32 bytes
+	let mut next_hash = Some(*root);
+	let mut embedded = Vec::<u8>::new();
+
+	loop {
+		let node = match next_hash.take() {
+			Some(hash) => {
+				let node = proof.next().ok_or(ProofError::MissingNode)?;
+				if keccak_256(node) != hash.0 {
+					return Err(ProofError::HashMismatch);
+				}
+				node.clone()
+			}
+			None => sp_std::mem::take(&mut embedded),
+		};
+		let node = Rlp::new(&node);
+
+		let child = match node.item_count().map_err(|_| ProofError::InvalidNode)? {
+			// branch node: 16 children and a value
+			17 => match path.split_first() {
+				Some((nibble, rest)) => {
+					path = rest;
+					node.at(*nibble as usize).map_err(|_| ProofError::InvalidNode)?
+				}
+				None => {
+					let value = node
+						.at(16)
+						.and_then(|v| v.data())
+						.map_err(|_| ProofError::InvalidNode)?;
+					if value.is_empty() {
+						return Err(ProofError::KeyNotFound);
+					}
+					return Ok(value.to_vec());
+				}
+			},
+			// leaf or extension node: a hex-prefix encoded partial path and a value/child
+			2 => {
+				let encoded_path = node.at(0).and_then(|p| p.data()).map_err(|_| ProofError::InvalidNode)?;
+				let (partial, is_leaf) = decode_hex_prefix(encoded_path)?;
+				if !path.starts_with(&partial) {
+					return Err(ProofError::KeyNotFound);
+				}
+				path = &path[partial.len()..];
+				if is_leaf {
+					if !path.is_empty() {
+						return Err(ProofError::KeyNotFound);
+					}
+					let value = node.at(1).and_then(|v| v.data()).map_err(|_| ProofError::InvalidNode)?;
+					return Ok(value.to_vec());
+				}
+				node.at(1).map_err(|_| ProofError::InvalidNode)?
+			}
+			_ => return Err(ProofError::InvalidNode),
+		};
+
+		if child.is_list() {
+			embedded = child.as_raw().to_vec();
+		} else {
+			match child.data().map_err(|_| ProofError::InvalidNode)? {
+				hash if hash.len() == 32 => next_hash = Some(EthHash::from_slice(hash)),
+				empty if empty.is_empty() => return Err(ProofError::KeyNotFound),
+				_ => return Err(ProofError::InvalidNode),
+			}
+		}
+	}
+}
+
+/// Decode a receipt as stored in the receipts trie
+/// Typed receipts (EIP-2718) are prefixed with their type byte, legacy receipts are a plain RLP list
+/// Pre-Byzantium receipts (with an intermediate state root instead of a status) are not supported
+pub fn decode_receipt(encoded: &[u8]) -> Result<ConsensusReceipt, rlp::DecoderError> {
+	let payload = match encoded.split_first() {
+		Some((tx_type, payload)) if *tx_type <= 0x7f => payload,
+		_ => encoded,
+	};
+	let receipt = Rlp::new(payload);
+	let status = match receipt.at(0)?.data()? {
+		[] => false,
+		[1] => true,
+		_ => return Err(rlp::DecoderError::Custom("unsupported receipt status")),
+	};
+	let logs = receipt
+		.at(3)?
+		.iter()
+		.map(|log| {
+			Ok(ReceiptLog {
+				address: log.val_at(0)?,
+				topics: log.list_at(1)?,
+				data: log.val_at(2)?,
+			})
+		})
+		.collect::<Result<Vec<ReceiptLog>, rlp::DecoderError>>()?;
+
+	Ok(ConsensusReceipt { status, logs })
+}
+
+/// Split bytes into nibbles (high nibble first)
+fn to_nibbles(bytes: &[u8]) -> Vec<u8> {
+	let mut nibbles = Vec::with_capacity(bytes.len() * 2);
+	for b in bytes {
+		nibbles.push(b >> 4);
+		nibbles.push(b & 0x0f);
+	}
+	nibbles
+}
+
+/// Decode a hex-prefix encoded path, returning its nibbles and whether it terminates at a leaf
+fn decode_hex_prefix(encoded: &[u8]) -> Result<(Vec<u8>, bool), ProofError> {
+	let (flags, rest) = encoded.split_first().ok_or(ProofError::InvalidNode)?;
+	let is_leaf = match flags >> 4 {
+		0 | 1 => false,
+		2 | 3 => true,
+		_ => return Err(ProofError::InvalidNode),
+	};
+	// odd length paths carry their first nibble in the flags byte
+	let mut nibbles = if flags & 0x10 != 0 { vec![flags & 0x0f] } else { vec![] };
+	nibbles.extend(to_nibbles(rest));
+
+	Ok((nibbles, is_leaf))
+}
+
+/// Receipts and transactions tries for a block of 3 transactions with trie proofs for the first two
+/// Generated offline with an independent trie implementation
+/// - tx 0: legacy transaction, its receipt is the devnet deposit receipt recorded in `types::tests`
+/// - tx 1: EIP-1559 transaction with a typed receipt
+/// - tx 2: failed legacy transaction
+#[cfg(test)]
+pub(crate) mod fixtures {
+	pub const RECEIPTS_ROOT: &str = "756abdc2aa0a4f45342c65f5dc22947b7673088a29341ad1845bd8786960a601";
+	pub const TRANSACTIONS_ROOT: &str = "cc64ecdbea060ed2ec59a452194c50da6ef66cf6a11b3b81dca9cbebe0f118e0";
+	pub const TX_0_HASH: &str = "af6f7382566e430526577aa5d92569d6af04e55585da1bf435030067b64d46b7";
+	pub const TX_0_RECEIPT_PROOF: [&str; 2] = [
+		"f851a097225d2474d167a08fe9a05abd900ac0a947db8a5be253847cbe91fae89f46ec80808080808080a07208cabf18225614d56ef507e6df1572b0ad102e0a2a8f865176e73a3c0276ca8080808080808080",
+		"f9030930b90305f90302018301685cb9010000000000000000000000000000000000000000000000000000000000800000000000000000000000000000000000000000000000000000000010000000200200000000000000000000000008000000000000000000000000000000000000000000000000000000001000000000000000000000000010000000000010000000800000000000000000000000000002000000000000000000000000000040000000020000000000000000000010000000000000000000000000000000000000000000000002000000000000000000000000200000000000008000000004000000000010001000000000000000020000000000000000000000000000001000000000f901f7f89b9417c54edee4d6bccf2379daa328dcc0fbd9c6ce2bf863a0ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3efa0000000000000000000000000ec2c80a819ee8e42c624f6a5de930e8184c0801fa000000000000000000000000087015d61b82a3808d9720a79573bf75deb8a1e90a0000000000000000000000000000000000000000000000000000000000000007bf89b9417c54edee4d6bccf2379daa328dcc0fbd9c6ce2bf863a08c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925a0000000000000000000000000ec2c80a819ee8e42c624f6a5de930e8184c0801fa000000000000000000000000087015d61b82a3808d9720a79573bf75deb8a1e90a0000000000000000000000000000000000000000000000000000000000001e1c5f8bb9487015d61b82a3808d9720a79573bf75deb8a1e90f842a076bb911c362d5b1feb3058bc7dc9354703e4b6eb9c61cc845f73da880cf62f61a0000000000000000000000000ec2c80a819ee8e42c624f6a5de930e8184c0801fb86000000000000000000000000017c54edee4d6bccf2379daa328dcc0fbd9c6ce2b000000000000000000000000000000000000000000000000000000000000007bacd6118e217e552ba801f7aa8a934ea6a300a5b394e7c3f42cd9d6dd9a457c10",
+	];
+	pub const TX_0_TRANSACTION_PROOF: [&str; 2] = [
+		"f851a01eda887cee77e14ecf51cc976949c768dd50a78896a646eb8e117414fba4cd9680808080808080a06bf0c4545d4de334cd568449d860af714128f50fd35586987ccade4a9b96a92a8080808080808080",
+		"f8ce30b8cbf8c9808430cb962f830186a09487015d61b82a3808d9720a79573bf75deb8a1e9080b864e6f2e5ee00000000000000000000000017c54edee4d6bccf2379daa328dcc0fbd9c6ce2b000000000000000000000000000000000000000000000000000000000000007bacd6118e217e552ba801f7aa8a934ea6a300a5b394e7c3f42cd9d6dd9a457c101ba01111111111111111111111111111111111111111111111111111111111111111a02222222222222222222222222222222222222222222222222222222222222222",
+	];
+	pub const TX_1_HASH: &str = "053e4e4b515a6a689fa9f84045ecf6fb686ecb9365d864257230139b3abf93ef";
+	pub const TX_1_RECEIPT_PROOF: [&str; 3] = [
+		"f851a097225d2474d167a08fe9a05abd900ac0a947db8a5be253847cbe91fae89f46ec80808080808080a07208cabf18225614d56ef507e6df1572b0ad102e0a2a8f865176e73a3c0276ca8080808080808080",
+		"f85180a04e9e4a4dee5206e5eb80215eaac8e2ea5917c64b37348d184c31876514b7da9aa048dd633758815dd22c020d99c1e9c261ca3bd0af60e31f7ab5eca1370a85b7538080808080808080808080808080",
+		"f9018e20b9018a02f90186018302b0a4b9010000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000f87cf87a9487015d61b82a3808d9720a79573bf75deb8a1e90f842a076bb911c362d5b1feb3058bc7dc9354703e4b6eb9c61cc845f73da880cf62f61a0000000000000000000000000ec2c80a819ee8e42c624f6a5de930e8184c0801fa00000000000000000000000000000000000000000000000000000000000000001",
+	];
+	pub const TX_1_TRANSACTION_PROOF: [&str; 3] = [
+		"f851a01eda887cee77e14ecf51cc976949c768dd50a78896a646eb8e117414fba4cd9680808080808080a06bf0c4545d4de334cd568449d860af714128f50fd35586987ccade4a9b96a92a8080808080808080",
+		"f85180a0a0bc04695ff018d7d7418d6f5ae3401696ec0f3508b4832d9a98da894d2cd828a0c3fbc45549ccc3d303ad8f9afd52d68b743ab0f139cef2ce98707517d66189e08080808080808080808080808080",
+		"f86e20b86b02f868018430cb962f830186a09487015d61b82a3808d9720a79573bf75deb8a1e908084e6f2e5ee1ba01111111111111111111111111111111111111111111111111111111111111111a02222222222222222222222222222222222222222222222222222222222222222",
+	];
+
+	/// Decode hex encoded trie nodes
+	pub fn nodes(nodes: &[&str]) -> Vec<Vec<u8>> {
+		nodes
+			.iter()
+			.map(|node| crate::types::decode_hex(node).expect("valid hex"))
+			.collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{fixtures::*, *};
+	use std::str::FromStr;
+
+	fn root(hex: &str) -> EthHash {
+		EthHash::from_str(hex).unwrap()
+	}
+
+	#[test]
+	fn verify_receipt_proof() {
+		let receipt =
+			verify_proof(&root(RECEIPTS_ROOT), &tx_index_key(0), &nodes(&TX_0_RECEIPT_PROOF)).expect("proof is valid");
+		let receipt = decode_receipt(&receipt).expect("receipt decodes");
+		assert!(receipt.status);
+		assert_eq!(receipt.logs.len(), 3);
+		let deposit = &receipt.logs[2];
+		assert_eq!(
+			deposit.address,
+			EthAddress::from_str("0x87015d61b82a3808d9720a79573bf75deb8a1e90").unwrap()
+		);
+		assert_eq!(
+			deposit.topics[0],
+			root("0x76bb911c362d5b1feb3058bc7dc9354703e4b6eb9c61cc845f73da880cf62f61")
+		);
+		assert_eq!(deposit.data.len(), 96);
+	}
+
+	#[test]
+	fn verify_typed_receipt_proof() {
+		let receipt =
+			verify_proof(&root(RECEIPTS_ROOT), &tx_index_key(1), &nodes(&TX_1_RECEIPT_PROOF)).expect("proof is valid");
+		assert_eq!(receipt[0], 2);
+		let receipt = decode_receipt(&receipt).expect("receipt decodes");
+		assert!(receipt.status);
+		assert_eq!(receipt.logs.len(), 1);
+	}
+
+	#[test]
+	fn verify_transaction_proof() {
+		for (tx_index, proof, tx_hash) in vec![
+			(0, &TX_0_TRANSACTION_PROOF[..], TX_0_HASH),
+			(1, &TX_1_TRANSACTION_PROOF[..], TX_1_HASH),
+		] {
+			let transaction =
+				verify_proof(&root(TRANSACTIONS_ROOT), &tx_index_key(tx_index), &nodes(proof)).expect("proof is valid");
+			assert_eq!(keccak_256(&transaction), root(tx_hash).0);
+		}
+	}
+
+	#[test]
+	fn invalid_proofs_fail() {
+		let receipts_root = root(RECEIPTS_ROOT);
+		let proof = nodes(&TX_1_RECEIPT_PROOF);
+
+		// wrong key
+		assert_eq!(
+			verify_proof(&receipts_root, &tx_index_key(3), &proof),
+			Err(ProofError::KeyNotFound)
+		);
+		// wrong root
+		assert_eq!(
+			verify_proof(&root(TRANSACTIONS_ROOT), &tx_index_key(1), &proof),
+			Err(ProofError::HashMismatch)
+		);
+		// truncated
+		assert_eq!(
+			verify_proof(&receipts_root, &tx_index_key(1), &proof[..2]),
+			Err(ProofError::MissingNode)
+		);
+		// tampered receipt
+		let mut tampered = proof.clone();
+		*tampered[2].last_mut().unwrap() ^= 1;
+		assert_eq!(
+			verify_proof(&receipts_root, &tx_index_key(1), &tampered),
+			Err(ProofError::HashMismatch)
+		);
+	}
+
+	#[test]
+	fn verify_proof_with_embedded_nodes() {
+		// trie {0x01: "one", 0x02: "two"} is an extension to a branch with both leaves embedded in the root node
+		let trie_root = root("0x795fa3242eb94dabc5eb1e87108d4632afc5ac67c3484dcd2ce067b2a0bb41ae");
+		let proof = nodes(&["dd10db80c520836f6e65c5208374776f8080808080808080808080808080"]);
+
+		assert_eq!(verify_proof(&trie_root, &[1], &proof), Ok(b"one".to_vec()));
+		assert_eq!(verify_proof(&trie_root, &[2], &proof), Ok(b"two".to_vec()));
+		assert_eq!(verify_proof(&trie_root, &[3], &proof), Err(ProofError::KeyNotFound));
+		assert_eq!(verify_proof(&trie_root, &[0x80], &proof), Err(ProofError::KeyNotFound));
+	}
+}
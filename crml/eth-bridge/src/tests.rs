@@ -14,14 +14,34 @@
 */
 
 use crate as crml_eth_bridge;
-use crate::{Config, Module};
-use cennznet_primitives::eth::crypto::AuthorityId;
-use crml_support::{EventClaimSubscriber, FinalSessionTracker, NotarizationRewardHandler, H160, H256 as H256Crml};
+use crate::{
+	claim_retry_backoff,
+	proof::fixtures,
+	types::{
+		decode_hex, ClaimRecord, ClaimStatus, EndpointHealth, EthHeader, EventClaim, EventClaimResult, EvmChainConfig,
+		GetBlockRequest, GetTxReceiptRequest, HeaderAttestationPayload, LatestOrNumber, Message, NotarizationPayload,
+		ReceiptProof, RelayedHeader, Releases,
+	},
+	Config, Error, EventClaims, EventData, IdentificationTuple, Module, NotaryOffence, NotaryPerformance,
+	CLAIM_EXPIRY_BLOCKS, CLAIM_PRUNING_INTERVAL, CLAIM_STATUS_RETENTION_BLOCKS, MAX_EVM_BLOCKS_PER_SECOND,
+	MAX_MESSAGE_PAYLOAD, MAX_RECEIPT_PROOF_BLOCK_LEAD, MESSAGE_RETENTION_BLOCKS, MIN_SESSION_CLAIMS,
+};
+use cennznet_primitives::eth::{
+	crypto::{AuthorityId, AuthoritySignature},
+	ConsensusLog, ETHY_ENGINE_ID,
+};
+use codec::Encode;
+use crml_eth_bridge_mock_rpc::{MockEthereum, MockLog, MockReceipt};
+use crml_support::{
+	EthAbiCodec, EthChainId, EventClaimSubscriber, EventClaimVerifier, FinalSessionTracker, NotarizationRewardHandler,
+	H160, H256 as H256Crml,
+};
 use frame_support::traits::OneSessionHandler;
 use frame_support::{
-	parameter_types,
-	storage::StorageValue,
-	traits::{UnixTime, ValidatorSet as ValidatorSetT},
+	assert_noop, assert_ok, parameter_types,
+	storage::{StorageMap, StorageValue},
+	traits::{OnInitialize, OnRuntimeUpgrade, UnixTime, ValidatorSet as ValidatorSetT, ValidatorSetWithIdentification},
+	unsigned::ValidateUnsigned,
 };
 use sp_core::{
 	ecdsa::Signature,
@@ -30,10 +50,14 @@ use sp_core::{
 };
 use sp_runtime::offchain::StorageKind;
 use sp_runtime::{
+	generic::DigestItem,
 	testing::{Header, TestXt},
 	traits::{BlakeTwo256, Convert, Extrinsic as ExtrinsicT, IdentifyAccount, IdentityLookup, Verify},
-	Percent,
+	transaction_validity::{InvalidTransaction, TransactionSource, TransactionValidity},
+	DispatchResult, Perbill, Percent,
 };
+use sp_staking::offence::{Offence, OffenceError, ReportOffence};
+use std::{cell::RefCell, str::FromStr};
 
 type SessionIndex = u32;
 type AccountId = <<Signature as Verify>::Signer as IdentifyAccount>::AccountId;
@@ -49,6 +73,7 @@ frame_support::construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 		EthBridge: crml_eth_bridge::{Pallet, Call, Storage, Event, ValidateUnsigned},
 	}
 );
@@ -74,7 +99,7 @@ impl frame_system::Config for TestRuntime {
 	type DbWeight = ();
 	type Version = ();
 	type PalletInfo = PalletInfo;
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u64>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
@@ -82,18 +107,55 @@ impl frame_system::Config for TestRuntime {
 	type OnSetCode = ();
 }
 
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+}
+impl pallet_balances::Config for TestRuntime {
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type MaxLocks = ();
+	type WeightInfo = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = ();
+}
+
 parameter_types! {
 	pub const DefaultListingDuration: u64 = 5;
 	pub const MaxAttributeLength: u8 = 140;
 	pub const NotarizationThreshold: Percent = Percent::from_parts(66_u8);
+	pub const EventConfirmations: u64 = 3;
+	pub const EventDeadlineSeconds: u64 = 604_800;
+	pub const EthereumChainId: EthChainId = 1;
+	pub const MaxMissedNotarizations: Percent = Percent::from_percent(50);
+	pub const MaxNotaryDisagreements: Percent = Percent::from_percent(20);
+	pub const ClaimDeposit: u64 = 10;
+	pub static MaxClaimsPerAccount: u64 = 100;
+	pub static MaxClaimsPerBlock: u64 = 100;
+	pub const MessageFee: u64 = 5;
 }
 impl Config for TestRuntime {
 	type AuthoritySet = MockValidatorSet;
 	type FinalSessionTracker = MockFinalSessionTracker;
 	type EthyId = AuthorityId;
 	type NotarizationThreshold = NotarizationThreshold;
+	type EventConfirmations = EventConfirmations;
+	type EventDeadlineSeconds = EventDeadlineSeconds;
+	type EthereumChainId = EthereumChainId;
 	type RewardHandler = MockRewardHandler;
+	type ReportNotaryOffence = MockOffenceReporter;
+	type MaxMissedNotarizations = MaxMissedNotarizations;
+	type MaxNotaryDisagreements = MaxNotaryDisagreements;
 	type Subscribers = MockClaimSubscriber;
+	type Currency = Balances;
+	type ClaimDeposit = ClaimDeposit;
+	type OnClaimSlash = ();
+	type MaxClaimsPerAccount = MaxClaimsPerAccount;
+	type MaxClaimsPerBlock = MaxClaimsPerBlock;
+	type MessageFee = MessageFee;
+	type OnMessageFee = ();
 	type UnixTime = MockUnixTime;
 	type Call = Call;
 	type Event = Event;
@@ -116,16 +178,66 @@ impl ValidatorSetT<AccountId> for MockValidatorSet {
 	}
 	/// Returns the active set of validators.
 	fn validators() -> Vec<Self::ValidatorId> {
-		Default::default()
+		vec![
+			AccountId::from([1_u8; 32]),
+			AccountId::from([2_u8; 32]),
+			AccountId::from([3_u8; 32]),
+		]
+	}
+}
+impl ValidatorSetWithIdentification<AccountId> for MockValidatorSet {
+	type Identification = AccountId;
+	type IdentificationOf = NoopConverter<TestRuntime>;
+}
+
+thread_local! {
+	/// Notary offences reported by the bridge
+	pub static OFFENCES: RefCell<Vec<NotaryOffence<IdentificationTuple<TestRuntime>>>> = RefCell::new(vec![]);
+	/// Claim results received by the subscriber (claim id, success)
+	pub static CLAIM_RESULTS: RefCell<Vec<(u64, bool)>> = RefCell::new(vec![]);
+	/// Seconds the mock clock is ahead of the system time
+	pub static TIME_OFFSET: RefCell<u64> = RefCell::new(0);
+}
+
+/// Records reported notary offences
+pub struct MockOffenceReporter;
+impl ReportOffence<AccountId, IdentificationTuple<TestRuntime>, NotaryOffence<IdentificationTuple<TestRuntime>>>
+	for MockOffenceReporter
+{
+	fn report_offence(
+		_reporters: Vec<AccountId>,
+		offence: NotaryOffence<IdentificationTuple<TestRuntime>>,
+	) -> Result<(), OffenceError> {
+		OFFENCES.with(|offences| offences.borrow_mut().push(offence));
+		Ok(())
+	}
+	fn is_known_offence(_offenders: &[IdentificationTuple<TestRuntime>], _time_slot: &SessionIndex) -> bool {
+		false
 	}
 }
 
 pub struct MockClaimSubscriber;
 impl EventClaimSubscriber for MockClaimSubscriber {
 	/// Notify subscriber about a successful event claim for the given event data
-	fn on_success(_event_claim_id: u64, _contract_address: &H160, _event_signature: &H256Crml, _event_data: &[u8]) {}
+	fn on_success(
+		event_claim_id: u64,
+		_chain_id: EthChainId,
+		_contract_address: &H160,
+		_event_signature: &H256Crml,
+		_event_data: &[u8],
+	) {
+		CLAIM_RESULTS.with(|results| results.borrow_mut().push((event_claim_id, true)));
+	}
 	/// Notify subscriber about a failed event claim for the given event data
-	fn on_failure(_event_claim_id: u64, _contract_address: &H160, _event_signature: &H256Crml, _event_data: &[u8]) {}
+	fn on_failure(
+		event_claim_id: u64,
+		_chain_id: EthChainId,
+		_contract_address: &H160,
+		_event_signature: &H256Crml,
+		_event_data: &[u8],
+	) {
+		CLAIM_RESULTS.with(|results| results.borrow_mut().push((event_claim_id, false)));
+	}
 }
 
 /// Mock final session tracker
@@ -150,13 +262,13 @@ impl NotarizationRewardHandler for MockRewardHandler {
 	}
 }
 
-/// Returns the current system time
+/// Returns the current system time, advanced by `TIME_OFFSET`
 pub struct MockUnixTime;
 impl UnixTime for MockUnixTime {
 	fn now() -> core::time::Duration {
 		std::time::SystemTime::now()
 			.duration_since(std::time::UNIX_EPOCH)
-			.unwrap()
+			.unwrap() + core::time::Duration::from_secs(TIME_OFFSET.with(|offset| *offset.borrow()))
 	}
 }
 
@@ -187,10 +299,22 @@ where
 	}
 }
 
+/// Submits claims in tests
+fn claimant() -> AccountId {
+	AccountId::from([9_u8; 32])
+}
+
+/// Another account submitting claims in tests
+fn other_claimant() -> AccountId {
+	AccountId::from([8_u8; 32])
+}
+
 #[derive(Clone, Copy, Default)]
 pub struct ExtBuilder {
 	next_session_final: bool,
 	active_session_final: bool,
+	max_claims_per_account: Option<u64>,
+	max_claims_per_block: Option<u64>,
 }
 
 impl ExtBuilder {
@@ -202,11 +326,30 @@ impl ExtBuilder {
 		self.next_session_final = true;
 		self
 	}
+	pub fn max_claims_per_account(&mut self, max_claims: u64) -> &mut Self {
+		self.max_claims_per_account = Some(max_claims);
+		self
+	}
+	pub fn max_claims_per_block(&mut self, max_claims: u64) -> &mut Self {
+		self.max_claims_per_block = Some(max_claims);
+		self
+	}
 	pub fn build(self) -> sp_io::TestExternalities {
-		let mut ext: sp_io::TestExternalities = frame_system::GenesisConfig::default()
+		if let Some(max_claims) = self.max_claims_per_account {
+			MAX_CLAIMS_PER_ACCOUNT.with(|v| *v.borrow_mut() = max_claims);
+		}
+		if let Some(max_claims) = self.max_claims_per_block {
+			MAX_CLAIMS_PER_BLOCK.with(|v| *v.borrow_mut() = max_claims);
+		}
+		let mut storage = frame_system::GenesisConfig::default()
 			.build_storage::<TestRuntime>()
-			.unwrap()
-			.into();
+			.unwrap();
+		pallet_balances::GenesisConfig::<TestRuntime> {
+			balances: vec![(claimant(), 1_000), (other_claimant(), 1_000)],
+		}
+		.assimilate_storage(&mut storage)
+		.unwrap();
+		let mut ext: sp_io::TestExternalities = storage.into();
 		if self.next_session_final {
 			ext.execute_with(|| frame_system::Pallet::<TestRuntime>::set_block_number(1));
 		} else if self.active_session_final {
@@ -281,8 +424,6 @@ fn eth_client_http_request() {
 	let mut t = sp_io::TestExternalities::default();
 	t.register_extension(OffchainDbExt::new(offchain.clone()));
 	t.register_extension(OffchainWorkerExt::new(offchain));
-	// Set the ethereum http endpoint for OCW queries
-	t.execute_with(|| sp_io::offchain::local_storage_set(StorageKind::PERSISTENT, b"ETH_HTTP", &MOCK_ETH_HTTP_URI));
 
 	// Setup
 	// Mock an ethereum JSON-RPC response
@@ -310,7 +451,8 @@ fn eth_client_http_request() {
 
 	// Test
 	t.execute_with(|| {
-		let response = Module::<TestRuntime>::query_eth_client(request_body).expect("got response");
+		let uri = core::str::from_utf8(&MOCK_ETH_HTTP_URI).expect("valid utf8");
+		let response = Module::<TestRuntime>::query_eth_client(uri, &request_body).expect("got response");
 		assert_eq!(
 			serde_json::from_slice::<'_, TestRequest>(response.as_slice()).unwrap(),
 			TestRequest {
@@ -320,11 +462,1576 @@ fn eth_client_http_request() {
 	})
 }
 
+/// Build offchain test externalities with the given eth http endpoints and quorum
+fn eth_endpoints_ext(endpoints: &[u8], quorum: &[u8]) -> (sp_io::TestExternalities, testing::TestOffchainExt) {
+	let (offchain, _offchain_state) = testing::TestOffchainExt::new();
+	let mut t = sp_io::TestExternalities::default();
+	t.register_extension(OffchainDbExt::new(offchain.clone()));
+	t.register_extension(OffchainWorkerExt::new(offchain.clone()));
+	t.execute_with(|| {
+		sp_io::offchain::local_storage_set(StorageKind::PERSISTENT, b"ETH_HTTP", endpoints);
+		sp_io::offchain::local_storage_set(StorageKind::PERSISTENT, b"ETH_QRUM", quorum);
+	});
+
+	(t, offchain)
+}
+
+/// Expect a JSON-RPC request `body` at `uri` and mock its `response`
+fn expect_eth_request(offchain: &testing::TestOffchainExt, uri: &str, body: &str, response: Vec<u8>) {
+	offchain.0.write().expect_request(testing::PendingRequest {
+		method: "POST".into(),
+		uri: uri.to_string(),
+		body: body.as_bytes().to_vec(),
+		response: Some(response),
+		headers: vec![
+			("Content-Type".to_string(), "application/json".to_string()),
+			("Content-Length".to_string(), body.len().to_string()),
+		],
+		sent: true,
+		..Default::default()
+	});
+}
+
+/// Mock `eth_getBlockByNumber` response
+fn mock_block_response(number: u64, hash: &str) -> Vec<u8> {
+	format!(
+		r#"{{"jsonrpc":"2.0","id":1,"result":{{"number":"{:#x}","hash":"0x{}","timestamp":"0x61a3c4c0"}}}}"#,
+		number,
+		hash.repeat(64)
+	)
+	.into_bytes()
+}
+
 #[test]
-fn prunes_expired_events() {}
+fn eth_endpoints_failover() {
+	let (mut t, offchain) = eth_endpoints_ext(b"http://a.example.com,http://b.example.com", b"1");
+	let request = serde_json::to_string(&GetBlockRequest::latest(1)).unwrap();
+	expect_eth_request(&offchain, "http://a.example.com", &request, b"bad gateway".to_vec());
+	expect_eth_request(
+		&offchain,
+		"http://b.example.com",
+		&request,
+		mock_block_response(16, "1"),
+	);
+
+	t.execute_with(|| {
+		let block = Module::<TestRuntime>::get_block(1, LatestOrNumber::Latest)
+			.expect("got response")
+			.expect("got block");
+		assert_eq!(block.number, Some(16_u64.into()));
+
+		// the failed endpoint is tried last from now on
+		let endpoints = Module::<TestRuntime>::endpoints_by_health(vec![
+			b"http://a.example.com".to_vec(),
+			b"http://b.example.com".to_vec(),
+		]);
+		assert_eq!(
+			endpoints
+				.iter()
+				.map(|(endpoint, _)| endpoint.as_slice())
+				.collect::<Vec<&[u8]>>(),
+			vec![&b"http://b.example.com"[..], &b"http://a.example.com"[..]],
+		);
+		assert_eq!(
+			endpoints[0].1,
+			EndpointHealth {
+				successes: 1,
+				..Default::default()
+			}
+		);
+		assert_eq!(endpoints[1].1.failures, 1);
+		assert_eq!(endpoints[1].1.consecutive_failures, 1);
+	});
+
+	expect_eth_request(
+		&offchain,
+		"http://b.example.com",
+		&request,
+		mock_block_response(17, "1"),
+	);
+	t.execute_with(|| {
+		let block = Module::<TestRuntime>::get_block(1, LatestOrNumber::Latest)
+			.expect("got response")
+			.expect("got block");
+		assert_eq!(block.number, Some(17_u64.into()));
+	});
+}
+
+#[test]
+fn eth_endpoints_quorum() {
+	let (mut t, offchain) = eth_endpoints_ext(b"http://a.example.com,http://b.example.com,http://c.example.com", b"2");
+	let request = serde_json::to_string(&GetBlockRequest::for_number(1, 16)).unwrap();
+	expect_eth_request(
+		&offchain,
+		"http://a.example.com",
+		&request,
+		mock_block_response(16, "1"),
+	);
+	expect_eth_request(
+		&offchain,
+		"http://b.example.com",
+		&request,
+		mock_block_response(16, "2"),
+	);
+	expect_eth_request(
+		&offchain,
+		"http://c.example.com",
+		&request,
+		mock_block_response(16, "1"),
+	);
+
+	t.execute_with(|| {
+		let block = Module::<TestRuntime>::get_block(1, LatestOrNumber::Number(16))
+			.expect("got response")
+			.expect("got block");
+		assert_eq!(block.hash, Some(H256Crml::repeat_byte(0x11)));
+	});
+}
 
 #[test]
-fn double_claim_fails() {}
+fn eth_endpoints_without_quorum_fail() {
+	let (mut t, offchain) = eth_endpoints_ext(b"http://a.example.com,http://b.example.com", b"2");
+	let request = serde_json::to_string(&GetBlockRequest::for_number(1, 16)).unwrap();
+	expect_eth_request(
+		&offchain,
+		"http://a.example.com",
+		&request,
+		mock_block_response(16, "1"),
+	);
+	expect_eth_request(
+		&offchain,
+		"http://b.example.com",
+		&request,
+		mock_block_response(16, "2"),
+	);
+
+	t.execute_with(|| {
+		assert!(matches!(
+			Module::<TestRuntime>::get_block(1, LatestOrNumber::Number(16)),
+			Err(Error::<TestRuntime>::EthRpcNoQuorum)
+		));
+	});
+}
 
 #[test]
-fn invalid_notarization_fails() {}
+fn eth_endpoints_latest_block_is_lowest_of_quorum() {
+	let (mut t, offchain) = eth_endpoints_ext(b"http://a.example.com,http://b.example.com", b"2");
+	let request = serde_json::to_string(&GetBlockRequest::latest(1)).unwrap();
+	expect_eth_request(
+		&offchain,
+		"http://a.example.com",
+		&request,
+		mock_block_response(17, "1"),
+	);
+	expect_eth_request(
+		&offchain,
+		"http://b.example.com",
+		&request,
+		mock_block_response(16, "2"),
+	);
+
+	t.execute_with(|| {
+		let block = Module::<TestRuntime>::get_block(1, LatestOrNumber::Latest)
+			.expect("got response")
+			.expect("got block");
+		assert_eq!(block.number, Some(16_u64.into()));
+	});
+}
+
+#[test]
+fn transient_notarization_is_rejected() {
+	ExtBuilder::default().build().execute_with(|| {
+		let payload = NotarizationPayload {
+			event_claim_id: 0,
+			authority_index: 0,
+			result: EventClaimResult::NotEnoughConfirmations,
+		};
+		let signature = AuthoritySignature::from(Signature::default());
+
+		assert_eq!(
+			Module::<TestRuntime>::validate_unsigned(
+				TransactionSource::Local,
+				&crate::Call::submit_notarization {
+					payload: payload.clone(),
+					_signature: signature.clone(),
+				}
+			),
+			Err(InvalidTransaction::Call.into())
+		);
+		assert_noop!(
+			EthBridge::submit_notarization(Origin::none(), payload, signature),
+			Error::<TestRuntime>::TransientNotarization
+		);
+	});
+}
+
+#[test]
+fn claim_retry_backoff_is_bounded() {
+	assert_eq!(claim_retry_backoff(1), 5);
+	assert_eq!(claim_retry_backoff(2), 10);
+	assert_eq!(claim_retry_backoff(3), 20);
+	assert_eq!(claim_retry_backoff(9), 720);
+	assert_eq!(claim_retry_backoff(u32::MAX), 720);
+}
+
+#[test]
+fn claim_retries_are_cleared_once_claims_conclude() {
+	let (mut t, _offchain) = eth_endpoints_ext(b"", b"1");
+	t.execute_with(|| {
+		let retry_key = |event_claim_id: u64| [&b"ETH_RTRY"[..], &event_claim_id.to_be_bytes()].concat();
+		for event_claim_id in 0_u64..2 {
+			sp_io::offchain::local_storage_set(
+				StorageKind::PERSISTENT,
+				&retry_key(event_claim_id),
+				&(1_u32, 10_u64).encode(),
+			);
+		}
+		sp_io::offchain::local_storage_set(StorageKind::PERSISTENT, b"ETH_RIDS", &vec![0_u64, 1].encode());
+		// claim 0 is still pending, claim 1 has concluded
+		EventData::insert(0, vec![1_u8, 2, 3]);
+
+		Module::<TestRuntime>::prune_claim_retries();
+
+		assert!(sp_io::offchain::local_storage_get(StorageKind::PERSISTENT, &retry_key(0)).is_some());
+		assert!(sp_io::offchain::local_storage_get(StorageKind::PERSISTENT, &retry_key(1)).is_none());
+		assert_eq!(Module::<TestRuntime>::claim_retry_ids(), vec![0]);
+	});
+}
+
+#[test]
+fn claim_expires_without_result() {
+	ExtBuilder::default().build().execute_with(|| {
+		frame_system::Pallet::<TestRuntime>::set_block_number(5);
+		let contract_address = H160::from_low_u64_be(1);
+		let event_signature = H256Crml::from_low_u64_be(2);
+		let tx_hash = H256Crml::from_low_u64_be(3);
+		let event_claim_id = Module::<TestRuntime>::submit_event_claim(
+			&claimant(),
+			1,
+			&contract_address,
+			&event_signature,
+			&tx_hash,
+			&[1_u8, 2, 3],
+		)
+		.expect("claim submitted");
+		let expiry_block = 5 + CLAIM_EXPIRY_BLOCKS as u64;
+
+		Module::<TestRuntime>::on_initialize(expiry_block - 1);
+		assert!(Module::<TestRuntime>::event_data(event_claim_id).is_some());
+
+		Module::<TestRuntime>::on_initialize(expiry_block);
+		assert!(Module::<TestRuntime>::event_data(event_claim_id).is_none());
+		assert!(!EventClaims::contains_key(event_claim_id));
+		assert!(frame_system::Pallet::<TestRuntime>::events()
+			.iter()
+			.any(|record| record.event == Event::EthBridge(crate::Event::ClaimExpired(event_claim_id))));
+
+		// the tx hash may be claimed again
+		assert_ok!(Module::<TestRuntime>::submit_event_claim(
+			&claimant(),
+			1,
+			&contract_address,
+			&event_signature,
+			&tx_hash,
+			&[1_u8, 2, 3]
+		));
+	});
+}
+
+#[test]
+fn claim_status_retained_until_pruned() {
+	ExtBuilder::default().build().execute_with(|| {
+		frame_system::Pallet::<TestRuntime>::set_block_number(5);
+		let tx_hash = H256Crml::from_low_u64_be(3);
+		let event_claim_id = Module::<TestRuntime>::submit_event_claim(
+			&claimant(),
+			1,
+			&H160::from_low_u64_be(1),
+			&H256Crml::from_low_u64_be(2),
+			&tx_hash,
+			&[1_u8, 2, 3],
+		)
+		.expect("claim submitted");
+		let pending = ClaimRecord {
+			event_claim_id,
+			chain_id: 1,
+			tx_hash,
+			status: ClaimStatus::Pending { yay: 0, nay: 0 },
+		};
+		assert_eq!(
+			Module::<TestRuntime>::claim_status(event_claim_id),
+			Some(pending.clone())
+		);
+		assert_eq!(
+			Module::<TestRuntime>::claim_status_by_tx_hash(1, &tx_hash),
+			Some(pending)
+		);
+
+		let expiry_block = 5 + CLAIM_EXPIRY_BLOCKS as u64;
+		frame_system::Pallet::<TestRuntime>::set_block_number(expiry_block);
+		Module::<TestRuntime>::on_initialize(expiry_block);
+		let expired = ClaimRecord {
+			event_claim_id,
+			chain_id: 1,
+			tx_hash,
+			status: ClaimStatus::Expired(expiry_block),
+		};
+		assert_eq!(
+			Module::<TestRuntime>::claim_status_by_tx_hash(1, &tx_hash),
+			Some(expired)
+		);
+
+		let prune_block = expiry_block + CLAIM_STATUS_RETENTION_BLOCKS as u64;
+		Module::<TestRuntime>::on_initialize(prune_block - 1);
+		assert!(Module::<TestRuntime>::claim_status(event_claim_id).is_some());
+
+		Module::<TestRuntime>::on_initialize(prune_block);
+		assert!(Module::<TestRuntime>::claim_status(event_claim_id).is_none());
+		assert!(Module::<TestRuntime>::claim_status_by_tx_hash(1, &tx_hash).is_none());
+	});
+}
+
+/// Polygon mainnet
+const POLYGON: EthChainId = 137;
+
+#[test]
+fn set_evm_chain() {
+	ExtBuilder::default().build().execute_with(|| {
+		frame_system::Pallet::<TestRuntime>::set_block_number(5);
+		let config = EvmChainConfig {
+			event_confirmations: 128,
+			event_deadline_seconds: 86_400,
+		};
+		assert_noop!(
+			EthBridge::set_evm_chain(
+				Origin::signed(AccountId::from([1_u8; 32])),
+				POLYGON,
+				Some(config.clone())
+			),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		// Ethereum is configured by the runtime
+		assert_noop!(
+			EthBridge::set_evm_chain(Origin::root(), 1, Some(config.clone())),
+			Error::<TestRuntime>::InvalidChain
+		);
+
+		assert_ok!(EthBridge::set_evm_chain(Origin::root(), POLYGON, Some(config.clone())));
+		assert!(has_event(crate::Event::EvmChainSet(POLYGON)));
+		assert_eq!(Module::<TestRuntime>::evm_chain_config(POLYGON), Some(config));
+		assert_eq!(
+			Module::<TestRuntime>::evm_chain_config(1),
+			Some(EvmChainConfig {
+				event_confirmations: EventConfirmations::get(),
+				event_deadline_seconds: EventDeadlineSeconds::get(),
+			})
+		);
+
+		assert_ok!(EthBridge::set_evm_chain(Origin::root(), POLYGON, None));
+		assert!(has_event(crate::Event::EvmChainRemoved(POLYGON)));
+		assert!(Module::<TestRuntime>::evm_chain_config(POLYGON).is_none());
+	});
+}
+
+#[test]
+fn claims_keyed_by_chain() {
+	ExtBuilder::default().build().execute_with(|| {
+		frame_system::Pallet::<TestRuntime>::set_block_number(5);
+		let contract_address = H160::from_low_u64_be(1);
+		let event_signature = H256Crml::from_low_u64_be(2);
+		let tx_hash = H256Crml::from_low_u64_be(3);
+		assert_noop!(
+			Module::<TestRuntime>::submit_event_claim(
+				&claimant(),
+				POLYGON,
+				&contract_address,
+				&event_signature,
+				&tx_hash,
+				&[]
+			),
+			Error::<TestRuntime>::UnsupportedChain
+		);
+
+		assert_ok!(EthBridge::set_evm_chain(
+			Origin::root(),
+			POLYGON,
+			Some(Default::default())
+		));
+		let eth_claim_id = Module::<TestRuntime>::submit_event_claim(
+			&claimant(),
+			1,
+			&contract_address,
+			&event_signature,
+			&tx_hash,
+			&[],
+		)
+		.expect("claim submitted");
+		// the same tx hash on another chain is a distinct claim
+		let polygon_claim_id = Module::<TestRuntime>::submit_event_claim(
+			&claimant(),
+			POLYGON,
+			&contract_address,
+			&event_signature,
+			&tx_hash,
+			&[],
+		)
+		.expect("claim submitted");
+		assert_ne!(eth_claim_id, polygon_claim_id);
+		assert_eq!(
+			Module::<TestRuntime>::claim_status_by_tx_hash(POLYGON, &tx_hash).map(|record| record.event_claim_id),
+			Some(polygon_claim_id)
+		);
+		assert_eq!(
+			Module::<TestRuntime>::claim_status_by_tx_hash(1, &tx_hash).map(|record| record.event_claim_id),
+			Some(eth_claim_id)
+		);
+
+		// processed tx hashes are tracked per chain
+		crate::ProcessedTxHashes::insert(1, tx_hash, ());
+		assert_noop!(
+			Module::<TestRuntime>::submit_event_claim(
+				&claimant(),
+				1,
+				&contract_address,
+				&event_signature,
+				&tx_hash,
+				&[]
+			),
+			Error::<TestRuntime>::AlreadyNotarized
+		);
+		assert!(!crate::ProcessedTxHashes::contains_key(POLYGON, tx_hash));
+	});
+}
+
+#[test]
+fn event_proof_tagged_with_chain() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			Module::<TestRuntime>::generate_event_proof(POLYGON, &7_u64),
+			Error::<TestRuntime>::UnsupportedChain
+		);
+		assert_ok!(EthBridge::set_evm_chain(
+			Origin::root(),
+			POLYGON,
+			Some(Default::default())
+		));
+
+		let event_proof_id = Module::<TestRuntime>::generate_event_proof(POLYGON, &7_u64).expect("proof requested");
+		let expected_log = DigestItem::Consensus(
+			ETHY_ENGINE_ID,
+			ConsensusLog::<AccountId>::TaggedSigningRequest((
+				[
+					EthAbiCodec::encode(&7_u64),
+					EthAbiCodec::encode(&POLYGON),
+					EthAbiCodec::encode(&Module::<TestRuntime>::validator_set().id),
+					EthAbiCodec::encode(&event_proof_id),
+				]
+				.concat(),
+				event_proof_id,
+				b"chain:137".to_vec(),
+			))
+			.encode(),
+		);
+		assert!(frame_system::Pallet::<TestRuntime>::digest()
+			.logs
+			.contains(&expected_log));
+	});
+}
+
+#[test]
+fn evm_chain_endpoints_keyed_by_chain() {
+	assert_eq!(
+		Module::<TestRuntime>::chain_storage_key(b"ETH_HTTP", 1),
+		b"ETH_HTTP".to_vec()
+	);
+	assert_eq!(
+		Module::<TestRuntime>::chain_storage_key(b"ETH_HTTP", POLYGON),
+		b"ETH_HTTP:137".to_vec()
+	);
+}
+
+#[test]
+fn migrate_to_multi_chain() {
+	ExtBuilder::default().build().execute_with(|| {
+		use frame_support::{storage::migration::put_storage_value, Blake2_128Concat, StorageHasher, Twox64Concat};
+
+		let tx_hash = H256Crml::from_low_u64_be(3);
+		let event_type = (H160::from_low_u64_be(1), H256Crml::from_low_u64_be(2));
+		put_storage_value(
+			b"EthBridge",
+			b"TxHashClaims",
+			&Twox64Concat::hash(&tx_hash.encode()),
+			5_u64,
+		);
+		put_storage_value(
+			b"EthBridge",
+			b"ProcessedTxHashes",
+			&Twox64Concat::hash(&tx_hash.encode()),
+			(),
+		);
+		put_storage_value(
+			b"EthBridge",
+			b"TypeIdToEventType",
+			&Blake2_128Concat::hash(&0_u32.encode()),
+			event_type,
+		);
+		put_storage_value(
+			b"EthBridge",
+			b"EventTypeToTypeId",
+			&Blake2_128Concat::hash(&event_type.encode()),
+			0_u32,
+		);
+
+		let _ = EthBridge::on_runtime_upgrade();
+
+		assert_eq!(crate::StorageVersion::get(), Releases::V2 as u32);
+		assert_eq!(crate::TxHashClaims::get(1, tx_hash), Some(5));
+		assert!(crate::ProcessedTxHashes::contains_key(1, tx_hash));
+		assert_eq!(crate::TypeIdToEventType::get(0), (1, event_type.0, event_type.1));
+		assert_eq!(crate::EventTypeToTypeId::get((1, event_type.0, event_type.1)), 0);
+	});
+}
+
+#[test]
+fn migrate_processed_tx_buckets() {
+	ExtBuilder::default().build().execute_with(|| {
+		crate::StorageVersion::put(Releases::V1 as u32);
+		let current_bucket_index = MockUnixTime::now().as_secs() / 3_600;
+		let legacy_tx_hash = H256Crml::from_low_u64_be(1);
+		let tx_hash = H256Crml::from_low_u64_be(2);
+		// bucketed by the second of the hour
+		crate::ProcessedTxBuckets::insert((1, 3_599), legacy_tx_hash, ());
+		crate::ProcessedTxBuckets::insert((1, current_bucket_index), tx_hash, ());
+
+		let _ = EthBridge::on_runtime_upgrade();
+
+		assert_eq!(crate::StorageVersion::get(), Releases::V2 as u32);
+		assert!(!crate::ProcessedTxBuckets::contains_key((1, 3_599), legacy_tx_hash));
+		assert!(crate::ProcessedTxBuckets::contains_key(
+			(1, current_bucket_index),
+			legacy_tx_hash
+		));
+		assert!(crate::ProcessedTxBuckets::contains_key(
+			(1, current_bucket_index),
+			tx_hash
+		));
+	});
+}
+
+/// Notary keys for header attestations
+fn setup_notaries() {
+	crate::NotaryKeys::<TestRuntime>::put(vec![
+		AuthorityId::from_slice(&[1_u8; 33]),
+		AuthorityId::from_slice(&[2_u8; 33]),
+		AuthorityId::from_slice(&[3_u8; 33]),
+	]);
+}
+
+/// Deposit event data logged by tx 0 of the proof fixtures
+const FIXTURE_DEPOSIT_DATA: &str = "00000000000000000000000017c54edee4d6bccf2379daa328dcc0fbd9c6ce2b000000000000000000000000000000000000000000000000000000000000007bacd6118e217e552ba801f7aa8a934ea6a300a5b394e7c3f42cd9d6dd9a457c10";
+
+/// Claim the deposit event of `tx_hash` with `event_data`
+fn submit_fixture_claim(tx_hash: &str, event_data: &str) -> u64 {
+	Module::<TestRuntime>::submit_event_claim(
+		&claimant(),
+		1,
+		&H160::from_str("0x87015d61b82a3808d9720a79573bf75deb8a1e90").unwrap(),
+		&H256Crml::from_str("0x76bb911c362d5b1feb3058bc7dc9354703e4b6eb9c61cc845f73da880cf62f61").unwrap(),
+		&H256Crml::from_str(tx_hash).unwrap(),
+		&decode_hex(event_data).unwrap(),
+	)
+	.expect("claim submitted")
+}
+
+/// A header for Ethereum block `number` committing to the fixture tries, mined `age` seconds ago
+fn fixture_header(number: u64, age: u64) -> EthHeader {
+	EthHeader {
+		number,
+		receipts_root: H256Crml::from_str(fixtures::RECEIPTS_ROOT).unwrap(),
+		transactions_root: H256Crml::from_str(fixtures::TRANSACTIONS_ROOT).unwrap(),
+		timestamp: MockUnixTime::now().as_secs() - age,
+		..Default::default()
+	}
+}
+
+/// A receipt proof of fixture tx `tx_index` in Ethereum block `block_number`
+fn fixture_proof(block_number: u64, tx_index: u32) -> ReceiptProof {
+	let (receipt_proof, transaction_proof) = match tx_index {
+		0 => (&fixtures::TX_0_RECEIPT_PROOF[..], &fixtures::TX_0_TRANSACTION_PROOF[..]),
+		_ => (&fixtures::TX_1_RECEIPT_PROOF[..], &fixtures::TX_1_TRANSACTION_PROOF[..]),
+	};
+	ReceiptProof {
+		block_number,
+		tx_index,
+		receipt_proof: fixtures::nodes(receipt_proof),
+		transaction_proof: fixtures::nodes(transaction_proof),
+	}
+}
+
+fn attest_header(header: &EthHeader, authority_index: u16) -> DispatchResult {
+	EthBridge::submit_header_attestation(
+		Origin::none(),
+		HeaderAttestationPayload {
+			chain_id: 1,
+			header: header.clone(),
+			authority_index,
+		},
+		AuthoritySignature::from(Signature::default()),
+	)
+}
+
+fn has_event(event: crate::Event) -> bool {
+	frame_system::Pallet::<TestRuntime>::events()
+		.iter()
+		.any(|record| record.event == Event::EthBridge(event.clone()))
+}
+
+#[test]
+fn receipt_proof_verified_once_header_relayed() {
+	ExtBuilder::default().build().execute_with(|| {
+		frame_system::Pallet::<TestRuntime>::set_block_number(5);
+		setup_notaries();
+		let submitter = claimant();
+		let event_claim_id = submit_fixture_claim(fixtures::TX_0_HASH, FIXTURE_DEPOSIT_DATA);
+		// tx 1 did not log this event data
+		let invalid_claim_id = submit_fixture_claim(fixtures::TX_1_HASH, FIXTURE_DEPOSIT_DATA);
+
+		assert_ok!(EthBridge::submit_receipt_proof(
+			Origin::signed(submitter.clone()),
+			event_claim_id,
+			fixture_proof(100, 0)
+		));
+		assert_ok!(EthBridge::submit_receipt_proof(
+			Origin::signed(submitter.clone()),
+			invalid_claim_id,
+			fixture_proof(100, 1)
+		));
+		assert!(has_event(crate::Event::ReceiptProofPending(event_claim_id, 1, 100)));
+		assert_eq!(Module::<TestRuntime>::requested_headers(), vec![(1, 100, 5)]);
+
+		// notaries disagree, no header reaches the threshold
+		let header = fixture_header(100, 60);
+		let conflicting_header = fixture_header(100, 61);
+		assert_ok!(attest_header(&header, 0));
+		assert_ok!(attest_header(&conflicting_header, 1));
+		assert!(Module::<TestRuntime>::relayed_header(1, 100).is_none());
+		assert!(Module::<TestRuntime>::event_data(event_claim_id).is_some());
+
+		assert_ok!(attest_header(&header, 2));
+		assert_eq!(
+			Module::<TestRuntime>::relayed_header(1, 100),
+			Some(RelayedHeader::from(&header))
+		);
+		assert!(has_event(crate::Event::HeaderRelayed(1, 100, header.hash())));
+		assert!(Module::<TestRuntime>::requested_headers().is_empty());
+		assert!(Module::<TestRuntime>::pending_receipt_proof(event_claim_id).is_none());
+
+		// the proven claim is verified
+		assert!(has_event(crate::Event::Verified(event_claim_id)));
+		assert_eq!(
+			Module::<TestRuntime>::claim_status(event_claim_id).map(|record| record.status),
+			Some(ClaimStatus::Verified(5))
+		);
+		assert!(crate::ProcessedTxHashes::contains_key(
+			1,
+			H256Crml::from_str(fixtures::TX_0_HASH).unwrap()
+		));
+
+		// the invalid proof is dropped, its claim remains pending
+		assert!(has_event(crate::Event::ReceiptProofInvalid(invalid_claim_id)));
+		assert!(Module::<TestRuntime>::event_data(invalid_claim_id).is_some());
+	});
+}
+
+#[test]
+fn receipt_proof_only_from_claim_submitter() {
+	ExtBuilder::default().build().execute_with(|| {
+		frame_system::Pallet::<TestRuntime>::set_block_number(5);
+		setup_notaries();
+		let event_claim_id = submit_fixture_claim(fixtures::TX_0_HASH, FIXTURE_DEPOSIT_DATA);
+
+		assert_noop!(
+			EthBridge::submit_receipt_proof(
+				Origin::signed(AccountId::from([1_u8; 32])),
+				event_claim_id,
+				fixture_proof(u64::MAX, 0)
+			),
+			Error::<TestRuntime>::NotClaimSubmitter
+		);
+
+		// the submitter replaces a proof of the wrong block, withdrawing its header request
+		assert_ok!(EthBridge::submit_receipt_proof(
+			Origin::signed(claimant()),
+			event_claim_id,
+			fixture_proof(99, 0)
+		));
+		assert_ok!(attest_header(&fixture_header(99, 60), 0));
+		assert_ok!(EthBridge::submit_receipt_proof(
+			Origin::signed(claimant()),
+			event_claim_id,
+			fixture_proof(100, 0)
+		));
+		assert_eq!(Module::<TestRuntime>::requested_headers(), vec![(1, 100, 5)]);
+		assert!(Module::<TestRuntime>::header_proof_claims((1, 99)).is_empty());
+		assert!(Module::<TestRuntime>::header_attestations((1, 99), AuthorityId::from_slice(&[1_u8; 33])).is_none());
+		assert_eq!(
+			Module::<TestRuntime>::header_proof_claims((1, 100)),
+			vec![event_claim_id]
+		);
+		assert_eq!(
+			Module::<TestRuntime>::pending_receipt_proof(event_claim_id),
+			Some(fixture_proof(100, 0))
+		);
+	});
+}
+
+#[test]
+fn receipt_proof_block_must_be_plausible() {
+	ExtBuilder::default().build().execute_with(|| {
+		frame_system::Pallet::<TestRuntime>::set_block_number(5);
+		setup_notaries();
+		let event_claim_id = submit_fixture_claim(fixtures::TX_0_HASH, FIXTURE_DEPOSIT_DATA);
+		// a header of block 100 was relayed 60 seconds ago
+		crate::LatestRelayedHeader::insert(1, (100, MockUnixTime::now().as_secs() - 60));
+
+		let max_block_number = 100 + 60 * MAX_EVM_BLOCKS_PER_SECOND + MAX_RECEIPT_PROOF_BLOCK_LEAD;
+		assert_noop!(
+			EthBridge::submit_receipt_proof(
+				Origin::signed(claimant()),
+				event_claim_id,
+				fixture_proof(max_block_number + 1, 0)
+			),
+			Error::<TestRuntime>::ImplausibleReceiptProofBlock
+		);
+		assert_ok!(EthBridge::submit_receipt_proof(
+			Origin::signed(claimant()),
+			event_claim_id,
+			fixture_proof(max_block_number, 0)
+		));
+
+		// blocks up to an expired header are expired too
+		crate::LatestRelayedHeader::insert(
+			1,
+			(100, MockUnixTime::now().as_secs() - EventDeadlineSeconds::get() - 1),
+		);
+		assert_noop!(
+			EthBridge::submit_receipt_proof(Origin::signed(claimant()), event_claim_id, fixture_proof(100, 0)),
+			Error::<TestRuntime>::ImplausibleReceiptProofBlock
+		);
+		assert_ok!(EthBridge::submit_receipt_proof(
+			Origin::signed(claimant()),
+			event_claim_id,
+			fixture_proof(101, 0)
+		));
+	});
+}
+
+#[test]
+fn receipt_proof_verified_against_relayed_header() {
+	ExtBuilder::default().build().execute_with(|| {
+		frame_system::Pallet::<TestRuntime>::set_block_number(5);
+		let submitter = claimant();
+		crate::RelayedHeaders::insert(1, 100, RelayedHeader::from(&fixture_header(100, 60)));
+		let event_claim_id = submit_fixture_claim(fixtures::TX_0_HASH, FIXTURE_DEPOSIT_DATA);
+
+		// a valid proof of a different tx
+		assert_noop!(
+			EthBridge::submit_receipt_proof(Origin::signed(submitter.clone()), event_claim_id, fixture_proof(100, 1)),
+			Error::<TestRuntime>::InvalidReceiptProof
+		);
+		// a tampered receipt
+		let mut tampered_proof = fixture_proof(100, 0);
+		*tampered_proof.receipt_proof[1].last_mut().unwrap() ^= 1;
+		assert_noop!(
+			EthBridge::submit_receipt_proof(Origin::signed(submitter.clone()), event_claim_id, tampered_proof),
+			Error::<TestRuntime>::InvalidReceiptProof
+		);
+		// oversized
+		let mut oversized_proof = fixture_proof(100, 0);
+		oversized_proof.receipt_proof = vec![vec![0_u8; 1_024]; 64];
+		assert_noop!(
+			EthBridge::submit_receipt_proof(Origin::signed(submitter.clone()), event_claim_id, oversized_proof),
+			Error::<TestRuntime>::ReceiptProofTooLarge
+		);
+
+		assert_ok!(EthBridge::submit_receipt_proof(
+			Origin::signed(submitter.clone()),
+			event_claim_id,
+			fixture_proof(100, 0)
+		));
+		assert!(has_event(crate::Event::Verified(event_claim_id)));
+		assert_noop!(
+			EthBridge::submit_receipt_proof(Origin::signed(submitter), event_claim_id, fixture_proof(100, 0)),
+			Error::<TestRuntime>::InvalidClaim
+		);
+	});
+}
+
+#[test]
+fn receipt_proof_past_deadline_fails() {
+	ExtBuilder::default().build().execute_with(|| {
+		let header = fixture_header(100, EventDeadlineSeconds::get() + 60);
+		crate::RelayedHeaders::insert(1, 100, RelayedHeader::from(&header));
+		let event_claim_id = submit_fixture_claim(fixtures::TX_0_HASH, FIXTURE_DEPOSIT_DATA);
+
+		assert_noop!(
+			EthBridge::submit_receipt_proof(Origin::signed(claimant()), event_claim_id, fixture_proof(100, 0)),
+			Error::<TestRuntime>::ReceiptProofExpired
+		);
+	});
+}
+
+#[test]
+fn unrequested_header_attestation_fails() {
+	ExtBuilder::default().build().execute_with(|| {
+		setup_notaries();
+		let header = fixture_header(100, 60);
+
+		assert_eq!(
+			Module::<TestRuntime>::validate_unsigned(
+				TransactionSource::Local,
+				&crate::Call::submit_header_attestation {
+					payload: HeaderAttestationPayload {
+						chain_id: 1,
+						header: header.clone(),
+						authority_index: 0,
+					},
+					_signature: AuthoritySignature::from(Signature::default()),
+				}
+			),
+			Err(InvalidTransaction::Stale.into())
+		);
+		assert_noop!(attest_header(&header, 0), Error::<TestRuntime>::UnexpectedHeader);
+	});
+}
+
+#[test]
+fn header_requests_expire() {
+	ExtBuilder::default().build().execute_with(|| {
+		frame_system::Pallet::<TestRuntime>::set_block_number(5);
+		setup_notaries();
+		let event_claim_id = submit_fixture_claim(fixtures::TX_0_HASH, FIXTURE_DEPOSIT_DATA);
+		assert_ok!(EthBridge::submit_receipt_proof(
+			Origin::signed(claimant()),
+			event_claim_id,
+			fixture_proof(100, 0)
+		));
+		assert_ok!(attest_header(&fixture_header(100, 60), 0));
+
+		let expiry_block = 5 + CLAIM_EXPIRY_BLOCKS as u64;
+		Module::<TestRuntime>::on_initialize(expiry_block - 1);
+		assert_eq!(Module::<TestRuntime>::requested_headers(), vec![(1, 100, 5)]);
+
+		Module::<TestRuntime>::on_initialize(expiry_block);
+		assert!(Module::<TestRuntime>::requested_headers().is_empty());
+		assert!(Module::<TestRuntime>::pending_receipt_proof(event_claim_id).is_none());
+		assert!(Module::<TestRuntime>::header_proof_claims((1, 100)).is_empty());
+		assert!(Module::<TestRuntime>::header_attestations((1, 100), AuthorityId::from_slice(&[1_u8; 33])).is_none());
+	});
+}
+
+/// Submit a claim on a distinct tx hash
+fn submit_claim(tx_hash: u64) -> u64 {
+	submit_claim_as(&claimant(), tx_hash).expect("claim submitted")
+}
+
+/// Submit a claim on a distinct tx hash from `submitter`
+fn submit_claim_as(submitter: &AccountId, tx_hash: u64) -> Result<u64, sp_runtime::DispatchError> {
+	Module::<TestRuntime>::submit_event_claim(
+		submitter,
+		1,
+		&H160::from_low_u64_be(1),
+		&H256Crml::from_low_u64_be(2),
+		&H256Crml::from_low_u64_be(tx_hash),
+		&[1_u8, 2, 3],
+	)
+}
+
+fn notarize(event_claim_id: u64, authority_index: u16, result: EventClaimResult) -> DispatchResult {
+	EthBridge::submit_notarization(
+		Origin::none(),
+		NotarizationPayload {
+			event_claim_id,
+			authority_index,
+			result,
+		},
+		AuthoritySignature::from(Signature::default()),
+	)
+}
+
+/// Validate an unsigned notarization of `event_claim_id` by `authority_index`
+fn validate_notarization(event_claim_id: u64, authority_index: u16) -> TransactionValidity {
+	Module::<TestRuntime>::validate_unsigned(
+		TransactionSource::Local,
+		&crate::Call::submit_notarization {
+			payload: NotarizationPayload {
+				event_claim_id,
+				authority_index,
+				result: EventClaimResult::Valid,
+			},
+			_signature: AuthoritySignature::from(Signature::default()),
+		},
+	)
+}
+
+#[test]
+fn late_notarization_counts_towards_notary_votes_only() {
+	ExtBuilder::default().build().execute_with(|| {
+		setup_notaries();
+		let event_claim_id = submit_claim(1);
+		assert_ok!(notarize(event_claim_id, 0, EventClaimResult::Valid));
+		assert_ok!(notarize(event_claim_id, 1, EventClaimResult::Valid));
+		assert_eq!(
+			Module::<TestRuntime>::claim_status(event_claim_id).map(|record| record.status),
+			Some(ClaimStatus::Verified(0))
+		);
+
+		// notaries counted on the claim may not vote again
+		assert_eq!(
+			validate_notarization(event_claim_id, 1),
+			Err(InvalidTransaction::Stale.into())
+		);
+		assert_noop!(
+			notarize(event_claim_id, 1, EventClaimResult::Valid),
+			Error::<TestRuntime>::AlreadyNotarized
+		);
+
+		// notary 2 votes after the claim has been verified
+		assert_ok!(notarize(event_claim_id, 2, EventClaimResult::NoTxLogs));
+		assert_eq!(
+			Module::<TestRuntime>::claim_status(event_claim_id).map(|record| record.status),
+			Some(ClaimStatus::Verified(0))
+		);
+		assert_eq!(
+			<crate::EventNotarizations<TestRuntime>>::iter_prefix(event_claim_id).count(),
+			0
+		);
+		assert_eq!(
+			Module::<TestRuntime>::notary_performance()[2].1,
+			NotaryPerformance {
+				claims: 1,
+				votes: 1,
+				missed: 0,
+				disagreements: 1
+			}
+		);
+		assert_noop!(
+			notarize(event_claim_id, 2, EventClaimResult::Valid),
+			Error::<TestRuntime>::AlreadyNotarized
+		);
+
+		// results are forgotten at the end of the session
+		let event_claim_id = submit_claim(2);
+		assert_ok!(notarize(event_claim_id, 0, EventClaimResult::Valid));
+		assert_ok!(notarize(event_claim_id, 1, EventClaimResult::Valid));
+		<Module<TestRuntime> as OneSessionHandler<AccountId>>::on_before_session_ending();
+		assert_eq!(
+			validate_notarization(event_claim_id, 2),
+			Err(InvalidTransaction::Stale.into())
+		);
+		assert_noop!(
+			notarize(event_claim_id, 2, EventClaimResult::Valid),
+			Error::<TestRuntime>::InvalidClaim
+		);
+	});
+}
+
+#[test]
+fn notary_votes_tracked_against_claim_result() {
+	ExtBuilder::default().build().execute_with(|| {
+		setup_notaries();
+		// notaries 0 & 1 verify the claim, notary 2 does not vote
+		let verified_claim_id = submit_claim(1);
+		assert_ok!(notarize(verified_claim_id, 0, EventClaimResult::Valid));
+		assert_ok!(notarize(verified_claim_id, 1, EventClaimResult::Valid));
+		assert_eq!(
+			Module::<TestRuntime>::claim_status(verified_claim_id).map(|record| record.status),
+			Some(ClaimStatus::Verified(0))
+		);
+		// notary 0 contradicts the rejection of the claim
+		let rejected_claim_id = submit_claim(2);
+		assert_ok!(notarize(rejected_claim_id, 0, EventClaimResult::Valid));
+		assert_ok!(notarize(rejected_claim_id, 1, EventClaimResult::UnexpectedData));
+		assert_ok!(notarize(rejected_claim_id, 2, EventClaimResult::NoTxLogs));
+		// pending claims are not counted
+		let pending_claim_id = submit_claim(3);
+		assert_ok!(notarize(pending_claim_id, 2, EventClaimResult::Valid));
+
+		assert_eq!(Module::<TestRuntime>::session_claims(), 2);
+		assert_eq!(
+			Module::<TestRuntime>::notary_performance(),
+			vec![
+				(
+					AccountId::from([1_u8; 32]),
+					NotaryPerformance {
+						claims: 2,
+						votes: 2,
+						missed: 0,
+						disagreements: 1
+					}
+				),
+				(
+					AccountId::from([2_u8; 32]),
+					NotaryPerformance {
+						claims: 2,
+						votes: 2,
+						missed: 0,
+						disagreements: 0
+					}
+				),
+				(
+					AccountId::from([3_u8; 32]),
+					NotaryPerformance {
+						claims: 2,
+						votes: 1,
+						missed: 1,
+						disagreements: 0
+					}
+				),
+			]
+		);
+	});
+}
+
+#[test]
+fn notaries_reported_for_missed_votes() {
+	ExtBuilder::default().build().execute_with(|| {
+		setup_notaries();
+		// notary 2 never votes
+		for tx_hash in 0..MIN_SESSION_CLAIMS as u64 {
+			let event_claim_id = submit_claim(tx_hash);
+			assert_ok!(notarize(event_claim_id, 0, EventClaimResult::Valid));
+			assert_ok!(notarize(event_claim_id, 1, EventClaimResult::Valid));
+		}
+
+		<Module<TestRuntime> as OneSessionHandler<AccountId>>::on_before_session_ending();
+
+		let offender = AccountId::from([3_u8; 32]);
+		OFFENCES.with(|offences| {
+			assert_eq!(
+				*offences.borrow(),
+				vec![NotaryOffence {
+					session_index: 1,
+					validator_set_count: 3,
+					offenders: vec![(offender.clone(), offender)],
+				}]
+			)
+		});
+		// counts reset for the next session
+		assert_eq!(Module::<TestRuntime>::session_claims(), 0);
+		assert!(Module::<TestRuntime>::notary_performance()
+			.iter()
+			.all(|(_, performance)| *performance == NotaryPerformance::default()));
+	});
+}
+
+#[test]
+fn notaries_not_reported_with_few_claims() {
+	ExtBuilder::default().build().execute_with(|| {
+		setup_notaries();
+		for tx_hash in 1..MIN_SESSION_CLAIMS as u64 {
+			let event_claim_id = submit_claim(tx_hash);
+			assert_ok!(notarize(event_claim_id, 0, EventClaimResult::Valid));
+			assert_ok!(notarize(event_claim_id, 1, EventClaimResult::Valid));
+		}
+
+		<Module<TestRuntime> as OneSessionHandler<AccountId>>::on_before_session_ending();
+
+		OFFENCES.with(|offences| assert!(offences.borrow().is_empty()));
+		assert_eq!(Module::<TestRuntime>::session_claims(), 0);
+	});
+}
+
+#[test]
+fn notary_offence_slash_fraction() {
+	// a few offenders are chilled but not slashed
+	assert_eq!(NotaryOffence::<()>::slash_fraction(1, 10), Perbill::zero());
+	assert_eq!(NotaryOffence::<()>::slash_fraction(2, 10), Perbill::zero());
+	// min((3 * (5 - (10 / 10 + 1))) / 10, 1) * 0.07
+	assert_eq!(
+		NotaryOffence::<()>::slash_fraction(5, 10),
+		Perbill::from_parts(63_000_000)
+	);
+	assert_eq!(NotaryOffence::<()>::slash_fraction(10, 10), Perbill::from_percent(7));
+}
+
+#[test]
+fn eth_endpoints_latest_block_ignores_endpoints_without_a_block() {
+	let (mut t, offchain) = eth_endpoints_ext(b"http://a.example.com,http://b.example.com", b"2");
+	let request = serde_json::to_string(&GetBlockRequest::latest(1)).unwrap();
+	expect_eth_request(
+		&offchain,
+		"http://a.example.com",
+		&request,
+		br#"{"jsonrpc":"2.0","id":1,"result":null}"#.to_vec(),
+	);
+	expect_eth_request(
+		&offchain,
+		"http://b.example.com",
+		&request,
+		mock_block_response(16, "2"),
+	);
+
+	t.execute_with(|| {
+		let block = Module::<TestRuntime>::get_block(1, LatestOrNumber::Latest)
+			.expect("got response")
+			.expect("got block");
+		assert_eq!(block.number, Some(16_u64.into()));
+	});
+}
+
+#[test]
+fn claim_deposit_returned_or_slashed() {
+	ExtBuilder::default().build().execute_with(|| {
+		frame_system::Pallet::<TestRuntime>::set_block_number(5);
+		setup_notaries();
+		let verified_claim_id = submit_claim(1);
+		let rejected_claim_id = submit_claim(2);
+		let expired_claim_id = submit_claim(3);
+		assert_eq!(Balances::reserved_balance(&claimant()), 3 * ClaimDeposit::get());
+		assert_eq!(Module::<TestRuntime>::account_claim_count(&claimant()), 3);
+		assert_eq!(
+			Module::<TestRuntime>::claim_deposit(verified_claim_id),
+			Some((claimant(), ClaimDeposit::get()))
+		);
+
+		assert_ok!(notarize(verified_claim_id, 0, EventClaimResult::Valid));
+		assert_ok!(notarize(verified_claim_id, 1, EventClaimResult::Valid));
+		assert_eq!(Balances::reserved_balance(&claimant()), 2 * ClaimDeposit::get());
+		assert_eq!(Balances::free_balance(&claimant()), 1_000 - 2 * ClaimDeposit::get());
+		assert!(Module::<TestRuntime>::claim_deposit(verified_claim_id).is_none());
+
+		assert_ok!(notarize(rejected_claim_id, 0, EventClaimResult::NoTxLogs));
+		assert_ok!(notarize(rejected_claim_id, 1, EventClaimResult::NoTxLogs));
+		assert!(has_event(crate::Event::ClaimDepositSlashed(rejected_claim_id)));
+		assert_eq!(Balances::reserved_balance(&claimant()), ClaimDeposit::get());
+		assert_eq!(Balances::free_balance(&claimant()), 1_000 - 2 * ClaimDeposit::get());
+
+		Module::<TestRuntime>::on_initialize(5 + CLAIM_EXPIRY_BLOCKS as u64);
+		assert!(Module::<TestRuntime>::event_data(expired_claim_id).is_none());
+		assert_eq!(Balances::reserved_balance(&claimant()), 0);
+		assert_eq!(Balances::free_balance(&claimant()), 1_000 - ClaimDeposit::get());
+		assert_eq!(Module::<TestRuntime>::account_claim_count(&claimant()), 0);
+	});
+}
+
+#[test]
+fn claim_without_deposit_fails() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			submit_claim_as(&AccountId::from([7_u8; 32]), 1),
+			pallet_balances::Error::<TestRuntime>::InsufficientBalance
+		);
+	});
+}
+
+#[test]
+fn claims_limited_per_account() {
+	ExtBuilder::default()
+		.max_claims_per_account(2)
+		.build()
+		.execute_with(|| {
+			frame_system::Pallet::<TestRuntime>::set_block_number(5);
+			setup_notaries();
+			let event_claim_id = submit_claim(1);
+			submit_claim(2);
+			assert_noop!(
+				submit_claim_as(&claimant(), 3),
+				Error::<TestRuntime>::TooManyAccountClaims
+			);
+			assert_ok!(submit_claim_as(&other_claimant(), 3));
+
+			// a result frees a pending claim slot
+			assert_ok!(notarize(event_claim_id, 0, EventClaimResult::Valid));
+			assert_ok!(notarize(event_claim_id, 1, EventClaimResult::Valid));
+			assert_ok!(submit_claim_as(&claimant(), 4));
+		});
+}
+
+#[test]
+fn claims_limited_per_block() {
+	ExtBuilder::default().max_claims_per_block(2).build().execute_with(|| {
+		frame_system::Pallet::<TestRuntime>::set_block_number(5);
+		submit_claim(1);
+		assert_ok!(submit_claim_as(&other_claimant(), 2));
+		assert_noop!(
+			submit_claim_as(&other_claimant(), 3),
+			Error::<TestRuntime>::TooManyBlockClaims
+		);
+
+		frame_system::Pallet::<TestRuntime>::set_block_number(6);
+		Module::<TestRuntime>::on_initialize(6);
+		assert_ok!(submit_claim_as(&other_claimant(), 3));
+	});
+}
+
+#[test]
+fn claims_prioritised_by_submitter() {
+	ExtBuilder::default().build().execute_with(|| {
+		let spam_claims: Vec<u64> = (1..=3).map(submit_claim).collect();
+		let other_claim_id = submit_claim_as(&other_claimant(), 4).expect("claim submitted");
+
+		let claim_ids: Vec<u64> = Module::<TestRuntime>::prioritised_claims()
+			.into_iter()
+			.map(|(event_claim_id, _)| event_claim_id)
+			.collect();
+		// the other claimant is not queued behind all of the first claimant's claims
+		assert_eq!(
+			claim_ids,
+			vec![spam_claims[0], other_claim_id, spam_claims[1], spam_claims[2]]
+		);
+	});
+}
+
+#[test]
+fn message_abi_encoding() {
+	let message = Message {
+		sender: H256Crml::repeat_byte(1),
+		destination: H160::repeat_byte(2),
+		nonce: 3,
+		payload: vec![4_u8; 33],
+	};
+	let encoded = EthAbiCodec::encode(&message);
+
+	let mut expected = [0_u8; 32 * 7];
+	expected[0..32].copy_from_slice(&[1_u8; 32]);
+	expected[44..64].copy_from_slice(&[2_u8; 20]);
+	expected[95] = 3;
+	expected[127] = 128;
+	expected[159] = 33;
+	expected[160..193].copy_from_slice(&[4_u8; 33]);
+	assert_eq!(encoded, expected.to_vec());
+	assert_eq!(<Message as EthAbiCodec>::decode(&encoded), Some(message));
+}
+
+#[test]
+fn send_message() {
+	ExtBuilder::default().build().execute_with(|| {
+		frame_system::Pallet::<TestRuntime>::set_block_number(5);
+		let destination = H160::from_low_u64_be(1);
+		let event_proof_id = Module::<TestRuntime>::next_proof_id();
+		assert_ok!(EthBridge::send_message(
+			Origin::signed(claimant()),
+			1,
+			destination,
+			vec![1_u8, 2, 3]
+		));
+
+		let message = Message {
+			sender: H256Crml::from(<[u8; 32]>::from(claimant())),
+			destination,
+			nonce: 0,
+			payload: vec![1_u8, 2, 3],
+		};
+		assert_eq!(
+			Module::<TestRuntime>::message(event_proof_id),
+			Some((1, message.clone()))
+		);
+		assert_eq!(Module::<TestRuntime>::next_message_nonce(), 1);
+		assert_eq!(Balances::free_balance(&claimant()), 1_000 - MessageFee::get());
+		assert!(has_event(crate::Event::MessageSent(event_proof_id, 1, destination, 0)));
+		let expected_log = DigestItem::Consensus(
+			ETHY_ENGINE_ID,
+			ConsensusLog::<AccountId>::TaggedSigningRequest((
+				[
+					EthAbiCodec::encode(&message),
+					EthAbiCodec::encode(&Module::<TestRuntime>::validator_set().id),
+					EthAbiCodec::encode(&event_proof_id),
+				]
+				.concat(),
+				event_proof_id,
+				b"chain:1".to_vec(),
+			))
+			.encode(),
+		);
+		assert!(frame_system::Pallet::<TestRuntime>::digest()
+			.logs
+			.contains(&expected_log));
+
+		// pruned after the retention period
+		let prune_block = 5 + MESSAGE_RETENTION_BLOCKS as u64;
+		Module::<TestRuntime>::on_initialize(prune_block - 1);
+		assert!(Module::<TestRuntime>::message(event_proof_id).is_some());
+		Module::<TestRuntime>::on_initialize(prune_block);
+		assert!(Module::<TestRuntime>::message(event_proof_id).is_none());
+	});
+}
+
+#[test]
+fn send_message_fails() {
+	ExtBuilder::default().build().execute_with(|| {
+		let destination = H160::from_low_u64_be(1);
+		assert_noop!(
+			EthBridge::send_message(
+				Origin::signed(claimant()),
+				1,
+				destination,
+				vec![0_u8; MAX_MESSAGE_PAYLOAD + 1]
+			),
+			Error::<TestRuntime>::MessageTooLarge
+		);
+		assert_noop!(
+			EthBridge::send_message(Origin::signed(claimant()), POLYGON, destination, vec![]),
+			Error::<TestRuntime>::UnsupportedChain
+		);
+		assert_noop!(
+			EthBridge::send_message(Origin::signed(AccountId::from([7_u8; 32])), 1, destination, vec![]),
+			pallet_balances::Error::<TestRuntime>::InsufficientBalance
+		);
+	});
+}
+
+/// Mock Ethereum chain endpoint
+const MOCK_ETHEREUM_URI: &str = "http://127.0.0.1:8645";
+
+/// Build test externalities bridging the mock Ethereum chain
+fn mock_ethereum_ext() -> (sp_io::TestExternalities, testing::TestOffchainExt) {
+	let (offchain, _offchain_state) = testing::TestOffchainExt::new();
+	let mut ext = ExtBuilder::default().build();
+	ext.register_extension(OffchainDbExt::new(offchain.clone()));
+	ext.register_extension(OffchainWorkerExt::new(offchain.clone()));
+	ext.execute_with(|| {
+		frame_system::Pallet::<TestRuntime>::set_block_number(5);
+		sp_io::offchain::local_storage_set(StorageKind::PERSISTENT, b"ETH_HTTP", MOCK_ETHEREUM_URI.as_bytes());
+		setup_notaries();
+	});
+
+	(ext, offchain)
+}
+
+/// A mock Ethereum chain with its genesis `age` seconds ago
+fn mock_ethereum(age: u64) -> MockEthereum {
+	MockEthereum::new(1, MockUnixTime::now().as_secs() - age)
+}
+
+/// Include the deposit claimed by `submit_claim(tx_hash)` in the next block of `mock`, logging `event_data`
+fn mock_deposit(mock: &MockEthereum, tx_hash: u64, event_data: &[u8]) -> u64 {
+	mock.add_receipt(MockReceipt {
+		transaction_hash: H256Crml::from_low_u64_be(tx_hash),
+		from: H160::from_low_u64_be(7),
+		to: Some(H160::from_low_u64_be(1)),
+		status: true,
+		logs: vec![MockLog {
+			address: H160::from_low_u64_be(1),
+			topics: vec![H256Crml::from_low_u64_be(2)],
+			data: event_data.to_vec(),
+		}],
+	})
+}
+
+/// Expect the JSON-RPC `request`, answered by `mock`
+fn expect_mock_request<R: serde::Serialize>(offchain: &testing::TestOffchainExt, mock: &MockEthereum, request: &R) {
+	let body = serde_json::to_string(request).unwrap();
+	let response = mock.handle_request(&body).expect("mock responds");
+	expect_eth_request(offchain, MOCK_ETHEREUM_URI, &body, response.into_bytes());
+}
+
+/// Expect the offchain worker queries to notarize `tx_hash`, answered by `mock`
+/// `observed_block` - the block of the tx, queried if there are enough confirmations
+fn expect_notarization_queries(
+	offchain: &testing::TestOffchainExt,
+	mock: &MockEthereum,
+	tx_hash: u64,
+	observed_block: Option<u64>,
+) {
+	expect_mock_request(
+		offchain,
+		mock,
+		&GetTxReceiptRequest::new(H256Crml::from_low_u64_be(tx_hash), 0),
+	);
+	expect_mock_request(offchain, mock, &GetBlockRequest::latest(1));
+	if let Some(number) = observed_block {
+		expect_mock_request(offchain, mock, &GetBlockRequest::for_number(1, number as u32));
+	}
+}
+
+/// Run the offchain worker checks of `event_claim_id` against the mock Ethereum chain
+fn offchain_notarize(event_claim_id: u64) -> EventClaimResult {
+	let (tx_hash, event_type_id) = EventClaims::get(event_claim_id);
+	let (chain_id, contract_address, event_signature) = crate::TypeIdToEventType::get(event_type_id);
+	let event_claim = EventClaim {
+		chain_id,
+		tx_hash,
+		data: Module::<TestRuntime>::event_data(event_claim_id).expect("claim is pending"),
+		contract_address,
+		event_signature,
+	};
+	Module::<TestRuntime>::offchain_try_notarize_event(
+		event_claim,
+		&Module::<TestRuntime>::evm_chain_config(chain_id).unwrap(),
+	)
+}
+
+/// Claim results received by the subscriber
+fn claim_results() -> Vec<(u64, bool)> {
+	CLAIM_RESULTS.with(|results| results.borrow().clone())
+}
+
+#[test]
+fn mock_ethereum_claim_verified() {
+	let (mut ext, offchain) = mock_ethereum_ext();
+	let mock = mock_ethereum(86_400);
+	let observed_block = mock_deposit(&mock, 1, &[1_u8, 2, 3]);
+	mock.mine_blocks(EventConfirmations::get() + 1);
+
+	ext.execute_with(|| {
+		let event_claim_id = submit_claim(1);
+		expect_notarization_queries(&offchain, &mock, 1, Some(observed_block));
+		assert_eq!(offchain_notarize(event_claim_id), EventClaimResult::Valid);
+
+		assert_ok!(notarize(event_claim_id, 0, EventClaimResult::Valid));
+		assert!(claim_results().is_empty());
+		assert_ok!(notarize(event_claim_id, 1, EventClaimResult::Valid));
+		assert_eq!(claim_results(), vec![(event_claim_id, true)]);
+		assert_eq!(
+			Module::<TestRuntime>::claim_status(event_claim_id).map(|record| record.status),
+			Some(ClaimStatus::Verified(0))
+		);
+		assert_eq!(Balances::reserved_balance(&claimant()), 0);
+	});
+}
+
+#[test]
+fn mock_ethereum_claim_awaits_confirmations() {
+	let (mut ext, offchain) = mock_ethereum_ext();
+	let mock = mock_ethereum(86_400);
+
+	ext.execute_with(|| {
+		let event_claim_id = submit_claim(1);
+		// the tx is not mined yet
+		expect_mock_request(
+			&offchain,
+			&mock,
+			&GetTxReceiptRequest::new(H256Crml::from_low_u64_be(1), 0),
+		);
+		assert_eq!(offchain_notarize(event_claim_id), EventClaimResult::NoTxLogs);
+
+		let observed_block = mock_deposit(&mock, 1, &[1_u8, 2, 3]);
+		mock.mine_blocks(EventConfirmations::get());
+		expect_notarization_queries(&offchain, &mock, 1, None);
+		assert_eq!(
+			offchain_notarize(event_claim_id),
+			EventClaimResult::NotEnoughConfirmations
+		);
+		// transient results are not notarized
+		assert_noop!(
+			notarize(event_claim_id, 0, EventClaimResult::NotEnoughConfirmations),
+			Error::<TestRuntime>::TransientNotarization
+		);
+
+		mock.mine_blocks(1);
+		expect_notarization_queries(&offchain, &mock, 1, Some(observed_block));
+		assert_eq!(offchain_notarize(event_claim_id), EventClaimResult::Valid);
+	});
+}
+
+#[test]
+fn mock_ethereum_claim_past_deadline_expires() {
+	let (mut ext, offchain) = mock_ethereum_ext();
+	let mock = mock_ethereum(EventDeadlineSeconds::get() + 3_600);
+	let observed_block = mock_deposit(&mock, 1, &[1_u8, 2, 3]);
+	mock.mine_blocks(EventConfirmations::get() + 1);
+
+	ext.execute_with(|| {
+		let event_claim_id = submit_claim(1);
+		expect_notarization_queries(&offchain, &mock, 1, Some(observed_block));
+		assert_eq!(offchain_notarize(event_claim_id), EventClaimResult::Expired);
+
+		assert_ok!(notarize(event_claim_id, 0, EventClaimResult::Expired));
+		assert_ok!(notarize(event_claim_id, 1, EventClaimResult::Expired));
+		assert_eq!(claim_results(), vec![(event_claim_id, false)]);
+		assert!(!crate::ProcessedTxHashes::contains_key(1, H256Crml::from_low_u64_be(1)));
+	});
+}
+
+#[test]
+fn prunes_expired_events() {
+	let (mut ext, offchain) = mock_ethereum_ext();
+	let mock = mock_ethereum(86_400);
+	let observed_block = mock_deposit(&mock, 1, &[1_u8, 2, 3]);
+	mock.mine_blocks(EventConfirmations::get() + 1);
+
+	ext.execute_with(|| {
+		let event_claim_id = submit_claim(1);
+		expect_notarization_queries(&offchain, &mock, 1, Some(observed_block));
+		assert_eq!(offchain_notarize(event_claim_id), EventClaimResult::Valid);
+		assert_ok!(notarize(event_claim_id, 0, EventClaimResult::Valid));
+		assert_ok!(notarize(event_claim_id, 1, EventClaimResult::Valid));
+		// processed txs are bucketed by the hour
+		let tx_hash = H256Crml::from_low_u64_be(1);
+		let bucket_index = MockUnixTime::now().as_secs() / 3_600;
+		assert!(crate::ProcessedTxHashes::contains_key(1, tx_hash));
+		assert!(crate::ProcessedTxBuckets::contains_key((1, bucket_index), tx_hash));
+
+		// replay protection holds until the deadline, buckets are pruned each interval
+		let pruning_block = CLAIM_PRUNING_INTERVAL as u64 * 10;
+		for hours in [1, 24, EventDeadlineSeconds::get() / 3_600 - 1] {
+			TIME_OFFSET.with(|offset| *offset.borrow_mut() = hours * 3_600);
+			EthBridge::on_initialize(pruning_block);
+			assert!(crate::ProcessedTxHashes::contains_key(1, tx_hash));
+		}
+
+		// the tx bucket is pruned once past the deadline
+		TIME_OFFSET.with(|offset| *offset.borrow_mut() = EventDeadlineSeconds::get());
+		EthBridge::on_initialize(pruning_block);
+		assert!(!crate::ProcessedTxHashes::contains_key(1, tx_hash));
+		assert!(!crate::ProcessedTxBuckets::contains_key((1, bucket_index), tx_hash));
+	});
+}
+
+#[test]
+fn double_claim_fails() {
+	let (mut ext, offchain) = mock_ethereum_ext();
+	let mock = mock_ethereum(86_400);
+	let observed_block = mock_deposit(&mock, 1, &[1_u8, 2, 3]);
+	mock.mine_blocks(EventConfirmations::get() + 1);
+
+	ext.execute_with(|| {
+		let event_claim_id = submit_claim(1);
+		expect_notarization_queries(&offchain, &mock, 1, Some(observed_block));
+		assert_eq!(offchain_notarize(event_claim_id), EventClaimResult::Valid);
+		assert_ok!(notarize(event_claim_id, 0, EventClaimResult::Valid));
+		assert_ok!(notarize(event_claim_id, 1, EventClaimResult::Valid));
+
+		// the deposit can't be claimed again
+		assert_noop!(submit_claim_as(&claimant(), 1), Error::<TestRuntime>::AlreadyNotarized);
+		assert_noop!(
+			submit_claim_as(&other_claimant(), 1),
+			Error::<TestRuntime>::AlreadyNotarized
+		);
+		assert_eq!(claim_results(), vec![(event_claim_id, true)]);
+	});
+}
+
+#[test]
+fn invalid_notarization_fails() {
+	let (mut ext, offchain) = mock_ethereum_ext();
+	let mock = mock_ethereum(86_400);
+	// the deposit logged different data than claimed
+	mock_deposit(&mock, 1, &[3_u8, 2, 1]);
+	mock.mine_blocks(EventConfirmations::get() + 1);
+
+	ext.execute_with(|| {
+		let event_claim_id = submit_claim(1);
+		expect_mock_request(
+			&offchain,
+			&mock,
+			&GetTxReceiptRequest::new(H256Crml::from_low_u64_be(1), 0),
+		);
+		assert_eq!(offchain_notarize(event_claim_id), EventClaimResult::UnexpectedData);
+
+		// only active notaries may notarize
+		assert_noop!(
+			notarize(event_claim_id, 3, EventClaimResult::Valid),
+			Error::<TestRuntime>::InvalidNotarization
+		);
+		assert_ok!(notarize(event_claim_id, 0, EventClaimResult::UnexpectedData));
+		assert_ok!(notarize(event_claim_id, 1, EventClaimResult::UnexpectedData));
+		assert_eq!(claim_results(), vec![(event_claim_id, false)]);
+		// the claimant's deposit is slashed
+		assert_eq!(Balances::reserved_balance(&claimant()), 0);
+		assert_eq!(Balances::free_balance(&claimant()), 1_000 - ClaimDeposit::get());
+		// the deposit may be claimed again with the observed data
+		assert_ok!(submit_claim_as(&claimant(), 1));
+	});
+}
@@ -17,13 +17,16 @@
 
 use codec::{Decode, Encode};
 use core::fmt;
-pub use crml_support::{H160, H256, U256};
-use ethereum_types::{Bloom as H2048, U64};
+use crml_support::EthAbiCodec;
+pub use crml_support::{EthChainId, H160, H256, U256};
+use ethereum_types::{Bloom as H2048, H64, U64};
+use rlp::RlpStream;
 use scale_info::TypeInfo;
 use serde::de::{Error, Visitor};
 use serde::{Deserialize, Deserializer, Serialize};
-use sp_runtime::RuntimeDebug;
-use sp_std::{prelude::*, vec::Vec};
+use sp_io::hashing::keccak_256;
+use sp_runtime::{traits::SaturatedConversion, RuntimeDebug};
+use sp_std::{convert::TryInto, prelude::*, vec::Vec};
 
 /// A bridge message id
 pub type EventClaimId = u64;
@@ -40,9 +43,79 @@ pub type EthAddress = H160;
 /// The ethereum transaction hash type
 pub type EthHash = H256;
 
+/// Settings for bridging events from an EVM chain
+#[derive(Encode, Decode, Default, Debug, Clone, PartialEq, Eq, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct EvmChainConfig {
+	/// The minimum number of block confirmations needed to notarize an event
+	pub event_confirmations: u64,
+	/// Events cannot be claimed after this time (seconds)
+	pub event_deadline_seconds: u64,
+}
+
+/// A message sent from CENNZnet to a contract on an EVM chain
+#[derive(Encode, Decode, Default, Debug, Clone, PartialEq, Eq, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Message {
+	/// The CENNZnet account that sent the message
+	pub sender: H256,
+	/// The contract the message is sent to
+	pub destination: EthAddress,
+	/// Unique nonce of the message
+	pub nonce: u64,
+	/// Opaque data for the destination contract
+	pub payload: Vec<u8>,
+}
+
+impl EthAbiCodec for Message {
+	/// Encode `Message` as `abi.encode(bytes32 sender, address destination, uint256 nonce, bytes payload)`
+	/// https://docs.soliditylang.org/en/v0.5.3/abi-spec.html#formal-specification-of-the-encoding
+	fn encode(&self) -> Vec<u8> {
+		let padded_len = (self.payload.len() + 31) / 32 * 32;
+		let mut buf = vec![0_u8; 32 * 5 + padded_len];
+		buf[0..32].copy_from_slice(self.sender.as_bytes());
+		buf[44..64].copy_from_slice(self.destination.as_bytes());
+		buf[64..96].copy_from_slice(&Into::<[u8; 32]>::into(U256::from(self.nonce)));
+		// offset of the dynamic `payload` data
+		buf[96..128].copy_from_slice(&Into::<[u8; 32]>::into(U256::from(128_u64)));
+		buf[128..160].copy_from_slice(&Into::<[u8; 32]>::into(U256::from(self.payload.len() as u64)));
+		buf[160..160 + self.payload.len()].copy_from_slice(&self.payload);
+		buf
+	}
+
+	fn decode(data: &[u8]) -> Option<Self> {
+		if data.len() < 32 * 5 || U256::from(&data[96..128]) != U256::from(128_u64) {
+			return None;
+		}
+		let payload_len: usize = U256::from(&data[128..160]).try_into().ok()?;
+		let payload = data.get(160..160_usize.checked_add(payload_len)?)?.to_vec();
+
+		Some(Self {
+			sender: H256::from_slice(&data[0..32]),
+			destination: EthAddress::from_slice(&data[44..64]),
+			nonce: U256::from(&data[64..96]).try_into().ok()?,
+			payload,
+		})
+	}
+}
+
+// A value placed in storage that represents the current version of the eth bridge storage. This value
+// is used by the `on_runtime_upgrade` logic to determine whether we run storage migration logic.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, TypeInfo)]
+pub enum Releases {
+	/// Events bridged from Ethereum only
+	V0 = 0,
+	/// Events bridged from multiple EVM chains
+	V1 = 1,
+	/// Processed txs bucketed by the hour they were processed
+	V2 = 2,
+}
+
 #[derive(Debug, Default, Clone, PartialEq, Decode, Encode, TypeInfo)]
 /// Info required to claim an Ethereum event happened
 pub struct EventClaim {
+	/// The EVM chain of the event
+	pub chain_id: EthChainId,
 	/// The ethereum transaction hash
 	pub tx_hash: EthHash,
 	/// The event data as logged on Ethereum
@@ -62,6 +135,7 @@ pub trait EthereumHttpApi {
 
 /// Possible outcomes from attempting to verify an Ethereum event claim
 #[derive(Decode, Encode, Debug, PartialEq, Clone, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum EventClaimResult {
 	/// It's valid
 	Valid,
@@ -81,6 +155,68 @@ pub enum EventClaimResult {
 	Expired,
 }
 
+impl EventClaimResult {
+	/// Whether checking the claim again later could give a different result
+	/// i.e. more block confirmations have arrived or the data provider has recovered
+	pub fn is_transient(&self) -> bool {
+		matches!(self, Self::DataProviderErr | Self::NotEnoughConfirmations)
+	}
+}
+
+/// The status of an event claim
+#[derive(Decode, Encode, Debug, PartialEq, Clone, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum ClaimStatus<BlockNumber> {
+	/// Awaiting notarization, with the votes cast so far
+	Pending { yay: u32, nay: u32 },
+	/// Verified by notaries at the block
+	Verified(BlockNumber),
+	/// Rejected by notaries at the block, with the number of votes for each result
+	Rejected {
+		block_number: BlockNumber,
+		reasons: Vec<(EventClaimResult, u32)>,
+	},
+	/// Expired at the block before notaries reached a result
+	Expired(BlockNumber),
+}
+
+/// The status of an event claim and the Ethereum tx it claims
+#[derive(Decode, Encode, Debug, PartialEq, Clone, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ClaimRecord<BlockNumber> {
+	/// The claim Id
+	pub event_claim_id: EventClaimId,
+	/// The EVM chain of the transaction
+	pub chain_id: EthChainId,
+	/// The ethereum transaction hash
+	pub tx_hash: EthHash,
+	/// The current status
+	pub status: ClaimStatus<BlockNumber>,
+}
+
+/// A notary's votes on claims notaries reached a result on, over the current session
+#[derive(Encode, Decode, Default, Debug, Clone, PartialEq, Eq, TypeInfo)]
+pub struct NotaryVotes {
+	/// Votes cast on those claims, including votes arriving after their result
+	pub votes: u32,
+	/// Votes contradicting the result of the claim
+	pub disagreements: u32,
+}
+
+/// A validator's bridge performance over the current session
+#[derive(Encode, Decode, Default, Debug, Clone, PartialEq, Eq, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct NotaryPerformance {
+	/// Claims notaries reached a result on this session
+	pub claims: u32,
+	/// Votes cast on those claims, including votes arriving after their result
+	pub votes: u32,
+	/// Claims without a vote from the notary
+	pub missed: u32,
+	/// Votes contradicting the result of the claim
+	pub disagreements: u32,
+}
+
 /// An independent notarization vote on a claim
 /// This is signed and shared with the runtime after verification by a particular validator
 #[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug, TypeInfo)]
@@ -179,47 +315,238 @@ pub struct TransactionReceipt {
 
 /// Standard Eth block type
 ///
-/// NB: for the bridge we only need the `timestamp` however the only RPCs available require fetching the whole block
+/// NB: the header fields are deserialized so the header may be rebuilt and hashed, the body is not needed
 #[derive(Clone, Debug, PartialEq, Deserialize)]
 pub struct EthBlock {
 	pub number: Option<U64>,
 	pub hash: Option<H256>,
 	pub timestamp: U256,
-	// don't deserialize anything else
-	#[serde(rename = "parentHash", skip_deserializing)]
+	#[serde(rename = "parentHash", default)]
 	pub parent_hash: H256,
-	#[serde(skip_deserializing)]
+	#[serde(default)]
 	pub nonce: Option<U64>,
-	#[serde(rename = "sha3Uncles", skip_deserializing)]
+	#[serde(rename = "sha3Uncles", default)]
 	pub sha3_uncles: H256,
-	#[serde(rename = "logsBloom", skip_deserializing)]
+	#[serde(rename = "logsBloom", default)]
 	pub logs_bloom: Option<H2048>,
-	#[serde(rename = "transactionsRoot", skip_deserializing)]
+	#[serde(rename = "transactionsRoot", default)]
 	pub transactions_root: H256,
-	#[serde(rename = "stateRoot", skip_deserializing)]
+	#[serde(rename = "stateRoot", default)]
 	pub state_root: H256,
-	#[serde(rename = "receiptsRoot", skip_deserializing)]
+	#[serde(rename = "receiptsRoot", default)]
 	pub receipts_root: H256,
-	#[serde(skip_deserializing)]
+	#[serde(default)]
 	pub miner: EthAddress,
-	#[serde(skip_deserializing)]
+	#[serde(default)]
 	pub difficulty: U256,
+	#[serde(rename = "gasLimit", default)]
+	pub gas_limit: U256,
+	#[serde(rename = "gasUsed", default)]
+	pub gas_used: U256,
+	#[serde(rename = "extraData", default, deserialize_with = "deserialize_hex")]
+	pub extra_data: Vec<u8>,
+	#[serde(rename = "mixHash", default)]
+	pub mix_hash: H256,
+	/// London onwards
+	#[serde(rename = "baseFeePerGas", default)]
+	pub base_fee_per_gas: Option<U256>,
+	/// Shanghai onwards
+	#[serde(rename = "withdrawalsRoot", default)]
+	pub withdrawals_root: Option<H256>,
+	/// Cancun onwards
+	#[serde(rename = "blobGasUsed", default)]
+	pub blob_gas_used: Option<U64>,
+	/// Cancun onwards
+	#[serde(rename = "excessBlobGas", default)]
+	pub excess_blob_gas: Option<U64>,
+	/// Cancun onwards
+	#[serde(rename = "parentBeaconBlockRoot", default)]
+	pub parent_beacon_block_root: Option<H256>,
+	/// Prague onwards
+	#[serde(rename = "requestsHash", default)]
+	pub requests_hash: Option<H256>,
+	// don't deserialize anything else
 	#[serde(rename = "totalDifficulty", skip_deserializing)]
 	pub total_difficulty: U256,
-	#[serde(rename = "extraData", skip_deserializing)]
-	pub extra_data: Vec<u8>,
 	#[serde(skip_deserializing)]
 	pub size: U256,
-	#[serde(rename = "gasLimit", skip_deserializing)]
-	pub gas_limit: U256,
-	#[serde(rename = "gasUsed", skip_deserializing)]
-	pub gas_used: U256,
 	#[serde(skip_deserializing)]
 	pub transactions: Vec<H256>,
 	#[serde(skip_deserializing)]
 	pub uncles: Vec<H256>,
 }
 
+impl EthBlock {
+	/// The header of this block
+	pub fn header(&self) -> EthHeader {
+		EthHeader {
+			parent_hash: self.parent_hash,
+			uncles_hash: self.sha3_uncles,
+			author: self.miner,
+			state_root: self.state_root,
+			transactions_root: self.transactions_root,
+			receipts_root: self.receipts_root,
+			logs_bloom: self.logs_bloom.unwrap_or_default(),
+			difficulty: self.difficulty,
+			number: self.number.unwrap_or_default().as_u64(),
+			gas_limit: self.gas_limit,
+			gas_used: self.gas_used,
+			timestamp: self.timestamp.saturated_into(),
+			extra_data: self.extra_data.clone(),
+			mix_hash: self.mix_hash,
+			nonce: H64::from_low_u64_be(self.nonce.unwrap_or_default().as_u64()),
+			base_fee_per_gas: self.base_fee_per_gas,
+			withdrawals_root: self.withdrawals_root,
+			blob_gas_used: self.blob_gas_used.map(|gas| gas.as_u64()),
+			excess_blob_gas: self.excess_blob_gas.map(|gas| gas.as_u64()),
+			parent_beacon_block_root: self.parent_beacon_block_root,
+			requests_hash: self.requests_hash,
+		}
+	}
+}
+
+/// An Ethereum block header
+/// Optional fields were added by later hard forks and are present from their activation onwards
+#[derive(Encode, Decode, Default, Debug, Clone, PartialEq, Eq, TypeInfo)]
+pub struct EthHeader {
+	pub parent_hash: H256,
+	pub uncles_hash: H256,
+	pub author: EthAddress,
+	pub state_root: H256,
+	pub transactions_root: H256,
+	pub receipts_root: H256,
+	pub logs_bloom: H2048,
+	pub difficulty: U256,
+	pub number: u64,
+	pub gas_limit: U256,
+	pub gas_used: U256,
+	pub timestamp: u64,
+	pub extra_data: Vec<u8>,
+	pub mix_hash: H256,
+	pub nonce: H64,
+	pub base_fee_per_gas: Option<U256>,
+	pub withdrawals_root: Option<H256>,
+	pub blob_gas_used: Option<u64>,
+	pub excess_blob_gas: Option<u64>,
+	pub parent_beacon_block_root: Option<H256>,
+	pub requests_hash: Option<H256>,
+}
+
+impl EthHeader {
+	/// The RLP encoding of this header as hashed by Ethereum
+	pub fn rlp_encode(&self) -> Vec<u8> {
+		let mut stream = RlpStream::new();
+		stream.begin_unbounded_list();
+		stream
+			.append(&self.parent_hash)
+			.append(&self.uncles_hash)
+			.append(&self.author)
+			.append(&self.state_root)
+			.append(&self.transactions_root)
+			.append(&self.receipts_root)
+			.append(&self.logs_bloom)
+			.append(&self.difficulty)
+			.append(&self.number)
+			.append(&self.gas_limit)
+			.append(&self.gas_used)
+			.append(&self.timestamp)
+			.append(&self.extra_data)
+			.append(&self.mix_hash)
+			.append(&self.nonce);
+		// fork fields are only present when all prior fork fields are
+		if let Some(base_fee_per_gas) = self.base_fee_per_gas {
+			stream.append(&base_fee_per_gas);
+			if let Some(withdrawals_root) = self.withdrawals_root {
+				stream.append(&withdrawals_root);
+				if let (Some(blob_gas_used), Some(excess_blob_gas), Some(parent_beacon_block_root)) =
+					(self.blob_gas_used, self.excess_blob_gas, self.parent_beacon_block_root)
+				{
+					stream
+						.append(&blob_gas_used)
+						.append(&excess_blob_gas)
+						.append(&parent_beacon_block_root);
+					if let Some(requests_hash) = self.requests_hash {
+						stream.append(&requests_hash);
+					}
+				}
+			}
+		}
+		stream.finalize_unbounded_list();
+
+		stream.out().to_vec()
+	}
+	/// The Ethereum block hash of this header
+	pub fn hash(&self) -> EthHash {
+		EthHash::from(keccak_256(&self.rlp_encode()))
+	}
+}
+
+/// The fields of a notarized Ethereum block header needed to verify claims
+#[derive(Encode, Decode, Default, Debug, Clone, PartialEq, Eq, TypeInfo)]
+pub struct RelayedHeader {
+	/// The block hash
+	pub hash: EthHash,
+	/// Root of the block's receipts trie
+	pub receipts_root: H256,
+	/// Root of the block's transactions trie
+	pub transactions_root: H256,
+	/// The block timestamp (seconds)
+	pub timestamp: u64,
+}
+
+impl From<&EthHeader> for RelayedHeader {
+	fn from(header: &EthHeader) -> Self {
+		Self {
+			hash: header.hash(),
+			receipts_root: header.receipts_root,
+			transactions_root: header.transactions_root,
+			timestamp: header.timestamp,
+		}
+	}
+}
+
+/// A notary's attestation to an Ethereum block header at a requested height
+/// This is signed and shared with the runtime after fetching the header by a particular validator
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct HeaderAttestationPayload {
+	/// The EVM chain of the header
+	pub chain_id: EthChainId,
+	/// The attested header
+	pub header: EthHeader,
+	/// The ordinal index of the signer in the notary set
+	pub authority_index: u16,
+}
+
+/// Proof a transaction and its receipt are included in an Ethereum block
+/// Trie nodes are ordered from the root down, their keys are `rlp(tx_index)`
+/// Nodes embedded in their parent (encoded in < 32 bytes) are not listed separately
+#[derive(Encode, Decode, Default, Debug, Clone, PartialEq, Eq, TypeInfo)]
+pub struct ReceiptProof {
+	/// The block including the transaction
+	pub block_number: u64,
+	/// The index of the transaction in the block
+	pub tx_index: u32,
+	/// Receipts trie nodes on the path to the receipt
+	pub receipt_proof: Vec<Vec<u8>>,
+	/// Transactions trie nodes on the path to the transaction
+	pub transaction_proof: Vec<Vec<u8>>,
+}
+
+impl ReceiptProof {
+	/// Number of trie nodes in the proof
+	pub fn node_count(&self) -> usize {
+		self.receipt_proof.len() + self.transaction_proof.len()
+	}
+	/// Total size of the trie nodes in the proof (bytes)
+	pub fn size(&self) -> usize {
+		self.receipt_proof
+			.iter()
+			.chain(self.transaction_proof.iter())
+			.map(|node| node.len())
+			.sum()
+	}
+}
+
 #[derive(Debug, Default, Clone, Eq, PartialEq, Deserialize, TypeInfo)]
 pub struct EthResponse<'a, D> {
 	jsonrpc: &'a str,
@@ -279,6 +606,34 @@ pub enum LatestOrNumber {
 	Number(u32),
 }
 
+/// Offchain record of an Ethereum JSON-RPC endpoint's reliability
+/// Used to prefer healthy endpoints when failing over between providers
+#[derive(Encode, Decode, Default, Debug, Clone, PartialEq)]
+pub struct EndpointHealth {
+	/// Total successful requests
+	pub successes: u32,
+	/// Total failed requests
+	pub failures: u32,
+	/// Failed requests since the last successful request
+	pub consecutive_failures: u32,
+	/// Offchain timestamp of the last failed request (milliseconds)
+	pub last_failure_ms: u64,
+}
+
+impl EndpointHealth {
+	/// Note a successful request
+	pub fn record_success(&mut self) {
+		self.successes = self.successes.saturating_add(1);
+		self.consecutive_failures = 0;
+	}
+	/// Note a failed request at `now_ms`
+	pub fn record_failure(&mut self, now_ms: u64) {
+		self.failures = self.failures.saturating_add(1);
+		self.consecutive_failures = self.consecutive_failures.saturating_add(1);
+		self.last_failure_ms = now_ms;
+	}
+}
+
 /// Serializes the parameters for `GetBlockRequest`
 pub fn serialize_params<S: serde::Serializer>(v: &(LatestOrNumber, bool), s: S) -> Result<S::Ok, S::Error> {
 	use core::fmt::Write;
@@ -348,7 +703,7 @@ impl<'a> Visitor<'a> for BytesVisitor {
 }
 
 // decode a non-0x prefixed hex string into a `Vec<u8>`
-fn decode_hex(s: &str) -> Result<Vec<u8>, core::num::ParseIntError> {
+pub(crate) fn decode_hex(s: &str) -> Result<Vec<u8>, core::num::ParseIntError> {
 	(0..s.len())
 		.step_by(2)
 		.map(|i| u8::from_str_radix(&s[i..i + 2], 16))
@@ -373,9 +728,8 @@ mod tests {
 		)
 	}
 
-	#[test]
-	fn deserialize_eth_block_request() {
-		let response = r#"
+	/// `eth_getBlockByNumber` response for Ethereum mainnet block 6008149
+	const MAINNET_BLOCK_6008149: &str = r#"
 			{
 				"jsonrpc": "2.0",
 				"id": 1,
@@ -487,7 +841,166 @@ mod tests {
 		}
 		"#;
 
-		let _result: EthResponse<EthBlock> = serde_json::from_str(response).expect("it deserializes");
+	#[test]
+	fn deserialize_eth_block_request() {
+		let _result: EthResponse<EthBlock> = serde_json::from_str(MAINNET_BLOCK_6008149).expect("it deserializes");
+	}
+
+	#[test]
+	fn eth_block_header_hash() {
+		let result: EthResponse<EthBlock> = serde_json::from_str(MAINNET_BLOCK_6008149).expect("it deserializes");
+		let block = result.result.expect("block is present");
+		let header = block.header();
+
+		assert_eq!(header.number, 6_008_149);
+		assert_eq!(header.extra_data, b"sparkpool-cn-node-12".to_vec());
+		assert_eq!(Some(header.hash()), block.hash);
+		assert_eq!(
+			header.hash(),
+			EthHash::from_str("0xb3b20624f8f0f86eb50dd04688409e5cea4bd02d700bf6e79e9384d47d6a5a35").unwrap()
+		);
+
+		// any change to the header changes its hash
+		let mut tampered = header.clone();
+		tampered.receipts_root = H256::zero();
+		assert_ne!(tampered.hash(), header.hash());
+	}
+
+	/// `eth_getBlockByNumber` response for block 3 of a local Ethereum dev chain (chain id 1)
+	/// The block has a single legacy transfer, so its transactions and receipts tries are a single leaf
+	const DEV_CHAIN_BLOCK_3: &str = r#"
+			{
+				"jsonrpc": "2.0",
+				"id": 1,
+				"result": {
+				  "difficulty": "0x0",
+				  "extraData": "0x",
+				  "gasLimit": "0x6691b7",
+				  "gasUsed": "0x5208",
+				  "hash": "0xda53da08ef6a3cbde84c33e51c04f68c3853b6a3731f10baa2324968eee63972",
+				  "logsBloom": "0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+				  "miner": "0x0000000000000000000000000000000000000000",
+				  "mixHash": "0x0000000000000000000000000000000000000000000000000000000000000000",
+				  "nonce": "0x0000000000000000",
+				  "number": "0x3",
+				  "parentHash": "0x689c70c080ca22bc0e681694fa803c1aba16a69c8b6368fed5311d279eb9de90",
+				  "receiptsRoot": "0x056b23fbba480696b65fe5a59b8f2148a1299103c4f57df839233af2cf4ca2d2",
+				  "sha3Uncles": "0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347",
+				  "size": "0x3e8",
+				  "stateRoot": "0x29f32984517a7d25607da485b23cefabfd443751422ca7e603395e1de9bc8a4b",
+				  "timestamp": "0x5ecedbb9",
+				  "totalDifficulty": "0x0",
+				  "transactions": [
+					"0xc3c5f700243de37ae986082fd2af88d2a7c2752a0c0f7b9d6ac47c729d45e067"
+				  ],
+				  "transactionsRoot": "0x7270c1c4440180f2bd5215809ee3d545df042b67329499e1ab97eb759d31610d",
+				  "uncles": []
+				}
+		}
+		"#;
+	/// Transaction proof of the only transaction in `DEV_CHAIN_BLOCK_3`
+	const DEV_CHAIN_BLOCK_3_TRANSACTION_PROOF: [&str; 1] = [
+		"f86c822080b867f865028504a817c80083015f9094dca8ce283150ab773bcbeb8d38289bdb5661de1e808025a019f2694eb9113656dbea0b925e2e7ceb43df83e601c4116aee9c0dd99130be88a073e5764b324a4f7679d890a198ba658ba1c8cd36983ff9797e10b1b89dbb448e",
+	];
+	/// Receipt proof of the only transaction in `DEV_CHAIN_BLOCK_3`
+	const DEV_CHAIN_BLOCK_3_RECEIPT_PROOF: [&str; 1] = [
+		"f90111822080b9010bf9010801825208b9010000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000c0",
+	];
+
+	#[test]
+	fn recorded_block_proofs_verify_against_header() {
+		use crate::proof::{decode_receipt, fixtures::nodes, tx_index_key, verify_proof};
+
+		let result: EthResponse<EthBlock> = serde_json::from_str(DEV_CHAIN_BLOCK_3).expect("it deserializes");
+		let block = result.result.expect("block is present");
+		let header = block.header();
+		assert_eq!(Some(header.hash()), block.hash);
+
+		let transaction = verify_proof(
+			&header.transactions_root,
+			&tx_index_key(0),
+			&nodes(&DEV_CHAIN_BLOCK_3_TRANSACTION_PROOF),
+		)
+		.expect("proof is valid");
+		assert_eq!(
+			EthHash::from(keccak_256(&transaction)),
+			EthHash::from_str("0xc3c5f700243de37ae986082fd2af88d2a7c2752a0c0f7b9d6ac47c729d45e067").unwrap()
+		);
+
+		let receipt = verify_proof(
+			&header.receipts_root,
+			&tx_index_key(0),
+			&nodes(&DEV_CHAIN_BLOCK_3_RECEIPT_PROOF),
+		)
+		.expect("proof is valid");
+		let receipt = decode_receipt(&receipt).expect("receipt decodes");
+		assert!(receipt.status);
+		assert!(receipt.logs.is_empty());
+
+		// the proofs do not verify against the other root
+		assert!(verify_proof(
+			&header.receipts_root,
+			&tx_index_key(0),
+			&nodes(&DEV_CHAIN_BLOCK_3_TRANSACTION_PROOF)
+		)
+		.is_err());
+	}
+
+	#[test]
+	fn eth_header_hash_with_fork_fields() {
+		// fork fields extend the legacy encoding in activation order
+		let legacy = EthHeader::default();
+		let london = EthHeader {
+			base_fee_per_gas: Some(7_u64.into()),
+			..Default::default()
+		};
+		let shanghai = EthHeader {
+			withdrawals_root: Some(H256::repeat_byte(1)),
+			..london.clone()
+		};
+		let cancun = EthHeader {
+			blob_gas_used: Some(131_072),
+			excess_blob_gas: Some(0),
+			parent_beacon_block_root: Some(H256::repeat_byte(2)),
+			..shanghai.clone()
+		};
+		let prague = EthHeader {
+			requests_hash: Some(H256::repeat_byte(3)),
+			..cancun.clone()
+		};
+		assert_eq!(rlp::Rlp::new(&legacy.rlp_encode()).item_count(), Ok(15));
+		assert_eq!(rlp::Rlp::new(&london.rlp_encode()).item_count(), Ok(16));
+		assert_eq!(rlp::Rlp::new(&shanghai.rlp_encode()).item_count(), Ok(17));
+		assert_eq!(rlp::Rlp::new(&cancun.rlp_encode()).item_count(), Ok(20));
+		assert_eq!(rlp::Rlp::new(&prague.rlp_encode()).item_count(), Ok(21));
+		assert_eq!(
+			rlp::Rlp::new(&prague.rlp_encode()).val_at::<H256>(20),
+			Ok(H256::repeat_byte(3))
+		);
+		assert_ne!(london.hash(), legacy.hash());
+		assert_ne!(prague.hash(), cancun.hash());
+	}
+
+	#[test]
+	fn eth_block_fork_fields_deserialize_into_header() {
+		let response = r#"{"jsonrpc":"2.0","id":1,"result":{
+			"number":"0x1",
+			"timestamp":"0x6",
+			"baseFeePerGas":"0x7",
+			"withdrawalsRoot":"0x0101010101010101010101010101010101010101010101010101010101010101",
+			"blobGasUsed":"0x20000",
+			"excessBlobGas":"0x0",
+			"parentBeaconBlockRoot":"0x0202020202020202020202020202020202020202020202020202020202020202",
+			"requestsHash":"0x0303030303030303030303030303030303030303030303030303030303030303"
+		}}"#;
+		let result: EthResponse<EthBlock> = serde_json::from_str(response).expect("it deserializes");
+		let header = result.result.expect("block is present").header();
+
+		assert_eq!(header.blob_gas_used, Some(131_072));
+		assert_eq!(header.excess_blob_gas, Some(0));
+		assert_eq!(header.parent_beacon_block_root, Some(H256::repeat_byte(2)));
+		assert_eq!(header.requests_hash, Some(H256::repeat_byte(3)));
+		assert_eq!(rlp::Rlp::new(&header.rlp_encode()).item_count(), Ok(21));
 	}
 
 	#[test]
@@ -53,14 +53,69 @@ pub fn signable_message(what: &[u8]) -> Vec<u8> {
 	v
 }
 
+/// EIP-712 domain of CENNZnet typed data
+/// https://eips.ethereum.org/EIPS/eip-712
+pub struct Eip712Domain<'a> {
+	/// The chain name
+	pub name: &'a [u8],
+	/// The runtime spec version
+	pub version: &'a [u8],
+	/// The chain genesis hash, distinguishing chains of the same name
+	pub salt: [u8; 32],
+}
+
+impl<'a> Eip712Domain<'a> {
+	/// The domain separator i.e. `hashStruct(domain)`
+	pub fn separator(&self) -> [u8; 32] {
+		keccak_256(
+			&[
+				&keccak_256(b"EIP712Domain(string name,string version,bytes32 salt)")[..],
+				&keccak_256(self.name)[..],
+				&keccak_256(self.version)[..],
+				&self.salt[..],
+			]
+			.concat(),
+		)
+	}
+}
+
+/// Constructs the EIP-712 digest of a runtime call i.e. what `eth_signTypedData_v4` would sign
+/// The call is presented as `Call(string pallet,string method,uint32 nonce,bytes32 args)`
+/// `args` is the keccak256 hash of the SCALE encoded signed message, binding the call and its versioned payload
+pub fn typed_call_digest(domain: &Eip712Domain, pallet: &[u8], method: &[u8], nonce: u32, args: &[u8]) -> [u8; 32] {
+	let mut nonce_word = [0_u8; 32];
+	nonce_word[28..].copy_from_slice(&nonce.to_be_bytes());
+	let struct_hash = keccak_256(
+		&[
+			&keccak_256(b"Call(string pallet,string method,uint32 nonce,bytes32 args)")[..],
+			&keccak_256(pallet)[..],
+			&keccak_256(method)[..],
+			&nonce_word[..],
+			&keccak_256(args)[..],
+		]
+		.concat(),
+	);
+
+	keccak_256(&[&b"\x19\x01"[..], &domain.separator()[..], &struct_hash[..]].concat())
+}
+
 /// Return the ECDSA public key if `signature` is valid or `msg` from `address`, otherwise None
 pub fn ecrecover<L: Lazy<[u8]>>(
 	signature: &EthereumSignature,
 	mut msg: L,
 	address: &EthAddress,
 ) -> Option<ecdsa::Public> {
-	let msg = keccak_256(&signable_message(&msg.get()));
-	if let Some(public) = secp256k1_ecdsa_recover_compressed(&signature.0, &msg).ok() {
+	ecrecover_digest(signature, &keccak_256(&signable_message(&msg.get())), address)
+}
+
+/// Return the ECDSA public key if `signature` is valid for the 32 byte `digest` from `address`, otherwise None
+/// e.g. for EIP-712 typed data
+pub fn ecrecover_digest(
+	signature: &EthereumSignature,
+	digest: &[u8; 32],
+	address: &EthAddress,
+) -> Option<ecdsa::Public> {
+	if let Some(public) = secp256k1_ecdsa_recover_compressed(&signature.0, digest).ok() {
 		let public = ecdsa::Public { 0: public };
 		if let Some(address_) = cennznet_primitives::eth::EthyEcdsaToEthereum::convert(public.clone()) {
 			if address_ == address.0 {
@@ -2,29 +2,44 @@
 //! Provides shims for Ethereum wallets (e.g. metamask) to interact with CENNZnet
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use crate::ethereum::{ecrecover, EthereumSignature};
-use codec::Encode;
+use crate::ethereum::{ecrecover, ecrecover_digest, typed_call_digest, Eip712Domain, EthereumSignature};
+use codec::{Decode, Encode};
 use crml_support::{TransactionFeeHandler, H160 as EthAddress};
 use frame_support::{
 	decl_error, decl_event, decl_module, decl_storage,
-	dispatch::{DispatchInfo, Dispatchable},
+	dispatch::{DispatchInfo, Dispatchable, GetCallMetadata},
 	traits::{Get, UnfilteredDispatchable},
 	weights::GetDispatchInfo,
-	Parameter,
+	Parameter, RuntimeDebug,
 };
 use frame_system::ensure_none;
+use scale_info::TypeInfo;
 use sp_runtime::{
-	traits::IdentifyAccount,
+	generic::Era,
+	traits::{IdentifyAccount, Zero},
 	transaction_validity::{
 		InvalidTransaction, TransactionPriority, TransactionSource, TransactionValidity, ValidTransaction,
 	},
 	DispatchResult, SaturatedConversion,
 };
+#[cfg(not(feature = "std"))]
+use sp_std::alloc::string::ToString;
 use sp_std::prelude::*;
 
 /// Ethereum-compatible signatures (eth_sign API call).
 pub mod ethereum;
 
+/// Name of the EIP-712 domain, chains are distinguished by genesis hash
+const EIP712_DOMAIN_NAME: &[u8] = b"CENNZnet";
+
+/// Details signed with an Ethereum signed call besides the call & its nonce
+/// Versioned so the signed format may be extended
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum SignedPayload {
+	/// Signs the chain's genesis hash and the call's era, a mortal call also signs the hash of the block its era began
+	V1(Era),
+}
+
 /// The module's configuration trait.
 pub trait Config: frame_system::Config {
 	/// The overarching event type.
@@ -34,7 +49,8 @@ pub trait Config: frame_system::Config {
 	type Call: Parameter
 		+ Dispatchable<Info = DispatchInfo>
 		+ UnfilteredDispatchable<Origin = Self::Origin>
-		+ GetDispatchInfo;
+		+ GetDispatchInfo
+		+ GetCallMetadata;
 
 	/// Provides transaction fee handling
 	type TransactionFeeHandler: TransactionFeeHandler<AccountId = Self::AccountId, Call = <Self as Config>::Call>;
@@ -89,25 +105,28 @@ decl_module! {
 			let dispatch_info = call.get_dispatch_info();
 			(dispatch_info.weight + 10_000, dispatch_info.class)
 		}]
-		/// Execute a runtime `call` signed using `eth_sign`
-		/// Expects signature validates the payload `(call, nonce)`
+		/// Execute a runtime `call` signed using `eth_sign` or `eth_signTypedData_v4` (EIP-712)
+		/// Expects signature validates the message `(call, nonce, payload, genesis hash, era birth hash)`
+		/// or the typed data of the call
 		///
 		/// origin must be `none`
 		/// call - runtime call to execute
 		/// account - signed by this account
+		/// payload - versioned details signed with the call e.g. its era
 		fn call(
 			origin,
 			call: Box<<T as Config>::Call> ,
 			eth_address: EthAddress,
 			signature: EthereumSignature,
+			payload: SignedPayload,
 		) -> DispatchResult {
 			ensure_none(origin)?;
 
 			// check the known nonce for this ethereum address
 			let address_nonce = Self::stored_address_nonce(eth_address);
-			let message = &(&call, address_nonce).encode()[..];
+			let message = Self::signed_message(&call, address_nonce, &payload).ok_or(Error::<T>::InvalidSignature)?;
 
-			if let Some(public_key) = ecrecover(&signature, message, &eth_address) {
+			if let Some(public_key) = Self::recover_signer(&call, address_nonce, &message, &eth_address, &signature) {
 				let account = T::Signer::from(public_key).into_account(); // CENNZnet address
 
 				// it's possible this account is used normally outside of eth signing-
@@ -117,7 +136,7 @@ decl_module! {
 				let new_nonce = highest_nonce.checked_add(1).ok_or(Error::<T>::InvalidNonce)?;
 
 				// Pay fee, increment nonce
-				let _ = Self::pay_fee(&call, &account)?;
+				let _ = Self::pay_fee(&call, &payload, &account)?;
 				AddressNonce::insert(eth_address, new_nonce);
 				<frame_system::Pallet<T>>::inc_account_nonce(&account);
 
@@ -136,14 +155,68 @@ decl_module! {
 
 impl<T: Config> Module<T> {
 	/// Take required fees from `account` to dispatch `call`
-	fn pay_fee(call: &<T as Config>::Call, account: &T::AccountId) -> DispatchResult {
+	fn pay_fee(call: &<T as Config>::Call, payload: &SignedPayload, account: &T::AccountId) -> DispatchResult {
 		let info = call.get_dispatch_info();
-		let len = call.clone().encode()[..].len() as u32 + 33 + 65 + 4; // call + account, signature, nonce bytes
+		// call + account, signature, nonce, payload bytes
+		let len = call.clone().encode()[..].len() as u32 + 33 + 65 + 4 + payload.encoded_size() as u32;
 
 		T::TransactionFeeHandler::pay_fee(len, call, &info, account)
 			.map(|_| ())
 			.map_err(|_| Error::<T>::CantPay.into())
 	}
+	/// The message signed for `call` at `nonce` i.e. `(call, nonce, payload, genesis hash, era birth hash)`
+	/// None if the era has expired
+	pub fn signed_message(call: &<T as Config>::Call, nonce: u32, payload: &SignedPayload) -> Option<Vec<u8>> {
+		match payload {
+			SignedPayload::V1(era) => {
+				let current = <frame_system::Pallet<T>>::block_number().saturated_into::<u64>();
+				// an immortal era begins at genesis
+				let birth: T::BlockNumber = era.birth(current).saturated_into();
+				if !<frame_system::BlockHash<T>>::contains_key(birth) {
+					return None;
+				}
+				let genesis_hash = <frame_system::Pallet<T>>::block_hash(T::BlockNumber::zero());
+				let birth_hash = <frame_system::Pallet<T>>::block_hash(birth);
+
+				Some((call, nonce, payload, genesis_hash, birth_hash).encode())
+			}
+		}
+	}
+	/// Return the public key of `eth_address` if `signature` signs the `message` of `call` at `nonce`, otherwise None
+	/// Accepts an `eth_sign` signature of `message` or an EIP-712 signature of the call's typed data
+	fn recover_signer(
+		call: &<T as Config>::Call,
+		nonce: u32,
+		message: &[u8],
+		eth_address: &EthAddress,
+		signature: &EthereumSignature,
+	) -> Option<sp_core::ecdsa::Public> {
+		ecrecover(signature, message, eth_address)
+			.or_else(|| ecrecover_digest(signature, &Self::typed_call_digest(call, nonce, message), eth_address))
+	}
+	/// The EIP-712 digest of `call` at `nonce` with its signed `message`, signed with `eth_signTypedData_v4`
+	pub fn typed_call_digest(call: &<T as Config>::Call, nonce: u32, message: &[u8]) -> [u8; 32] {
+		let spec_version = T::Version::get().spec_version.to_string();
+		// the genesis hash is 32 bytes on CENNZnet, shorter hashes are zero padded
+		let genesis_hash = <frame_system::Pallet<T>>::block_hash(T::BlockNumber::zero());
+		let len = genesis_hash.as_ref().len().min(32);
+		let mut salt = [0_u8; 32];
+		salt[..len].copy_from_slice(&genesis_hash.as_ref()[..len]);
+		let domain = Eip712Domain {
+			name: EIP712_DOMAIN_NAME,
+			version: spec_version.as_bytes(),
+			salt,
+		};
+		let metadata = call.get_call_metadata();
+
+		typed_call_digest(
+			&domain,
+			metadata.pallet_name.as_bytes(),
+			metadata.function_name.as_bytes(),
+			nonce,
+			message,
+		)
+	}
 	/// Return the known CENNZnet nonce for a given Ethereum address
 	pub fn address_nonce(eth_address: &EthAddress) -> u32 {
 		Self::stored_address_nonce(eth_address)
@@ -158,15 +231,21 @@ impl<T: Config> frame_support::unsigned::ValidateUnsigned for Module<T> {
 			call,
 			eth_address,
 			signature,
+			payload,
 		} = call
 		{
 			let address_nonce = Self::stored_address_nonce(eth_address);
-			let message = &(&call, address_nonce).encode()[..];
-			if let Some(_public_key) = ecrecover(&signature, message, &eth_address) {
+			let message = match Self::signed_message(&call, address_nonce, payload) {
+				Some(message) => message,
+				None => return InvalidTransaction::AncientBirthBlock.into(),
+			};
+			if let Some(_public_key) = Self::recover_signer(&call, address_nonce, &message, &eth_address, &signature) {
+				let SignedPayload::V1(era) = payload;
+				let current = <frame_system::Pallet<T>>::block_number().saturated_into::<u64>();
 				return ValidTransaction::with_tag_prefix("EthWallet")
 					.priority(T::UnsignedPriority::get())
 					.and_provides((eth_address, address_nonce))
-					.longevity(64_u64)
+					.longevity(era.death(current).saturating_sub(current))
 					.propagate(true)
 					.build();
 			} else {
@@ -182,19 +261,20 @@ impl<T: Config> frame_support::unsigned::ValidateUnsigned for Module<T> {
 mod tests {
 	use crate as crml_eth_wallet;
 	use crml_eth_wallet::*;
-	use frame_support::{assert_err, assert_ok, parameter_types, storage};
+	use frame_support::{assert_err, assert_ok, parameter_types, storage, unsigned::ValidateUnsigned};
 	use hex_literal::hex;
 	use libsecp256k1 as secp256k1;
 	use sp_core::{ecdsa, keccak_256, Pair};
 	use sp_runtime::{
 		testing::{Header, H256},
 		traits::{BlakeTwo256, IdentifyAccount, IdentityLookup, Verify},
-		transaction_validity::TransactionPriority,
+		transaction_validity::{TransactionPriority, TransactionSource},
 		MultiSignature, MultiSigner,
 	};
 	use std::{convert::TryFrom, marker::PhantomData};
 
 	pub const ECDSA_SEED: [u8; 32] = hex!("7e9c7ad85df5cdc88659f53e06fb2eb9bab3ebc59083a3190eaf2c730332529c");
+	const IMMORTAL: SignedPayload = SignedPayload::V1(Era::Immortal);
 
 	type BlockNumber = u64;
 	type Signature = MultiSignature;
@@ -287,7 +367,12 @@ mod tests {
 	// Simple `eth_sign` implementation, should be equal to exported by RPC
 	fn eth_sign(seed: &[u8; 32], data: &[u8]) -> Vec<u8> {
 		let call_msg = ethereum::signable_message(data);
-		let ecdsa_msg = secp256k1::Message::parse(&keccak_256(&call_msg));
+		sign_digest(seed, &keccak_256(&call_msg))
+	}
+
+	// Sign a 32 byte digest e.g. the EIP-712 digest signed by `eth_signTypedData_v4`
+	fn sign_digest(seed: &[u8; 32], digest: &[u8; 32]) -> Vec<u8> {
+		let ecdsa_msg = secp256k1::Message::parse(digest);
 		let secret = secp256k1::SecretKey::parse(&seed).expect("valid seed");
 		let (signature, recovery_id) = libsecp256k1::sign(&ecdsa_msg, &secret);
 		let mut out = Vec::with_capacity(65);
@@ -314,7 +399,7 @@ mod tests {
 				0: hex!("dd0992d40e5cdf99db76bed162808508ac65acd7ae2fdc8573594f03ed9c939773e813181788fc02c3c68f3fdc592759b35f6354484343e18cb5317d34dab6c61b")
 			};
 			assert_err!(
-				EthWallet::call(Origin::none(), Box::new(call), bob, signature),
+				EthWallet::call(Origin::none(), Box::new(call), bob, signature, IMMORTAL),
 				Error::<Test>::InvalidSignature,
 			);
 		});
@@ -334,12 +419,17 @@ mod tests {
 			let system_nonce = <frame_system::Pallet<Test>>::account_nonce(&cennznet_address);
 			let module_nonce = EthWallet::address_nonce(&eth_address);
 			assert_eq!(system_nonce as u32, module_nonce);
-			let signature =
-				EthereumSignature::try_from(eth_sign(&ECDSA_SEED, (call.clone(), module_nonce).encode().as_ref()))
-					.expect("valid sig");
+			let message = EthWallet::signed_message(&call, module_nonce, &IMMORTAL).expect("genesis is known");
+			let signature = EthereumSignature::try_from(eth_sign(&ECDSA_SEED, &message)).expect("valid sig");
 
 			// execute the call
-			assert_ok!(EthWallet::call(Origin::none(), Box::new(call), eth_address, signature));
+			assert_ok!(EthWallet::call(
+				Origin::none(),
+				Box::new(call),
+				eth_address,
+				signature,
+				IMMORTAL
+			));
 
 			// nonces incremented
 			assert_eq!(EthWallet::address_nonce(&eth_address), module_nonce + 1,);
@@ -352,4 +442,116 @@ mod tests {
 			assert_eq!(storage::unhashed::get(&MOCK_FEE_PAID), Some(1u32),);
 		})
 	}
+
+	#[test]
+	fn unversioned_message_is_rejected() {
+		new_test_ext().execute_with(|| {
+			let eth_address: EthAddress = hex!("420aC537F1a4f78d4Dfb3A71e902be0E3d480AFB").into();
+			let call: Call = frame_system::Call::<Test>::remark {
+				remark: b"hello world".to_vec(),
+			}
+			.into();
+			let signature = EthereumSignature::try_from(eth_sign(&ECDSA_SEED, (call.clone(), 0_u32).encode().as_ref()))
+				.expect("valid sig");
+			assert_err!(
+				EthWallet::call(Origin::none(), Box::new(call), eth_address, signature, IMMORTAL),
+				Error::<Test>::InvalidSignature,
+			);
+		})
+	}
+
+	#[test]
+	fn mortal_call_expires() {
+		new_test_ext().execute_with(|| {
+			let eth_address: EthAddress = hex!("420aC537F1a4f78d4Dfb3A71e902be0E3d480AFB").into();
+			let call: Call = frame_system::Call::<Test>::remark {
+				remark: b"hello world".to_vec(),
+			}
+			.into();
+			System::set_block_number(10);
+			<frame_system::BlockHash<Test>>::insert(10, H256::repeat_byte(10));
+			let payload = SignedPayload::V1(Era::mortal(16, 10));
+			let message = EthWallet::signed_message(&call, 0, &payload).expect("birth block is known");
+			let signature = EthereumSignature::try_from(eth_sign(&ECDSA_SEED, &message)).expect("valid sig");
+			let unsigned_call = crml_eth_wallet::Call::<Test>::call {
+				call: Box::new(call.clone()),
+				eth_address,
+				signature: signature.clone(),
+				payload,
+			};
+
+			// longevity is the remainder of the era
+			System::set_block_number(12);
+			assert_eq!(
+				EthWallet::validate_unsigned(TransactionSource::External, &unsigned_call)
+					.expect("valid")
+					.longevity,
+				14,
+			);
+
+			// the era has passed
+			System::set_block_number(26);
+			assert!(EthWallet::validate_unsigned(TransactionSource::External, &unsigned_call).is_err());
+			assert_err!(
+				EthWallet::call(Origin::none(), Box::new(call), eth_address, signature, payload),
+				Error::<Test>::InvalidSignature,
+			);
+		})
+	}
+
+	#[test]
+	fn typed_data_remark() {
+		new_test_ext().execute_with(|| {
+			let eth_address: EthAddress = hex!("420aC537F1a4f78d4Dfb3A71e902be0E3d480AFB").into();
+			let call: Call = frame_system::Call::<Test>::remark {
+				remark: b"hello world".to_vec(),
+			}
+			.into();
+			let nonce = EthWallet::address_nonce(&eth_address);
+			let message = EthWallet::signed_message(&call, nonce, &IMMORTAL).expect("genesis is known");
+			let digest = EthWallet::typed_call_digest(&call, nonce, &message);
+			let signature = EthereumSignature::try_from(sign_digest(&ECDSA_SEED, &digest)).expect("valid sig");
+
+			let unsigned_call = crml_eth_wallet::Call::<Test>::call {
+				call: Box::new(call.clone()),
+				eth_address,
+				signature: signature.clone(),
+				payload: IMMORTAL,
+			};
+			assert!(EthWallet::validate_unsigned(TransactionSource::External, &unsigned_call).is_ok());
+			assert_ok!(EthWallet::call(
+				Origin::none(),
+				Box::new(call),
+				eth_address,
+				signature,
+				IMMORTAL
+			));
+			assert_eq!(EthWallet::address_nonce(&eth_address), nonce + 1);
+		})
+	}
+
+	#[test]
+	fn signed_message_is_bound_to_genesis() {
+		new_test_ext().execute_with(|| {
+			let eth_address: EthAddress = hex!("420aC537F1a4f78d4Dfb3A71e902be0E3d480AFB").into();
+			let call: Call = frame_system::Call::<Test>::remark {
+				remark: b"hello world".to_vec(),
+			}
+			.into();
+			let message = EthWallet::signed_message(&call, 0, &IMMORTAL).expect("genesis is known");
+			let digest = EthWallet::typed_call_digest(&call, 0, &message);
+			assert_ne!(digest, EthWallet::typed_call_digest(&call, 1, &message));
+
+			// signatures for another chain are rejected
+			<frame_system::BlockHash<Test>>::insert(0, H256::repeat_byte(1));
+			assert_ne!(Some(message.clone()), EthWallet::signed_message(&call, 0, &IMMORTAL));
+			for signature in [sign_digest(&ECDSA_SEED, &digest), eth_sign(&ECDSA_SEED, &message)] {
+				let signature = EthereumSignature::try_from(signature).expect("valid sig");
+				assert_err!(
+					EthWallet::call(Origin::none(), Box::new(call.clone()), eth_address, signature, IMMORTAL),
+					Error::<Test>::InvalidSignature,
+				);
+			}
+		})
+	}
 }
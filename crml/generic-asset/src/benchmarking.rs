@@ -61,6 +61,50 @@ benchmarks! {
 		assert_eq!(GenericAsset::<T>::free_balance(asset_id, &recipient), transfer_amount);
 	}
 
+	// Benchmark `transfer_many` with the worst possible conditions:
+	// Every transfer will create the recipient account.
+	transfer_many {
+		let n in 1 .. MAX_BATCH_TRANSFERS;
+		let caller: T::AccountId = whitelisted_caller();
+
+		// spending asset id
+		let asset_id = GenericAsset::<T>::spending_asset_id();
+		let transfer_amount = T::Balance::from(5_000_000u32);
+		let initial_balance = transfer_amount.mul(T::Balance::from(n));
+		GenericAsset::<T>::set_free_balance(asset_id, &caller, initial_balance);
+
+		let transfers: Vec<(T::AccountId, T::Balance)> = (0..n)
+			.map(|i| (account("recipient", i, SEED), transfer_amount))
+			.collect();
+		let last_recipient = transfers[n as usize - 1].0.clone();
+	}: transfer_many(RawOrigin::Signed(caller.clone()), asset_id, transfers)
+	verify {
+		assert_eq!(GenericAsset::<T>::free_balance(asset_id, &caller), Zero::zero());
+		assert_eq!(GenericAsset::<T>::free_balance(asset_id, &last_recipient), transfer_amount);
+	}
+
+	// Benchmark `transfer_multi_asset` with the worst possible conditions:
+	// Every transfer will create the recipient account for a new asset.
+	transfer_multi_asset {
+		let n in 1 .. MAX_BATCH_TRANSFERS;
+		let caller: T::AccountId = whitelisted_caller();
+		let recipient: T::AccountId = account("recipient", 0, SEED);
+
+		let transfer_amount = T::Balance::from(5_000_000u32);
+		let transfers: Vec<(T::AssetId, T::Balance)> = (0..n)
+			.map(|i| {
+				let asset_id = T::AssetId::from(i + 1);
+				GenericAsset::<T>::set_free_balance(asset_id, &caller, transfer_amount);
+				(asset_id, transfer_amount)
+			})
+			.collect();
+	}: transfer_multi_asset(RawOrigin::Signed(caller.clone()), recipient.clone(), transfers)
+	verify {
+		let asset_id = T::AssetId::from(n);
+		assert_eq!(GenericAsset::<T>::free_balance(asset_id, &caller), Zero::zero());
+		assert_eq!(GenericAsset::<T>::free_balance(asset_id, &recipient), transfer_amount);
+	}
+
 	// Benchmark `burn`, GA's create comes from ROOT account. This always creates an asset.
 	// Mint some amount of new asset to an account and burn the asset from it.
 	burn {
@@ -22,6 +22,7 @@ use crate::{
 };
 use crml_support::{AssetIdAuthority, MultiCurrency};
 use frame_support::{
+	ensure,
 	traits::{ExistenceRequirement, Get, Imbalance, OnUnbalanced, SignedImbalance, WithdrawReasons},
 	PalletId,
 };
@@ -52,6 +53,10 @@ impl<T: Config> MultiCurrency for Module<T> {
 			.unique_saturated_into()
 	}
 
+	fn total_issuance(currency: Self::CurrencyId) -> Self::Balance {
+		<Module<T>>::total_issuance(currency)
+	}
+
 	fn total_balance(who: &T::AccountId, currency: Self::CurrencyId) -> Self::Balance {
 		<Module<T>>::total_balance(currency, who)
 	}
@@ -70,7 +75,13 @@ impl<T: Config> MultiCurrency for Module<T> {
 		}
 
 		let asset_id = currency;
-		let imbalance = Self::make_free_balance_be(who, currency, <Module<T>>::free_balance(asset_id, who) + value);
+		let new_balance = <Module<T>>::free_balance(asset_id, who) + value;
+		// a deposit leaving the account with dust is dropped
+		if new_balance < <Module<T>>::existential_deposit(asset_id) {
+			<Module<T>>::drop_dust_credit(asset_id, who, value);
+			return Self::PositiveImbalance::zero();
+		}
+		let imbalance = Self::make_free_balance_be(who, currency, new_balance);
 		if let SignedImbalance::Positive(p) = imbalance {
 			p
 		} else {
@@ -84,7 +95,13 @@ impl<T: Config> MultiCurrency for Module<T> {
 		currency: Self::CurrencyId,
 		value: Self::Balance,
 	) -> result::Result<Self::PositiveImbalance, DispatchError> {
-		// No existential deposit rule and creation fee in GA. `deposit_into_existing` is same with `deposit_creating`.
+		// No creation fee in GA. `deposit_into_existing` is same with `deposit_creating`, except a deposit which
+		// would be dropped as dust is an error.
+		ensure!(
+			value.is_zero()
+				|| <Module<T>>::free_balance(currency, who) + value >= <Module<T>>::existential_deposit(currency),
+			Error::<T>::ExistentialDeposit
+		);
 		Ok(Self::deposit_creating(who, currency, value))
 	}
 
@@ -132,7 +149,7 @@ impl<T: Config> MultiCurrency for Module<T> {
 		currency: Self::CurrencyId,
 		value: Self::Balance,
 		reasons: WithdrawReasons,
-		_ex: ExistenceRequirement, // no existential deposit policy for generic asset
+		_ex: ExistenceRequirement, // dust is always reclaimed by generic asset
 	) -> result::Result<Self::NegativeImbalance, DispatchError> {
 		if value.is_zero() {
 			return Ok(Self::NegativeImbalance::zero());
@@ -144,7 +161,7 @@ impl<T: Config> MultiCurrency for Module<T> {
 			.ok_or(Error::<T>::InsufficientBalance)?;
 
 		<Module<T>>::ensure_can_withdraw(asset_id, who, value, reasons, new_balance)?;
-		<Module<T>>::set_free_balance(asset_id, who, new_balance);
+		<Module<T>>::set_free_balance_or_reclaim(asset_id, who, new_balance);
 
 		Ok(Self::NegativeImbalance::new(value, asset_id))
 	}
@@ -76,6 +76,8 @@
 //! - `create`: Create a new kind of asset and nominates the owner of this asset. The origin of this call must
 //! be root.
 //! - `transfer`: Transfer some liquid free balance to another account.
+//! - `transfer_many`: Transfer some liquid free balance of one asset to many accounts.
+//! - `transfer_multi_asset`: Transfer some liquid free balance of many assets to another account.
 //! - `update_permission`: Updates permission for a given `asset_id` and an account. The origin of this call
 //! must have update permissions.
 //! - `mint`: Mint an asset, increases its total issuance. The origin of this call must have mint permissions.
@@ -112,11 +114,12 @@ use crml_support::AssetIdAuthority;
 use frame_support::pallet_prelude::*;
 use frame_support::{
 	decl_error, decl_event, decl_module, decl_storage, ensure,
+	storage::StoragePrefixedMap,
 	traits::{
 		BalanceStatus, Currency, ExistenceRequirement, Imbalance, IsType, LockIdentifier, LockableCurrency,
 		OnUnbalanced, ReservableCurrency, SignedImbalance, WithdrawReasons,
 	},
-	IterableStorageMap, Parameter, StorageMap,
+	transactional, Blake2_128Concat, IterableStorageMap, Parameter, ReversibleStorageHasher, StorageMap, Twox64Concat,
 };
 use frame_system::pallet_prelude::*;
 use sp_runtime::{
@@ -126,7 +129,7 @@ use sp_runtime::{
 	},
 	DispatchError, DispatchResult, RuntimeDebug, SaturatedConversion,
 };
-use sp_std::{cmp, fmt::Debug, iter::Sum, prelude::*, result};
+use sp_std::{cmp, collections::btree_map::BTreeMap, fmt::Debug, iter::Sum, prelude::*, result};
 
 mod benchmarking;
 mod imbalances;
@@ -141,6 +144,11 @@ pub use self::imbalances::{NegativeImbalance, OffsetResult, PositiveImbalance};
 pub use types::*;
 use weights::WeightInfo;
 
+/// The maximum number of transfers allowed in a single `transfer_many` or `transfer_multi_asset` call
+pub const MAX_BATCH_TRANSFERS: u32 = 500;
+/// The maximum number of free balances checked for dust per block while sweeping (storage v2 -> v3)
+const DUST_SWEEP_BATCH_SIZE: u32 = 500;
+
 pub trait Config: frame_system::Config {
 	/// The type for asset IDs
 	type AssetId: Parameter + Member + Default + AtLeast32BitUnsigned + Copy;
@@ -193,6 +201,12 @@ decl_error! {
 		DecimalTooLarge,
 		/// The integer for initial issuance is too large for conversion into u128.
 		InitialIssuanceTooLarge,
+		/// A batch transfer must contain at least one transfer.
+		EmptyBatch,
+		/// A batch transfer contains more than `MAX_BATCH_TRANSFERS` transfers.
+		BatchTooLarge,
+		/// The credited account's free balance would be below the existential deposit of the asset.
+		ExistentialDeposit,
 	}
 }
 
@@ -203,41 +217,27 @@ decl_module! {
 		fn deposit_event() = default;
 
 		fn on_runtime_upgrade() -> Weight {
-			if StorageVersion::get() != Releases::V1 as u32 {
-				return Zero::zero();
-			}
-
-			// Update to v2
-			StorageVersion::put(Releases::V2 as u32);
-			// `TokenLocks` migrating from `bool` to `TokenLockReason`
-			#[allow(dead_code)]
-			mod old_storage {
-				use super::{Config, BalanceLockOld};
-				use sp_std::prelude::*;
-				pub struct Module<T>(sp_std::marker::PhantomData<T>);
-				frame_support::decl_storage! {
-					trait Store for Module<T: Config> as GenericAsset {
-						/// Any liquidity locks on some account balances.
-						pub Locks get(fn locks): double_map hasher(twox_64_concat) T::AssetId, hasher(blake2_128_concat) T::AccountId => Vec<BalanceLockOld<T::Balance>>;
-					}
-				}
+			let mut weight: Weight = Zero::zero();
+			if StorageVersion::get() == Releases::V1 as u32 {
+				weight = weight.saturating_add(Self::migrate_locks());
 			}
-
-			// original migration here:
-			// https://github.com/paritytech/substrate/pull/4649/files
-			let locks: Vec<(T::AssetId, T::AccountId, Vec<BalanceLockOld<T::Balance>>)> = old_storage::Locks::<T>::iter().collect();
-			let weight = locks.len() as Weight * 100_000;
-			for (asset_id, address, old_locks) in &locks {
-				Locks::<T>::insert(
-					asset_id,
-					address,
-					old_locks.iter().map(|l| l.clone().upgrade()).collect::<Vec<BalanceLock<T::Balance>>>()
-				);
+			if StorageVersion::get() == Releases::V2 as u32 {
+				// Update to v3, dust is swept over the following blocks
+				StorageVersion::put(Releases::V3 as u32);
+				DustSweepCursor::put(<FreeBalance<T>>::final_prefix().to_vec());
+				weight = weight.saturating_add(T::DbWeight::get().writes(2));
 			}
 
 			weight
 		}
 
+		fn on_initialize(_block_number: T::BlockNumber) -> Weight {
+			if DustSweepCursor::exists() {
+				Self::sweep_dust(DUST_SWEEP_BATCH_SIZE)
+			} else {
+				T::DbWeight::get().reads(1)
+			}
+		}
 		/// Create a new kind of asset and nominates the owner of this asset.
 		/// The asset_id will be the next unoccupied asset_id
 		/// Accounts who will have the permissions to mint/burn/change permission are passed in via 'options'
@@ -288,6 +288,58 @@ decl_module! {
 			Self::make_transfer_with_event(asset_id, &origin, &to, Self::free_balance(asset_id, &origin), ExistenceRequirement::AllowDeath)?;
 		}
 
+		/// Transfer some liquid free balance of `asset_id` to many accounts.
+		///
+		/// The batch is all or nothing, if any transfer fails the whole call is reverted.
+		/// A single `BatchTransferred` event is emitted in place of per transfer events.
+		///
+		/// The dispatch origin for this call must be `Signed` by the transactor.
+		///
+		/// # <weight>
+		/// - O(N) where N is the number of transfers, bounded by `MAX_BATCH_TRANSFERS`.
+		/// # </weight>
+		#[weight = T::WeightInfo::transfer_many(transfers.len() as u32)]
+		#[transactional]
+		pub fn transfer_many(origin, #[compact] asset_id: T::AssetId, transfers: Vec<(T::AccountId, T::Balance)>) {
+			let origin = ensure_signed(origin)?;
+			ensure!(!transfers.is_empty(), Error::<T>::EmptyBatch);
+			ensure!(transfers.len() as u32 <= MAX_BATCH_TRANSFERS, Error::<T>::BatchTooLarge);
+
+			let mut total_amount = T::Balance::zero();
+			for (to, amount) in transfers.iter() {
+				ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+				Self::make_transfer(asset_id, &origin, to, *amount, ExistenceRequirement::AllowDeath)?;
+				total_amount = total_amount.checked_add(amount).ok_or(Error::<T>::TransferOverflow)?;
+			}
+
+			Self::deposit_event(Event::<T>::BatchTransferred(asset_id, origin, transfers.len() as u32, total_amount));
+		}
+
+		/// Transfer some liquid free balance of many assets to another account.
+		///
+		/// The batch is all or nothing, if any transfer fails the whole call is reverted.
+		/// A single `MultiAssetTransferred` event is emitted in place of per transfer events.
+		///
+		/// The dispatch origin for this call must be `Signed` by the transactor.
+		///
+		/// # <weight>
+		/// - O(N) where N is the number of assets, bounded by `MAX_BATCH_TRANSFERS`.
+		/// # </weight>
+		#[weight = T::WeightInfo::transfer_multi_asset(transfers.len() as u32)]
+		#[transactional]
+		pub fn transfer_multi_asset(origin, to: T::AccountId, transfers: Vec<(T::AssetId, T::Balance)>) {
+			let origin = ensure_signed(origin)?;
+			ensure!(!transfers.is_empty(), Error::<T>::EmptyBatch);
+			ensure!(transfers.len() as u32 <= MAX_BATCH_TRANSFERS, Error::<T>::BatchTooLarge);
+
+			for (asset_id, amount) in transfers.iter() {
+				ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+				Self::make_transfer(*asset_id, &origin, &to, *amount, ExistenceRequirement::AllowDeath)?;
+			}
+
+			Self::deposit_event(Event::<T>::MultiAssetTransferred(origin, to, transfers.len() as u32));
+		}
+
 		/// Updates permissions(mint/burn/change permission) for a given `asset_id` and an account.
 		///
 		/// The `origin` must have `update` permission.
@@ -397,6 +449,8 @@ enum Releases {
 	V1 = 1,
 	/// Storage version as of runtime version 47
 	V2 = 2,
+	/// Storage version as of runtime version 48
+	V3 = 3,
 }
 
 impl Default for Releases {
@@ -456,6 +510,10 @@ decl_storage! {
 		///
 		/// This is set to v1 for new networks.
 		StorageVersion build(|_: &GenesisConfig<T>| Releases::V1 as u32): u32;
+
+		/// Raw key of the last free balance checked while sweeping sub-existential dust (storage v2 -> v3)
+		/// Unset once every free balance has been checked.
+		DustSweepCursor: Option<Vec<u8>>;
 	}
 	add_extra_genesis {
 		config(assets): Vec<T::AssetId>;
@@ -487,6 +545,10 @@ decl_event! {
 		Created(AssetId, AccountId, AssetOptions),
 		/// Asset transfer succeeded (asset_id, from, to, amount).
 		Transferred(AssetId, AccountId, AccountId, Balance),
+		/// Asset transferred to many accounts (asset_id, from, number of transfers, total amount).
+		BatchTransferred(AssetId, AccountId, u32, Balance),
+		/// Many assets transferred to an account (from, to, number of assets).
+		MultiAssetTransferred(AccountId, AccountId, u32),
 		/// Asset permission updated (asset_id, new_permissions).
 		PermissionUpdated(AssetId, PermissionLatest<AccountId>),
 		/// Asset info updated (asset_id, asset_info).
@@ -495,12 +557,98 @@ decl_event! {
 		Minted(AssetId, AccountId, Balance),
 		/// Asset burned (asset_id, account, amount).
 		Burned(AssetId, AccountId, Balance),
-		/// Asset balance storage has been reclaimed due to falling below the existential deposit
-		DustReclaimed(AssetId, AccountId, Balance),
+		/// Free balance below the existential deposit was removed and passed to the dust handler, or a credit which
+		/// would have left the account with less than the existential deposit was dropped (asset_id, account, amount).
+		DustLost(AssetId, AccountId, Balance),
+		/// An account no longer holds any balance of an asset, its storage has been reaped (asset_id, account).
+		AccountReaped(AssetId, AccountId),
 	}
 }
 
 impl<T: Config> Module<T> {
+	/// Migrate `Locks` from `BalanceLockOld` to `BalanceLock` (storage v1 -> v2)
+	fn migrate_locks() -> Weight {
+		// Update to v2
+		StorageVersion::put(Releases::V2 as u32);
+		// `TokenLocks` migrating from `bool` to `TokenLockReason`
+		#[allow(dead_code)]
+		mod old_storage {
+			use super::{BalanceLockOld, Config};
+			use sp_std::prelude::*;
+			pub struct Module<T>(sp_std::marker::PhantomData<T>);
+			frame_support::decl_storage! {
+				trait Store for Module<T: Config> as GenericAsset {
+					/// Any liquidity locks on some account balances.
+					pub Locks get(fn locks): double_map hasher(twox_64_concat) T::AssetId, hasher(blake2_128_concat) T::AccountId => Vec<BalanceLockOld<T::Balance>>;
+				}
+			}
+		}
+
+		// original migration here:
+		// https://github.com/paritytech/substrate/pull/4649/files
+		let locks: Vec<(T::AssetId, T::AccountId, Vec<BalanceLockOld<T::Balance>>)> =
+			old_storage::Locks::<T>::iter().collect();
+		let weight = locks.len() as Weight * 100_000;
+		for (asset_id, address, old_locks) in &locks {
+			Locks::<T>::insert(
+				asset_id,
+				address,
+				old_locks
+					.iter()
+					.map(|l| l.clone().upgrade())
+					.collect::<Vec<BalanceLock<T::Balance>>>(),
+			);
+		}
+
+		weight
+	}
+
+	/// Sweep sub-existential free balances left from before storage v3, checking at most `limit` balances
+	/// Dust is passed to the dust imbalance handler and zero balance entries are removed.
+	/// The sweep continues from `DustSweepCursor` and clears it once all free balances have been checked.
+	fn sweep_dust(limit: u32) -> Weight {
+		let mut cursor = match DustSweepCursor::get() {
+			Some(cursor) => cursor,
+			None => return T::DbWeight::get().reads(1),
+		};
+		let prefix = <FreeBalance<T>>::final_prefix();
+		let mut existential_deposits = BTreeMap::<T::AssetId, T::Balance>::new();
+		let mut reads: Weight = 1;
+		let mut writes: Weight = 1;
+
+		for _ in 0..limit {
+			let key = match sp_io::storage::next_key(&cursor).filter(|key| key.starts_with(&prefix)) {
+				Some(key) => key,
+				None => {
+					DustSweepCursor::kill();
+					return T::DbWeight::get().reads_writes(reads + existential_deposits.len() as Weight, writes);
+				}
+			};
+			reads += 1;
+			if let Some((asset_id, who)) = Self::decode_free_balance_key(&key[prefix.len()..]) {
+				let existential_deposit = *existential_deposits
+					.entry(asset_id)
+					.or_insert_with(|| Self::existential_deposit(asset_id));
+				if Self::free_balance(asset_id, &who) < existential_deposit {
+					Self::reclaim_free_balance(asset_id, &who);
+					writes += 2;
+				}
+			}
+			cursor = key;
+		}
+
+		DustSweepCursor::put(cursor);
+		T::DbWeight::get().reads_writes(reads + existential_deposits.len() as Weight, writes)
+	}
+
+	/// Decode the (asset Id, account) of a `FreeBalance` storage key, without the storage prefix
+	fn decode_free_balance_key(key: &[u8]) -> Option<(T::AssetId, T::AccountId)> {
+		let mut key = Twox64Concat::reverse(key);
+		let asset_id = T::AssetId::decode(&mut key).ok()?;
+		let who = T::AccountId::decode(&mut Blake2_128Concat::reverse(key)).ok()?;
+		Some((asset_id, who))
+	}
+
 	/// Get an account's total balance of an asset kind.
 	pub fn total_balance(asset_id: T::AssetId, who: &T::AccountId) -> T::Balance {
 		Self::free_balance(asset_id, who) + Self::reserved_balance(asset_id, who)
@@ -532,6 +680,10 @@ impl<T: Config> Module<T> {
 			let value = original_free_balance
 				.checked_add(&amount)
 				.ok_or(Error::<T>::FreeMintingOverflow)?;
+			ensure!(
+				value >= Self::existential_deposit(asset_id),
+				Error::<T>::ExistentialDeposit
+			);
 
 			<TotalIssuance<T>>::insert(asset_id, new_total_issuance);
 			Self::set_free_balance(asset_id, &to, value);
@@ -560,7 +712,7 @@ impl<T: Config> Module<T> {
 				.ok_or(Error::<T>::FreeBurningUnderflow)?;
 
 			<TotalIssuance<T>>::insert(asset_id, new_total_issuance);
-			Self::set_free_balance(asset_id, &to, value);
+			Self::set_free_balance_or_reclaim(asset_id, &to, value);
 			Ok(())
 		} else {
 			Err(Error::<T>::NoBurnPermission)?
@@ -641,14 +793,13 @@ impl<T: Config> Module<T> {
 		if from == to {
 			return Ok(());
 		}
+		ensure!(
+			new_to_balance >= Self::existential_deposit(asset_id),
+			Error::<T>::ExistentialDeposit
+		);
 
 		Self::set_free_balance(asset_id, to, new_to_balance);
-		Self::set_free_balance(asset_id, from, new_from_balance);
-
-		let existential_deposit = Self::asset_meta(asset_id).existential_deposit();
-		if new_from_balance < existential_deposit.saturated_into() {
-			Self::reclaim_free_balance(asset_id, from);
-		}
+		Self::set_free_balance_or_reclaim(asset_id, from, new_from_balance);
 
 		Ok(())
 	}
@@ -686,15 +837,9 @@ impl<T: Config> Module<T> {
 		let new_reserve_balance = original_reserve_balance + amount;
 		let new_free_balance = original_free_balance - amount;
 
-		// `free` balance should be freed if set to a dust amount
-		let existential_deposit = Self::asset_meta(asset_id).existential_deposit();
-		if new_free_balance < existential_deposit.saturated_into() {
-			Self::reclaim_free_balance(asset_id, who);
-		} else {
-			<FreeBalance<T>>::insert(asset_id, who, &new_free_balance);
-		}
-
 		<ReservedBalance<T>>::insert(asset_id, who, &new_reserve_balance);
+		// `free` balance should be freed if set to a dust amount
+		Self::set_free_balance_or_reclaim(asset_id, who, new_free_balance);
 
 		Ok(())
 	}
@@ -711,8 +856,8 @@ impl<T: Config> Module<T> {
 		let new_free_balance = original_free_balance + actual;
 		let new_reserved_balance = b - actual;
 
+		Self::set_free_balance(asset_id, who, new_free_balance);
 		Self::set_reserved_balance(asset_id, who, new_reserved_balance);
-		<FreeBalance<T>>::insert(asset_id, &who, new_free_balance);
 
 		amount - actual
 	}
@@ -728,7 +873,7 @@ impl<T: Config> Module<T> {
 		let free_balance = Self::free_balance(asset_id, who);
 		let free_slash = cmp::min(free_balance, amount);
 		let new_free_balance = free_balance - free_slash;
-		Self::set_free_balance(asset_id, who, new_free_balance);
+		Self::set_free_balance_or_reclaim(asset_id, who, new_free_balance);
 		if free_slash < amount {
 			Self::slash_reserved(asset_id, who, amount - free_slash)
 		} else {
@@ -876,15 +1021,38 @@ impl<T: Config> Module<T> {
 		}
 	}
 
+	/// Return the existential deposit of an asset
+	pub fn existential_deposit(asset_id: T::AssetId) -> T::Balance {
+		Self::asset_meta(asset_id).existential_deposit().saturated_into()
+	}
+
 	/// Reclaim asset storage items for an account
 	/// Any dust imbalance from free balance is passed to a dust imbalance handler.
 	fn reclaim_free_balance(asset_id: T::AssetId, who: &T::AccountId) {
+		if !<FreeBalance<T>>::contains_key(asset_id, who) {
+			return;
+		}
+
 		let amount = <FreeBalance<T>>::take(asset_id, who);
 		if amount > Zero::zero() {
 			T::OnDustImbalance::on_nonzero_unbalanced(NegativeImbalance::new(amount, asset_id));
+			Self::deposit_event(Event::<T>::DustLost(asset_id, who.clone(), amount));
 		}
 
-		Self::deposit_event(Event::<T>::DustReclaimed(asset_id, who.clone(), amount));
+		Self::maybe_reap_account(asset_id, who);
+	}
+
+	/// Notify that a credit of `amount` to `who` was dropped, it would have left the account with less than the
+	/// existential deposit of `asset_id`
+	fn drop_dust_credit(asset_id: T::AssetId, who: &T::AccountId, amount: T::Balance) {
+		Self::deposit_event(Event::<T>::DustLost(asset_id, who.clone(), amount));
+	}
+
+	/// Notify that `who` holds no more balance of `asset_id` once both balances have been freed
+	fn maybe_reap_account(asset_id: T::AssetId, who: &T::AccountId) {
+		if !<FreeBalance<T>>::contains_key(asset_id, who) && !<ReservedBalance<T>>::contains_key(asset_id, who) {
+			Self::deposit_event(Event::<T>::AccountReaped(asset_id, who.clone()));
+		}
 	}
 
 	/// NOTE: LOW-LEVEL: This will not attempt to maintain total issuance. It is expected that
@@ -892,15 +1060,40 @@ impl<T: Config> Module<T> {
 	fn set_reserved_balance(asset_id: T::AssetId, who: &T::AccountId, reserved: T::Balance) {
 		if reserved.is_zero() {
 			// `who` account's reserve balance storage should be freed
-			<ReservedBalance<T>>::remove(asset_id, who);
+			if <ReservedBalance<T>>::contains_key(asset_id, who) {
+				<ReservedBalance<T>>::remove(asset_id, who);
+				Self::maybe_reap_account(asset_id, who);
+			}
 		} else {
 			<ReservedBalance<T>>::insert(asset_id, who, &reserved);
 		}
 	}
 
+	/// Set the free balance of an account, reclaiming it if the balance falls below the
+	/// existential deposit of `asset_id`.
+	/// NOTE: LOW-LEVEL: This will not attempt to maintain total issuance. It is expected that
+	/// the caller will do this.
+	fn set_free_balance_or_reclaim(asset_id: T::AssetId, who: &T::AccountId, free: T::Balance) {
+		if !free.is_zero() && free < Self::existential_deposit(asset_id) {
+			<FreeBalance<T>>::insert(asset_id, who, &free);
+			Self::reclaim_free_balance(asset_id, who);
+		} else {
+			Self::set_free_balance(asset_id, who, free);
+		}
+	}
+
 	/// NOTE: LOW-LEVEL: This will not attempt to maintain total issuance. It is expected that
 	/// the caller will do this.
 	fn set_free_balance(asset_id: T::AssetId, who: &T::AccountId, free: T::Balance) {
+		if free.is_zero() {
+			// `who` account's free balance storage should be freed
+			if <FreeBalance<T>>::contains_key(asset_id, who) {
+				<FreeBalance<T>>::remove(asset_id, who);
+				Self::maybe_reap_account(asset_id, who);
+			}
+			return;
+		}
+
 		<FreeBalance<T>>::mutate(asset_id, who, |balance| {
 			// Tell the system module we are "providing" the account
 			// This is only done so that FRAME pallets from substrate think
@@ -1003,9 +1196,7 @@ where
 	}
 
 	fn minimum_balance() -> Self::Balance {
-		AssetMeta::<T>::get(U::asset_id())
-			.existential_deposit()
-			.unique_saturated_into()
+		<Module<T>>::existential_deposit(U::asset_id())
 	}
 
 	fn transfer(
@@ -1038,15 +1229,7 @@ where
 		Self::ensure_can_withdraw(who, value, reasons, new_balance)?;
 
 		// `free` balance should be freed if set to a dust amount
-		if new_balance < Self::minimum_balance() {
-			let amount = <FreeBalance<T>>::take(U::asset_id(), who);
-			if amount > Zero::zero() {
-				T::OnDustImbalance::on_nonzero_unbalanced(NegativeImbalance::new(amount, U::asset_id()));
-			}
-			<Module<T>>::deposit_event(Event::<T>::DustReclaimed(U::asset_id(), who.clone(), amount));
-		} else {
-			<Module<T>>::set_free_balance(U::asset_id(), who, new_balance);
-		}
+		<Module<T>>::set_free_balance_or_reclaim(U::asset_id(), who, new_balance);
 
 		Ok(NegativeImbalance::new(value, U::asset_id()))
 	}
@@ -1055,15 +1238,24 @@ where
 		who: &T::AccountId,
 		value: Self::Balance,
 	) -> result::Result<Self::PositiveImbalance, DispatchError> {
-		if <FreeBalance<T>>::contains_key(U::asset_id(), who) {
-			Ok(Self::deposit_creating(who, value))
-		} else {
+		if !<FreeBalance<T>>::contains_key(U::asset_id(), who) {
 			Err(Error::<T>::AccountIdNotExist)?
 		}
+		ensure!(
+			Self::free_balance(who) + value >= <Module<T>>::existential_deposit(U::asset_id()),
+			Error::<T>::ExistentialDeposit
+		);
+		Ok(Self::deposit_creating(who, value))
 	}
 
 	fn deposit_creating(who: &T::AccountId, value: Self::Balance) -> Self::PositiveImbalance {
-		let imbalance = Self::make_free_balance_be(who, Self::free_balance(who) + value);
+		let new_balance = Self::free_balance(who) + value;
+		// a deposit leaving the account with dust is dropped
+		if new_balance < <Module<T>>::existential_deposit(U::asset_id()) {
+			<Module<T>>::drop_dust_credit(U::asset_id(), who, value);
+			return Self::PositiveImbalance::zero();
+		}
+		let imbalance = Self::make_free_balance_be(who, new_balance);
 		if let SignedImbalance::Positive(p) = imbalance {
 			p
 		} else {
@@ -27,9 +27,10 @@ use crate::mock::{
 	System, Test, TreasuryPalletId, ALICE, ASSET_ID, BOB, CHARLIE, ID_1, ID_2, INITIAL_BALANCE, INITIAL_ISSUANCE,
 	SPENDING_ASSET_ID, STAKING_ASSET_ID, TEST1_ASSET_ID, TEST2_ASSET_ID,
 };
+use crml_support::MultiCurrency;
 use frame_support::{
 	assert_noop, assert_ok,
-	traits::{Imbalance, OnRuntimeUpgrade},
+	traits::{Imbalance, OnInitialize, OnRuntimeUpgrade},
 };
 use sp_runtime::traits::AccountIdConversion;
 
@@ -475,14 +476,9 @@ fn on_runtime_upgrade() {
 			asset_options(PermissionLatest::new(BOB), asset_info_2.decimal_places()),
 			asset_info_2.clone()
 		));
-		// Transfer Alice some dust (asset 1)
+		// Give Alice some dust (asset 1)
 		// It should be freed during the migration
-		assert_ok!(GenericAsset::transfer(
-			Origin::signed(BOB),
-			ASSET_ID,
-			ALICE,
-			asset_info_1.existential_deposit() - 1,
-		));
+		<FreeBalance<Test>>::insert(ASSET_ID, &ALICE, asset_info_1.existential_deposit() - 1);
 		// Reduce BOB's (asset 1) balance below ED
 		// It should be freed during the migration
 		GenericAsset::set_free_balance(ASSET_ID, &BOB, asset_info_1.existential_deposit() - 1);
@@ -493,6 +489,7 @@ fn on_runtime_upgrade() {
 
 		// On runtime upgrade should be able to fix the account store
 		let _ = GenericAsset::on_runtime_upgrade();
+		let _ = GenericAsset::on_initialize(1);
 
 		// Test accounts are restored now
 		assert!(System::account_exists(&ALICE));
@@ -508,6 +505,210 @@ fn on_runtime_upgrade() {
 	});
 }
 
+#[test]
+fn dust_lost_and_account_reaped_events_on_transfer() {
+	new_test_ext_with_default().execute_with(|| {
+		System::set_block_number(1);
+		let asset_info = AssetInfo::new(b"TST1".to_vec(), 1, 5);
+		assert_ok!(GenericAsset::create(
+			Origin::root(),
+			BOB,
+			asset_options(PermissionLatest::new(BOB), asset_info.decimal_places()),
+			asset_info.clone()
+		));
+
+		// Leave BOB with 1 below ED
+		let dust = asset_info.existential_deposit() - 1;
+		assert_ok!(GenericAsset::transfer(
+			Origin::signed(BOB),
+			ASSET_ID,
+			ALICE,
+			INITIAL_ISSUANCE - dust
+		));
+
+		assert!(!<FreeBalance<Test>>::contains_key(ASSET_ID, &BOB));
+		let expected_events = vec![
+			TestEvent::GenericAsset(RawEvent::DustLost(ASSET_ID, BOB, dust)),
+			TestEvent::GenericAsset(RawEvent::AccountReaped(ASSET_ID, BOB)),
+		];
+		for expected_event in expected_events {
+			assert!(System::events().iter().any(|record| record.event == expected_event));
+		}
+	});
+}
+
+#[test]
+fn account_with_reserved_balance_is_not_reaped() {
+	new_test_ext_with_default().execute_with(|| {
+		System::set_block_number(1);
+		let asset_info = AssetInfo::new(b"TST1".to_vec(), 1, 5);
+		assert_ok!(GenericAsset::create(
+			Origin::root(),
+			BOB,
+			asset_options(PermissionLatest::new(BOB), asset_info.decimal_places()),
+			asset_info.clone()
+		));
+
+		// Reserve all but dust, the dust goes to the treasury
+		let dust = asset_info.existential_deposit() - 1;
+		assert_ok!(GenericAsset::reserve(ASSET_ID, &BOB, INITIAL_ISSUANCE - dust));
+		assert_eq!(GenericAsset::free_balance(ASSET_ID, &BOB), 0);
+		assert_eq!(GenericAsset::reserved_balance(ASSET_ID, &BOB), INITIAL_ISSUANCE - dust);
+		let treasury_account_id = TreasuryPalletId::get().into_account();
+		assert_eq!(GenericAsset::free_balance(ASSET_ID, &treasury_account_id), dust);
+		assert_eq!(GenericAsset::total_issuance(ASSET_ID), INITIAL_ISSUANCE);
+
+		let expected_event = TestEvent::GenericAsset(RawEvent::DustLost(ASSET_ID, BOB, dust));
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+		let reaped_event = TestEvent::GenericAsset(RawEvent::AccountReaped(ASSET_ID, BOB));
+		assert!(!System::events().iter().any(|record| record.event == reaped_event));
+
+		// Once the reserve is freed the account is reaped
+		let _ = GenericAsset::slash_reserved(ASSET_ID, &BOB, INITIAL_ISSUANCE - dust);
+		assert!(System::events().iter().any(|record| record.event == reaped_event));
+	});
+}
+
+#[test]
+fn zero_balances_are_not_stored() {
+	new_test_ext_with_balance(STAKING_ASSET_ID, ALICE, INITIAL_BALANCE).execute_with(|| {
+		assert_ok!(GenericAsset::transfer(
+			Origin::signed(ALICE),
+			STAKING_ASSET_ID,
+			BOB,
+			INITIAL_BALANCE
+		));
+		assert!(!<FreeBalance<Test>>::contains_key(STAKING_ASSET_ID, &ALICE));
+
+		GenericAsset::set_free_balance(STAKING_ASSET_ID, &CHARLIE, 0);
+		assert!(!<FreeBalance<Test>>::contains_key(STAKING_ASSET_ID, &CHARLIE));
+	});
+}
+
+#[test]
+fn sweep_dust_on_runtime_upgrade() {
+	new_test_ext_with_balance(STAKING_ASSET_ID, ALICE, INITIAL_BALANCE).execute_with(|| {
+		System::set_block_number(1);
+		StorageVersion::put(Releases::V2 as u32);
+
+		// TEST1_ASSET_ID has an ED of 3
+		<FreeBalance<Test>>::insert(TEST1_ASSET_ID, &ALICE, 2);
+		<FreeBalance<Test>>::insert(TEST1_ASSET_ID, &BOB, 3);
+		<FreeBalance<Test>>::insert(STAKING_ASSET_ID, &CHARLIE, 0);
+		<TotalIssuance<Test>>::insert(TEST1_ASSET_ID, 5);
+
+		let _ = GenericAsset::on_runtime_upgrade();
+		assert_eq!(StorageVersion::get(), Releases::V3 as u32);
+		// dust is swept in the following blocks
+		assert!(<FreeBalance<Test>>::contains_key(TEST1_ASSET_ID, &ALICE));
+		let _ = GenericAsset::on_initialize(1);
+
+		assert!(!DustSweepCursor::exists());
+		assert!(!<FreeBalance<Test>>::contains_key(TEST1_ASSET_ID, &ALICE));
+		assert!(!<FreeBalance<Test>>::contains_key(STAKING_ASSET_ID, &CHARLIE));
+		assert_eq!(GenericAsset::free_balance(TEST1_ASSET_ID, &BOB), 3);
+		assert_eq!(GenericAsset::free_balance(STAKING_ASSET_ID, &ALICE), INITIAL_BALANCE);
+
+		let treasury_account_id = TreasuryPalletId::get().into_account();
+		assert_eq!(GenericAsset::free_balance(TEST1_ASSET_ID, &treasury_account_id), 2);
+		assert_eq!(GenericAsset::total_issuance(TEST1_ASSET_ID), 5);
+
+		let expected_events = vec![
+			TestEvent::GenericAsset(RawEvent::DustLost(TEST1_ASSET_ID, ALICE, 2)),
+			TestEvent::GenericAsset(RawEvent::AccountReaped(TEST1_ASSET_ID, ALICE)),
+			TestEvent::GenericAsset(RawEvent::AccountReaped(STAKING_ASSET_ID, CHARLIE)),
+		];
+		for expected_event in expected_events {
+			assert!(System::events().iter().any(|record| record.event == expected_event));
+		}
+	});
+}
+
+#[test]
+fn sweep_dust_over_many_blocks() {
+	new_test_ext_with_balance(STAKING_ASSET_ID, ALICE, INITIAL_BALANCE).execute_with(|| {
+		StorageVersion::put(Releases::V2 as u32);
+
+		// TEST1_ASSET_ID has an ED of 3
+		for who in 10..15 {
+			<FreeBalance<Test>>::insert(TEST1_ASSET_ID, &who, 2);
+		}
+		let _ = GenericAsset::on_runtime_upgrade();
+
+		// balances are checked 2 at a time
+		let _ = GenericAsset::sweep_dust(2);
+		assert!(DustSweepCursor::exists());
+		assert!((10..15).any(|who| <FreeBalance<Test>>::contains_key(TEST1_ASSET_ID, &who)));
+
+		for _ in 0..10 {
+			let _ = GenericAsset::sweep_dust(2);
+		}
+		assert!(!DustSweepCursor::exists());
+		assert!((10..15).all(|who| !<FreeBalance<Test>>::contains_key(TEST1_ASSET_ID, &who)));
+		assert_eq!(GenericAsset::free_balance(STAKING_ASSET_ID, &ALICE), INITIAL_BALANCE);
+	});
+}
+
+#[test]
+fn credit_below_existential_deposit_fails() {
+	new_test_ext_with_balance(STAKING_ASSET_ID, ALICE, INITIAL_BALANCE).execute_with(|| {
+		// TEST1_ASSET_ID has an ED of 3
+		GenericAsset::set_free_balance(TEST1_ASSET_ID, &ALICE, INITIAL_BALANCE);
+		assert_noop!(
+			GenericAsset::transfer(Origin::signed(ALICE), TEST1_ASSET_ID, BOB, 2),
+			Error::<Test>::ExistentialDeposit
+		);
+		assert_noop!(
+			GenericAsset::transfer_many(Origin::signed(ALICE), TEST1_ASSET_ID, vec![(BOB, 3), (CHARLIE, 2)]),
+			Error::<Test>::ExistentialDeposit
+		);
+		assert_ok!(GenericAsset::transfer(Origin::signed(ALICE), TEST1_ASSET_ID, BOB, 3));
+		// an existing account may receive less than the ED
+		assert_ok!(GenericAsset::transfer(Origin::signed(ALICE), TEST1_ASSET_ID, BOB, 1));
+		assert_eq!(GenericAsset::free_balance(TEST1_ASSET_ID, &BOB), 4);
+	});
+}
+
+#[test]
+fn mint_below_existential_deposit_fails() {
+	new_test_ext_with_balance(STAKING_ASSET_ID, ALICE, INITIAL_BALANCE).execute_with(|| {
+		let asset_info = AssetInfo::new(b"TST".to_vec(), 1, 5);
+		assert_ok!(GenericAsset::create(
+			Origin::root(),
+			ALICE,
+			asset_options(PermissionLatest::new(ALICE), asset_info.decimal_places()),
+			asset_info
+		));
+		assert_noop!(
+			GenericAsset::mint(Origin::signed(ALICE), ASSET_ID, BOB, 4),
+			Error::<Test>::ExistentialDeposit
+		);
+		assert_ok!(GenericAsset::mint(Origin::signed(ALICE), ASSET_ID, BOB, 5));
+		assert_eq!(GenericAsset::free_balance(ASSET_ID, &BOB), 5);
+	});
+}
+
+#[test]
+fn deposit_below_existential_deposit_is_dropped() {
+	new_test_ext_with_balance(STAKING_ASSET_ID, ALICE, INITIAL_BALANCE).execute_with(|| {
+		System::set_block_number(1);
+		// TEST1_ASSET_ID has an ED of 3
+		assert_eq!(
+			<GenericAsset as MultiCurrency>::deposit_into_existing(&BOB, TEST1_ASSET_ID, 2).map(|i| i.peek()),
+			Err(Error::<Test>::ExistentialDeposit.into())
+		);
+		let imbalance = <GenericAsset as MultiCurrency>::deposit_creating(&BOB, TEST1_ASSET_ID, 2);
+		assert_eq!(imbalance.peek(), 0);
+		assert!(!<FreeBalance<Test>>::contains_key(TEST1_ASSET_ID, &BOB));
+		let expected_event = TestEvent::GenericAsset(RawEvent::DustLost(TEST1_ASSET_ID, BOB, 2));
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+
+		let imbalance = <GenericAsset as MultiCurrency>::deposit_creating(&BOB, TEST1_ASSET_ID, 3);
+		assert_eq!(imbalance.peek(), 3);
+		assert_eq!(GenericAsset::free_balance(TEST1_ASSET_ID, &BOB), 3);
+	});
+}
+
 #[test]
 fn migrate_locks_on_runtime_upgrade() {
 	new_test_ext_with_balance(STAKING_ASSET_ID, ALICE, INITIAL_BALANCE).execute_with(|| {
@@ -555,7 +756,7 @@ fn migrate_locks_on_runtime_upgrade() {
 		assert_eq!(<Locks<Test>>::iter().count(), 2);
 		assert_eq!(<Locks<Test>>::get(STAKING_ASSET_ID, ALICE), alice_locks.iter().map(|l| l.clone().upgrade()).collect::<Vec<BalanceLock<u64>>>());
 		assert_eq!(<Locks<Test>>::get(STAKING_ASSET_ID, BOB), bob_locks.iter().map(|l| l.clone().upgrade()).collect::<Vec<BalanceLock<u64>>>());
-		assert_eq!(StorageVersion::get(), Releases::V2 as u32);
+		assert_eq!(StorageVersion::get(), Releases::V3 as u32);
 
 	});
 }
@@ -1843,3 +2044,107 @@ fn non_owner_permitted_update_asset_info() {
 		assert_eq!(<AssetMeta<Test>>::get(ASSET_ID), web3_asset_info);
 	});
 }
+
+#[test]
+fn transfer_many_should_work() {
+	new_test_ext_with_balance(STAKING_ASSET_ID, ALICE, INITIAL_BALANCE).execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!(GenericAsset::transfer_many(
+			Origin::signed(ALICE),
+			STAKING_ASSET_ID,
+			vec![(BOB, 30), (CHARLIE, 20)],
+		));
+
+		assert_eq!(
+			GenericAsset::free_balance(STAKING_ASSET_ID, &ALICE),
+			INITIAL_BALANCE - 50
+		);
+		assert_eq!(GenericAsset::free_balance(STAKING_ASSET_ID, &BOB), 30);
+		assert_eq!(GenericAsset::free_balance(STAKING_ASSET_ID, &CHARLIE), 20);
+
+		let expected_event = TestEvent::GenericAsset(RawEvent::BatchTransferred(STAKING_ASSET_ID, ALICE, 2, 50));
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+	});
+}
+
+#[test]
+fn transfer_many_is_all_or_nothing() {
+	new_test_ext_with_balance(STAKING_ASSET_ID, ALICE, INITIAL_BALANCE).execute_with(|| {
+		// the second transfer exceeds Alice's remaining balance
+		assert_noop!(
+			GenericAsset::transfer_many(Origin::signed(ALICE), STAKING_ASSET_ID, vec![(BOB, 60), (CHARLIE, 60)],),
+			Error::<Test>::InsufficientBalance,
+		);
+		assert_noop!(
+			GenericAsset::transfer_many(Origin::signed(ALICE), STAKING_ASSET_ID, vec![(BOB, 60), (CHARLIE, 0)]),
+			Error::<Test>::ZeroAmount,
+		);
+	});
+}
+
+#[test]
+fn transfer_many_with_invalid_batch_size_should_fail() {
+	new_test_ext_with_balance(STAKING_ASSET_ID, ALICE, INITIAL_BALANCE).execute_with(|| {
+		assert_noop!(
+			GenericAsset::transfer_many(Origin::signed(ALICE), STAKING_ASSET_ID, vec![]),
+			Error::<Test>::EmptyBatch,
+		);
+		assert_noop!(
+			GenericAsset::transfer_many(
+				Origin::signed(ALICE),
+				STAKING_ASSET_ID,
+				vec![(BOB, 1); MAX_BATCH_TRANSFERS as usize + 1],
+			),
+			Error::<Test>::BatchTooLarge,
+		);
+	});
+}
+
+#[test]
+fn transfer_multi_asset_should_work() {
+	new_test_ext_with_balance(STAKING_ASSET_ID, ALICE, INITIAL_BALANCE).execute_with(|| {
+		System::set_block_number(1);
+		GenericAsset::set_free_balance(SPENDING_ASSET_ID, &ALICE, INITIAL_BALANCE);
+
+		let transfers = vec![(STAKING_ASSET_ID, 40), (SPENDING_ASSET_ID, 60)];
+		assert_ok!(GenericAsset::transfer_multi_asset(
+			Origin::signed(ALICE),
+			BOB,
+			transfers,
+		));
+
+		assert_eq!(
+			GenericAsset::free_balance(STAKING_ASSET_ID, &ALICE),
+			INITIAL_BALANCE - 40
+		);
+		assert_eq!(
+			GenericAsset::free_balance(SPENDING_ASSET_ID, &ALICE),
+			INITIAL_BALANCE - 60
+		);
+		assert_eq!(GenericAsset::free_balance(STAKING_ASSET_ID, &BOB), 40);
+		assert_eq!(GenericAsset::free_balance(SPENDING_ASSET_ID, &BOB), 60);
+
+		let expected_event = TestEvent::GenericAsset(RawEvent::MultiAssetTransferred(ALICE, BOB, 2));
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+	});
+}
+
+#[test]
+fn transfer_multi_asset_is_all_or_nothing() {
+	new_test_ext_with_balance(STAKING_ASSET_ID, ALICE, INITIAL_BALANCE).execute_with(|| {
+		// Alice has no spending asset balance
+		assert_noop!(
+			GenericAsset::transfer_multi_asset(
+				Origin::signed(ALICE),
+				BOB,
+				vec![(STAKING_ASSET_ID, 40), (SPENDING_ASSET_ID, 60)],
+			),
+			Error::<Test>::InsufficientBalance,
+		);
+		assert_noop!(
+			GenericAsset::transfer_multi_asset(Origin::signed(ALICE), BOB, vec![]),
+			Error::<Test>::EmptyBatch,
+		);
+	});
+}
@@ -28,6 +28,8 @@ pub trait WeightInfo {
 	fn mint() -> Weight;
 	fn transfer() -> Weight;
 	fn transfer_keep_alive() -> Weight;
+	fn transfer_many(n: u32) -> Weight;
+	fn transfer_multi_asset(n: u32) -> Weight;
 	fn update_asset_info() -> Weight;
 	fn update_permission() -> Weight;
 }
@@ -43,6 +45,20 @@ impl WeightInfo for () {
 			.saturating_add(DbWeight::get().reads(8 as Weight))
 			.saturating_add(DbWeight::get().writes(3 as Weight))
 	}
+	fn transfer_many(n: u32) -> Weight {
+		(21_000_000 as Weight)
+			.saturating_add((118_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().reads((3 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(DbWeight::get().writes((2 as Weight).saturating_mul(n as Weight)))
+	}
+	fn transfer_multi_asset(n: u32) -> Weight {
+		(19_000_000 as Weight)
+			.saturating_add((124_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().reads((5 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(DbWeight::get().writes((2 as Weight).saturating_mul(n as Weight)))
+	}
 	fn burn() -> Weight {
 		(95_000_000 as Weight)
 			.saturating_add(DbWeight::get().reads(6 as Weight))
@@ -25,16 +25,34 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use codec::Codec;
-use crml_governance::{ProposalId, ProposalVoteInfo};
+use crml_governance::{
+	ProposalCallInfo, ProposalDetails, ProposalId, ProposalStatusInfo, ProposalVoteInfo, SpendingProposal,
+};
 use sp_std::vec::Vec;
 
 sp_api::decl_runtime_apis! {
-	pub trait GovernanceRuntimeApi<AccountId> where
+	/// Version 2 adds spending proposals and their block number type
+	#[api_version(2)]
+	pub trait GovernanceRuntimeApi<AccountId, BlockNumber, Hash> where
 		AccountId: Codec,
+		BlockNumber: Codec,
+		Hash: Codec,
 	{
 		/// Get council members
 		fn council() -> Vec<AccountId>;
 		/// Get proposal votes
 		fn proposal_votes() -> Vec<(ProposalId, ProposalVoteInfo)>;
+		/// Get spending proposals and their milestone releases
+		fn spending_proposals() -> Vec<(ProposalId, SpendingProposal<AccountId, BlockNumber>)>;
+		/// Get council votes on pending milestone releases
+		fn milestone_votes() -> Vec<(ProposalId, ProposalVoteInfo)>;
+		/// Get the decoded call of a proposal
+		fn proposal_call(proposal_id: ProposalId) -> Option<ProposalCallInfo<Hash>>;
+		/// Get the lifecycle details of a proposal
+		fn proposal(proposal_id: ProposalId) -> Option<ProposalDetails<AccountId, BlockNumber>>;
+		/// Get the lifecycle details of all proposals, optionally only those with `status`
+		fn proposals(status: Option<ProposalStatusInfo>) -> Vec<ProposalDetails<AccountId, BlockNumber>>;
+		/// Get referendum vote delegates and the accounts delegating to them
+		fn delegations() -> Vec<(AccountId, Vec<AccountId>)>;
 	}
 }
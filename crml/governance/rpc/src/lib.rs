@@ -17,22 +17,52 @@
 //! RPC interface for the governance module.
 
 pub use self::gen_client::Client as GovernanceClient;
+use cennznet_primitives::types::{AssetId, Balance};
 use codec::Codec;
-use crml_governance::ProposalId;
+use crml_governance::{ProposalDetails, ProposalId, ProposalStatusInfo, ProposalVoteInfo};
 pub use crml_governance_rpc_runtime_api::GovernanceRuntimeApi;
 use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
 use jsonrpc_derive::rpc;
 use serde::{Deserialize, Serialize};
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
 use sp_runtime::{generic::BlockId, traits::Block as BlockT};
 use std::sync::Arc;
 
 #[rpc]
-pub trait GovernanceApi<AccountId, BlockHash> {
+pub trait GovernanceApi<AccountId, BlockNumber, BlockHash> {
 	/// Get all governance proposal votes
 	#[rpc(name = "governance_getProposalVotes")]
 	fn proposal_votes(&self, at: Option<BlockHash>) -> Result<Vec<ProposalVotes<AccountId>>>;
+
+	/// Get all spending proposals, their milestone releases and council votes on the next milestone
+	#[rpc(name = "governance_getSpendingProposals")]
+	fn spending_proposals(&self, at: Option<BlockHash>) -> Result<Vec<SpendingProposalInfo<AccountId, BlockNumber>>>;
+
+	/// Get the decoded call a proposal will enact
+	#[rpc(name = "governance_getProposalCall")]
+	fn proposal_call(&self, proposal_id: ProposalId, at: Option<BlockHash>) -> Result<Option<ProposalCall<BlockHash>>>;
+
+	/// Get the lifecycle details of a proposal
+	#[rpc(name = "governance_getProposal")]
+	fn proposal(
+		&self,
+		proposal_id: ProposalId,
+		at: Option<BlockHash>,
+	) -> Result<Option<ProposalInfo<AccountId, BlockNumber>>>;
+
+	/// Get the lifecycle details of all proposals, optionally only those with `status`
+	#[rpc(name = "governance_getProposals")]
+	fn proposals(
+		&self,
+		status: Option<ProposalStatusInfo>,
+		at: Option<BlockHash>,
+	) -> Result<Vec<ProposalInfo<AccountId, BlockNumber>>>;
+
+	/// Get referendum vote delegates and the accounts delegating to them
+	#[rpc(name = "governance_getDelegations")]
+	fn delegations(&self, at: Option<BlockHash>) -> Result<Vec<DelegationInfo<AccountId>>>;
 }
 
 /// A struct that implements the [`GovernanceApi`].
@@ -63,12 +93,106 @@ pub struct ProposalVotes<AccountId> {
 	votes: Vec<(AccountId, Option<bool>)>,
 }
 
-impl<C, Block, AccountId> GovernanceApi<AccountId, <Block as BlockT>::Hash> for Governance<C, (Block, AccountId)>
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct MilestoneInfo<BlockNumber> {
+	#[serde(with = "serde_balance")]
+	amount: Balance,
+	deliverable_uri: Vec<u8>,
+	released_at: Option<BlockNumber>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct SpendingProposalInfo<AccountId, BlockNumber> {
+	proposal_id: ProposalId,
+	beneficiary: AccountId,
+	asset_id: AssetId,
+	#[serde(with = "serde_balance")]
+	total_amount: Balance,
+	#[serde(with = "serde_balance")]
+	released_amount: Balance,
+	milestones: Vec<MilestoneInfo<BlockNumber>>,
+	/// Council votes on releasing the next milestone
+	next_milestone_votes: Vec<(AccountId, Option<bool>)>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct ProposalInfo<AccountId, BlockNumber> {
+	proposal_id: ProposalId,
+	sponsor: AccountId,
+	justification_uri: Bytes,
+	enactment_delay: BlockNumber,
+	status: ProposalStatusInfo,
+	deliberation_deadline: Option<BlockNumber>,
+	referendum_start_time: Option<BlockNumber>,
+	referendum_end: Option<BlockNumber>,
+	#[serde(with = "serde_balance")]
+	referendum_approve: Balance,
+	#[serde(with = "serde_balance")]
+	referendum_veto: Balance,
+}
+
+impl<AccountId, BlockNumber> From<ProposalDetails<AccountId, BlockNumber>> for ProposalInfo<AccountId, BlockNumber> {
+	fn from(details: ProposalDetails<AccountId, BlockNumber>) -> Self {
+		ProposalInfo {
+			proposal_id: details.proposal_id,
+			sponsor: details.sponsor,
+			justification_uri: details.justification_uri.into(),
+			enactment_delay: details.enactment_delay,
+			status: details.status,
+			deliberation_deadline: details.deliberation_deadline,
+			referendum_start_time: details.referendum_start_time,
+			referendum_end: details.referendum_end,
+			referendum_approve: details.referendum_tally.approve,
+			referendum_veto: details.referendum_tally.veto,
+		}
+	}
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct ProposalCall<Hash> {
+	call_hash: Hash,
+	pallet_name: String,
+	function_name: String,
+	weight: u64,
+	encoded_call: Bytes,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct DelegationInfo<AccountId> {
+	delegate: AccountId,
+	delegators: Vec<AccountId>,
+}
+
+mod serde_balance {
+	use cennznet_primitives::types::Balance;
+	use serde::{Deserialize, Deserializer, Serializer};
+
+	pub fn serialize<S: Serializer>(t: &Balance, serializer: S) -> Result<S::Ok, S::Error> {
+		serializer.serialize_str(&t.to_string())
+	}
+
+	pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Balance, D::Error> {
+		let s = String::deserialize(deserializer)?;
+		s.parse::<Balance>()
+			.map_err(|_| serde::de::Error::custom("Parse from string failed"))
+	}
+}
+
+/// Pair the council with their votes
+fn council_votes<AccountId: Clone>(council: &[AccountId], votes: &ProposalVoteInfo) -> Vec<(AccountId, Option<bool>)> {
+	(0..council.len())
+		.map(|idx| (council[idx].clone(), votes.get_vote(idx as u8)))
+		.collect()
+}
+
+impl<C, Block, AccountId, BlockNumber> GovernanceApi<AccountId, BlockNumber, <Block as BlockT>::Hash>
+	for Governance<C, (Block, AccountId, BlockNumber)>
 where
 	Block: BlockT,
 	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
-	C::Api: GovernanceRuntimeApi<Block, AccountId>,
+	C::Api: GovernanceRuntimeApi<Block, AccountId, BlockNumber, <Block as BlockT>::Hash>,
 	AccountId: Codec + Clone + Send + Sync + 'static,
+	BlockNumber: Codec + Send + Sync + 'static,
 {
 	fn proposal_votes(&self, at: Option<<Block as BlockT>::Hash>) -> Result<Vec<ProposalVotes<AccountId>>> {
 		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
@@ -89,15 +213,143 @@ where
 
 		Ok(proposal_votes_info
 			.iter()
-			.map(|(proposal_id, votes)| {
-				let votes: Vec<(AccountId, Option<bool>)> = (0..council.len())
-					.map(|idx| (council[idx].clone(), votes.get_vote(idx as u8)))
-					.collect();
-				ProposalVotes {
-					proposal_id: *proposal_id,
-					votes,
+			.map(|(proposal_id, votes)| ProposalVotes {
+				proposal_id: *proposal_id,
+				votes: council_votes(&council, votes),
+			})
+			.collect())
+	}
+
+	fn spending_proposals(
+		&self,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<SpendingProposalInfo<AccountId, BlockNumber>>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		let api = self.client.runtime_api();
+
+		let mut spending_proposals = api.spending_proposals(&at).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError as i64),
+			message: "Unable to query spending proposals.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})?;
+		// sort by proposal Id for the receiver
+		spending_proposals.sort_by_key(|(proposal_id, _)| *proposal_id);
+
+		let milestone_votes = api.milestone_votes(&at).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError as i64),
+			message: "Unable to query milestone votes.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})?;
+
+		let council = api.council(&at).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError as i64),
+			message: "Unable to query council.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})?;
+
+		Ok(spending_proposals
+			.into_iter()
+			.map(|(proposal_id, spending)| {
+				let next_milestone_votes = milestone_votes
+					.iter()
+					.find(|(id, _)| *id == proposal_id)
+					.map(|(_, votes)| council_votes(&council, votes))
+					.unwrap_or_default();
+				SpendingProposalInfo {
+					proposal_id,
+					total_amount: spending.total_amount(),
+					released_amount: spending.released_amount(),
+					beneficiary: spending.beneficiary,
+					asset_id: spending.asset_id,
+					milestones: spending
+						.milestones
+						.into_iter()
+						.map(|m| MilestoneInfo {
+							amount: m.amount,
+							deliverable_uri: m.deliverable_uri,
+							released_at: m.released_at,
+						})
+						.collect(),
+					next_milestone_votes,
 				}
 			})
 			.collect())
 	}
+	fn proposal_call(
+		&self,
+		proposal_id: ProposalId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<ProposalCall<<Block as BlockT>::Hash>>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let call_info = self
+			.client
+			.runtime_api()
+			.proposal_call(&at, proposal_id)
+			.map_err(|e| RpcError {
+				code: ErrorCode::ServerError(Error::RuntimeError as i64),
+				message: "Unable to query proposal call.".into(),
+				data: Some(format!("{:?}", e).into()),
+			})?;
+
+		Ok(call_info.map(|info| ProposalCall {
+			call_hash: info.call_hash,
+			pallet_name: String::from_utf8_lossy(&info.pallet_name).into_owned(),
+			function_name: String::from_utf8_lossy(&info.function_name).into_owned(),
+			weight: info.weight,
+			encoded_call: info.encoded_call.into(),
+		}))
+	}
+	fn proposal(
+		&self,
+		proposal_id: ProposalId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<ProposalInfo<AccountId, BlockNumber>>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let details = self
+			.client
+			.runtime_api()
+			.proposal(&at, proposal_id)
+			.map_err(|e| RpcError {
+				code: ErrorCode::ServerError(Error::RuntimeError as i64),
+				message: "Unable to query proposal.".into(),
+				data: Some(format!("{:?}", e).into()),
+			})?;
+
+		Ok(details.map(Into::into))
+	}
+
+	fn proposals(
+		&self,
+		status: Option<ProposalStatusInfo>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<ProposalInfo<AccountId, BlockNumber>>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let mut proposals = self.client.runtime_api().proposals(&at, status).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError as i64),
+			message: "Unable to query proposals.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})?;
+		// sort by proposal Id for the receiver
+		proposals.sort_by_key(|details| details.proposal_id);
+
+		Ok(proposals.into_iter().map(Into::into).collect())
+	}
+
+	fn delegations(&self, at: Option<<Block as BlockT>::Hash>) -> Result<Vec<DelegationInfo<AccountId>>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let delegations = self.client.runtime_api().delegations(&at).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError as i64),
+			message: "Unable to query delegations.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})?;
+
+		Ok(delegations
+			.into_iter()
+			.map(|(delegate, delegators)| DelegationInfo { delegate, delegators })
+			.collect())
+	}
 }
@@ -0,0 +1,142 @@
+/* Copyright 2019-2021 Centrality Investments Limited
+*
+* Licensed under the LGPL, Version 3.0 (the "License");
+* you may not use this file except in compliance with the License.
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific language governing permissions and
+* limitations under the License.
+* You may obtain a copy of the License at the root of this project source code,
+* or at:
+*     https://centrality.ai/licenses/gplv3.txt
+*     https://centrality.ai/licenses/lgplv3.txt
+*/
+//! Governance benchmarking.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_system::RawOrigin;
+
+use crate::Module as Governance;
+
+/// The max. number of referendum votes benchmarked
+const MAX_VOTES: u32 = 1_000;
+/// The max. length of a noted call benchmarked
+const MAX_PREIMAGE_BYTES: u32 = 16_384;
+
+// Create a proposal in referendum with `v` approving voters
+// The first voter is delegated to by `d` accounts who have not voted
+// Returns the proposal id
+fn setup_referendum<T: Config>(v: u32, d: u32) -> ProposalId {
+	let proposal_id = Governance::<T>::next_proposal_id();
+	<Proposals<T>>::insert(
+		proposal_id,
+		Proposal {
+			sponsor: account("sponsor", 0, 0),
+			justification_uri: b"http://example.com/proposal".to_vec(),
+			enactment_delay: T::BlockNumber::from(1_u32),
+		},
+	);
+	NextProposalId::put(proposal_id + 1);
+	ProposalStatus::insert(proposal_id, ProposalStatusInfo::ReferendumDeliberation);
+	<ReferendumStartTime<T>>::insert(proposal_id, T::BlockNumber::zero());
+
+	for i in 0..v {
+		let voter: T::AccountId = account("voter", i, 0);
+		DelegateVotes::<T>::insert(proposal_id, &voter, 0);
+		ReferendumVotes::<T>::insert(
+			proposal_id,
+			voter,
+			ReferendumVoteCount {
+				vote: 0,
+				stake: 1_000_000,
+				conviction: Conviction::Locked1x,
+			},
+		);
+	}
+	let delegate: T::AccountId = account("voter", 0, 0);
+	let mut delegators: Vec<T::AccountId> = (0..d).map(|i| account("delegator", i, 0)).collect();
+	delegators.sort();
+	for delegator in delegators.iter() {
+		<Delegations<T>>::insert(delegator, delegate.clone());
+	}
+	<Delegators<T>>::insert(&delegate, delegators);
+
+	proposal_id
+}
+
+benchmarks! {
+	undelegate {
+		let caller: T::AccountId = whitelisted_caller();
+		let delegate: T::AccountId = account("delegate", 0, 0);
+		let mut delegators: Vec<T::AccountId> = (1..MAX_DELEGATORS as u32).map(|i| account("delegator", i, 0)).collect();
+		delegators.push(caller.clone());
+		delegators.sort();
+		<Delegators<T>>::insert(&delegate, delegators);
+		<Delegations<T>>::insert(&caller, delegate.clone());
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert!(Governance::<T>::delegation(&caller).is_none());
+		assert_eq!(Governance::<T>::delegators(&delegate).len(), MAX_DELEGATORS - 1);
+	}
+
+	note_preimage {
+		let b in 0 .. MAX_PREIMAGE_BYTES;
+		let caller: T::AccountId = whitelisted_caller();
+		let encoded_call = <T as Config>::Call::from(Call::<T>::submit_proposal {
+			call_hash: None,
+			justification_uri: sp_std::vec![1_u8; b as usize],
+			enactment_delay: T::BlockNumber::from(1_u32),
+		})
+		.encode();
+		let call_hash = T::Hashing::hash(&encoded_call[..]);
+		let deposit = Governance::<T>::preimage_byte_deposit().saturating_mul(encoded_call.len() as Balance);
+		let _ = T::Currency::deposit_creating(&caller, deposit.saturating_add(1_000_000));
+	}: _(RawOrigin::Signed(caller), encoded_call)
+	verify {
+		assert!(Governance::<T>::preimages(call_hash).is_some());
+	}
+
+	end_referendum {
+		let v in 1 .. MAX_VOTES;
+		let d in 0 .. MAX_DELEGATORS as u32;
+		let proposal_id = setup_referendum::<T>(v, d);
+	}: {
+		let _ = Governance::<T>::end_referendum(proposal_id);
+	}
+	verify {
+		assert_eq!(Governance::<T>::proposal_status(proposal_id), Some(ProposalStatusInfo::ApprovedEnacted(true)));
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::{ExtBuilder, Test};
+	use frame_support::assert_ok;
+
+	#[test]
+	fn undelegate() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_undelegate::<Test>());
+		});
+	}
+
+	#[test]
+	fn note_preimage() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_note_preimage::<Test>());
+		});
+	}
+
+	#[test]
+	fn end_referendum() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_end_referendum::<Test>());
+		});
+	}
+}
@@ -18,30 +18,37 @@
 //!
 #![cfg_attr(not(feature = "std"), no_std)]
 
+mod benchmarking;
 #[cfg(test)]
 mod mock;
 #[cfg(test)]
 mod tests;
 mod types;
 pub use types::*;
+mod weights;
+pub use weights::WeightInfo;
 
-use cennznet_primitives::types::Balance;
+use cennznet_primitives::types::{AssetId, Balance};
 use codec::{Decode, Encode};
-use crml_support::{RegistrationInfo, StakingAmount};
+use crml_support::{MultiCurrency, RegistrationInfo, StakingAmount};
 use frame_support::{
 	decl_error, decl_event, decl_module, decl_storage,
 	dispatch::{DispatchResult, Dispatchable},
 	pallet_prelude::*,
 	traits::{
 		schedule::{DispatchTime, Named as ScheduleNamed},
-		Currency, LockIdentifier, ReservableCurrency,
+		Currency, ExistenceRequirement, GetCallMetadata, LockIdentifier, LockableCurrency, ReservableCurrency,
+		WithdrawReasons,
 	},
+	transactional,
+	weights::GetDispatchInfo,
+	PalletId,
 };
 use frame_system::pallet_prelude::*;
 use log::warn;
-use sp_runtime::traits::Zero;
-use sp_runtime::Permill;
-use sp_std::prelude::*;
+use sp_runtime::traits::{AccountIdConversion, Hash, Zero};
+use sp_runtime::{FixedU128, PerThing, Permill};
+use sp_std::{convert::TryFrom, marker::PhantomData, prelude::*};
 
 /// Identifies governance scheduled calls
 const GOVERNANCE_ID: LockIdentifier = *b"governan";
@@ -49,36 +56,58 @@ const GOVERNANCE_ID: LockIdentifier = *b"governan";
 const MINIMUM_REGISTERED_IDENTITIES: u32 = 2;
 /// The length in blocks of a referendum voting cycle
 const REFERENDUM_LENGTH: u32 = 21600;
-/// The interval in which the referendum ending is checked
-const REFERENDUM_CHECK_INTERVAL: u32 = 1000;
+/// The maximum number of candidates standing in a council election
+const MAX_COUNCIL_CANDIDATES: usize = 100;
+/// The maximum number of milestones in a spending proposal
+const MAX_MILESTONES: usize = 16;
+/// The maximum number of accounts delegating their referendum votes to a single delegate
+pub const MAX_DELEGATORS: usize = 1_000;
+/// The maximum number of changes kept in a parameter's history
+const MAX_PARAMETER_HISTORY: usize = 16;
+/// Records of concluded proposals are retained for this many blocks (~28 days)
+const PROPOSAL_RETENTION_BLOCKS: u32 = 483_840;
 
 pub trait Config: frame_system::Config {
 	/// Maximum size of the council
 	type MaxCouncilSize: Get<u16>;
+	/// The length in blocks of a council term, an election is held at the end of each term
+	type CouncilTermLength: Get<Self::BlockNumber>;
+	/// The length in blocks the council has to reach a majority on a proposal before it expires
+	type DeliberationPeriod: Get<Self::BlockNumber>;
 	/// The Scheduler.
 	type Scheduler: ScheduleNamed<Self::BlockNumber, <Self as Config>::Call, Self::PalletsOrigin>;
 	/// Overarching type of all pallets origins.
 	type PalletsOrigin: From<frame_system::RawOrigin<Self::AccountId>>;
 	/// Runtime currency system
 	type Currency: Currency<Self::AccountId, Balance = Balance> + ReservableCurrency<Self::AccountId>;
+	/// The staked currency, locked by referendum votes with conviction
+	type StakingCurrency: LockableCurrency<Self::AccountId, Balance = Balance, Moment = Self::BlockNumber>;
 	/// Runtime call type
-	type Call: Parameter + Dispatchable<Origin = Self::Origin> + From<Call<Self>>;
+	type Call: Parameter + Dispatchable<Origin = Self::Origin> + From<Call<Self>> + GetDispatchInfo + GetCallMetadata;
 	/// The system event type
-	type Event: From<Event> + Into<<Self as frame_system::Config>::Event>;
+	type Event: From<Event<Self>> + Into<<Self as frame_system::Config>::Event>;
 	/// Weight information for extrinsics in this module.
 	type WeightInfo: WeightInfo;
 	/// Registrations for identities
 	type Registration: RegistrationInfo<AccountId = Self::AccountId>;
 	/// staking information of an account
 	type StakingAmount: StakingAmount<AccountId = Self::AccountId, Balance = Balance>;
+	/// Multi-currency system, spending proposals are paid in any asset
+	type MultiCurrency: MultiCurrency<AccountId = Self::AccountId, Balance = Balance, CurrencyId = AssetId>;
+	/// The treasury PalletId, spending proposals are paid from the treasury account
+	type TreasuryPalletId: Get<PalletId>;
+	/// Deposit reserved per byte of a noted call, unless adjusted by governance
+	type DefaultPreimageByteDeposit: Get<Balance>;
+	/// Origin which may request a shortened referendum e.g. a technical committee
+	type FastTrackOrigin: EnsureOrigin<Self::Origin, Success = Self::AccountId>;
 }
 
-/// TODO: move to weights
-pub trait WeightInfo {}
-impl WeightInfo for () {}
-
 decl_event! {
-	pub enum Event {
+	pub enum Event<T> where
+		AccountId = <T as frame_system::Config>::AccountId,
+		BlockNumber = <T as frame_system::Config>::BlockNumber,
+		Hash = <T as frame_system::Config>::Hash,
+	{
 		/// A proposal was submitted
 		SubmitProposal(ProposalId),
 		/// A proposal was enacted, success
@@ -91,6 +120,34 @@ decl_event! {
 		ReferendumCreated(ProposalId),
 		/// A referendum has been approved and is awaiting enactment
 		ReferendumApproved(ProposalId),
+		/// An account is standing for the next council election
+		CandidacySubmitted(AccountId),
+		/// An account withdrew from the next council election
+		CandidacyRenounced(AccountId),
+		/// A new council was elected (term, members)
+		CouncilElected(u32, Vec<AccountId>),
+		/// A spending proposal milestone was paid (proposal, milestone index, beneficiary, asset, amount)
+		MilestoneReleased(ProposalId, u32, AccountId, AssetId, Balance),
+		/// The council voted against releasing a milestone, the spending proposal is cancelled (proposal, milestone index)
+		MilestoneRejected(ProposalId, u32),
+		/// A proposal call was noted (call hash, provider, deposit)
+		PreimageNoted(Hash, AccountId, Balance),
+		/// A noted proposal call was removed and its deposit returned (call hash, provider)
+		PreimageReaped(Hash, AccountId),
+		/// The council did not reach a majority on a proposal before its deadline (proposal, bond returned)
+		ProposalExpired(ProposalId, bool),
+		/// A shortened referendum was requested (proposal, requester)
+		FastTrackRequested(ProposalId, AccountId),
+		/// The council approved a shortened referendum (proposal, referendum end)
+		FastTrackApproved(ProposalId, BlockNumber),
+		/// The council rejected a shortened referendum
+		FastTrackRejected(ProposalId),
+		/// An account delegated its referendum votes (delegator, delegate)
+		Delegated(AccountId, AccountId),
+		/// An account revoked its referendum vote delegation (delegator)
+		Undelegated(AccountId),
+		/// A network parameter was changed (parameter, new value)
+		ParameterChanged(ParameterKey, ParameterValue),
 	}
 }
 
@@ -118,37 +175,146 @@ decl_error! {
 		ReferendumNotDeliberating,
 		/// This account does not meet the required amount of registered identities
 		NotEnoughRegistrations,
+		/// The account has no active stake bonded
+		NotStaker,
+		/// The account's referendum vote lock has not expired
+		VoteLockNotExpired,
+		/// The account is already a council candidate
+		AlreadyCandidate,
+		/// The account is not a council candidate
+		NotCandidate,
+		/// Reached the max. number of council candidates
+		TooManyCandidates,
+		/// Approved more candidates than there are council seats
+		TooManyApprovals,
+		/// Must approve at least one candidate
+		NoApprovals,
+		/// A spending proposal must have at least one milestone
+		NoMilestones,
+		/// Reached the max. number of milestones in a spending proposal
+		TooManyMilestones,
+		/// The proposal is not releasing spending milestones
+		ProposalNotFunding,
+		/// The call could not be decoded as a runtime call
+		InvalidCall,
+		/// The call's weight exceeds the block limit
+		CallWeightTooHigh,
+		/// No call has been noted with this hash
+		PreimageMissing,
+		/// A call with this hash has already been noted
+		DuplicatePreimage,
+		/// Operation can only be performed by the account that noted the call
+		NotPreimageProvider,
+		/// The council isn't currently deliberating the proposal
+		ProposalNotDeliberating,
+		/// A fast-track requires a justification
+		MissingJustification,
+		/// A fast-tracked referendum must be shorter than the standard referendum
+		InvalidReferendumLength,
+		/// The proposal already has a fast-track request
+		FastTrackExists,
+		/// The proposal has no fast-track request
+		FastTrackMissing,
+		/// An account cannot delegate to itself
+		SelfDelegation,
+		/// The account has not delegated its votes
+		NotDelegating,
+		/// Reached the max. number of delegators for the delegate
+		TooManyDelegators,
+		/// The account already delegates to this delegate
+		AlreadyDelegating,
+		/// The value has the wrong type or is out of bounds for the parameter
+		InvalidParameter,
 	}
 }
 
 decl_storage! {
 	trait Store for Module<T: Config> as Governance {
-		/// Map from proposal Id to proposal info
+		/// Map from proposal Id to proposal info, retained for `PROPOSAL_RETENTION_BLOCKS` once concluded
 		Proposals get(fn proposals): map hasher(twox_64_concat) ProposalId => Option<Proposal<T>>;
 		/// Map from proposal Id to call if any
 		ProposalCalls get(fn proposal_calls): map hasher(twox_64_concat) ProposalId => Option<Vec<u8>>;
+		/// Map from proposal Id to the provider and deposit of the proposal's call
+		ProposalCallDeposits get(fn proposal_call_deposit): map hasher(twox_64_concat) ProposalId => Option<(T::AccountId, Balance)>;
+		/// Map from call hash to noted calls awaiting a proposal
+		Preimages get(fn preimages): map hasher(identity) T::Hash => Option<Preimage<T::AccountId>>;
+		/// Deposit reserved per byte of a noted call in 'wei', overrides `DefaultPreimageByteDeposit` once set
+		PreimageByteDeposit: Option<Balance>;
 		/// Map from proposal Id to votes
 		ProposalVotes get(fn proposal_votes): map hasher(twox_64_concat) ProposalId => ProposalVoteInfo;
 		/// Map from proposal Id to status
 		ProposalStatus get(fn proposal_status): map hasher(twox_64_concat) ProposalId => Option<ProposalStatusInfo>;
-		/// Map from proposal Id to referendum votes
+		/// Map from proposal Id to referendum votes by voter stash
 		ReferendumVotes get(fn referendum_votes): double_map hasher(twox_64_concat) ProposalId, hasher(twox_64_concat) T::AccountId => ReferendumVoteCount;
-		/// Running tally of referendum votes
-		ReferendumVetoSum get(fn referendum_veto_sum): map hasher(twox_64_concat) ProposalId => u32;
+		/// Running tally of direct stake weighted referendum votes, delegated stake is added and the final tally kept once the referendum ends
+		ReferendumTallies get(fn referendum_tally): map hasher(twox_64_concat) ProposalId => ReferendumTally;
+		/// Map from proposal Id to the referendum vote of each voting account, their delegators are counted with it when the referendum ends
+		DelegateVotes get(fn delegate_vote): double_map hasher(twox_64_concat) ProposalId, hasher(twox_64_concat) T::AccountId => Option<u8>;
+		/// Stake locked by referendum votes with conviction (stash => (amount, locked until))
+		ReferendumVoteLocks get(fn referendum_vote_lock): map hasher(twox_64_concat) T::AccountId => Option<(Balance, T::BlockNumber)>;
 		/// Map from proposal id to referendum start time
 		ReferendumStartTime get(fn referendum_start_time): map hasher(twox_64_concat) ProposalId => Option<T::BlockNumber>;
+		/// Referendums to end at a block
+		ReferendumEnds get(fn referendum_ends): double_map hasher(twox_64_concat) T::BlockNumber, hasher(twox_64_concat) ProposalId => ();
+		/// Concluded proposals to prune at a block
+		ProposalPruning get(fn proposal_pruning): double_map hasher(twox_64_concat) T::BlockNumber, hasher(twox_64_concat) ProposalId => ();
+		/// Map from proposal id to the block the council's deliberation expires
+		DeliberationDeadlines get(fn deliberation_deadline): map hasher(twox_64_concat) ProposalId => Option<T::BlockNumber>;
+		/// Council deliberations to expire at a block
+		DeliberationExpiries get(fn deliberation_expiries): double_map hasher(twox_64_concat) T::BlockNumber, hasher(twox_64_concat) ProposalId => ();
 		/// Ordered set of active council members
 		Council get(fn council): Vec<T::AccountId>;
 		/// Next available ID for proposal
 		NextProposalId get(fn next_proposal_id): ProposalId;
-		/// Proposal bond amount in 'wei'
-		ProposalBond get(fn proposal_bond): Balance;
 		/// Minimum amount of staked CENNZ required to vote
 		MinVoterStakedAmount get(fn min_voter_staked_amount): Balance = 10_000;
-		/// Permill of vetos needed for a referendum to fail
-		ReferendumThreshold get(fn referendum_threshold): Permill = Permill::from_percent(33);
 		/// Minimum stake required to create a new council member
 		MinimumCouncilStake get(fn minimum_council_stake): Balance = 10_000_000;
+		/// Ordered set of accounts standing in the next council election
+		CouncilCandidates get(fn council_candidates): Vec<T::AccountId>;
+		/// Map from voter to the council candidates they approve
+		CouncilVotes get(fn council_votes): map hasher(twox_64_concat) T::AccountId => Vec<T::AccountId>;
+		/// Map from voter to the bond reserved for their council approvals
+		CouncilVoterBonds get(fn council_voter_bond): map hasher(twox_64_concat) T::AccountId => Balance;
+		/// Bond reserved by council candidates in 'wei'
+		CandidacyBond get(fn candidacy_bond): Balance;
+		/// Bond reserved by accounts approving council candidates in 'wei', returned after the election
+		CouncilVotingBond get(fn council_voting_bond): Balance;
+		/// Index of the current council term
+		CouncilTerm get(fn council_term): u32;
+		/// Map from proposal Id to spending proposal info
+		SpendingProposals get(fn spending_proposals): map hasher(twox_64_concat) ProposalId => Option<SpendingProposal<T::AccountId, T::BlockNumber>>;
+		/// Map from proposal Id to council votes on releasing the next milestone
+		MilestoneVotes get(fn milestone_votes): map hasher(twox_64_concat) ProposalId => ProposalVoteInfo;
+		/// Permill of the council required to approve a fast-track
+		FastTrackThreshold get(fn fast_track_threshold): Permill = Permill::from_percent(67);
+		/// Map from proposal Id to a pending fast-track request
+		FastTracks get(fn fast_track): map hasher(twox_64_concat) ProposalId => Option<FastTrack<T::AccountId, T::BlockNumber>>;
+		/// Map from proposal Id to council votes on a fast-track request
+		FastTrackVotes get(fn fast_track_votes): map hasher(twox_64_concat) ProposalId => ProposalVoteInfo;
+		/// Map from proposal Id to the end of its fast-tracked referendum
+		FastTrackedReferendums get(fn fast_tracked_referendum): map hasher(twox_64_concat) ProposalId => Option<T::BlockNumber>;
+		/// Fast-tracked referendums to end at a block
+		FastTrackedReferendumEnds get(fn fast_tracked_referendum_ends): double_map hasher(twox_64_concat) T::BlockNumber, hasher(twox_64_concat) ProposalId => ();
+		/// Map from delegator to the account voting on referendums on their behalf
+		Delegations get(fn delegation): map hasher(twox_64_concat) T::AccountId => Option<T::AccountId>;
+		/// Map from delegate to the ordered set of accounts delegating to them
+		Delegators get(fn delegators): map hasher(twox_64_concat) T::AccountId => Vec<T::AccountId>;
+		/// Map from network parameter to its value, parameters which have never been changed have their default value
+		Parameters get(fn parameters): map hasher(twox_64_concat) ParameterKey => Option<ParameterValue>;
+		/// Map from network parameter to its most recent changes, oldest first
+		ParameterHistory get(fn parameter_history): map hasher(twox_64_concat) ParameterKey => Vec<ParameterChange<T::BlockNumber>>;
+		/// The current version of the governance storage, used for migrations
+		StorageVersion build(|_: &GenesisConfig| Releases::V2 as u32): u32;
+	}
+	add_extra_genesis {
+		config(parameters): Vec<(ParameterKey, ParameterValue)>;
+		build(|config: &GenesisConfig| {
+			for (key, value) in config.parameters.iter() {
+				assert!(key.is_valid(value), "invalid genesis value for parameter: {:?}", key);
+				Parameters::insert(key, value);
+			}
+		});
 	}
 }
 
@@ -157,32 +323,102 @@ decl_module! {
 
 		fn deposit_event() = default;
 
-		/// Check whether any referendums have finished
+		fn on_runtime_upgrade() -> Weight {
+			let mut weight = Zero::zero();
+			if StorageVersion::get() == Releases::V0 as u32 {
+				StorageVersion::put(Releases::V1 as u32);
+				weight = Self::migrate_referendum_votes().saturating_add(Self::index_referendum_ends());
+			}
+			if StorageVersion::get() == Releases::V1 as u32 {
+				StorageVersion::put(Releases::V2 as u32);
+				weight = weight
+					.saturating_add(Self::migrate_parameters())
+					.saturating_add(Self::invalidate_proposal_calls());
+			}
+			weight
+		}
+
+		/// Check whether any referendums or council deliberations have finished and whether the council term has ended
 		fn on_initialize(block_number: T::BlockNumber) -> Weight {
-			if (block_number % T::BlockNumber::from(REFERENDUM_CHECK_INTERVAL)).is_zero() {
-				// Check referendums
-				let mut weight_count = 0;
-				let proposal_ids = <ReferendumStartTime<T>>::iter();
-				proposal_ids.for_each(|(proposal_id, block)| {
-					if block_number >= block + T::BlockNumber::from(REFERENDUM_LENGTH) {
-						if Self::proposal_status(proposal_id) == Some(ProposalStatusInfo::ReferendumDeliberation) {
-							Self::end_referendum(proposal_id);
-							weight_count += 1;
-						}
-					}
-				});
-				weight_count * 1_000_000u64
-			} else  {
-				0
+			let mut weight: Weight = Self::prune_proposals(block_number);
+			// Fast-tracked referendums end at the block they are scheduled for
+			let fast_tracked: Vec<ProposalId> = <FastTrackedReferendumEnds<T>>::drain_prefix(block_number)
+				.map(|(proposal_id, _)| proposal_id)
+				.collect();
+			weight = weight.saturating_add(T::DbWeight::get().reads(1));
+			for proposal_id in fast_tracked {
+				weight = weight.saturating_add(Self::end_referendum(proposal_id));
+			}
+			// Referendums end at the block they are scheduled for, unless they ended early
+			let ended: Vec<ProposalId> = <ReferendumEnds<T>>::drain_prefix(block_number)
+				.map(|(proposal_id, _)| proposal_id)
+				.collect();
+			weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, ended.len() as Weight));
+			for proposal_id in ended {
+				weight = weight.saturating_add(T::DbWeight::get().reads(1));
+				if Self::proposal_status(proposal_id) == Some(ProposalStatusInfo::ReferendumDeliberation) {
+					weight = weight.saturating_add(Self::end_referendum(proposal_id));
+				}
+			}
+			// Council deliberations expire at their deadline
+			let expired: Vec<ProposalId> = <DeliberationExpiries<T>>::drain_prefix(block_number)
+				.map(|(proposal_id, _)| proposal_id)
+				.collect();
+			weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, expired.len() as Weight));
+			for proposal_id in expired {
+				weight = weight.saturating_add(Self::expire_proposal(proposal_id));
 			}
+
+			let term_length = T::CouncilTermLength::get();
+			if !term_length.is_zero() && (block_number % term_length).is_zero() {
+				weight = weight.saturating_add(Self::elect_council());
+			}
+
+			weight
+		}
+
+		#[weight = T::WeightInfo::note_preimage(encoded_call.len() as u32)]
+		/// Note a call for use by a proposal
+		/// The call must decode as a runtime call and fit within a block
+		/// A deposit is reserved per byte of the call until the call is reaped or its proposal is concluded
+		fn note_preimage(origin, encoded_call: Vec<u8>) {
+			let origin = ensure_signed(origin)?;
+			Self::validate_call(&encoded_call)?;
+			let call_hash = T::Hashing::hash(&encoded_call[..]);
+			ensure!(!<Preimages<T>>::contains_key(call_hash), Error::<T>::DuplicatePreimage);
+
+			let deposit = Self::preimage_byte_deposit().saturating_mul(encoded_call.len() as Balance);
+			let _ = T::Currency::reserve(&origin, deposit)?;
+			<Preimages<T>>::insert(call_hash, Preimage {
+				provider: origin.clone(),
+				deposit,
+				call: encoded_call,
+			});
+
+			Self::deposit_event(RawEvent::PreimageNoted(call_hash, origin, deposit));
+		}
+
+		#[weight = 500_000]
+		/// Remove a noted call which is not part of a proposal, returning the deposit
+		/// Caller must be the account that noted the call
+		fn reap_preimage(origin, call_hash: T::Hash) {
+			let origin = ensure_signed(origin)?;
+			let preimage = Self::preimages(call_hash).ok_or(Error::<T>::PreimageMissing)?;
+			ensure!(preimage.provider == origin, Error::<T>::NotPreimageProvider);
+
+			let _ = T::Currency::unreserve(&origin, preimage.deposit);
+			<Preimages<T>>::remove(call_hash);
+
+			Self::deposit_event(RawEvent::PreimageReaped(call_hash, origin));
 		}
 
 		#[weight = 1_000_000]
 		/// Submit a proposal for consideration by the council
+		/// `call_hash` refers to a call noted by `note_preimage` which is enacted on approval, if any
 		/// Caller must be a council member
 		fn submit_proposal(
 			origin,
-			call: Vec<u8>,
+			call_hash: Option<T::Hash>,
 			justification_uri: Vec<u8>,
 			enactment_delay: T::BlockNumber,
 		) {
@@ -191,6 +427,14 @@ decl_module! {
 			Self::check_council_account_validity(&origin)?;
 			let sponsor_idx = Self::council().binary_search(&origin);
 			ensure!(sponsor_idx.is_ok(), Error::<T>::NotCouncilor);
+			let preimage = match call_hash {
+				Some(call_hash) => Some(<Preimages<T>>::get(call_hash).ok_or(Error::<T>::PreimageMissing)?),
+				None => None,
+			};
+			// the call may no longer be valid if the runtime was upgraded since it was noted
+			if let Some(preimage) = &preimage {
+				Self::validate_call(&preimage.call)?;
+			}
 			let proposal_id = Self::next_proposal_id();
 			let _ = T::Currency::reserve(&origin, Self::proposal_bond())?;
 			let enactment_delay = sp_std::cmp::max(T::BlockNumber::from(1u32), enactment_delay);
@@ -200,20 +444,75 @@ decl_module! {
 				justification_uri,
 				enactment_delay,
 			});
-			ProposalCalls::insert(proposal_id, call);
+			if let (Some(call_hash), Some(preimage)) = (call_hash, preimage) {
+				// the proposal takes ownership of the call, the deposit is held until the proposal concludes
+				<Preimages<T>>::remove(call_hash);
+				ProposalCalls::insert(proposal_id, preimage.call);
+				<ProposalCallDeposits<T>>::insert(proposal_id, (preimage.provider, preimage.deposit));
+			}
 
 			// sponsor should vote yes
 			let mut votes = ProposalVoteInfo::default();
 			votes.record_vote(sponsor_idx.unwrap() as u8, true);
 			ProposalVotes::insert(proposal_id, votes);
 			ProposalStatus::insert(proposal_id, ProposalStatusInfo::Deliberation);
+			Self::set_deliberation_deadline(proposal_id);
 
 			NextProposalId::put(proposal_id.saturating_add(1));
 		}
 
+		#[weight = 1_000_000]
+		/// Submit a proposal to pay `beneficiary` from the treasury in `asset_id`
+		/// `milestones` are (amount, deliverable URI) paid in order, each release must be approved by the council
+		/// Caller must be a council member
+		fn submit_spending_proposal(
+			origin,
+			beneficiary: T::AccountId,
+			asset_id: AssetId,
+			milestones: Vec<(Balance, Vec<u8>)>,
+			justification_uri: Vec<u8>,
+		) {
+			let origin = ensure_signed(origin)?;
+			ensure!(!milestones.is_empty(), Error::<T>::NoMilestones);
+			ensure!(milestones.len() <= MAX_MILESTONES, Error::<T>::TooManyMilestones);
+			// Validate council members identity and staking assets
+			Self::check_council_account_validity(&origin)?;
+			let sponsor_idx = Self::council().binary_search(&origin);
+			ensure!(sponsor_idx.is_ok(), Error::<T>::NotCouncilor);
+			let proposal_id = Self::next_proposal_id();
+			let _ = T::Currency::reserve(&origin, Self::proposal_bond())?;
+
+			<Proposals<T>>::insert(proposal_id, Proposal {
+				sponsor: origin,
+				justification_uri,
+				enactment_delay: T::BlockNumber::from(1u32),
+			});
+			<SpendingProposals<T>>::insert(proposal_id, SpendingProposal {
+				beneficiary,
+				asset_id,
+				milestones: milestones
+					.into_iter()
+					.map(|(amount, deliverable_uri)| Milestone { amount, deliverable_uri, released_at: None })
+					.collect(),
+			});
+
+			// sponsor should vote yes
+			let mut votes = ProposalVoteInfo::default();
+			votes.record_vote(sponsor_idx.unwrap() as u8, true);
+			ProposalVotes::insert(proposal_id, votes);
+			ProposalStatus::insert(proposal_id, ProposalStatusInfo::Deliberation);
+			Self::set_deliberation_deadline(proposal_id);
+
+			NextProposalId::put(proposal_id.saturating_add(1));
+			Self::deposit_event(RawEvent::SubmitProposal(proposal_id));
+		}
+
 		#[weight = 1_000_000]
 		/// Vote on an active proposal
 		/// Caller must be a council member
+		/// On a council majority for, the sponsor's bond is returned for spending proposals and proposals without a call,
+		/// proposals with a call go to referendum and hold the bond until they conclude
+		/// On a council majority against, the bond is slashed
 		fn vote_on_proposal(
 			origin,
 			proposal_id: ProposalId,
@@ -224,6 +523,10 @@ decl_module! {
 			ensure!(voter_idx.is_ok(), Error::<T>::NotCouncilor);
 
 			let proposal = Self::proposals(proposal_id).ok_or(Error::<T>::ProposalMissing)?;
+			ensure!(
+				Self::proposal_status(proposal_id) == Some(ProposalStatusInfo::Deliberation),
+				Error::<T>::ProposalNotDeliberating
+			);
 			let mut votes = Self::proposal_votes(proposal_id);
 
 			let voter_idx = voter_idx.unwrap() as u8;
@@ -238,31 +541,102 @@ decl_module! {
 			// if we have more than 50% approval
 			let threshold = <Council<T>>::decode_len().unwrap_or(1) as u32 / 2;
 			if tally.yes > threshold {
-				if ProposalCalls::contains_key(proposal_id) {
+				Self::remove_deliberation_deadline(proposal_id);
+				if <SpendingProposals<T>>::contains_key(proposal_id) {
+					// Funds are released per milestone by further council votes
+					let _ = T::Currency::unreserve(&proposal.sponsor, Self::proposal_bond());
+					ProposalStatus::insert(proposal_id, ProposalStatusInfo::FundingMilestones);
+					ProposalVotes::remove(proposal_id);
+				} else if ProposalCalls::contains_key(proposal_id) {
 					let start_time: T::BlockNumber = <frame_system::Pallet<T>>::block_number();
 
 					ProposalStatus::insert(proposal_id, ProposalStatusInfo::ReferendumDeliberation);
 					ProposalVotes::remove(proposal_id);
 					ReferendumStartTime::<T>::insert(proposal_id, start_time);
-					ReferendumVetoSum::insert(proposal_id, 0);
-					Self::deposit_event(Event::ReferendumCreated(proposal_id));
+					<ReferendumEnds<T>>::insert(start_time + T::BlockNumber::from(REFERENDUM_LENGTH), proposal_id, ());
+					ReferendumTallies::insert(proposal_id, ReferendumTally::default());
+					Self::deposit_event(RawEvent::ReferendumCreated(proposal_id));
 				} else {
-					// Proposal does not have a onchain call, it can be considered enacted
-					ProposalStatus::insert(proposal_id, ProposalStatusInfo::ApprovedEnacted(true));
+					// Proposal does not have a onchain call, it can be considered enacted and its bond returned
+					let _ = T::Currency::unreserve(&proposal.sponsor, Self::proposal_bond());
+					Self::conclude_proposal(proposal_id, ProposalStatusInfo::ApprovedEnacted(true));
+					ProposalVotes::remove(proposal_id);
 				}
 			} else if tally.no > threshold {
 				// failed, clean up...
-				Self::deposit_event(Event::ProposalVeto(proposal_id));
+				Self::deposit_event(RawEvent::ProposalVeto(proposal_id));
 				let _ = T::Currency::slash_reserved(&proposal.sponsor, Self::proposal_bond());
-				<Proposals<T>>::remove(proposal_id);
-				ProposalCalls::remove(proposal_id);
+				Self::remove_deliberation_deadline(proposal_id);
+				Self::remove_proposal_call(proposal_id);
 				ProposalVotes::remove(proposal_id);
-				ProposalStatus::insert(proposal_id, ProposalStatusInfo::Disapproved);
+				<SpendingProposals<T>>::remove(proposal_id);
+				Self::conclude_proposal(proposal_id, ProposalStatusInfo::Disapproved);
+			}
+		}
+
+		#[weight = 1_000_000]
+		#[transactional]
+		/// Vote on releasing the next milestone of an approved spending proposal
+		/// The milestone is paid from the treasury on a majority for, a majority against cancels the proposal
+		/// Caller must be a council member
+		fn vote_on_milestone(
+			origin,
+			proposal_id: ProposalId,
+			vote: bool,
+		) {
+			let origin = ensure_signed(origin)?;
+			let voter_idx = Self::council().binary_search(&origin);
+			ensure!(voter_idx.is_ok(), Error::<T>::NotCouncilor);
+			ensure!(
+				Self::proposal_status(proposal_id) == Some(ProposalStatusInfo::FundingMilestones),
+				Error::<T>::ProposalNotFunding
+			);
+			let mut spending = Self::spending_proposals(proposal_id).ok_or(Error::<T>::ProposalMissing)?;
+			let milestone_idx = spending.next_milestone().ok_or(Error::<T>::ProposalNotFunding)?;
+			let mut votes = Self::milestone_votes(proposal_id);
+
+			let voter_idx = voter_idx.unwrap() as u8;
+			ensure!(votes.get_vote(voter_idx).is_none(), Error::<T>::DoubleVote);
+
+			votes.record_vote(voter_idx, vote);
+			let tally = votes.count_votes();
+			MilestoneVotes::insert(proposal_id, votes);
+
+			// if we have more than 50% approval
+			let threshold = <Council<T>>::decode_len().unwrap_or(1) as u32 / 2;
+			if tally.yes > threshold {
+				let milestone = &mut spending.milestones[milestone_idx as usize];
+				T::MultiCurrency::transfer(
+					&Self::treasury_account(),
+					&spending.beneficiary,
+					spending.asset_id,
+					milestone.amount,
+					ExistenceRequirement::AllowDeath,
+				)?;
+				milestone.released_at = Some(<frame_system::Pallet<T>>::block_number());
+				Self::deposit_event(RawEvent::MilestoneReleased(
+					proposal_id,
+					milestone_idx,
+					spending.beneficiary.clone(),
+					spending.asset_id,
+					milestone.amount,
+				));
+				MilestoneVotes::remove(proposal_id);
+				if spending.next_milestone().is_none() {
+					// all milestones paid, the proposal is complete
+					Self::conclude_proposal(proposal_id, ProposalStatusInfo::ApprovedEnacted(true));
+				}
+				<SpendingProposals<T>>::insert(proposal_id, spending);
+			} else if tally.no > threshold {
+				Self::deposit_event(RawEvent::MilestoneRejected(proposal_id, milestone_idx));
+				MilestoneVotes::remove(proposal_id);
+				Self::conclude_proposal(proposal_id, ProposalStatusInfo::FundingCancelled);
 			}
 		}
 
 		/// Add a member to the council
 		/// This must be submitted like any other proposal
+		/// Members are usually elected, see `submit_candidacy`
 		#[weight = 100_000]
 		fn add_council_member(
 			origin,
@@ -270,7 +644,6 @@ decl_module! {
 		) {
 			ensure_root(origin)?;
 			let mut council = Self::council();
-			// TODO: add voter to all active proposals
 
 			// Validate council members identity and staking assets
 			Self::check_council_account_validity(&new_member)?;
@@ -278,6 +651,11 @@ decl_module! {
 			ensure!(council.len() < T::MaxCouncilSize::get() as usize, Error::<T>::MaxCouncilReached);
 			if let Err(idx) = council.binary_search(&new_member) {
 				council.insert(idx, new_member);
+				// shift votes on active proposals to make room for the new member
+				Self::map_council_votes(|mut votes| {
+					votes.insert_voter(idx as u8);
+					votes
+				});
 				Council::<T>::put(council);
 			}
 		}
@@ -292,13 +670,107 @@ decl_module! {
 			ensure_root(origin)?;
 			let mut council = Self::council();
 			ensure!(council.len() > 1, Error::<T>::MinCouncilReached);
-			// TODO: remove voter from all active proposals
 			if let Ok(idx) = council.binary_search(&remove_member) {
 				council.remove(idx);
+				// remove the member's votes from active proposals
+				Self::map_council_votes(|mut votes| {
+					votes.remove_voter(idx as u8);
+					votes
+				});
 				Council::<T>::put(council);
 			}
 		}
 
+		/// Stand for election in the next council term
+		/// Caller must meet the council stake and identity requirements, the candidacy bond is reserved
+		/// until the election
+		#[weight = 500_000]
+		fn submit_candidacy(origin) {
+			let origin = ensure_signed(origin)?;
+			Self::check_council_account_validity(&origin)?;
+
+			let mut candidates = Self::council_candidates();
+			ensure!(candidates.len() < MAX_COUNCIL_CANDIDATES, Error::<T>::TooManyCandidates);
+			let idx = match candidates.binary_search(&origin) {
+				Ok(_) => return Err(Error::<T>::AlreadyCandidate.into()),
+				Err(idx) => idx,
+			};
+			let _ = T::Currency::reserve(&origin, Self::candidacy_bond())?;
+			candidates.insert(idx, origin.clone());
+			<CouncilCandidates<T>>::put(candidates);
+
+			Self::deposit_event(RawEvent::CandidacySubmitted(origin));
+		}
+
+		/// Withdraw from the next council election, returning the candidacy bond
+		#[weight = 500_000]
+		fn renounce_candidacy(origin) {
+			let origin = ensure_signed(origin)?;
+			let mut candidates = Self::council_candidates();
+			let idx = candidates.binary_search(&origin).map_err(|_| Error::<T>::NotCandidate)?;
+			candidates.remove(idx);
+			<CouncilCandidates<T>>::put(candidates);
+			let _ = T::Currency::unreserve(&origin, Self::candidacy_bond());
+
+			Self::deposit_event(RawEvent::CandidacyRenounced(origin));
+		}
+
+		/// Approve council candidates for the next election
+		/// Approvals are weighted by the voter's active stake at the time of the election
+		/// Replaces any prior approvals of the caller, the voting bond is reserved on the caller's first approval
+		#[weight = 500_000]
+		fn vote_for_council(origin, mut approvals: Vec<T::AccountId>) {
+			let origin = ensure_signed(origin)?;
+			Self::check_voter_account_validity(&origin)?;
+			ensure!(!approvals.is_empty(), Error::<T>::NoApprovals);
+			ensure!(approvals.len() <= T::MaxCouncilSize::get() as usize, Error::<T>::TooManyApprovals);
+
+			let candidates = Self::council_candidates();
+			ensure!(
+				approvals.iter().all(|candidate| candidates.binary_search(candidate).is_ok()),
+				Error::<T>::NotCandidate
+			);
+			approvals.sort();
+			approvals.dedup();
+			if !<CouncilVotes<T>>::contains_key(&origin) {
+				let bond = Self::council_voting_bond();
+				let _ = T::Currency::reserve(&origin, bond)?;
+				<CouncilVoterBonds<T>>::insert(&origin, bond);
+			}
+			<CouncilVotes<T>>::insert(origin, approvals);
+		}
+
+		/// Remove all council approvals of the caller, returning the voting bond
+		#[weight = 100_000]
+		fn remove_council_vote(origin) {
+			let origin = ensure_signed(origin)?;
+			<CouncilVotes<T>>::remove(&origin);
+			let _ = T::Currency::unreserve(&origin, <CouncilVoterBonds<T>>::take(&origin));
+		}
+
+		/// Adjust the candidacy bond
+		/// This must be submitted like any other proposal
+		#[weight = 100_000]
+		fn set_candidacy_bond(
+			origin,
+			new_candidacy_bond: Balance
+		) {
+			ensure_root(origin)?;
+			CandidacyBond::put(new_candidacy_bond);
+		}
+
+		/// Adjust the bond reserved by accounts approving council candidates
+		/// Bonds already reserved are unchanged
+		/// This must be submitted like any other proposal
+		#[weight = 100_000]
+		fn set_council_voting_bond(
+			origin,
+			new_council_voting_bond: Balance
+		) {
+			ensure_root(origin)?;
+			CouncilVotingBond::put(new_council_voting_bond);
+		}
+
 		/// Cancel a proposal queued for enactment.
 		#[weight = 1_000_000]
 		fn cancel_enactment(origin, proposal_id: ProposalId) -> DispatchResult {
@@ -308,8 +780,8 @@ decl_module! {
 				.map_err(|_| Error::<T>::ProposalMissing)?;
 
 			let _ = T::Currency::slash_reserved(&proposal.sponsor, Self::proposal_bond());
-			ProposalStatus::insert(proposal_id, ProposalStatusInfo::ApprovedEnactmentCancelled);
-			ProposalCalls::remove(proposal_id);
+			Self::conclude_proposal(proposal_id, ProposalStatusInfo::ApprovedEnactmentCancelled);
+			Self::remove_proposal_call(proposal_id);
 			ProposalVotes::remove(proposal_id);
 			<ReferendumStartTime<T>>::remove(proposal_id);
 
@@ -317,31 +789,149 @@ decl_module! {
 		}
 
 		/// Submit a veto for a referendum
-		#[weight = 1_000_000]
+		/// The veto is weighted by the caller's active stake and `conviction`
+		/// The stake of accounts delegating to the caller is counted with the veto
+		#[weight = T::WeightInfo::vote_on_referendum()]
 		fn vote_against_referendum(
 			origin,
 			proposal_id: ProposalId,
+			conviction: Conviction,
 		) -> DispatchResult {
 			let origin = ensure_signed(origin)?;
-			ensure!(Self::proposal_status(proposal_id) == Some(ProposalStatusInfo::ReferendumDeliberation), Error::<T>::ReferendumNotDeliberating);
-			ensure!(ReferendumVotes::<T>::contains_key(proposal_id, &origin), Error::<T>::DoubleVote);
-			// Validate council members identity and staking assets
+			Self::do_vote_on_referendum(origin, proposal_id, false, conviction)
+		}
+
+		/// Submit an approval for a referendum
+		/// The approval is weighted by the caller's active stake and `conviction`
+		/// The stake of accounts delegating to the caller is counted with the approval
+		#[weight = T::WeightInfo::vote_on_referendum()]
+		fn vote_for_referendum(
+			origin,
+			proposal_id: ProposalId,
+			conviction: Conviction,
+		) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+			Self::do_vote_on_referendum(origin, proposal_id, true, conviction)
+		}
+
+		/// Remove an expired referendum vote lock from `target`'s stake
+		#[weight = 500_000]
+		fn unlock_vote(origin, target: T::AccountId) -> DispatchResult {
+			let _ = ensure_signed(origin)?;
+			let (_, locked_until) = Self::referendum_vote_lock(&target).ok_or(Error::<T>::VoteLockNotExpired)?;
+			ensure!(<frame_system::Pallet<T>>::block_number() >= locked_until, Error::<T>::VoteLockNotExpired);
+
+			T::StakingCurrency::remove_lock(GOVERNANCE_ID, &target);
+			<ReferendumVoteLocks<T>>::remove(&target);
+			Ok(())
+		}
+
+		/// Delegate the caller's referendum votes to `to`
+		/// When a referendum ends the caller's active stake is counted with `to`'s vote, unless the caller voted directly
+		/// Delegated stake is counted without conviction and is not locked, delegation is not transitive
+		#[weight = T::WeightInfo::delegate()]
+		fn delegate(origin, to: T::AccountId) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+			ensure!(origin != to, Error::<T>::SelfDelegation);
+			// Validate delegators identity and staking assets
 			Self::check_voter_account_validity(&origin)?;
-			let block_number = <frame_system::Pallet<T>>::block_number();
-			let start_time = Self::referendum_start_time(proposal_id).ok_or(Error::<T>::ProposalMissing)?;
-			ensure!(block_number >= start_time, Error::<T>::ReferendumNotStarted);
-			// Enter vote in storage
-			ReferendumVotes::<T>::insert(
-				proposal_id,
-				origin,
-				ReferendumVoteCount {
-					vote: 1, // 1 for no vote
-				}
-			);
-			ReferendumVetoSum::mutate(proposal_id, |n| *n += 1);
+
+			let mut delegators = Self::delegators(&to);
+			let idx = delegators.binary_search(&origin).err().ok_or(Error::<T>::AlreadyDelegating)?;
+			ensure!(delegators.len() < MAX_DELEGATORS, Error::<T>::TooManyDelegators);
+			if let Some(previous) = Self::delegation(&origin) {
+				Self::remove_delegator(&previous, &origin);
+			}
+			delegators.insert(idx, origin.clone());
+			<Delegators<T>>::insert(&to, delegators);
+			<Delegations<T>>::insert(&origin, to.clone());
+
+			Self::deposit_event(RawEvent::Delegated(origin, to));
 			Ok(())
 		}
 
+		/// Revoke the caller's referendum vote delegation
+		#[weight = T::WeightInfo::undelegate()]
+		fn undelegate(origin) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+			let delegate = <Delegations<T>>::take(&origin).ok_or(Error::<T>::NotDelegating)?;
+			Self::remove_delegator(&delegate, &origin);
+
+			Self::deposit_event(RawEvent::Undelegated(origin));
+			Ok(())
+		}
+
+		/// Request a shortened referendum for a council approved proposal
+		/// The fast-track takes effect once approved by `FastTrackThreshold` of the council
+		/// Caller must be the `FastTrackOrigin`
+		#[weight = 1_000_000]
+		fn fast_track_proposal(
+			origin,
+			proposal_id: ProposalId,
+			referendum_length: T::BlockNumber,
+			justification_uri: Vec<u8>,
+		) {
+			let origin = T::FastTrackOrigin::ensure_origin(origin)?;
+			ensure!(!justification_uri.is_empty(), Error::<T>::MissingJustification);
+			ensure!(
+				!referendum_length.is_zero() && referendum_length < T::BlockNumber::from(REFERENDUM_LENGTH),
+				Error::<T>::InvalidReferendumLength
+			);
+			ensure!(
+				Self::proposal_status(proposal_id) == Some(ProposalStatusInfo::ReferendumDeliberation),
+				Error::<T>::ReferendumNotDeliberating
+			);
+			ensure!(
+				!<FastTracks<T>>::contains_key(proposal_id) && !<FastTrackedReferendums<T>>::contains_key(proposal_id),
+				Error::<T>::FastTrackExists
+			);
+
+			<FastTracks<T>>::insert(proposal_id, FastTrack {
+				requester: origin.clone(),
+				referendum_length,
+				justification_uri,
+			});
+			Self::deposit_event(RawEvent::FastTrackRequested(proposal_id, origin));
+		}
+
+		/// Vote on a fast-track request
+		/// Caller must be a council member
+		#[weight = 1_000_000]
+		fn vote_on_fast_track(
+			origin,
+			proposal_id: ProposalId,
+			vote: bool,
+		) {
+			let origin = ensure_signed(origin)?;
+			let voter_idx = Self::council().binary_search(&origin);
+			ensure!(voter_idx.is_ok(), Error::<T>::NotCouncilor);
+			let fast_track = Self::fast_track(proposal_id).ok_or(Error::<T>::FastTrackMissing)?;
+			let mut votes = Self::fast_track_votes(proposal_id);
+
+			let voter_idx = voter_idx.unwrap() as u8;
+			ensure!(votes.get_vote(voter_idx).is_none(), Error::<T>::DoubleVote);
+
+			votes.record_vote(voter_idx, vote);
+			let tally = votes.count_votes();
+			FastTrackVotes::insert(proposal_id, votes);
+
+			let council_size = <Council<T>>::decode_len().unwrap_or(1) as u32;
+			let threshold = Self::fast_track_threshold();
+			if Permill::from_rational(tally.yes, council_size) >= threshold {
+				let referendum_end = <frame_system::Pallet<T>>::block_number() + fast_track.referendum_length;
+				<FastTrackedReferendums<T>>::insert(proposal_id, referendum_end);
+				<FastTrackedReferendumEnds<T>>::insert(referendum_end, proposal_id, ());
+				<FastTracks<T>>::remove(proposal_id);
+				FastTrackVotes::remove(proposal_id);
+				Self::deposit_event(RawEvent::FastTrackApproved(proposal_id, referendum_end));
+			} else if Permill::from_rational(tally.no, council_size) > threshold.left_from_one() {
+				// the threshold can no longer be reached
+				<FastTracks<T>>::remove(proposal_id);
+				FastTrackVotes::remove(proposal_id);
+				Self::deposit_event(RawEvent::FastTrackRejected(proposal_id));
+			}
+		}
+
 		/// Execute a proposal transaction
 		#[weight = 1_000_000]
 		fn enact_referendum(origin, proposal_id: ProposalId) -> DispatchResult {
@@ -351,28 +941,53 @@ decl_module! {
 
 			if let Ok(call) = <T as Config>::Call::decode(&mut &proposal_call[..]) {
 				let ok = call.dispatch(frame_system::RawOrigin::Root.into()).is_ok();
-				Self::deposit_event(Event::EnactReferendum(proposal_id, ok));
+				Self::deposit_event(RawEvent::EnactReferendum(proposal_id, ok));
 
 				let _ = T::Currency::unreserve(&proposal.sponsor, Self::proposal_bond());
-				ProposalStatus::insert(proposal_id, ProposalStatusInfo::ApprovedEnacted(ok));
-				<Proposals<T>>::remove(proposal_id);
-				ProposalCalls::remove(proposal_id);
-				ReferendumVetoSum::remove(proposal_id);
+				Self::conclude_proposal(proposal_id, ProposalStatusInfo::ApprovedEnacted(ok));
+				Self::remove_proposal_call(proposal_id);
 				<ReferendumStartTime<T>>::remove(proposal_id);
 			}
 
 			Ok(())
 		}
 
-		/// Adjust the proposal bond
+		/// Change a network parameter, `value` must have the parameter's type and be within its bounds
+		/// The change is recorded in the parameter's history
 		/// This must be submitted like any other proposal
 		#[weight = 100_000]
-		fn set_proposal_bond(
+		fn set_parameter(
 			origin,
-			new_proposal_bond: Balance
+			key: ParameterKey,
+			value: ParameterValue,
 		) {
 			ensure_root(origin)?;
-			ProposalBond::put(new_proposal_bond);
+			ensure!(key.is_valid(&value), Error::<T>::InvalidParameter);
+
+			let previous = Self::parameters(key).unwrap_or_else(|| key.default_value());
+			Parameters::insert(key, value);
+			<ParameterHistory<T>>::mutate(key, |history| {
+				if history.len() >= MAX_PARAMETER_HISTORY {
+					history.remove(0);
+				}
+				history.push(ParameterChange {
+					block_number: <frame_system::Pallet<T>>::block_number(),
+					previous,
+					value,
+				});
+			});
+			Self::deposit_event(RawEvent::ParameterChanged(key, value));
+		}
+
+		/// Adjust the deposit per byte of noted calls
+		/// This must be submitted like any other proposal
+		#[weight = 100_000]
+		fn set_preimage_byte_deposit(
+			origin,
+			new_preimage_byte_deposit: Balance
+		) {
+			ensure_root(origin)?;
+			PreimageByteDeposit::put(new_preimage_byte_deposit);
 		}
 
 		/// Adjust the minimum stake required for new council members
@@ -385,16 +1000,15 @@ decl_module! {
 			MinimumCouncilStake::put(new_minimum_council_stake);
 		}
 
-
-		/// Adjust the referendum veto threshold
+		/// Adjust the share of the council required to approve a fast-track
 		/// This must be submitted like any other proposal
 		#[weight = 100_000]
-		fn set_referendum_threshold(
+		fn set_fast_track_threshold(
 			origin,
-			new_referendum_threshold: Permill,
+			new_fast_track_threshold: Permill,
 		) {
 			ensure_root(origin)?;
-			ReferendumThreshold::put(new_referendum_threshold);
+			FastTrackThreshold::put(new_fast_track_threshold);
 		}
 
 		/// Adjust the minimum staked amount
@@ -411,14 +1025,395 @@ decl_module! {
 }
 
 impl<T: Config> Module<T> {
+	/// Return the current value of a network parameter
+	pub fn parameter<V: TryFrom<ParameterValue> + Default>(key: ParameterKey) -> V {
+		let value = Self::parameters(key).unwrap_or_else(|| key.default_value());
+		V::try_from(value).unwrap_or_else(|_| {
+			warn!("parameter: {:?} read as the wrong type", key);
+			V::default()
+		})
+	}
+	/// Proposal bond amount in 'wei'
+	pub fn proposal_bond() -> Balance {
+		Self::parameter(ParameterKey::ProposalBond)
+	}
+	/// Permill of vetos needed for a referendum to fail, as a share of all stake that voted
+	pub fn referendum_threshold() -> Permill {
+		Self::parameter(ParameterKey::ReferendumThreshold)
+	}
+	/// Move network parameters from their previous pallet storage into the registry
+	/// Parameters already in the registry are left unchanged
+	fn migrate_parameters() -> Weight {
+		use frame_support::storage::migration::take_storage_value;
+
+		// values out of the registry's bounds are moved to the nearest bound
+		let migrate = |key: ParameterKey, value: Option<ParameterValue>| {
+			let value = match value {
+				Some(value) if !Parameters::contains_key(key) => value,
+				_ => return,
+			};
+			match key.clamp(value) {
+				Some(clamped) => {
+					if clamped != value {
+						warn!(
+							"migrate parameter: {:?} value {:?} clamped to {:?}",
+							key, value, clamped
+						);
+					}
+					Parameters::insert(key, clamped);
+				}
+				None => warn!("migrate parameter: {:?} value {:?} dropped, wrong type", key, value),
+			}
+		};
+		migrate(
+			ParameterKey::EthBridgeEventConfirmations,
+			take_storage_value::<u64>(b"EthBridge", b"EventConfirmations", &[]).map(Into::into),
+		);
+		migrate(
+			ParameterKey::EthBridgeEventDeadlineSeconds,
+			take_storage_value::<u64>(b"EthBridge", b"EventDeadlineSeconds", &[]).map(Into::into),
+		);
+		// stored as a `FeeRate<PerMillion>`
+		migrate(
+			ParameterKey::CennzxDefaultFeeRate,
+			take_storage_value::<u128>(b"Cennzx", b"DefaultFeeRate", &[])
+				.and_then(|parts| u32::try_from(parts).ok())
+				.map(|parts| Permill::from_parts(parts).into()),
+		);
+		migrate(
+			ParameterKey::ProposalBond,
+			take_storage_value::<Balance>(b"Governance", b"ProposalBond", &[]).map(Into::into),
+		);
+		migrate(
+			ParameterKey::ReferendumThreshold,
+			take_storage_value::<Permill>(b"Governance", b"ReferendumThreshold", &[]).map(Into::into),
+		);
+		migrate(
+			ParameterKey::StakingInflationRate,
+			take_storage_value::<FixedU128>(b"Rewards", b"BaseInflationRate", &[]).map(Into::into),
+		);
+
+		T::DbWeight::get().reads_writes(12, 12)
+	}
+	/// Invalidate calls noted or proposed before the parameter registry, removed setters changed the call encoding
+	/// Noted calls are removed and proposals with a call are concluded, returning their deposits and bonds
+	fn invalidate_proposal_calls() -> Weight {
+		let preimages: Vec<_> = <Preimages<T>>::drain().collect();
+		for (_, preimage) in preimages.iter() {
+			let _ = T::Currency::unreserve(&preimage.provider, preimage.deposit);
+		}
+		let proposal_ids: Vec<ProposalId> = ProposalCalls::iter_keys().collect();
+		for proposal_id in proposal_ids.iter().copied() {
+			// proposals approved by referendum are scheduled for enactment
+			let _ = T::Scheduler::cancel_named((GOVERNANCE_ID, proposal_id).encode());
+			if let Some(proposal) = Self::proposals(proposal_id) {
+				let _ = T::Currency::unreserve(&proposal.sponsor, Self::proposal_bond());
+			}
+			Self::remove_proposal_call(proposal_id);
+			ProposalVotes::remove(proposal_id);
+			Self::remove_deliberation_deadline(proposal_id);
+			<ReferendumStartTime<T>>::remove(proposal_id);
+			ReferendumVotes::<T>::remove_prefix(proposal_id, None);
+			DelegateVotes::<T>::remove_prefix(proposal_id, None);
+			<FastTracks<T>>::remove(proposal_id);
+			FastTrackVotes::remove(proposal_id);
+			if let Some(referendum_end) = <FastTrackedReferendums<T>>::take(proposal_id) {
+				<FastTrackedReferendumEnds<T>>::remove(referendum_end, proposal_id);
+			}
+			Self::conclude_proposal(proposal_id, ProposalStatusInfo::CallInvalidated);
+		}
+		let (preimage_count, proposal_count) = (preimages.len() as u64, proposal_ids.len() as u64);
+
+		T::DbWeight::get().reads_writes(
+			1 + preimage_count * 2 + proposal_count * 6,
+			preimage_count * 2 + proposal_count * 16,
+		)
+	}
+	/// Migrate referendum votes counted per account to stake weighted votes keyed by stash
+	/// Votes in progress are counted with the voter's current stake and without conviction
+	fn migrate_referendum_votes() -> Weight {
+		#[allow(dead_code)]
+		mod v0_storage {
+			use super::{Config, ProposalId};
+			use codec::{Decode, Encode};
+			use scale_info::TypeInfo;
+
+			#[derive(Decode, Encode, Debug, Default, PartialEq, TypeInfo)]
+			pub struct ReferendumVoteCount {
+				pub vote: u8,
+			}
+
+			pub struct Module<T>(sp_std::marker::PhantomData<T>);
+			frame_support::decl_storage! {
+				trait Store for Module<T: Config> as Governance {
+					pub ReferendumVotes: double_map hasher(twox_64_concat) ProposalId, hasher(twox_64_concat) T::AccountId => ReferendumVoteCount;
+					pub ReferendumVetoSum: map hasher(twox_64_concat) ProposalId => u32;
+				}
+			}
+		}
+
+		let votes: Vec<_> = <v0_storage::ReferendumVotes<T>>::drain().collect();
+		v0_storage::ReferendumVetoSum::remove_all(None);
+		let migrated = votes.len() as u64;
+
+		for (proposal_id, voter, v0_storage::ReferendumVoteCount { vote }) in votes {
+			// accounts which no longer stake have no vote
+			let stash = match T::StakingAmount::stash(&voter) {
+				Some(stash) => stash,
+				None => continue,
+			};
+			if ReferendumVotes::<T>::contains_key(proposal_id, &stash) {
+				continue;
+			}
+			let vote = ReferendumVoteCount {
+				vote,
+				stake: T::StakingAmount::active_balance(&voter),
+				conviction: Conviction::None,
+			};
+			ReferendumTallies::mutate(proposal_id, |tally| tally.add(&vote));
+			ReferendumVotes::<T>::insert(proposal_id, stash, vote);
+		}
+
+		T::DbWeight::get().reads_writes(3 * migrated + 1, 3 * migrated + 2)
+	}
 	/// Return current council members
 	pub fn get_council() -> Vec<T::AccountId> {
 		Self::council()
 	}
+	/// Return all referendum vote delegates and the accounts delegating to them
+	pub fn get_delegations() -> Vec<(T::AccountId, Vec<T::AccountId>)> {
+		<Delegators<T>>::iter().collect()
+	}
 	/// Return all vote information on active proposals
 	pub fn get_proposal_votes() -> Vec<(ProposalId, ProposalVoteInfo)> {
 		ProposalVotes::iter().collect()
 	}
+	/// Return all spending proposals and their milestone release history
+	pub fn get_spending_proposals() -> Vec<(ProposalId, SpendingProposal<T::AccountId, T::BlockNumber>)> {
+		<SpendingProposals<T>>::iter().collect()
+	}
+	/// Return all council vote information on pending milestone releases
+	pub fn get_milestone_votes() -> Vec<(ProposalId, ProposalVoteInfo)> {
+		MilestoneVotes::iter().collect()
+	}
+	/// Return the lifecycle details of a proposal
+	pub fn get_proposal(proposal_id: ProposalId) -> Option<ProposalDetails<T::AccountId, T::BlockNumber>> {
+		let proposal = Self::proposals(proposal_id)?;
+		let status = Self::proposal_status(proposal_id)?;
+		let referendum_start_time = Self::referendum_start_time(proposal_id);
+		let referendum_end = Self::fast_tracked_referendum(proposal_id)
+			.or_else(|| referendum_start_time.map(|start| start + T::BlockNumber::from(REFERENDUM_LENGTH)));
+		Some(ProposalDetails {
+			proposal_id,
+			sponsor: proposal.sponsor,
+			justification_uri: proposal.justification_uri,
+			enactment_delay: proposal.enactment_delay,
+			status,
+			deliberation_deadline: Self::deliberation_deadline(proposal_id),
+			referendum_start_time,
+			referendum_end,
+			referendum_tally: Self::referendum_tally(proposal_id),
+		})
+	}
+	/// Return the lifecycle details of all proposals, optionally only those with `status`
+	pub fn get_proposals(status: Option<ProposalStatusInfo>) -> Vec<ProposalDetails<T::AccountId, T::BlockNumber>> {
+		<Proposals<T>>::iter_keys()
+			.filter_map(Self::get_proposal)
+			.filter(|details| status.as_ref().map_or(true, |status| details.status == *status))
+			.collect()
+	}
+	/// Return a description of the call a proposal will enact, if any
+	pub fn get_proposal_call(proposal_id: ProposalId) -> Option<ProposalCallInfo<T::Hash>> {
+		let encoded_call = Self::proposal_calls(proposal_id)?;
+		let call = <T as Config>::Call::decode(&mut &encoded_call[..]).ok()?;
+		let metadata = call.get_call_metadata();
+		Some(ProposalCallInfo {
+			call_hash: T::Hashing::hash(&encoded_call[..]),
+			pallet_name: metadata.pallet_name.as_bytes().to_vec(),
+			function_name: metadata.function_name.as_bytes().to_vec(),
+			weight: call.get_dispatch_info().weight,
+			encoded_call,
+		})
+	}
+	/// The deposit reserved per byte of a noted call
+	pub fn preimage_byte_deposit() -> Balance {
+		PreimageByteDeposit::get().unwrap_or_else(T::DefaultPreimageByteDeposit::get)
+	}
+	/// Decode `encoded_call` as a runtime call and check it fits within a block
+	pub fn validate_call(encoded_call: &[u8]) -> Result<<T as Config>::Call, DispatchError> {
+		let input = &mut &encoded_call[..];
+		let call = <T as Config>::Call::decode(input).map_err(|_| Error::<T>::InvalidCall)?;
+		// trailing bytes are not part of the call
+		ensure!(input.is_empty(), Error::<T>::InvalidCall);
+		ensure!(
+			call.get_dispatch_info().weight <= T::BlockWeights::get().max_block,
+			Error::<T>::CallWeightTooHigh
+		);
+		Ok(call)
+	}
+	/// Record the final status of a proposal, its record is retained for `PROPOSAL_RETENTION_BLOCKS`
+	fn conclude_proposal(proposal_id: ProposalId, status: ProposalStatusInfo) {
+		ProposalStatus::insert(proposal_id, status);
+		let prune_block = <frame_system::Pallet<T>>::block_number() + T::BlockNumber::from(PROPOSAL_RETENTION_BLOCKS);
+		<ProposalPruning<T>>::insert(prune_block, proposal_id, ());
+	}
+	/// Prune the records of proposals whose retention ends at `block_number`
+	fn prune_proposals(block_number: T::BlockNumber) -> Weight {
+		let mut pruned = 0_u64;
+		for (proposal_id, _) in <ProposalPruning<T>>::drain_prefix(block_number) {
+			<Proposals<T>>::remove(proposal_id);
+			ProposalStatus::remove(proposal_id);
+			ProposalVotes::remove(proposal_id);
+			ReferendumTallies::remove(proposal_id);
+			<SpendingProposals<T>>::remove(proposal_id);
+			pruned += 1;
+		}
+
+		T::DbWeight::get().reads_writes(1 + pruned, pruned * 6)
+	}
+	/// Set the deadline for the council to reach a majority on a new proposal
+	fn set_deliberation_deadline(proposal_id: ProposalId) {
+		let deadline = <frame_system::Pallet<T>>::block_number() + T::DeliberationPeriod::get();
+		<DeliberationDeadlines<T>>::insert(proposal_id, deadline);
+		<DeliberationExpiries<T>>::insert(deadline, proposal_id, ());
+	}
+	/// Remove the deliberation deadline of a proposal the council is no longer deliberating
+	fn remove_deliberation_deadline(proposal_id: ProposalId) {
+		if let Some(deadline) = <DeliberationDeadlines<T>>::take(proposal_id) {
+			<DeliberationExpiries<T>>::remove(deadline, proposal_id);
+		}
+	}
+	/// Index the end of referendums started before referendum ends were scheduled
+	/// Referendums past their end are ended in the next block
+	fn index_referendum_ends() -> Weight {
+		let next_block = <frame_system::Pallet<T>>::block_number() + T::BlockNumber::from(1u32);
+		let mut indexed = 0_u64;
+		for (proposal_id, start_time) in <ReferendumStartTime<T>>::iter() {
+			if Self::proposal_status(proposal_id) == Some(ProposalStatusInfo::ReferendumDeliberation) {
+				let referendum_end = sp_std::cmp::max(start_time + T::BlockNumber::from(REFERENDUM_LENGTH), next_block);
+				<ReferendumEnds<T>>::insert(referendum_end, proposal_id, ());
+				indexed += 1;
+			}
+		}
+
+		T::DbWeight::get().reads_writes(1 + indexed * 2, indexed)
+	}
+	/// Expire a proposal the council did not reach a majority on before its deliberation deadline
+	/// The bond is returned if the proposal had at least as many council votes for as against, otherwise it is slashed
+	/// Returns the weight consumed
+	fn expire_proposal(proposal_id: ProposalId) -> Weight {
+		<DeliberationDeadlines<T>>::remove(proposal_id);
+		let proposal = match Self::proposals(proposal_id) {
+			Some(proposal) => proposal,
+			None => {
+				warn!("expire proposal: {:?} failed, not found", proposal_id);
+				return T::DbWeight::get().reads_writes(1, 1);
+			}
+		};
+		let tally = ProposalVotes::take(proposal_id).count_votes();
+		let bond_returned = tally.yes >= tally.no;
+		if bond_returned {
+			let _ = T::Currency::unreserve(&proposal.sponsor, Self::proposal_bond());
+		} else {
+			let _ = T::Currency::slash_reserved(&proposal.sponsor, Self::proposal_bond());
+		}
+		Self::remove_proposal_call(proposal_id);
+		<SpendingProposals<T>>::remove(proposal_id);
+		Self::conclude_proposal(proposal_id, ProposalStatusInfo::Expired);
+		Self::deposit_event(RawEvent::ProposalExpired(proposal_id, bond_returned));
+
+		T::DbWeight::get().reads_writes(4, 9)
+	}
+	/// Remove a proposal's call, returning the deposit to the account that noted it
+	fn remove_proposal_call(proposal_id: ProposalId) {
+		ProposalCalls::remove(proposal_id);
+		if let Some((provider, deposit)) = <ProposalCallDeposits<T>>::take(proposal_id) {
+			let _ = T::Currency::unreserve(&provider, deposit);
+		}
+	}
+	/// Elect a new council from the candidates by approval voting, weighted by the voters' active stake
+	/// The sitting council is kept if no valid candidate received approvals.
+	/// Candidates with approvals have their bond returned, others are slashed.
+	/// Voters have their voting bond returned
+	/// Returns the weight consumed
+	pub fn elect_council() -> Weight {
+		let candidates = <CouncilCandidates<T>>::take();
+		// the number of voters is bounded by the voting bond they reserve
+		let votes: Vec<(T::AccountId, Vec<T::AccountId>)> = <CouncilVotes<T>>::drain().collect();
+		for (voter, bond) in <CouncilVoterBonds<T>>::drain() {
+			let _ = T::Currency::unreserve(&voter, bond);
+		}
+		let weight = 1_000_000u64
+			.saturating_add(1_000_000u64.saturating_mul(votes.len() as Weight))
+			.saturating_add(500_000u64.saturating_mul(candidates.len() as Weight));
+
+		let mut approvals: Vec<(T::AccountId, Balance)> = candidates.iter().map(|c| (c.clone(), 0)).collect();
+		for (voter, approved) in votes.iter() {
+			let stake: Balance = T::StakingAmount::active_balance(voter);
+			for candidate in approved {
+				if let Ok(idx) = candidates.binary_search(candidate) {
+					approvals[idx].1 = approvals[idx].1.saturating_add(stake);
+				}
+			}
+		}
+		for (candidate, approval) in approvals.iter() {
+			if approval.is_zero() {
+				let _ = T::Currency::slash_reserved(candidate, Self::candidacy_bond());
+			} else {
+				let _ = T::Currency::unreserve(candidate, Self::candidacy_bond());
+			}
+		}
+
+		// candidates must still meet the council requirements at the time of election
+		approvals.retain(|(candidate, approval)| {
+			!approval.is_zero() && Self::check_council_account_validity(candidate).is_ok()
+		});
+		let term = CouncilTerm::mutate(|term| {
+			*term = term.saturating_add(1);
+			*term
+		});
+		if approvals.is_empty() {
+			warn!("council election for term: {:?} had no approved candidates", term);
+			return weight;
+		}
+
+		// highest approval first, ties are broken by account Id
+		approvals.sort_by(|(a, a_approval), (b, b_approval)| b_approval.cmp(a_approval).then_with(|| a.cmp(b)));
+		let mut new_council: Vec<T::AccountId> = approvals
+			.into_iter()
+			.take(T::MaxCouncilSize::get() as usize)
+			.map(|(candidate, _)| candidate)
+			.collect();
+		new_council.sort();
+		Self::set_council(new_council.clone());
+		Self::deposit_event(RawEvent::CouncilElected(term, new_council));
+
+		weight
+	}
+	/// Replace the council, carrying over the votes of continuing members on active proposals
+	fn set_council(new_council: Vec<T::AccountId>) {
+		let old_council = Self::council();
+		Self::map_council_votes(|votes| {
+			let mut new_votes = ProposalVoteInfo::default();
+			for (idx, member) in old_council.iter().enumerate() {
+				if let (Some(vote), Ok(new_idx)) = (votes.get_vote(idx as u8), new_council.binary_search(member)) {
+					new_votes.record_vote(new_idx as u8, vote);
+				}
+			}
+			new_votes
+		});
+		<Council<T>>::put(new_council);
+	}
+	/// Apply `f` to the council votes of all active proposals, spending milestones and fast-track requests
+	fn map_council_votes(f: impl Fn(ProposalVoteInfo) -> ProposalVoteInfo) {
+		ProposalVotes::translate::<ProposalVoteInfo, _>(|_, votes| Some(f(votes)));
+		MilestoneVotes::translate::<ProposalVoteInfo, _>(|_, votes| Some(f(votes)));
+		FastTrackVotes::translate::<ProposalVoteInfo, _>(|_, votes| Some(f(votes)));
+	}
+	/// The account spending proposals are paid from
+	pub fn treasury_account() -> T::AccountId {
+		T::TreasuryPalletId::get().into_account()
+	}
 	/// Check an accounts staked amount and total number of registered identities
 	pub fn check_council_account_validity(account: &T::AccountId) -> DispatchResult {
 		// Check the amount they have staked
@@ -453,27 +1448,108 @@ impl<T: Config> Module<T> {
 		);
 		Ok(())
 	}
+	/// Record a stake weighted referendum vote for `voter`
+	/// The vote is recorded against the voter's stash, where stake is locked according to `conviction`
+	fn do_vote_on_referendum(
+		voter: T::AccountId,
+		proposal_id: ProposalId,
+		approve: bool,
+		conviction: Conviction,
+	) -> DispatchResult {
+		ensure!(
+			Self::proposal_status(proposal_id) == Some(ProposalStatusInfo::ReferendumDeliberation),
+			Error::<T>::ReferendumNotDeliberating
+		);
+		// Validate voters identity and staking assets
+		Self::check_voter_account_validity(&voter)?;
+		let stash = T::StakingAmount::stash(&voter).ok_or(Error::<T>::NotStaker)?;
+		ensure!(
+			!ReferendumVotes::<T>::contains_key(proposal_id, &stash),
+			Error::<T>::DoubleVote
+		);
+		let block_number = <frame_system::Pallet<T>>::block_number();
+		let start_time = Self::referendum_start_time(proposal_id).ok_or(Error::<T>::ProposalMissing)?;
+		ensure!(block_number >= start_time, Error::<T>::ReferendumNotStarted);
+
+		let stake: Balance = T::StakingAmount::active_balance(&voter);
+		if conviction.lock_periods() > 0 {
+			let referendum_length = T::BlockNumber::from(REFERENDUM_LENGTH);
+			let locked_until = start_time + referendum_length + referendum_length * conviction.lock_periods().into();
+			Self::extend_vote_lock(&stash, stake, locked_until);
+		}
+
+		let vote = ReferendumVoteCount {
+			vote: if approve { 0 } else { 1 },
+			stake,
+			conviction,
+		};
+		ReferendumTallies::mutate(proposal_id, |tally| tally.add(&vote));
+		// Enter vote in storage, the voter's delegators are counted with it when the referendum ends
+		DelegateVotes::<T>::insert(proposal_id, &voter, vote.vote);
+		ReferendumVotes::<T>::insert(proposal_id, stash, vote);
+		Ok(())
+	}
+	/// Remove `delegator` from `delegate`'s ordered set of delegators
+	fn remove_delegator(delegate: &T::AccountId, delegator: &T::AccountId) {
+		<Delegators<T>>::mutate_exists(delegate, |maybe_delegators| {
+			if let Some(delegators) = maybe_delegators {
+				if let Ok(idx) = delegators.binary_search(delegator) {
+					delegators.remove(idx);
+				}
+				if delegators.is_empty() {
+					*maybe_delegators = None;
+				}
+			}
+		});
+	}
+	/// Lock `amount` of `stash`'s stake until at least `locked_until`
+	/// Overlapping locks are merged, keeping the greater amount and expiry
+	fn extend_vote_lock(stash: &T::AccountId, amount: Balance, locked_until: T::BlockNumber) {
+		let (amount, locked_until) = match Self::referendum_vote_lock(stash) {
+			Some((locked_amount, locked_until_prev)) => (
+				sp_std::cmp::max(locked_amount, amount),
+				sp_std::cmp::max(locked_until_prev, locked_until),
+			),
+			None => (amount, locked_until),
+		};
+		T::StakingCurrency::set_lock(GOVERNANCE_ID, stash, amount, WithdrawReasons::all());
+		<ReferendumVoteLocks<T>>::insert(stash, (amount, locked_until));
+	}
 	/// Finish up a referendum and tally vetos
-	pub fn end_referendum(proposal_id: ProposalId) {
+	/// Delegated stake is added to the tally following the delegations and active stake at this point
+	/// The referendum is vetoed when the veto share of all voted stake meets the `ReferendumThreshold`
+	/// Returns the weight consumed
+	pub fn end_referendum(proposal_id: ProposalId) -> Weight {
 		let proposal = match Self::proposals(proposal_id) {
 			Some(proposal) => proposal,
 			None => {
 				warn!("clean up proposal: {:?} failed, not found", proposal_id);
-				return;
+				return T::DbWeight::get().reads(1);
 			}
 		};
-		let max_stakers: u32 = T::StakingAmount::count_nominators();
-		ReferendumVotes::<T>::remove_prefix(proposal_id, None);
+		let delegate_votes: Vec<(T::AccountId, u8)> = DelegateVotes::<T>::drain_prefix(proposal_id).collect();
+		let delegation_count = ReferendumTallies::mutate(proposal_id, |tally| {
+			delegate_votes.iter().fold(0_u32, |count, (delegate, vote)| {
+				count.saturating_add(Self::tally_delegated_votes(proposal_id, delegate, *vote, tally))
+			})
+		});
+		let vote_count = ReferendumVotes::<T>::drain_prefix(proposal_id).count() as u32;
+		<FastTracks<T>>::remove(proposal_id);
+		FastTrackVotes::remove(proposal_id);
+		// the referendum may end before its fast-tracked end
+		if let Some(referendum_end) = <FastTrackedReferendums<T>>::take(proposal_id) {
+			<FastTrackedReferendumEnds<T>>::remove(referendum_end, proposal_id);
+		}
+		// the final tally is kept as a record of the referendum
+		let tally = Self::referendum_tally(proposal_id);
 
-		if Permill::from_rational(Self::referendum_veto_sum(proposal_id), max_stakers) >= Self::referendum_threshold() {
+		if tally.is_vetoed(Self::referendum_threshold()) {
 			// Too many veto votes, not going ahead
-			Self::deposit_event(Event::ReferendumVeto(proposal_id));
+			Self::deposit_event(RawEvent::ReferendumVeto(proposal_id));
 			let _ = T::Currency::slash_reserved(&proposal.sponsor, Self::proposal_bond());
-			<Proposals<T>>::remove(proposal_id);
-			ProposalCalls::remove(proposal_id);
+			Self::remove_proposal_call(proposal_id);
 			<ReferendumStartTime<T>>::remove(proposal_id);
-			ReferendumVetoSum::remove(proposal_id);
-			ProposalStatus::insert(proposal_id, ProposalStatusInfo::ReferendumVetoed);
+			Self::conclude_proposal(proposal_id, ProposalStatusInfo::ReferendumVetoed);
 		} else {
 			if ProposalCalls::contains_key(proposal_id) {
 				if T::Scheduler::schedule_named(
@@ -488,12 +1564,53 @@ impl<T: Config> Module<T> {
 				{
 					frame_support::print("LOGIC ERROR: governance/schedule_named failed");
 				}
-				Self::deposit_event(Event::ReferendumApproved(proposal_id));
+				Self::deposit_event(RawEvent::ReferendumApproved(proposal_id));
 				ProposalStatus::insert(proposal_id, ProposalStatusInfo::ApprovedWaitingEnactment);
 			} else {
 				// Proposal does not have a onchain call, it can be considered enacted
-				ProposalStatus::insert(proposal_id, ProposalStatusInfo::ApprovedEnacted(true));
+				Self::conclude_proposal(proposal_id, ProposalStatusInfo::ApprovedEnacted(true));
 			}
 		}
+
+		T::WeightInfo::end_referendum(vote_count, delegation_count)
+	}
+	/// Add the active stake of `delegate`'s eligible delegators who have not voted to `tally`, following the delegate's `vote`
+	/// Returns the number of delegations inspected
+	fn tally_delegated_votes(
+		proposal_id: ProposalId,
+		delegate: &T::AccountId,
+		vote: u8,
+		tally: &mut ReferendumTally,
+	) -> u32 {
+		let mut delegation_count = 0_u32;
+		for delegator in Self::delegators(delegate) {
+			delegation_count = delegation_count.saturating_add(1);
+			if Self::check_voter_account_validity(&delegator).is_err() {
+				continue;
+			}
+			let stash = match T::StakingAmount::stash(&delegator) {
+				Some(stash) => stash,
+				None => continue,
+			};
+			if ReferendumVotes::<T>::contains_key(proposal_id, &stash) {
+				continue;
+			}
+			tally.add(&ReferendumVoteCount {
+				vote,
+				stake: T::StakingAmount::active_balance(&delegator),
+				conviction: Conviction::None,
+			});
+		}
+		delegation_count
+	}
+}
+
+/// Reads a network parameter from the registry, for use as a pallet's `Get` config type
+/// `K` is the parameter to read
+pub struct GovernanceParameter<T, K>(PhantomData<(T, K)>);
+
+impl<T: Config, K: Get<ParameterKey>, V: TryFrom<ParameterValue> + Default> Get<V> for GovernanceParameter<T, K> {
+	fn get() -> V {
+		Module::<T>::parameter(K::get())
 	}
 }
@@ -17,8 +17,8 @@ use crate as crml_governance;
 use cennznet_primitives::types::{AssetId, Balance};
 use crml_generic_asset::StakingAssetCurrency;
 use crml_support::{RegistrationInfo, StakingAmount};
-use frame_support::{parameter_types, weights::Weight, PalletId};
-use frame_system::EnsureRoot;
+use frame_support::{ord_parameter_types, parameter_types, weights::Weight, PalletId};
+use frame_system::{EnsureRoot, EnsureSignedBy};
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
@@ -40,7 +40,7 @@ frame_support::construct_runtime!(
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
 		Scheduler: pallet_scheduler::{Pallet, Call, Config, Storage, Event<T>},
 		GenericAsset: crml_generic_asset::{Pallet, Call, Storage, Config<T>, Event<T>},
-		Governance: crml_governance::{Pallet, Call, Storage, Event},
+		Governance: crml_governance::{Pallet, Call, Storage, Event<T>},
 	}
 );
 
@@ -50,7 +50,7 @@ parameter_types! {
 		frame_system::limits::BlockWeights::simple_max(1_000_000);
 }
 impl frame_system::Config for Test {
-	type BlockWeights = ();
+	type BlockWeights = BlockWeights;
 	type BlockLength = ();
 	type BaseCallFilter = frame_support::traits::Everything;
 	type Origin = Origin;
@@ -113,6 +113,7 @@ impl StakingAmount for MockStakingAmount {
 			1 => 1_000_000,
 			2 => 20_000_000,
 			3 => 30_000_000,
+			6 => 10_000_000,
 			_ => 0,
 		}
 	}
@@ -120,6 +121,15 @@ impl StakingAmount for MockStakingAmount {
 	fn count_nominators() -> u32 {
 		1
 	}
+
+	fn stash(controller: &Self::AccountId) -> Option<Self::AccountId> {
+		match controller {
+			// 6 controls stash 7
+			6 => Some(7),
+			_ if Self::active_balance(controller) > 0 => Some(*controller),
+			_ => None,
+		}
+	}
 }
 
 pub struct MockRegistrationImplementation;
@@ -131,6 +141,7 @@ impl RegistrationInfo for MockRegistrationImplementation {
 			1 => 2,
 			2 => 1,
 			3 => 3,
+			6 => 2,
 			_ => 0,
 		}
 	}
@@ -140,17 +151,30 @@ parameter_types! {
 	pub const DefaultListingDuration: u64 = 5;
 	pub const MaxAttributeLength: u8 = 140;
 	pub const MaxCouncilSize: u16 = 2;
+	pub const CouncilTermLength: u64 = 100;
+	pub const DeliberationPeriod: u64 = 50;
+	pub const DefaultPreimageByteDeposit: Balance = 1;
+}
+ord_parameter_types! {
+	pub const FastTrackRequester: AccountId = 4;
 }
 impl crate::Config for Test {
 	type Call = Call;
 	type Currency = StakingAssetCurrency<Self>;
+	type StakingCurrency = StakingAssetCurrency<Self>;
 	type MaxCouncilSize = MaxCouncilSize;
+	type CouncilTermLength = CouncilTermLength;
+	type DeliberationPeriod = DeliberationPeriod;
 	type Scheduler = Scheduler;
 	type PalletsOrigin = OriginCaller;
 	type Event = Event;
 	type WeightInfo = ();
 	type Registration = MockRegistrationImplementation;
 	type StakingAmount = MockStakingAmount;
+	type MultiCurrency = GenericAsset;
+	type TreasuryPalletId = TreasuryPalletId;
+	type DefaultPreimageByteDeposit = DefaultPreimageByteDeposit;
+	type FastTrackOrigin = EnsureSignedBy<FastTrackRequester, AccountId>;
 }
 
 #[derive(Default)]
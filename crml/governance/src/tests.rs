@@ -1,7 +1,16 @@
 use super::*;
-use crate::mock::{ExtBuilder, Governance, Test};
-use frame_support::{assert_noop, assert_ok};
-use sp_runtime::DispatchError;
+use crate::mock::{
+	AccountId, Call as MockCall, CouncilTermLength, DefaultPreimageByteDeposit, DeliberationPeriod, Event as TestEvent,
+	ExtBuilder, GenericAsset, Governance, System, Test,
+};
+use codec::Encode;
+use crml_generic_asset::StakingAssetCurrency;
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{Currency, OnInitialize, OnRuntimeUpgrade, ReservableCurrency},
+};
+use sp_runtime::traits::{BlakeTwo256, Hash};
+use sp_runtime::{DispatchError, FixedPointNumber};
 
 #[test]
 fn add_council_member() {
@@ -89,3 +98,1589 @@ fn set_minimum_stake_bond_not_root_should_fail() {
 		);
 	});
 }
+
+/// Setup a proposal in the referendum phase, returning its Id
+fn setup_referendum(sponsor: AccountId) -> ProposalId {
+	let proposal_id = Governance::next_proposal_id();
+	<Proposals<Test>>::insert(
+		proposal_id,
+		Proposal {
+			sponsor,
+			justification_uri: b"https://example.com/proposal".to_vec(),
+			enactment_delay: 1,
+		},
+	);
+	ProposalCalls::insert(proposal_id, vec![]);
+	ProposalStatus::insert(proposal_id, ProposalStatusInfo::ReferendumDeliberation);
+	<ReferendumStartTime<Test>>::insert(proposal_id, System::block_number());
+	<ReferendumEnds<Test>>::insert(System::block_number() + REFERENDUM_LENGTH as u64, proposal_id, ());
+	NextProposalId::put(proposal_id + 1);
+	proposal_id
+}
+
+#[test]
+fn referendum_votes_are_stake_weighted() {
+	ExtBuilder::default().build().execute_with(|| {
+		let proposal_id = setup_referendum(3);
+
+		assert_ok!(Governance::vote_for_referendum(
+			frame_system::RawOrigin::Signed(1).into(),
+			proposal_id,
+			Conviction::Locked1x,
+		));
+		assert_ok!(Governance::vote_against_referendum(
+			frame_system::RawOrigin::Signed(3).into(),
+			proposal_id,
+			Conviction::None,
+		));
+
+		assert_eq!(
+			Governance::referendum_tally(proposal_id),
+			ReferendumTally {
+				approve: 1_000_000,
+				veto: 3_000_000,
+			}
+		);
+	});
+}
+
+#[test]
+fn referendum_double_vote_should_fail() {
+	ExtBuilder::default().build().execute_with(|| {
+		let proposal_id = setup_referendum(3);
+
+		assert_ok!(Governance::vote_against_referendum(
+			frame_system::RawOrigin::Signed(3).into(),
+			proposal_id,
+			Conviction::None,
+		));
+		assert_noop!(
+			Governance::vote_for_referendum(frame_system::RawOrigin::Signed(3).into(), proposal_id, Conviction::None),
+			Error::<Test>::DoubleVote
+		);
+	});
+}
+
+#[test]
+fn referendum_vetoed_by_share_of_voted_stake() {
+	ExtBuilder::default().build().execute_with(|| {
+		let proposal_id = setup_referendum(3);
+
+		// 30_000_000 veto vs. 1_000_000 approve
+		assert_ok!(Governance::vote_for_referendum(
+			frame_system::RawOrigin::Signed(1).into(),
+			proposal_id,
+			Conviction::Locked1x,
+		));
+		assert_ok!(Governance::vote_against_referendum(
+			frame_system::RawOrigin::Signed(3).into(),
+			proposal_id,
+			Conviction::Locked1x,
+		));
+		Governance::end_referendum(proposal_id);
+
+		assert_eq!(
+			Governance::proposal_status(proposal_id),
+			Some(ProposalStatusInfo::ReferendumVetoed)
+		);
+		// the final tally is kept
+		assert_eq!(
+			Governance::referendum_tally(proposal_id),
+			ReferendumTally {
+				approve: 1_000_000,
+				veto: 30_000_000,
+			}
+		);
+	});
+}
+
+#[test]
+fn referendum_approved_by_share_of_voted_stake() {
+	ExtBuilder::default().build().execute_with(|| {
+		let proposal_id = setup_referendum(3);
+
+		// 1_000_000 veto vs. 180_000_000 approve
+		assert_ok!(Governance::vote_against_referendum(
+			frame_system::RawOrigin::Signed(1).into(),
+			proposal_id,
+			Conviction::Locked1x,
+		));
+		assert_ok!(Governance::vote_for_referendum(
+			frame_system::RawOrigin::Signed(3).into(),
+			proposal_id,
+			Conviction::Locked6x,
+		));
+		Governance::end_referendum(proposal_id);
+
+		assert_eq!(
+			Governance::proposal_status(proposal_id),
+			Some(ProposalStatusInfo::ApprovedWaitingEnactment)
+		);
+	});
+}
+
+#[test]
+fn referendum_conviction_locks_stake() {
+	ExtBuilder::default().build().execute_with(|| {
+		let proposal_id = setup_referendum(3);
+		let start = System::block_number();
+
+		assert_ok!(Governance::vote_for_referendum(
+			frame_system::RawOrigin::Signed(3).into(),
+			proposal_id,
+			Conviction::Locked2x,
+		));
+		let locked_until = start + 3 * REFERENDUM_LENGTH as u64;
+		assert_eq!(Governance::referendum_vote_lock(3), Some((30_000_000, locked_until)));
+
+		assert_noop!(
+			Governance::unlock_vote(frame_system::RawOrigin::Signed(1).into(), 3),
+			Error::<Test>::VoteLockNotExpired
+		);
+		System::set_block_number(locked_until);
+		assert_ok!(Governance::unlock_vote(frame_system::RawOrigin::Signed(1).into(), 3));
+		assert!(Governance::referendum_vote_lock(3).is_none());
+	});
+}
+
+#[test]
+fn referendum_vote_without_conviction_is_not_locked() {
+	ExtBuilder::default().build().execute_with(|| {
+		let proposal_id = setup_referendum(3);
+
+		assert_ok!(Governance::vote_for_referendum(
+			frame_system::RawOrigin::Signed(3).into(),
+			proposal_id,
+			Conviction::None,
+		));
+		assert!(Governance::referendum_vote_lock(3).is_none());
+	});
+}
+
+#[test]
+fn delegate_counts_delegator_stake_with_delegate_vote() {
+	ExtBuilder::default().build().execute_with(|| {
+		let proposal_id = setup_referendum(3);
+
+		assert_ok!(Governance::delegate(frame_system::RawOrigin::Signed(1).into(), 3));
+		assert_eq!(Governance::delegation(1), Some(3));
+		assert_eq!(Governance::get_delegations(), vec![(3, vec![1])]);
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == TestEvent::Governance(RawEvent::Delegated(1, 3))));
+
+		assert_ok!(Governance::vote_against_referendum(
+			frame_system::RawOrigin::Signed(3).into(),
+			proposal_id,
+			Conviction::Locked1x,
+		));
+		// delegated stake is counted when the referendum ends
+		assert_eq!(Governance::referendum_tally(proposal_id).veto, 30_000_000);
+		Governance::end_referendum(proposal_id);
+
+		// 1's stake is counted without conviction
+		assert_eq!(
+			Governance::referendum_tally(proposal_id),
+			ReferendumTally {
+				approve: 0,
+				veto: 30_100_000,
+			}
+		);
+		assert_eq!(
+			Governance::proposal_status(proposal_id),
+			Some(ProposalStatusInfo::ReferendumVetoed)
+		);
+		// the delegator's stake is not locked
+		assert!(Governance::referendum_vote_lock(1).is_none());
+	});
+}
+
+#[test]
+fn direct_vote_overrides_delegation() {
+	ExtBuilder::default().build().execute_with(|| {
+		let proposal_id = setup_referendum(3);
+
+		assert_ok!(Governance::delegate(frame_system::RawOrigin::Signed(1).into(), 3));
+		assert_ok!(Governance::vote_for_referendum(
+			frame_system::RawOrigin::Signed(1).into(),
+			proposal_id,
+			Conviction::Locked1x,
+		));
+		assert_ok!(Governance::vote_against_referendum(
+			frame_system::RawOrigin::Signed(3).into(),
+			proposal_id,
+			Conviction::Locked1x,
+		));
+		Governance::end_referendum(proposal_id);
+
+		assert_eq!(
+			Governance::referendum_tally(proposal_id),
+			ReferendumTally {
+				approve: 1_000_000,
+				veto: 30_000_000,
+			}
+		);
+	});
+}
+
+#[test]
+fn direct_vote_after_delegate_vote_replaces_delegated_stake() {
+	ExtBuilder::default().build().execute_with(|| {
+		let proposal_id = setup_referendum(3);
+
+		assert_ok!(Governance::delegate(frame_system::RawOrigin::Signed(1).into(), 3));
+		assert_ok!(Governance::vote_against_referendum(
+			frame_system::RawOrigin::Signed(3).into(),
+			proposal_id,
+			Conviction::Locked1x,
+		));
+		assert_eq!(Governance::delegate_vote(proposal_id, 3), Some(1));
+		assert_ok!(Governance::vote_for_referendum(
+			frame_system::RawOrigin::Signed(1).into(),
+			proposal_id,
+			Conviction::Locked1x,
+		));
+		Governance::end_referendum(proposal_id);
+
+		assert!(Governance::delegate_vote(proposal_id, 3).is_none());
+		assert_eq!(
+			Governance::referendum_tally(proposal_id),
+			ReferendumTally {
+				approve: 1_000_000,
+				veto: 30_000_000,
+			}
+		);
+	});
+}
+
+#[test]
+fn delegation_after_delegate_vote_is_counted() {
+	ExtBuilder::default().build().execute_with(|| {
+		let proposal_id = setup_referendum(3);
+
+		assert_ok!(Governance::vote_against_referendum(
+			frame_system::RawOrigin::Signed(3).into(),
+			proposal_id,
+			Conviction::Locked1x,
+		));
+		assert_ok!(Governance::delegate(frame_system::RawOrigin::Signed(1).into(), 3));
+		Governance::end_referendum(proposal_id);
+
+		assert_eq!(Governance::referendum_tally(proposal_id).veto, 30_100_000);
+	});
+}
+
+#[test]
+fn undelegate() {
+	ExtBuilder::default().build().execute_with(|| {
+		let proposal_id = setup_referendum(3);
+
+		assert_ok!(Governance::delegate(frame_system::RawOrigin::Signed(1).into(), 3));
+		assert_ok!(Governance::vote_against_referendum(
+			frame_system::RawOrigin::Signed(3).into(),
+			proposal_id,
+			Conviction::Locked1x,
+		));
+		// stake undelegated before the referendum ends is not counted
+		assert_ok!(Governance::undelegate(frame_system::RawOrigin::Signed(1).into()));
+		assert!(Governance::delegation(1).is_none());
+		assert!(!<Delegators<Test>>::contains_key(3));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == TestEvent::Governance(RawEvent::Undelegated(1))));
+		Governance::end_referendum(proposal_id);
+		assert_eq!(Governance::referendum_tally(proposal_id).veto, 30_000_000);
+
+		assert_noop!(
+			Governance::undelegate(frame_system::RawOrigin::Signed(1).into()),
+			Error::<Test>::NotDelegating
+		);
+	});
+}
+
+#[test]
+fn redelegate_moves_delegator() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Governance::delegate(frame_system::RawOrigin::Signed(1).into(), 3));
+		assert_ok!(Governance::delegate(frame_system::RawOrigin::Signed(1).into(), 2));
+
+		assert_eq!(Governance::delegation(1), Some(2));
+		assert_eq!(Governance::delegators(2), vec![1]);
+		assert!(!<Delegators<Test>>::contains_key(3));
+	});
+}
+
+#[test]
+fn delegate_invalid_request_should_fail() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			Governance::delegate(frame_system::RawOrigin::Signed(1).into(), 1),
+			Error::<Test>::SelfDelegation
+		);
+		// 2 does not have enough registered identities to vote
+		assert_noop!(
+			Governance::delegate(frame_system::RawOrigin::Signed(2).into(), 3),
+			Error::<Test>::NotEnoughRegistrations
+		);
+		assert_ok!(Governance::delegate(frame_system::RawOrigin::Signed(1).into(), 3));
+		assert_noop!(
+			Governance::delegate(frame_system::RawOrigin::Signed(1).into(), 3),
+			Error::<Test>::AlreadyDelegating
+		);
+		<Delegators<Test>>::insert(2, (10..10 + MAX_DELEGATORS as u64).collect::<Vec<AccountId>>());
+		assert_noop!(
+			Governance::delegate(frame_system::RawOrigin::Signed(1).into(), 2),
+			Error::<Test>::TooManyDelegators
+		);
+	});
+}
+
+#[test]
+fn set_parameter_records_history() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(Governance::referendum_threshold(), Permill::from_percent(33));
+
+		assert_ok!(Governance::set_parameter(
+			frame_system::RawOrigin::Root.into(),
+			ParameterKey::ReferendumThreshold,
+			ParameterValue::Permill(Permill::from_percent(50)),
+		));
+		System::set_block_number(2);
+		assert_ok!(Governance::set_parameter(
+			frame_system::RawOrigin::Root.into(),
+			ParameterKey::ReferendumThreshold,
+			ParameterValue::Permill(Permill::from_percent(40)),
+		));
+
+		assert_eq!(Governance::referendum_threshold(), Permill::from_percent(40));
+		assert_eq!(
+			Governance::parameter_history(ParameterKey::ReferendumThreshold),
+			vec![
+				ParameterChange {
+					block_number: 1,
+					previous: ParameterValue::Permill(Permill::from_percent(33)),
+					value: ParameterValue::Permill(Permill::from_percent(50)),
+				},
+				ParameterChange {
+					block_number: 2,
+					previous: ParameterValue::Permill(Permill::from_percent(50)),
+					value: ParameterValue::Permill(Permill::from_percent(40)),
+				},
+			]
+		);
+		assert!(System::events().iter().any(|record| record.event
+			== TestEvent::Governance(RawEvent::ParameterChanged(
+				ParameterKey::ReferendumThreshold,
+				ParameterValue::Permill(Permill::from_percent(40))
+			))));
+	});
+}
+
+#[test]
+fn parameter_history_is_bounded() {
+	ExtBuilder::default().build().execute_with(|| {
+		for bond in 1..=(MAX_PARAMETER_HISTORY as Balance + 1) {
+			assert_ok!(Governance::set_parameter(
+				frame_system::RawOrigin::Root.into(),
+				ParameterKey::ProposalBond,
+				ParameterValue::Balance(bond),
+			));
+		}
+
+		let history = Governance::parameter_history(ParameterKey::ProposalBond);
+		assert_eq!(history.len(), MAX_PARAMETER_HISTORY);
+		// the oldest change was dropped
+		assert_eq!(history[0].previous, ParameterValue::Balance(1));
+		assert_eq!(Governance::proposal_bond(), MAX_PARAMETER_HISTORY as Balance + 1);
+	});
+}
+
+#[test]
+fn set_parameter_invalid_value_should_fail() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			Governance::set_parameter(
+				frame_system::RawOrigin::Signed(1).into(),
+				ParameterKey::ProposalBond,
+				ParameterValue::Balance(10),
+			),
+			DispatchError::BadOrigin
+		);
+		// wrong type
+		assert_noop!(
+			Governance::set_parameter(
+				frame_system::RawOrigin::Root.into(),
+				ParameterKey::ProposalBond,
+				ParameterValue::U64(10),
+			),
+			Error::<Test>::InvalidParameter
+		);
+		// out of bounds
+		assert_noop!(
+			Governance::set_parameter(
+				frame_system::RawOrigin::Root.into(),
+				ParameterKey::EthBridgeEventConfirmations,
+				ParameterValue::U64(0),
+			),
+			Error::<Test>::InvalidParameter
+		);
+	});
+}
+
+frame_support::parameter_types! {
+	pub const EventConfirmationsKey: ParameterKey = ParameterKey::EthBridgeEventConfirmations;
+}
+
+#[test]
+fn governance_parameter_adapter() {
+	ExtBuilder::default().build().execute_with(|| {
+		type EventConfirmations = GovernanceParameter<Test, EventConfirmationsKey>;
+		assert_eq!(<EventConfirmations as Get<u64>>::get(), 3);
+
+		assert_ok!(Governance::set_parameter(
+			frame_system::RawOrigin::Root.into(),
+			ParameterKey::EthBridgeEventConfirmations,
+			ParameterValue::U64(12),
+		));
+		assert_eq!(<EventConfirmations as Get<u64>>::get(), 12);
+	});
+}
+
+#[test]
+fn migrate_parameters_from_pallet_storage() {
+	ExtBuilder::default().build().execute_with(|| {
+		use frame_support::storage::migration::{get_storage_value, put_storage_value};
+
+		put_storage_value(b"EthBridge", b"EventConfirmations", &[], 12_u64);
+		put_storage_value(b"Cennzx", b"DefaultFeeRate", &[], 3_000_u128);
+		put_storage_value(b"Governance", b"ProposalBond", &[], 100 as Balance);
+		// already in the registry, the old value is discarded
+		Parameters::insert(
+			ParameterKey::ReferendumThreshold,
+			ParameterValue::Permill(Permill::from_percent(50)),
+		);
+		put_storage_value(b"Governance", b"ReferendumThreshold", &[], Permill::from_percent(20));
+
+		let _ = Governance::on_runtime_upgrade();
+
+		assert_eq!(
+			Governance::parameters(ParameterKey::EthBridgeEventConfirmations),
+			Some(ParameterValue::U64(12))
+		);
+		assert_eq!(
+			Governance::parameters(ParameterKey::CennzxDefaultFeeRate),
+			Some(ParameterValue::Permill(Permill::from_parts(3_000)))
+		);
+		assert_eq!(Governance::proposal_bond(), 100);
+		assert_eq!(Governance::referendum_threshold(), Permill::from_percent(50));
+		// parameters without previous storage keep their default
+		assert!(Governance::parameters(ParameterKey::StakingInflationRate).is_none());
+		assert!(get_storage_value::<u64>(b"EthBridge", b"EventConfirmations", &[]).is_none());
+	});
+}
+
+#[test]
+fn migrate_parameters_clamps_out_of_bounds_values() {
+	ExtBuilder::default().build().execute_with(|| {
+		use frame_support::storage::migration::put_storage_value;
+
+		put_storage_value(b"EthBridge", b"EventConfirmations", &[], 0_u64);
+		put_storage_value(b"EthBridge", b"EventDeadlineSeconds", &[], 10_000_000_u64);
+		put_storage_value(b"Cennzx", b"DefaultFeeRate", &[], 500_000_u128);
+		put_storage_value(b"Governance", b"ReferendumThreshold", &[], Permill::zero());
+		put_storage_value(
+			b"Rewards",
+			b"BaseInflationRate",
+			&[],
+			FixedU128::saturating_from_integer(2u64),
+		);
+
+		let _ = Governance::on_runtime_upgrade();
+
+		assert_eq!(
+			Governance::parameters(ParameterKey::EthBridgeEventConfirmations),
+			Some(ParameterValue::U64(1))
+		);
+		assert_eq!(
+			Governance::parameters(ParameterKey::EthBridgeEventDeadlineSeconds),
+			Some(ParameterValue::U64(2_419_200))
+		);
+		assert_eq!(
+			Governance::parameters(ParameterKey::CennzxDefaultFeeRate),
+			Some(ParameterValue::Permill(Permill::from_percent(10)))
+		);
+		assert_eq!(Governance::referendum_threshold(), Permill::from_percent(1));
+		assert_eq!(
+			Governance::parameters(ParameterKey::StakingInflationRate),
+			Some(ParameterValue::FixedU128(FixedU128::saturating_from_integer(1u64)))
+		);
+	});
+}
+
+#[test]
+fn migrate_parameters_only_once() {
+	ExtBuilder::default().build().execute_with(|| {
+		use frame_support::storage::migration::{get_storage_value, put_storage_value};
+
+		StorageVersion::put(Releases::V2 as u32);
+		put_storage_value(b"Governance", b"ProposalBond", &[], 100 as Balance);
+
+		let _ = Governance::on_runtime_upgrade();
+
+		assert!(Governance::parameters(ParameterKey::ProposalBond).is_none());
+		assert_eq!(
+			get_storage_value::<Balance>(b"Governance", b"ProposalBond", &[]),
+			Some(100)
+		);
+	});
+}
+
+#[test]
+fn proposal_calls_invalidated_on_parameter_registry_upgrade() {
+	ExtBuilder::default().build().execute_with(|| {
+		let _ = StakingAssetCurrency::<Test>::deposit_creating(&1, 1_000);
+		let _ = StakingAssetCurrency::<Test>::deposit_creating(&3, 1_000);
+		Parameters::insert(ParameterKey::ProposalBond, ParameterValue::Balance(100));
+		// a noted call and a referendum on a call, encoded by the previous runtime
+		let call_hash = BlakeTwo256::hash(&[1, 2, 3]);
+		assert_ok!(StakingAssetCurrency::<Test>::reserve(&1, 3));
+		<Preimages<Test>>::insert(
+			call_hash,
+			Preimage {
+				provider: 1,
+				deposit: 3,
+				call: vec![1, 2, 3],
+			},
+		);
+		let proposal_id = setup_referendum(3);
+		assert_ok!(StakingAssetCurrency::<Test>::reserve(&3, 100));
+		assert_ok!(Governance::vote_for_referendum(
+			frame_system::RawOrigin::Signed(3).into(),
+			proposal_id,
+			Conviction::None,
+		));
+		StorageVersion::put(Releases::V1 as u32);
+
+		let _ = Governance::on_runtime_upgrade();
+
+		assert_eq!(StorageVersion::get(), Releases::V2 as u32);
+		assert!(Governance::preimages(call_hash).is_none());
+		assert_eq!(StakingAssetCurrency::<Test>::reserved_balance(&1), 0);
+		assert!(Governance::proposal_calls(proposal_id).is_none());
+		assert_eq!(
+			Governance::proposal_status(proposal_id),
+			Some(ProposalStatusInfo::CallInvalidated)
+		);
+		assert!(Governance::referendum_start_time(proposal_id).is_none());
+		assert!(!ReferendumVotes::<Test>::contains_key(proposal_id, 3));
+		assert_eq!(StakingAssetCurrency::<Test>::reserved_balance(&3), 0);
+	});
+}
+
+#[test]
+fn referendum_vote_is_recorded_against_stash() {
+	ExtBuilder::default().build().execute_with(|| {
+		let proposal_id = setup_referendum(3);
+		let start = System::block_number();
+
+		// 6 is the controller of stash 7
+		assert_ok!(Governance::vote_for_referendum(
+			frame_system::RawOrigin::Signed(6).into(),
+			proposal_id,
+			Conviction::Locked1x,
+		));
+		assert_eq!(
+			Governance::referendum_votes(proposal_id, 7),
+			ReferendumVoteCount {
+				vote: 0,
+				stake: 10_000_000,
+				conviction: Conviction::Locked1x,
+			}
+		);
+		assert!(!ReferendumVotes::<Test>::contains_key(proposal_id, 6));
+		assert_eq!(
+			Governance::referendum_vote_lock(7),
+			Some((10_000_000, start + 2 * REFERENDUM_LENGTH as u64))
+		);
+		assert!(Governance::referendum_vote_lock(6).is_none());
+
+		assert_noop!(
+			Governance::vote_against_referendum(
+				frame_system::RawOrigin::Signed(6).into(),
+				proposal_id,
+				Conviction::None,
+			),
+			Error::<Test>::DoubleVote
+		);
+	});
+}
+
+#[test]
+fn migrate_referendum_votes_to_stash_stake_weighted_votes() {
+	ExtBuilder::default().build().execute_with(|| {
+		use frame_support::storage::migration::{get_storage_value, put_storage_value};
+		use frame_support::{StorageHasher, Twox64Concat};
+
+		let proposal_id = setup_referendum(3);
+		// proposals with a call are invalidated by later migrations
+		ProposalCalls::remove(proposal_id);
+		let proposal_key = Twox64Concat::hash(&proposal_id.encode());
+		// v0 votes are a bare yes (0) / no (1) per voting account
+		for (voter, vote) in [(6_u64, 0_u8), (3, 1), (9, 1)] {
+			let key = [proposal_key.clone(), Twox64Concat::hash(&voter.encode())].concat();
+			put_storage_value(b"Governance", b"ReferendumVotes", &key, vote);
+		}
+		put_storage_value(b"Governance", b"ReferendumVetoSum", &proposal_key, 2_u32);
+		StorageVersion::put(Releases::V0 as u32);
+
+		let _ = Governance::on_runtime_upgrade();
+
+		assert_eq!(StorageVersion::get(), Releases::V2 as u32);
+		assert_eq!(
+			Governance::referendum_votes(proposal_id, 7),
+			ReferendumVoteCount {
+				vote: 0,
+				stake: 10_000_000,
+				conviction: Conviction::None,
+			}
+		);
+		assert_eq!(
+			Governance::referendum_votes(proposal_id, 3),
+			ReferendumVoteCount {
+				vote: 1,
+				stake: 30_000_000,
+				conviction: Conviction::None,
+			}
+		);
+		assert!(!ReferendumVotes::<Test>::contains_key(proposal_id, 6));
+		// votes of accounts which no longer stake are dropped
+		assert!(!ReferendumVotes::<Test>::contains_key(proposal_id, 9));
+		assert_eq!(
+			Governance::referendum_tally(proposal_id),
+			ReferendumTally {
+				approve: 1_000_000,
+				veto: 3_000_000,
+			}
+		);
+		assert!(get_storage_value::<u32>(b"Governance", b"ReferendumVetoSum", &proposal_key).is_none());
+	});
+}
+
+#[test]
+fn submit_candidacy() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Governance::submit_candidacy(frame_system::RawOrigin::Signed(3).into()));
+		assert_eq!(Governance::council_candidates(), vec![3]);
+
+		assert_noop!(
+			Governance::submit_candidacy(frame_system::RawOrigin::Signed(3).into()),
+			Error::<Test>::AlreadyCandidate
+		);
+		assert_noop!(
+			Governance::submit_candidacy(frame_system::RawOrigin::Signed(2).into()),
+			Error::<Test>::NotEnoughRegistrations
+		);
+
+		assert_ok!(Governance::renounce_candidacy(
+			frame_system::RawOrigin::Signed(3).into()
+		));
+		assert!(Governance::council_candidates().is_empty());
+	});
+}
+
+#[test]
+fn vote_for_council_requires_candidates() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Governance::submit_candidacy(frame_system::RawOrigin::Signed(3).into()));
+
+		assert_noop!(
+			Governance::vote_for_council(frame_system::RawOrigin::Signed(1).into(), vec![2]),
+			Error::<Test>::NotCandidate
+		);
+		assert_noop!(
+			Governance::vote_for_council(frame_system::RawOrigin::Signed(1).into(), vec![]),
+			Error::<Test>::NoApprovals
+		);
+		assert_noop!(
+			Governance::vote_for_council(frame_system::RawOrigin::Signed(1).into(), vec![1, 2, 3]),
+			Error::<Test>::TooManyApprovals
+		);
+		assert_ok!(Governance::vote_for_council(
+			frame_system::RawOrigin::Signed(1).into(),
+			vec![3]
+		));
+		assert_eq!(Governance::council_votes(1), vec![3]);
+	});
+}
+
+#[test]
+fn vote_for_council_reserves_voting_bond() {
+	ExtBuilder::default().build().execute_with(|| {
+		let voting_bond = 50;
+		assert_ok!(Governance::set_council_voting_bond(
+			frame_system::RawOrigin::Root.into(),
+			voting_bond
+		));
+		let _ = StakingAssetCurrency::<Test>::deposit_creating(&3, 1_000);
+		assert_ok!(Governance::submit_candidacy(frame_system::RawOrigin::Signed(3).into()));
+
+		// the bond must be reserved to vote
+		assert_noop!(
+			Governance::vote_for_council(frame_system::RawOrigin::Signed(1).into(), vec![3]),
+			crml_generic_asset::Error::<Test>::InsufficientBalance
+		);
+		assert_ok!(Governance::vote_for_council(
+			frame_system::RawOrigin::Signed(3).into(),
+			vec![3]
+		));
+		assert_eq!(StakingAssetCurrency::<Test>::reserved_balance(&3), voting_bond);
+		assert_eq!(Governance::council_voter_bond(3), voting_bond);
+		// replacing approvals does not reserve another bond
+		assert_ok!(Governance::vote_for_council(
+			frame_system::RawOrigin::Signed(3).into(),
+			vec![3]
+		));
+		assert_eq!(StakingAssetCurrency::<Test>::reserved_balance(&3), voting_bond);
+
+		assert_ok!(Governance::remove_council_vote(
+			frame_system::RawOrigin::Signed(3).into()
+		));
+		assert_eq!(StakingAssetCurrency::<Test>::reserved_balance(&3), 0);
+		assert!(!<CouncilVotes<Test>>::contains_key(3));
+
+		// the bond is returned after the election
+		assert_ok!(Governance::vote_for_council(
+			frame_system::RawOrigin::Signed(3).into(),
+			vec![3]
+		));
+		Governance::elect_council();
+		assert_eq!(StakingAssetCurrency::<Test>::reserved_balance(&3), 0);
+		assert_eq!(StakingAssetCurrency::<Test>::free_balance(&3), 1_000);
+		assert!(!<CouncilVoterBonds<Test>>::contains_key(3));
+	});
+}
+
+#[test]
+fn council_elected_at_end_of_term() {
+	ExtBuilder::default().build().execute_with(|| {
+		let candidacy_bond = 100;
+		assert_ok!(Governance::set_candidacy_bond(
+			frame_system::RawOrigin::Root.into(),
+			candidacy_bond
+		));
+		assert_ok!(Governance::set_minimum_council_stake(
+			frame_system::RawOrigin::Root.into(),
+			500_000
+		));
+		for candidate in &[1, 3] {
+			let _ = StakingAssetCurrency::<Test>::deposit_creating(candidate, 1_000);
+			assert_ok!(Governance::submit_candidacy(
+				frame_system::RawOrigin::Signed(*candidate).into()
+			));
+		}
+		assert_eq!(StakingAssetCurrency::<Test>::reserved_balance(&1), candidacy_bond);
+
+		// sitting council has voted on an active proposal
+		Council::<Test>::put(vec![3]);
+		let mut votes = ProposalVoteInfo::default();
+		votes.record_vote(0, true);
+		ProposalVotes::insert(0, votes);
+
+		assert_ok!(Governance::vote_for_council(
+			frame_system::RawOrigin::Signed(3).into(),
+			vec![3]
+		));
+		Governance::on_initialize(CouncilTermLength::get());
+
+		assert_eq!(Governance::council(), vec![3]);
+		assert_eq!(Governance::council_term(), 1);
+		assert!(Governance::council_candidates().is_empty());
+		// unapproved candidate loses their bond, elected member is refunded
+		assert_eq!(StakingAssetCurrency::<Test>::reserved_balance(&1), 0);
+		assert_eq!(StakingAssetCurrency::<Test>::free_balance(&1), 1_000 - candidacy_bond);
+		assert_eq!(StakingAssetCurrency::<Test>::free_balance(&3), 1_000);
+
+		// next term, both candidates are approved
+		for candidate in &[1, 3] {
+			assert_ok!(Governance::submit_candidacy(
+				frame_system::RawOrigin::Signed(*candidate).into()
+			));
+		}
+		assert_ok!(Governance::vote_for_council(
+			frame_system::RawOrigin::Signed(1).into(),
+			vec![1, 3]
+		));
+		Governance::on_initialize(CouncilTermLength::get() * 2);
+
+		assert_eq!(Governance::council(), vec![1, 3]);
+		// votes of continuing members are carried over to their new seat
+		assert_eq!(Governance::proposal_votes(0).get_vote(0), None);
+		assert_eq!(Governance::proposal_votes(0).get_vote(1), Some(true));
+	});
+}
+
+#[test]
+fn council_kept_when_no_candidates_approved() {
+	ExtBuilder::default().build().execute_with(|| {
+		Council::<Test>::put(vec![3]);
+		assert_ok!(Governance::submit_candidacy(frame_system::RawOrigin::Signed(3).into()));
+		Governance::on_initialize(CouncilTermLength::get());
+
+		assert_eq!(Governance::council(), vec![3]);
+	});
+}
+
+#[test]
+fn council_membership_changes_update_proposal_votes() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Governance::add_council_member(frame_system::RawOrigin::Root.into(), 3));
+		let mut votes = ProposalVoteInfo::default();
+		votes.record_vote(0, true);
+		ProposalVotes::insert(0, votes);
+
+		assert_ok!(Governance::set_minimum_council_stake(
+			frame_system::RawOrigin::Root.into(),
+			500_000
+		));
+		// new member is sorted ahead of the existing member
+		assert_ok!(Governance::add_council_member(frame_system::RawOrigin::Root.into(), 1));
+		assert_eq!(Governance::proposal_votes(0).get_vote(0), None);
+		assert_eq!(Governance::proposal_votes(0).get_vote(1), Some(true));
+
+		assert_ok!(Governance::remove_council_member(
+			frame_system::RawOrigin::Root.into(),
+			1
+		));
+		assert_eq!(Governance::proposal_votes(0).get_vote(0), Some(true));
+	});
+}
+
+/// Setup a council approved spending proposal paying account 4 in two milestones
+fn setup_spending_proposal() -> ProposalId {
+	Council::<Test>::put(vec![1, 2, 3]);
+	let proposal_id = Governance::next_proposal_id();
+	assert_ok!(Governance::submit_spending_proposal(
+		frame_system::RawOrigin::Signed(3).into(),
+		4,
+		GenericAsset::staking_asset_id(),
+		vec![(100, b"milestone 1".to_vec()), (200, b"milestone 2".to_vec())],
+		b"https://example.com/grant".to_vec(),
+	));
+	assert_ok!(Governance::vote_on_proposal(
+		frame_system::RawOrigin::Signed(1).into(),
+		proposal_id,
+		true
+	));
+	assert_eq!(
+		Governance::proposal_status(proposal_id),
+		Some(ProposalStatusInfo::FundingMilestones)
+	);
+	proposal_id
+}
+
+#[test]
+fn spending_proposal_milestones_released_by_council() {
+	ExtBuilder::default().build().execute_with(|| {
+		let proposal_id = setup_spending_proposal();
+		let asset_id = GenericAsset::staking_asset_id();
+		let _ = StakingAssetCurrency::<Test>::deposit_creating(&Governance::treasury_account(), 1_000);
+
+		assert_ok!(Governance::vote_on_milestone(
+			frame_system::RawOrigin::Signed(3).into(),
+			proposal_id,
+			true
+		));
+		assert_ok!(Governance::vote_on_milestone(
+			frame_system::RawOrigin::Signed(1).into(),
+			proposal_id,
+			true
+		));
+		assert_eq!(GenericAsset::free_balance(asset_id, &4), 100);
+		assert!(System::events().iter().any(|record| record.event
+			== TestEvent::Governance(RawEvent::MilestoneReleased(proposal_id, 0, 4, asset_id, 100))));
+		let spending = Governance::spending_proposals(proposal_id).unwrap();
+		assert_eq!(spending.milestones[0].released_at, Some(1));
+		assert_eq!(spending.next_milestone(), Some(1));
+		assert_eq!(Governance::milestone_votes(proposal_id), ProposalVoteInfo::default());
+
+		assert_ok!(Governance::vote_on_milestone(
+			frame_system::RawOrigin::Signed(2).into(),
+			proposal_id,
+			true
+		));
+		assert_ok!(Governance::vote_on_milestone(
+			frame_system::RawOrigin::Signed(3).into(),
+			proposal_id,
+			true
+		));
+		assert_eq!(GenericAsset::free_balance(asset_id, &4), 300);
+		assert_eq!(
+			GenericAsset::free_balance(asset_id, &Governance::treasury_account()),
+			700
+		);
+		assert_eq!(
+			Governance::proposal_status(proposal_id),
+			Some(ProposalStatusInfo::ApprovedEnacted(true))
+		);
+		assert_eq!(Governance::get_spending_proposals()[0].1.released_amount(), 300);
+
+		// nothing left to release
+		assert_noop!(
+			Governance::vote_on_milestone(frame_system::RawOrigin::Signed(1).into(), proposal_id, true),
+			Error::<Test>::ProposalNotFunding
+		);
+	});
+}
+
+#[test]
+fn milestone_rejected_cancels_spending_proposal() {
+	ExtBuilder::default().build().execute_with(|| {
+		let proposal_id = setup_spending_proposal();
+		let _ = StakingAssetCurrency::<Test>::deposit_creating(&Governance::treasury_account(), 1_000);
+
+		assert_ok!(Governance::vote_on_milestone(
+			frame_system::RawOrigin::Signed(1).into(),
+			proposal_id,
+			false
+		));
+		assert_noop!(
+			Governance::vote_on_milestone(frame_system::RawOrigin::Signed(1).into(), proposal_id, true),
+			Error::<Test>::DoubleVote
+		);
+		assert_ok!(Governance::vote_on_milestone(
+			frame_system::RawOrigin::Signed(2).into(),
+			proposal_id,
+			false
+		));
+
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == TestEvent::Governance(RawEvent::MilestoneRejected(proposal_id, 0))));
+		assert_eq!(
+			Governance::proposal_status(proposal_id),
+			Some(ProposalStatusInfo::FundingCancelled)
+		);
+		assert_eq!(GenericAsset::free_balance(GenericAsset::staking_asset_id(), &4), 0);
+	});
+}
+
+#[test]
+fn milestone_release_without_treasury_funds_should_fail() {
+	ExtBuilder::default().build().execute_with(|| {
+		let proposal_id = setup_spending_proposal();
+		assert_ok!(Governance::vote_on_milestone(
+			frame_system::RawOrigin::Signed(1).into(),
+			proposal_id,
+			true
+		));
+		// the approving vote is reverted with the failed payment
+		assert!(Governance::vote_on_milestone(frame_system::RawOrigin::Signed(3).into(), proposal_id, true).is_err());
+		assert_eq!(Governance::milestone_votes(proposal_id).get_vote(2), None);
+		assert_eq!(
+			Governance::spending_proposals(proposal_id).unwrap().next_milestone(),
+			Some(0)
+		);
+	});
+}
+
+#[test]
+fn vote_on_milestone_before_approval_should_fail() {
+	ExtBuilder::default().build().execute_with(|| {
+		Council::<Test>::put(vec![1, 2, 3]);
+		assert_ok!(Governance::submit_spending_proposal(
+			frame_system::RawOrigin::Signed(3).into(),
+			4,
+			GenericAsset::staking_asset_id(),
+			vec![(100, b"milestone 1".to_vec())],
+			b"https://example.com/grant".to_vec(),
+		));
+		assert_noop!(
+			Governance::vote_on_milestone(frame_system::RawOrigin::Signed(3).into(), 0, true),
+			Error::<Test>::ProposalNotFunding
+		);
+	});
+}
+
+#[test]
+fn submit_spending_proposal_with_invalid_milestones_should_fail() {
+	ExtBuilder::default().build().execute_with(|| {
+		Council::<Test>::put(vec![3]);
+		assert_noop!(
+			Governance::submit_spending_proposal(
+				frame_system::RawOrigin::Signed(3).into(),
+				4,
+				GenericAsset::staking_asset_id(),
+				vec![],
+				b"https://example.com/grant".to_vec(),
+			),
+			Error::<Test>::NoMilestones
+		);
+		assert_noop!(
+			Governance::submit_spending_proposal(
+				frame_system::RawOrigin::Signed(3).into(),
+				4,
+				GenericAsset::staking_asset_id(),
+				vec![(1, vec![]); MAX_MILESTONES + 1],
+				b"https://example.com/grant".to_vec(),
+			),
+			Error::<Test>::TooManyMilestones
+		);
+	});
+}
+
+#[test]
+fn note_preimage_and_submit_proposal() {
+	ExtBuilder::default().build().execute_with(|| {
+		Council::<Test>::put(vec![1, 2, 3]);
+		let _ = StakingAssetCurrency::<Test>::deposit_creating(&1, 1_000);
+		assert_ok!(Governance::set_preimage_byte_deposit(
+			frame_system::RawOrigin::Root.into(),
+			2
+		));
+		let encoded_call = MockCall::Governance(crate::Call::set_parameter {
+			key: ParameterKey::ProposalBond,
+			value: ParameterValue::Balance(10),
+		})
+		.encode();
+		let call_hash = BlakeTwo256::hash(&encoded_call);
+		let deposit = 2 * encoded_call.len() as Balance;
+
+		assert_ok!(Governance::note_preimage(
+			frame_system::RawOrigin::Signed(1).into(),
+			encoded_call.clone()
+		));
+		assert_eq!(StakingAssetCurrency::<Test>::reserved_balance(&1), deposit);
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == TestEvent::Governance(RawEvent::PreimageNoted(call_hash, 1, deposit))));
+		assert_noop!(
+			Governance::note_preimage(frame_system::RawOrigin::Signed(1).into(), encoded_call.clone()),
+			Error::<Test>::DuplicatePreimage
+		);
+
+		assert_ok!(Governance::submit_proposal(
+			frame_system::RawOrigin::Signed(3).into(),
+			Some(call_hash),
+			b"https://example.com/proposal".to_vec(),
+			1
+		));
+		assert!(Governance::preimages(call_hash).is_none());
+		assert_eq!(Governance::proposal_calls(0), Some(encoded_call.clone()));
+		assert_eq!(
+			Governance::get_proposal_call(0),
+			Some(ProposalCallInfo {
+				call_hash,
+				pallet_name: b"Governance".to_vec(),
+				function_name: b"set_parameter".to_vec(),
+				weight: 100_000,
+				encoded_call,
+			})
+		);
+
+		// the deposit is returned when the proposal concludes
+		assert_ok!(Governance::vote_on_proposal(
+			frame_system::RawOrigin::Signed(1).into(),
+			0,
+			false
+		));
+		assert_ok!(Governance::vote_on_proposal(
+			frame_system::RawOrigin::Signed(2).into(),
+			0,
+			false
+		));
+		assert_eq!(Governance::proposal_status(0), Some(ProposalStatusInfo::Disapproved));
+		assert!(Governance::proposal_calls(0).is_none());
+		assert_eq!(StakingAssetCurrency::<Test>::reserved_balance(&1), 0);
+	});
+}
+
+#[test]
+fn note_preimage_invalid_call_should_fail() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			Governance::note_preimage(frame_system::RawOrigin::Signed(1).into(), vec![255, 0, 1]),
+			Error::<Test>::InvalidCall
+		);
+
+		// trailing bytes are rejected
+		let mut encoded_call = MockCall::Governance(crate::Call::set_parameter {
+			key: ParameterKey::ProposalBond,
+			value: ParameterValue::Balance(10),
+		})
+		.encode();
+		encoded_call.push(0);
+		assert_noop!(
+			Governance::note_preimage(frame_system::RawOrigin::Signed(1).into(), encoded_call),
+			Error::<Test>::InvalidCall
+		);
+
+		// exceeds the mock block weight limit
+		let encoded_call = MockCall::GenericAsset(crml_generic_asset::Call::transfer {
+			asset_id: GenericAsset::staking_asset_id(),
+			to: 2,
+			amount: 100,
+		})
+		.encode();
+		assert_noop!(
+			Governance::note_preimage(frame_system::RawOrigin::Signed(1).into(), encoded_call),
+			Error::<Test>::CallWeightTooHigh
+		);
+	});
+}
+
+#[test]
+fn reap_preimage() {
+	ExtBuilder::default().build().execute_with(|| {
+		let _ = StakingAssetCurrency::<Test>::deposit_creating(&1, 1_000);
+		PreimageByteDeposit::put(1);
+		let encoded_call = MockCall::Governance(crate::Call::set_parameter {
+			key: ParameterKey::ProposalBond,
+			value: ParameterValue::Balance(10),
+		})
+		.encode();
+		let call_hash = BlakeTwo256::hash(&encoded_call);
+		assert_ok!(Governance::note_preimage(
+			frame_system::RawOrigin::Signed(1).into(),
+			encoded_call
+		));
+
+		assert_noop!(
+			Governance::reap_preimage(frame_system::RawOrigin::Signed(2).into(), call_hash),
+			Error::<Test>::NotPreimageProvider
+		);
+		assert_ok!(Governance::reap_preimage(
+			frame_system::RawOrigin::Signed(1).into(),
+			call_hash
+		));
+		assert!(Governance::preimages(call_hash).is_none());
+		assert_eq!(StakingAssetCurrency::<Test>::reserved_balance(&1), 0);
+		assert_noop!(
+			Governance::reap_preimage(frame_system::RawOrigin::Signed(1).into(), call_hash),
+			Error::<Test>::PreimageMissing
+		);
+	});
+}
+
+#[test]
+fn submit_proposal_without_preimage_should_fail() {
+	ExtBuilder::default().build().execute_with(|| {
+		Council::<Test>::put(vec![3]);
+		assert_noop!(
+			Governance::submit_proposal(
+				frame_system::RawOrigin::Signed(3).into(),
+				Some(BlakeTwo256::hash(b"missing")),
+				b"https://example.com/proposal".to_vec(),
+				1
+			),
+			Error::<Test>::PreimageMissing
+		);
+	});
+}
+
+#[test]
+fn concluded_proposals_are_pruned_after_retention() {
+	ExtBuilder::default().build().execute_with(|| {
+		let proposal_id = setup_referendum(3);
+		ProposalCalls::remove(proposal_id);
+		assert_ok!(Governance::vote_for_referendum(
+			frame_system::RawOrigin::Signed(3).into(),
+			proposal_id,
+			Conviction::None,
+		));
+		Governance::end_referendum(proposal_id);
+
+		// the record is kept after the proposal concludes
+		let prune_block = System::block_number() + PROPOSAL_RETENTION_BLOCKS as u64;
+		assert_eq!(
+			Governance::get_proposal(proposal_id).map(|details| details.status),
+			Some(ProposalStatusInfo::ApprovedEnacted(true))
+		);
+		assert!(ProposalPruning::<Test>::contains_key(prune_block, proposal_id));
+
+		Governance::prune_proposals(prune_block - 1);
+		assert!(Governance::proposals(proposal_id).is_some());
+
+		Governance::prune_proposals(prune_block);
+		assert!(Governance::get_proposal(proposal_id).is_none());
+		assert!(Governance::proposals(proposal_id).is_none());
+		assert!(Governance::proposal_status(proposal_id).is_none());
+		assert_eq!(Governance::referendum_tally(proposal_id), ReferendumTally::default());
+		assert!(Governance::get_proposals(None).is_empty());
+	});
+}
+
+#[test]
+fn proposal_expires_after_deliberation_deadline() {
+	ExtBuilder::default().build().execute_with(|| {
+		Council::<Test>::put(vec![1, 2, 3]);
+		let _ = StakingAssetCurrency::<Test>::deposit_creating(&3, 1_000);
+		Parameters::insert(ParameterKey::ProposalBond, ParameterValue::Balance(100));
+		assert_ok!(Governance::submit_proposal(
+			frame_system::RawOrigin::Signed(3).into(),
+			None,
+			b"https://example.com/proposal".to_vec(),
+			1
+		));
+		assert_eq!(StakingAssetCurrency::<Test>::reserved_balance(&3), 100);
+		assert_eq!(
+			Governance::get_proposal(0),
+			Some(ProposalDetails {
+				proposal_id: 0,
+				sponsor: 3,
+				justification_uri: b"https://example.com/proposal".to_vec(),
+				enactment_delay: 1,
+				status: ProposalStatusInfo::Deliberation,
+				deliberation_deadline: Some(1 + DeliberationPeriod::get()),
+				referendum_start_time: None,
+				referendum_end: None,
+				referendum_tally: ReferendumTally::default(),
+			})
+		);
+
+		Governance::on_initialize(DeliberationPeriod::get());
+		assert_eq!(Governance::proposal_status(0), Some(ProposalStatusInfo::Deliberation));
+		Governance::on_initialize(1 + DeliberationPeriod::get());
+
+		assert_eq!(Governance::proposal_status(0), Some(ProposalStatusInfo::Expired));
+		assert!(Governance::deliberation_deadline(0).is_none());
+		assert!(!<DeliberationExpiries<Test>>::contains_key(
+			1 + DeliberationPeriod::get(),
+			0
+		));
+		// no council member voted against, the bond is returned
+		assert_eq!(StakingAssetCurrency::<Test>::reserved_balance(&3), 0);
+		assert_eq!(StakingAssetCurrency::<Test>::free_balance(&3), 1_000);
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == TestEvent::Governance(RawEvent::ProposalExpired(0, true))));
+		assert_noop!(
+			Governance::vote_on_proposal(frame_system::RawOrigin::Signed(1).into(), 0, true),
+			Error::<Test>::ProposalNotDeliberating
+		);
+	});
+}
+
+#[test]
+fn expired_proposal_opposed_by_council_is_slashed() {
+	ExtBuilder::default().build().execute_with(|| {
+		Council::<Test>::put(vec![1, 2, 3, 4, 5]);
+		let _ = StakingAssetCurrency::<Test>::deposit_creating(&3, 1_000);
+		Parameters::insert(ParameterKey::ProposalBond, ParameterValue::Balance(100));
+		assert_ok!(Governance::submit_proposal(
+			frame_system::RawOrigin::Signed(3).into(),
+			None,
+			b"https://example.com/proposal".to_vec(),
+			1
+		));
+		for voter in &[1, 2] {
+			assert_ok!(Governance::vote_on_proposal(
+				frame_system::RawOrigin::Signed(*voter).into(),
+				0,
+				false
+			));
+		}
+		// no majority yet
+		assert_eq!(Governance::proposal_status(0), Some(ProposalStatusInfo::Deliberation));
+
+		Governance::on_initialize(1 + DeliberationPeriod::get());
+
+		assert_eq!(Governance::proposal_status(0), Some(ProposalStatusInfo::Expired));
+		assert_eq!(StakingAssetCurrency::<Test>::reserved_balance(&3), 0);
+		assert_eq!(StakingAssetCurrency::<Test>::free_balance(&3), 900);
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == TestEvent::Governance(RawEvent::ProposalExpired(0, false))));
+	});
+}
+
+#[test]
+fn referendum_ends_at_scheduled_block() {
+	ExtBuilder::default().build().execute_with(|| {
+		let proposal_id = setup_referendum(3);
+		let referendum_end = System::block_number() + REFERENDUM_LENGTH as u64;
+
+		Governance::on_initialize(referendum_end - 1);
+		assert_eq!(
+			Governance::proposal_status(proposal_id),
+			Some(ProposalStatusInfo::ReferendumDeliberation)
+		);
+
+		Governance::on_initialize(referendum_end);
+		assert_eq!(
+			Governance::proposal_status(proposal_id),
+			Some(ProposalStatusInfo::ApprovedWaitingEnactment)
+		);
+		assert!(!<ReferendumEnds<Test>>::contains_key(referendum_end, proposal_id));
+	});
+}
+
+#[test]
+fn referendum_ends_indexed_on_upgrade() {
+	ExtBuilder::default().build().execute_with(|| {
+		let proposal_id = setup_referendum(3);
+		let start = System::block_number();
+		System::set_block_number(start + REFERENDUM_LENGTH as u64 + 10);
+		let overdue_proposal_id = setup_referendum(3);
+		<ReferendumStartTime<Test>>::insert(overdue_proposal_id, start);
+		// referendums started before ends were indexed
+		<ReferendumEnds<Test>>::remove_all(None);
+		// proposals with a call are invalidated by later migrations
+		ProposalCalls::remove(proposal_id);
+		ProposalCalls::remove(overdue_proposal_id);
+		StorageVersion::put(Releases::V0 as u32);
+
+		let _ = Governance::on_runtime_upgrade();
+
+		assert!(<ReferendumEnds<Test>>::contains_key(
+			start + REFERENDUM_LENGTH as u64,
+			proposal_id
+		));
+		// referendums past their end are ended in the next block
+		assert!(<ReferendumEnds<Test>>::contains_key(
+			System::block_number() + 1,
+			overdue_proposal_id
+		));
+	});
+}
+
+#[test]
+fn approved_proposal_without_call_returns_bond() {
+	ExtBuilder::default().build().execute_with(|| {
+		Council::<Test>::put(vec![1, 2, 3]);
+		let _ = StakingAssetCurrency::<Test>::deposit_creating(&3, 1_000);
+		Parameters::insert(ParameterKey::ProposalBond, ParameterValue::Balance(100));
+		assert_ok!(Governance::submit_proposal(
+			frame_system::RawOrigin::Signed(3).into(),
+			None,
+			b"https://example.com/proposal".to_vec(),
+			1
+		));
+		assert_eq!(StakingAssetCurrency::<Test>::reserved_balance(&3), 100);
+
+		assert_ok!(Governance::vote_on_proposal(
+			frame_system::RawOrigin::Signed(1).into(),
+			0,
+			true
+		));
+		assert_eq!(
+			Governance::proposal_status(0),
+			Some(ProposalStatusInfo::ApprovedEnacted(true))
+		);
+		assert_eq!(StakingAssetCurrency::<Test>::reserved_balance(&3), 0);
+		assert_eq!(StakingAssetCurrency::<Test>::free_balance(&3), 1_000);
+		// the council is no longer deliberating the proposal
+		assert!(Governance::deliberation_deadline(0).is_none());
+		assert!(!<DeliberationExpiries<Test>>::contains_key(
+			1 + DeliberationPeriod::get(),
+			0
+		));
+	});
+}
+
+#[test]
+fn get_proposals_filtered_by_status() {
+	ExtBuilder::default().build().execute_with(|| {
+		Council::<Test>::put(vec![1, 2, 3]);
+		for _ in 0..2 {
+			assert_ok!(Governance::submit_proposal(
+				frame_system::RawOrigin::Signed(3).into(),
+				None,
+				b"https://example.com/proposal".to_vec(),
+				1
+			));
+		}
+		// proposal without a call is enacted on council approval
+		assert_ok!(Governance::vote_on_proposal(
+			frame_system::RawOrigin::Signed(1).into(),
+			1,
+			true
+		));
+
+		assert_eq!(Governance::get_proposals(None).len(), 2);
+		let deliberating = Governance::get_proposals(Some(ProposalStatusInfo::Deliberation));
+		assert_eq!(deliberating.len(), 1);
+		assert_eq!(deliberating[0].proposal_id, 0);
+		let enacted = Governance::get_proposals(Some(ProposalStatusInfo::ApprovedEnacted(true)));
+		assert_eq!(enacted.len(), 1);
+		assert_eq!(enacted[0].proposal_id, 1);
+		assert!(enacted[0].deliberation_deadline.is_none());
+	});
+}
+
+#[test]
+fn fast_track_shortens_referendum() {
+	ExtBuilder::default().build().execute_with(|| {
+		Council::<Test>::put(vec![1, 2, 3]);
+		let proposal_id = setup_referendum(3);
+
+		assert_ok!(Governance::fast_track_proposal(
+			frame_system::RawOrigin::Signed(4).into(),
+			proposal_id,
+			10,
+			b"https://example.com/incident".to_vec(),
+		));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == TestEvent::Governance(RawEvent::FastTrackRequested(proposal_id, 4))));
+
+		// a simple majority is not enough
+		for voter in &[1, 2] {
+			assert_ok!(Governance::vote_on_fast_track(
+				frame_system::RawOrigin::Signed(*voter).into(),
+				proposal_id,
+				true
+			));
+		}
+		assert!(Governance::fast_tracked_referendum(proposal_id).is_none());
+		assert_ok!(Governance::vote_on_fast_track(
+			frame_system::RawOrigin::Signed(3).into(),
+			proposal_id,
+			true
+		));
+		assert_eq!(Governance::fast_tracked_referendum(proposal_id), Some(11));
+		assert!(FastTrackedReferendumEnds::<Test>::contains_key(11, proposal_id));
+		assert!(Governance::fast_track(proposal_id).is_none());
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == TestEvent::Governance(RawEvent::FastTrackApproved(proposal_id, 11))));
+		assert_eq!(Governance::get_proposal(proposal_id).unwrap().referendum_end, Some(11));
+
+		Governance::on_initialize(10);
+		assert_eq!(
+			Governance::proposal_status(proposal_id),
+			Some(ProposalStatusInfo::ReferendumDeliberation)
+		);
+		Governance::on_initialize(11);
+		assert_eq!(
+			Governance::proposal_status(proposal_id),
+			Some(ProposalStatusInfo::ApprovedWaitingEnactment)
+		);
+		assert!(Governance::fast_tracked_referendum(proposal_id).is_none());
+	});
+}
+
+#[test]
+fn fast_track_rejected_by_council() {
+	ExtBuilder::default().build().execute_with(|| {
+		Council::<Test>::put(vec![1, 2, 3]);
+		let proposal_id = setup_referendum(3);
+		assert_ok!(Governance::fast_track_proposal(
+			frame_system::RawOrigin::Signed(4).into(),
+			proposal_id,
+			10,
+			b"https://example.com/incident".to_vec(),
+		));
+
+		// one vote against makes the threshold unreachable
+		assert_ok!(Governance::vote_on_fast_track(
+			frame_system::RawOrigin::Signed(1).into(),
+			proposal_id,
+			false
+		));
+		assert!(Governance::fast_track(proposal_id).is_none());
+		assert!(Governance::fast_tracked_referendum(proposal_id).is_none());
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == TestEvent::Governance(RawEvent::FastTrackRejected(proposal_id))));
+		assert_noop!(
+			Governance::vote_on_fast_track(frame_system::RawOrigin::Signed(2).into(), proposal_id, true),
+			Error::<Test>::FastTrackMissing
+		);
+	});
+}
+
+#[test]
+fn fast_track_proposal_invalid_request_should_fail() {
+	ExtBuilder::default().build().execute_with(|| {
+		let proposal_id = setup_referendum(3);
+		let justification_uri = b"https://example.com/incident".to_vec();
+
+		assert_noop!(
+			Governance::fast_track_proposal(
+				frame_system::RawOrigin::Signed(3).into(),
+				proposal_id,
+				10,
+				justification_uri.clone()
+			),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Governance::fast_track_proposal(frame_system::RawOrigin::Signed(4).into(), proposal_id, 10, vec![]),
+			Error::<Test>::MissingJustification
+		);
+		for referendum_length in &[0, REFERENDUM_LENGTH as u64] {
+			assert_noop!(
+				Governance::fast_track_proposal(
+					frame_system::RawOrigin::Signed(4).into(),
+					proposal_id,
+					*referendum_length,
+					justification_uri.clone()
+				),
+				Error::<Test>::InvalidReferendumLength
+			);
+		}
+		assert_noop!(
+			Governance::fast_track_proposal(
+				frame_system::RawOrigin::Signed(4).into(),
+				proposal_id + 1,
+				10,
+				justification_uri.clone()
+			),
+			Error::<Test>::ReferendumNotDeliberating
+		);
+
+		assert_ok!(Governance::fast_track_proposal(
+			frame_system::RawOrigin::Signed(4).into(),
+			proposal_id,
+			10,
+			justification_uri.clone()
+		));
+		assert_noop!(
+			Governance::fast_track_proposal(
+				frame_system::RawOrigin::Signed(4).into(),
+				proposal_id,
+				10,
+				justification_uri
+			),
+			Error::<Test>::FastTrackExists
+		);
+	});
+}
+
+#[test]
+fn submit_proposal_revalidates_preimage() {
+	ExtBuilder::default().build().execute_with(|| {
+		Council::<Test>::put(vec![3]);
+		// noted before a runtime upgrade removed the call
+		let call_hash = BlakeTwo256::hash(&[255, 0, 1]);
+		<Preimages<Test>>::insert(
+			call_hash,
+			Preimage {
+				provider: 1,
+				deposit: 0,
+				call: vec![255, 0, 1],
+			},
+		);
+		assert_noop!(
+			Governance::submit_proposal(
+				frame_system::RawOrigin::Signed(3).into(),
+				Some(call_hash),
+				b"https://example.com/proposal".to_vec(),
+				1
+			),
+			Error::<Test>::InvalidCall
+		);
+	});
+}
+
+#[test]
+fn preimage_byte_deposit_defaults_to_runtime_value() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(Governance::preimage_byte_deposit(), DefaultPreimageByteDeposit::get());
+		assert_ok!(Governance::set_preimage_byte_deposit(
+			frame_system::RawOrigin::Root.into(),
+			5
+		));
+		assert_eq!(Governance::preimage_byte_deposit(), 5);
+	});
+}
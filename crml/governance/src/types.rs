@@ -13,9 +13,18 @@
 *     https://centrality.ai/licenses/lgplv3.txt
 */
 
+use cennznet_primitives::types::{AssetId, Balance};
 use codec::{Decode, Encode};
+use frame_support::weights::Weight;
 use scale_info::TypeInfo;
-use sp_std::prelude::*;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::{traits::Zero, FixedPointNumber, FixedU128, Permill};
+use sp_std::{
+	cmp::{max, min},
+	convert::TryFrom,
+	prelude::*,
+};
 
 /// Identifies proposals
 pub type ProposalId = u64;
@@ -33,7 +42,45 @@ pub struct Proposal<T: crate::Config> {
 	pub enactment_delay: T::BlockNumber,
 }
 
-#[derive(Debug, PartialEq, Encode, Decode, TypeInfo)]
+/// A call noted for use by a proposal
+#[derive(Debug, Clone, PartialEq, Encode, Decode, TypeInfo)]
+pub struct Preimage<AccountId> {
+	/// The account which noted the call
+	pub provider: AccountId,
+	/// The amount reserved from the provider
+	pub deposit: Balance,
+	/// The SCALE encoded runtime call
+	pub call: Vec<u8>,
+}
+
+/// A request to shorten a proposal's referendum
+#[derive(Debug, Clone, PartialEq, Encode, Decode, TypeInfo)]
+pub struct FastTrack<AccountId, BlockNumber> {
+	/// The account which requested the fast-track
+	pub requester: AccountId,
+	/// Length in blocks of the shortened referendum, from council approval of the fast-track
+	pub referendum_length: BlockNumber,
+	/// Justification document URI
+	pub justification_uri: Vec<u8>,
+}
+
+/// Describes the call a proposal will enact
+#[derive(Debug, Clone, PartialEq, Encode, Decode, TypeInfo)]
+pub struct ProposalCallInfo<Hash> {
+	/// Hash of the encoded call
+	pub call_hash: Hash,
+	/// Name of the pallet the call belongs to
+	pub pallet_name: Vec<u8>,
+	/// Name of the dispatchable function
+	pub function_name: Vec<u8>,
+	/// The call's dispatch weight
+	pub weight: Weight,
+	/// The SCALE encoded runtime call
+	pub encoded_call: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum ProposalStatusInfo {
 	/// Council is deliberating
 	Deliberation,
@@ -49,6 +96,84 @@ pub enum ProposalStatusInfo {
 	Disapproved,
 	/// The proposal was voted against during the referendum phase
 	ReferendumVetoed,
+	/// Spending proposal approved, milestones are being released by the council
+	FundingMilestones,
+	/// Spending proposal cancelled, the council voted against releasing a milestone
+	FundingCancelled,
+	/// The council did not reach a majority before the deliberation deadline
+	Expired,
+	/// The proposal's call was noted before a runtime upgrade which may have changed its encoding
+	CallInvalidated,
+}
+
+/// The lifecycle of a proposal
+#[derive(Debug, Clone, PartialEq, Encode, Decode, TypeInfo)]
+pub struct ProposalDetails<AccountId, BlockNumber> {
+	/// The proposal Id
+	pub proposal_id: ProposalId,
+	/// The submitter of the proposal
+	pub sponsor: AccountId,
+	/// Justification document URI
+	pub justification_uri: Vec<u8>,
+	/// Enactment delay in blocks
+	pub enactment_delay: BlockNumber,
+	/// The current status
+	pub status: ProposalStatusInfo,
+	/// The block the council's deliberation expires, while deliberating
+	pub deliberation_deadline: Option<BlockNumber>,
+	/// The block the referendum started, while the referendum is in progress
+	pub referendum_start_time: Option<BlockNumber>,
+	/// The block the referendum ends, while the referendum is in progress
+	pub referendum_end: Option<BlockNumber>,
+	/// Stake weighted referendum votes, final once the referendum has ended
+	pub referendum_tally: ReferendumTally,
+}
+
+/// A staged payment of a spending proposal
+#[derive(Debug, Clone, PartialEq, Encode, Decode, TypeInfo)]
+pub struct Milestone<BlockNumber> {
+	/// Amount paid to the beneficiary on release
+	pub amount: Balance,
+	/// Deliverables document URI
+	pub deliverable_uri: Vec<u8>,
+	/// The block the milestone was released at, if released
+	pub released_at: Option<BlockNumber>,
+}
+
+/// A request for treasury funds, paid to the beneficiary in milestones approved by the council
+#[derive(Debug, Clone, PartialEq, Encode, Decode, TypeInfo)]
+pub struct SpendingProposal<AccountId, BlockNumber> {
+	/// The recipient of the funds
+	pub beneficiary: AccountId,
+	/// The asset the funds are paid in
+	pub asset_id: AssetId,
+	/// Payments in order of release
+	pub milestones: Vec<Milestone<BlockNumber>>,
+}
+
+impl<AccountId, BlockNumber> SpendingProposal<AccountId, BlockNumber> {
+	/// The total amount requested over all milestones
+	pub fn total_amount(&self) -> Balance {
+		self.milestones
+			.iter()
+			.fold(Zero::zero(), |total: Balance, m| total.saturating_add(m.amount))
+	}
+
+	/// The total amount released to the beneficiary so far
+	pub fn released_amount(&self) -> Balance {
+		self.milestones
+			.iter()
+			.filter(|m| m.released_at.is_some())
+			.fold(Zero::zero(), |total: Balance, m| total.saturating_add(m.amount))
+	}
+
+	/// The index of the next milestone awaiting release, if any
+	pub fn next_milestone(&self) -> Option<u32> {
+		self.milestones
+			.iter()
+			.position(|m| m.released_at.is_none())
+			.map(|idx| idx as u32)
+	}
 }
 
 // Used to store votes on a referendum
@@ -56,6 +181,207 @@ pub enum ProposalStatusInfo {
 pub struct ReferendumVoteCount {
 	// u8 representing either a yes or no vote (0 for yes 1 for no)
 	pub vote: u8,
+	/// The active stake of the voter at the time of voting
+	pub stake: Balance,
+	/// The conviction the vote was cast with
+	pub conviction: Conviction,
+}
+
+impl ReferendumVoteCount {
+	/// The conviction weighted value of this vote
+	pub fn weight(&self) -> Balance {
+		self.conviction.votes(self.stake)
+	}
+}
+
+/// A multiplier on a referendum vote's stake in exchange for locking the stake for longer
+/// Each lock period is the length of a referendum
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub enum Conviction {
+	/// 0.1x votes, unlocked
+	None,
+	/// 1x votes, locked for 1x the referendum length after the referendum ends
+	Locked1x,
+	/// 2x votes, locked for 2x the referendum length after the referendum ends
+	Locked2x,
+	/// 3x votes, locked for 4x the referendum length after the referendum ends
+	Locked3x,
+	/// 4x votes, locked for 8x the referendum length after the referendum ends
+	Locked4x,
+	/// 5x votes, locked for 16x the referendum length after the referendum ends
+	Locked5x,
+	/// 6x votes, locked for 32x the referendum length after the referendum ends
+	Locked6x,
+}
+
+impl Default for Conviction {
+	fn default() -> Self {
+		Conviction::None
+	}
+}
+
+impl Conviction {
+	/// The number of referendum lengths the voter's stake is locked for
+	pub fn lock_periods(self) -> u32 {
+		match self {
+			Conviction::None => 0,
+			Conviction::Locked1x => 1,
+			Conviction::Locked2x => 2,
+			Conviction::Locked3x => 4,
+			Conviction::Locked4x => 8,
+			Conviction::Locked5x => 16,
+			Conviction::Locked6x => 32,
+		}
+	}
+
+	/// The votes given by `stake` with this conviction
+	pub fn votes(self, stake: Balance) -> Balance {
+		match self {
+			Conviction::None => stake / 10,
+			Conviction::Locked1x => stake,
+			Conviction::Locked2x => stake.saturating_mul(2),
+			Conviction::Locked3x => stake.saturating_mul(3),
+			Conviction::Locked4x => stake.saturating_mul(4),
+			Conviction::Locked5x => stake.saturating_mul(5),
+			Conviction::Locked6x => stake.saturating_mul(6),
+		}
+	}
+}
+
+/// Running tally of stake weighted referendum votes
+#[derive(Debug, Default, Clone, PartialEq, Encode, Decode, TypeInfo)]
+pub struct ReferendumTally {
+	/// Total conviction weighted stake in favour of the referendum
+	pub approve: Balance,
+	/// Total conviction weighted stake against the referendum
+	pub veto: Balance,
+}
+
+impl ReferendumTally {
+	/// Add a vote to the tally
+	pub fn add(&mut self, vote: &ReferendumVoteCount) {
+		if vote.vote == 0 {
+			self.approve = self.approve.saturating_add(vote.weight());
+		} else {
+			self.veto = self.veto.saturating_add(vote.weight());
+		}
+	}
+
+	/// Whether the veto share of all voted stake meets `threshold`
+	pub fn is_vetoed(&self, threshold: Permill) -> bool {
+		let total = self.approve.saturating_add(self.veto);
+		if total.is_zero() {
+			return false;
+		}
+		Permill::from_rational(self.veto, total) >= threshold
+	}
+}
+
+/// A network parameter held by the governance parameter registry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub enum ParameterKey {
+	/// Ethereum block confirmations required before a bridge event is verified (`U64`)
+	EthBridgeEventConfirmations,
+	/// Seconds after which an unverified bridge event expires (`U64`)
+	EthBridgeEventDeadlineSeconds,
+	/// Fee rate of CENNZX exchanges (`Permill`)
+	CennzxDefaultFeeRate,
+	/// Bond reserved by the sponsor of a governance proposal (`Balance`)
+	ProposalBond,
+	/// Share of voted stake required to veto a referendum (`Permill`)
+	ReferendumThreshold,
+	/// Staking reward inflation rate, applied from the next fiscal era (`FixedU128`)
+	StakingInflationRate,
+}
+
+impl ParameterKey {
+	/// The value of the parameter until it is first changed
+	pub fn default_value(&self) -> ParameterValue {
+		match self {
+			ParameterKey::EthBridgeEventConfirmations => ParameterValue::U64(3),
+			ParameterKey::EthBridgeEventDeadlineSeconds => ParameterValue::U64(604_800), // 1 week
+			ParameterKey::CennzxDefaultFeeRate => ParameterValue::Permill(Permill::zero()),
+			ParameterKey::ProposalBond => ParameterValue::Balance(0),
+			ParameterKey::ReferendumThreshold => ParameterValue::Permill(Permill::from_percent(33)),
+			ParameterKey::StakingInflationRate => {
+				ParameterValue::FixedU128(FixedU128::saturating_from_rational(1u64, 100u64))
+			}
+		}
+	}
+
+	/// Whether `value` has the parameter's type and is within its bounds
+	pub fn is_valid(&self, value: &ParameterValue) -> bool {
+		self.clamp(*value).as_ref() == Some(value)
+	}
+
+	/// `value` moved to the nearest bound of the parameter, `None` if it does not have the parameter's type
+	pub fn clamp(&self, value: ParameterValue) -> Option<ParameterValue> {
+		match (self, value) {
+			(ParameterKey::EthBridgeEventConfirmations, ParameterValue::U64(confirmations)) => {
+				Some(ParameterValue::U64(min(max(confirmations, 1), 1_000)))
+			}
+			// 1 hour to 4 weeks
+			(ParameterKey::EthBridgeEventDeadlineSeconds, ParameterValue::U64(seconds)) => {
+				Some(ParameterValue::U64(min(max(seconds, 3_600), 2_419_200)))
+			}
+			(ParameterKey::CennzxDefaultFeeRate, ParameterValue::Permill(fee_rate)) => {
+				Some(ParameterValue::Permill(min(fee_rate, Permill::from_percent(10))))
+			}
+			(ParameterKey::ProposalBond, ParameterValue::Balance(bond)) => Some(ParameterValue::Balance(bond)),
+			(ParameterKey::ReferendumThreshold, ParameterValue::Permill(threshold)) => {
+				Some(ParameterValue::Permill(max(threshold, Permill::from_percent(1))))
+			}
+			(ParameterKey::StakingInflationRate, ParameterValue::FixedU128(rate)) => Some(ParameterValue::FixedU128(
+				min(rate, FixedU128::saturating_from_integer(1u64)),
+			)),
+			_ => None,
+		}
+	}
+}
+
+/// The value of a network parameter
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub enum ParameterValue {
+	U64(u64),
+	Balance(Balance),
+	Permill(Permill),
+	FixedU128(FixedU128),
+}
+
+macro_rules! impl_parameter_value_conversion {
+	($variant:ident, $type:ty) => {
+		impl From<$type> for ParameterValue {
+			fn from(value: $type) -> Self {
+				ParameterValue::$variant(value)
+			}
+		}
+
+		impl TryFrom<ParameterValue> for $type {
+			type Error = ();
+			fn try_from(value: ParameterValue) -> Result<Self, Self::Error> {
+				match value {
+					ParameterValue::$variant(value) => Ok(value),
+					_ => Err(()),
+				}
+			}
+		}
+	};
+}
+
+impl_parameter_value_conversion!(U64, u64);
+impl_parameter_value_conversion!(Balance, Balance);
+impl_parameter_value_conversion!(Permill, Permill);
+impl_parameter_value_conversion!(FixedU128, FixedU128);
+
+/// A recorded change of a network parameter
+#[derive(Debug, Clone, PartialEq, Encode, Decode, TypeInfo)]
+pub struct ParameterChange<BlockNumber> {
+	/// The block the change was made
+	pub block_number: BlockNumber,
+	/// The value before the change
+	pub previous: ParameterValue,
+	/// The value after the change
+	pub value: ParameterValue,
 }
 
 /// Votes on a proposal
@@ -153,18 +479,18 @@ impl ProposalVoteInfo {
 	/// shifts all votes after index to the left
 	pub fn remove_voter(&mut self, index: u8) {
 		// bitfields has max capacity for votes up to u8 / 255
-		// Shift all votes at index to the right
+		// Drop the vote at index and shift all votes after index to the right
 		match index {
 			0..=127 => {
 				let low_mask = (1_u128 << index) - 1;
-				let high_mask = !low_mask;
+				let high_mask = !(low_mask | (1_u128 << index));
 				self.active_bits.0 = ((self.active_bits.0 & high_mask) >> 1) | (self.active_bits.0 & low_mask);
 				self.vote_bits.0 = ((self.vote_bits.0 & high_mask) >> 1) | (self.vote_bits.0 & low_mask);
 			}
 			128..=255 => {
 				let index = index - 128;
 				let low_mask = (1_u128 << index) - 1;
-				let high_mask = !low_mask;
+				let high_mask = !(low_mask | (1_u128 << index));
 				self.active_bits.1 = ((self.active_bits.1 & high_mask) >> 1) | (self.active_bits.1 & low_mask);
 				self.vote_bits.1 = ((self.vote_bits.1 & high_mask) >> 1) | (self.vote_bits.1 & low_mask);
 			}
@@ -172,9 +498,25 @@ impl ProposalVoteInfo {
 	}
 }
 
+// A value placed in storage that represents the current version of the governance storage. This value
+// is used by the `on_runtime_upgrade` logic to determine whether we run storage migration logic.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, TypeInfo)]
+pub enum Releases {
+	/// Referendum votes counted per account
+	V0 = 0,
+	/// Referendum votes weighted by stake and keyed by stash
+	V1 = 1,
+	/// Network parameters in the parameter registry
+	V2 = 2,
+}
+
 #[cfg(test)]
 mod tests {
-	use super::{CouncilVoteCount, ProposalVoteInfo};
+	use super::{
+		Conviction, CouncilVoteCount, ParameterKey, ParameterValue, ProposalVoteInfo, ReferendumTally,
+		ReferendumVoteCount,
+	};
+	use sp_runtime::{FixedPointNumber, FixedU128, Permill};
 
 	#[test]
 	fn add_voter() {
@@ -249,6 +591,31 @@ mod tests {
 		assert_eq!(votes.active_bits().0, 0b0000_0101 as u128);
 	}
 
+	#[test]
+	fn remove_voter_drops_their_vote() {
+		let mut votes = ProposalVoteInfo::default();
+		votes.record_vote(1, true);
+		votes.record_vote(2, false);
+		votes.remove_voter(1);
+		assert_eq!(votes.get_vote(0), None);
+		assert_eq!(votes.get_vote(1), Some(false));
+		assert_eq!(votes.active_bits().0, 0b0000_0010);
+		assert_eq!(votes.vote_bits().0, 0);
+
+		let mut votes = ProposalVoteInfo::default();
+		votes.record_vote(129, true);
+		votes.record_vote(u8::MAX, true);
+		votes.remove_voter(129);
+		assert_eq!(votes.get_vote(128), None);
+		assert_eq!(votes.get_vote(254), Some(true));
+		assert_eq!(votes.active_bits().1, 1_u128 << 126);
+
+		let mut votes = ProposalVoteInfo::default();
+		votes.record_vote(127, true);
+		votes.remove_voter(127);
+		assert_eq!(votes.count_votes(), CouncilVoteCount { yes: 0, no: 0 });
+	}
+
 	#[test]
 	fn count_votes() {
 		let mut votes = ProposalVoteInfo::default();
@@ -270,4 +637,93 @@ mod tests {
 		assert_eq!(votes.get_vote(2), Some(true));
 		assert_eq!(votes.get_vote(3), None);
 	}
+
+	#[test]
+	fn conviction_votes() {
+		assert_eq!(Conviction::None.votes(100), 10);
+		assert_eq!(Conviction::Locked1x.votes(100), 100);
+		assert_eq!(Conviction::Locked6x.votes(100), 600);
+		assert_eq!(Conviction::Locked6x.votes(u128::MAX), u128::MAX);
+	}
+
+	#[test]
+	fn referendum_tally_is_vetoed() {
+		let mut tally = ReferendumTally::default();
+		// no votes cannot veto
+		assert!(!tally.is_vetoed(Permill::from_percent(33)));
+
+		tally.add(&ReferendumVoteCount {
+			vote: 0,
+			stake: 200,
+			conviction: Conviction::Locked1x,
+		});
+		tally.add(&ReferendumVoteCount {
+			vote: 1,
+			stake: 50,
+			conviction: Conviction::Locked2x,
+		});
+		assert_eq!(
+			tally,
+			ReferendumTally {
+				approve: 200,
+				veto: 100
+			}
+		);
+		assert!(tally.is_vetoed(Permill::from_percent(33)));
+		assert!(!tally.is_vetoed(Permill::from_percent(34)));
+	}
+
+	#[test]
+	fn spending_proposal_milestones() {
+		let milestone = |amount, released_at| Milestone {
+			amount,
+			deliverable_uri: b"https://example.com/deliverable".to_vec(),
+			released_at,
+		};
+		let mut spending = SpendingProposal::<u64, u64> {
+			beneficiary: 1,
+			asset_id: 16_000,
+			milestones: vec![milestone(100, None), milestone(200, None)],
+		};
+		assert_eq!(spending.total_amount(), 300);
+		assert_eq!(spending.released_amount(), 0);
+		assert_eq!(spending.next_milestone(), Some(0));
+
+		spending.milestones[0].released_at = Some(5);
+		assert_eq!(spending.released_amount(), 100);
+		assert_eq!(spending.next_milestone(), Some(1));
+
+		spending.milestones[1].released_at = Some(10);
+		assert_eq!(spending.released_amount(), 300);
+		assert_eq!(spending.next_milestone(), None);
+	}
+
+	#[test]
+	fn parameter_bounds() {
+		// default values are valid
+		for key in [
+			ParameterKey::EthBridgeEventConfirmations,
+			ParameterKey::EthBridgeEventDeadlineSeconds,
+			ParameterKey::CennzxDefaultFeeRate,
+			ParameterKey::ProposalBond,
+			ParameterKey::ReferendumThreshold,
+			ParameterKey::StakingInflationRate,
+		]
+		.iter()
+		{
+			assert!(key.is_valid(&key.default_value()));
+		}
+
+		// wrong type
+		assert!(!ParameterKey::EthBridgeEventConfirmations.is_valid(&ParameterValue::Balance(3)));
+		assert!(!ParameterKey::ProposalBond.is_valid(&ParameterValue::U64(3)));
+		// out of bounds
+		assert!(!ParameterKey::EthBridgeEventConfirmations.is_valid(&ParameterValue::U64(0)));
+		assert!(!ParameterKey::EthBridgeEventDeadlineSeconds.is_valid(&ParameterValue::U64(60)));
+		assert!(!ParameterKey::CennzxDefaultFeeRate.is_valid(&ParameterValue::Permill(Permill::from_percent(11))));
+		assert!(!ParameterKey::ReferendumThreshold.is_valid(&ParameterValue::Permill(Permill::zero())));
+		assert!(!ParameterKey::StakingInflationRate.is_valid(&ParameterValue::FixedU128(
+			FixedU128::saturating_from_rational(3u64, 2u64)
+		)));
+	}
 }
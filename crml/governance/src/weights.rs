@@ -0,0 +1,64 @@
+/* Copyright 2019-2021 Centrality Investments Limited
+*
+* Licensed under the LGPL, Version 3.0 (the "License");
+* you may not use this file except in compliance with the License.
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific language governing permissions and
+* limitations under the License.
+* You may obtain a copy of the License at the root of this project source code,
+* or at:
+*     https://centrality.ai/licenses/gplv3.txt
+*     https://centrality.ai/licenses/lgplv3.txt
+*/
+
+//! Weights for crml_governance
+//! Estimates until regenerated from `benchmarking.rs` with the substrate benchmark CLI
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::weights::{constants::RocksDbWeight as DbWeight, Weight};
+
+/// Governance module weights
+pub trait WeightInfo {
+	fn delegate() -> Weight;
+	fn undelegate() -> Weight;
+	fn note_preimage(b: u32) -> Weight;
+	fn vote_on_referendum() -> Weight;
+	fn end_referendum(v: u32, d: u32) -> Weight;
+}
+
+impl WeightInfo for () {
+	fn delegate() -> Weight {
+		(60_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(6 as Weight))
+			.saturating_add(DbWeight::get().writes(3 as Weight))
+	}
+	fn undelegate() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn note_preimage(b: u32) -> Weight {
+		(50_000_000 as Weight)
+			.saturating_add((3_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn vote_on_referendum() -> Weight {
+		(80_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(9 as Weight))
+			.saturating_add(DbWeight::get().writes(5 as Weight))
+	}
+	fn end_referendum(v: u32, d: u32) -> Weight {
+		(70_000_000 as Weight)
+			.saturating_add((6_000_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add((8_000_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().reads((3 as Weight).saturating_mul(v as Weight)))
+			.saturating_add(DbWeight::get().reads((5 as Weight).saturating_mul(d as Weight)))
+			.saturating_add(DbWeight::get().writes(6 as Weight))
+			.saturating_add(DbWeight::get().writes((2 as Weight).saturating_mul(v as Weight)))
+	}
+}
@@ -33,7 +33,7 @@
 //!
 
 use cennznet_primitives::types::{AssetId, Balance};
-use crml_support::MultiCurrency;
+use crml_support::{MultiCurrency, NonFungibleTokens};
 use frame_support::{
 	decl_error, decl_event, decl_module, decl_storage,
 	pallet_prelude::*,
@@ -44,7 +44,7 @@ use frame_support::{
 use frame_system::pallet_prelude::*;
 use sp_runtime::{
 	traits::{One, Saturating, Zero},
-	DispatchResult, PerThing, Permill,
+	DispatchError, DispatchResult, PerThing, Permill,
 };
 use sp_std::prelude::*;
 
@@ -335,23 +335,7 @@ decl_module! {
 			royalties_schedule: Option<RoyaltiesSchedule<T::AccountId>>,
 		) -> DispatchResult {
 			let origin = ensure_signed(origin)?;
-
-			ensure!(!name.is_empty() && name.len() <= MAX_COLLECTION_NAME_LENGTH as usize, Error::<T>::CollectionNameInvalid);
-			ensure!(core::str::from_utf8(&name).is_ok(), Error::<T>::CollectionNameInvalid);
-
-			let collection_id = NextCollectionId::get();
-			ensure!(collection_id.checked_add(One::one()).is_some(), Error::<T>::NoAvailableIds);
-
-			// Create the collection, update ownership, and bookkeeping
-			if let Some(royalties_schedule) = royalties_schedule {
-				ensure!(royalties_schedule.validate(), Error::<T>::RoyaltiesInvalid);
-				<CollectionRoyalties<T>>::insert(collection_id, royalties_schedule);
-			}
-			<CollectionOwner<T>>::insert(collection_id, &origin);
-			CollectionName::insert(collection_id, &name);
-			NextCollectionId::mutate(|c| *c += 1);
-
-			Self::deposit_event(RawEvent::CreateCollection(collection_id, name, origin));
+			Self::do_create_collection(&origin, name, royalties_schedule)?;
 
 			Ok(())
 		}
@@ -503,26 +487,7 @@ decl_module! {
 		#[transactional]
 		fn burn_batch(origin, collection_id: CollectionId, series_id: SeriesId, serial_numbers: Vec<SerialNumber>) {
 			let origin = ensure_signed(origin)?;
-
-			ensure!(!serial_numbers.is_empty(), Error::<T>::NoToken);
-
-			for serial_number in serial_numbers.iter() {
-				ensure!(!TokenLocks::contains_key((collection_id, series_id, serial_number)), Error::<T>::TokenListingProtection);
-				ensure!(Self::token_owner((collection_id, series_id), serial_number) == origin, Error::<T>::NoPermission);
-				<TokenOwner<T>>::remove((collection_id, series_id), serial_number);
-			}
-
-			if Self::series_issuance(collection_id, series_id).saturating_sub(serial_numbers.len() as TokenCount).is_zero() {
-				// this is the last of the tokens
-				SeriesAttributes::remove(collection_id, series_id);
-				SeriesIssuance::remove(collection_id, series_id);
-				SeriesMetadataScheme::remove(collection_id, series_id);
-				<SeriesRoyalties<T>>::remove(collection_id, series_id);
-			} else {
-				SeriesIssuance::mutate(collection_id, series_id, |q| *q = q.saturating_sub(serial_numbers.len() as TokenCount));
-			}
-
-			Self::deposit_event(RawEvent::Burn(collection_id, series_id, serial_numbers));
+			Self::do_burn(&origin, collection_id, series_id, serial_numbers)?;
 		}
 
 		/// Sell a single token at a fixed price
@@ -899,6 +864,78 @@ impl<T: Config> Module<T> {
 		};
 		Ok(royalties)
 	}
+	/// Create a collection owned by `owner`
+	fn do_create_collection(
+		owner: &T::AccountId,
+		name: CollectionNameType,
+		royalties_schedule: Option<RoyaltiesSchedule<T::AccountId>>,
+	) -> Result<CollectionId, DispatchError> {
+		ensure!(
+			!name.is_empty() && name.len() <= MAX_COLLECTION_NAME_LENGTH as usize,
+			Error::<T>::CollectionNameInvalid
+		);
+		ensure!(core::str::from_utf8(&name).is_ok(), Error::<T>::CollectionNameInvalid);
+
+		let collection_id = NextCollectionId::get();
+		ensure!(
+			collection_id.checked_add(One::one()).is_some(),
+			Error::<T>::NoAvailableIds
+		);
+
+		// Create the collection, update ownership, and bookkeeping
+		if let Some(royalties_schedule) = royalties_schedule {
+			ensure!(royalties_schedule.validate(), Error::<T>::RoyaltiesInvalid);
+			<CollectionRoyalties<T>>::insert(collection_id, royalties_schedule);
+		}
+		<CollectionOwner<T>>::insert(collection_id, owner);
+		CollectionName::insert(collection_id, &name);
+		NextCollectionId::mutate(|c| *c += 1);
+
+		Self::deposit_event(RawEvent::CreateCollection(collection_id, name, owner.clone()));
+
+		Ok(collection_id)
+	}
+	/// Burn tokens of `owner` in a series
+	/// Fails on duplicate serials
+	fn do_burn(
+		owner: &T::AccountId,
+		collection_id: CollectionId,
+		series_id: SeriesId,
+		serial_numbers: Vec<SerialNumber>,
+	) -> DispatchResult {
+		ensure!(!serial_numbers.is_empty(), Error::<T>::NoToken);
+
+		for serial_number in serial_numbers.iter() {
+			ensure!(
+				!TokenLocks::contains_key((collection_id, series_id, serial_number)),
+				Error::<T>::TokenListingProtection
+			);
+			ensure!(
+				Self::token_owner((collection_id, series_id), serial_number) == *owner,
+				Error::<T>::NoPermission
+			);
+			<TokenOwner<T>>::remove((collection_id, series_id), serial_number);
+		}
+
+		if Self::series_issuance(collection_id, series_id)
+			.saturating_sub(serial_numbers.len() as TokenCount)
+			.is_zero()
+		{
+			// this is the last of the tokens
+			SeriesAttributes::remove(collection_id, series_id);
+			SeriesIssuance::remove(collection_id, series_id);
+			SeriesMetadataScheme::remove(collection_id, series_id);
+			<SeriesRoyalties<T>>::remove(collection_id, series_id);
+		} else {
+			SeriesIssuance::mutate(collection_id, series_id, |q| {
+				*q = q.saturating_sub(serial_numbers.len() as TokenCount)
+			});
+		}
+
+		Self::deposit_event(RawEvent::Burn(collection_id, series_id, serial_numbers));
+
+		Ok(())
+	}
 	/// Transfer the given tokens from `current_owner` to `new_owner`
 	/// Does no verification
 	fn do_transfer_unchecked(tokens: &[TokenId], new_owner: &T::AccountId) {
@@ -1136,3 +1173,56 @@ impl<T: Config> Module<T> {
 		(new_cursor, response)
 	}
 }
+
+impl<T: Config> NonFungibleTokens for Module<T> {
+	type AccountId = T::AccountId;
+	type CollectionId = CollectionId;
+	type SeriesId = SeriesId;
+	type SerialNumber = SerialNumber;
+
+	fn create_collection(owner: &Self::AccountId, name: Vec<u8>) -> Result<CollectionId, DispatchError> {
+		Self::do_create_collection(owner, name, None)
+	}
+	fn create_series(collection_id: CollectionId) -> Result<SeriesId, DispatchError> {
+		ensure!(
+			<CollectionOwner<T>>::contains_key(collection_id),
+			Error::<T>::NoCollection
+		);
+		let series_id = Self::next_series_id(collection_id);
+		ensure!(series_id.checked_add(One::one()).is_some(), Error::<T>::NoAvailableIds);
+		// will not overflow, asserted prior qed.
+		NextSeriesId::mutate(collection_id, |i| *i += SeriesId::one());
+		Self::deposit_event(RawEvent::CreateSeries(
+			collection_id,
+			series_id,
+			Zero::zero(),
+			Self::collection_owner(collection_id).unwrap_or_default(),
+		));
+
+		Ok(series_id)
+	}
+	fn mint(
+		owner: &T::AccountId,
+		collection_id: CollectionId,
+		series_id: SeriesId,
+	) -> Result<SerialNumber, DispatchError> {
+		ensure!(series_id < Self::next_series_id(collection_id), Error::<T>::NoToken);
+		let serial_number = Self::next_serial_number(collection_id, series_id);
+		ensure!(
+			serial_number.checked_add(One::one()).is_some(),
+			Error::<T>::NoAvailableIds
+		);
+		Self::do_mint(owner, collection_id, series_id, serial_number, 1)?;
+		Self::deposit_event(RawEvent::CreateTokens(collection_id, series_id, 1, owner.clone()));
+
+		Ok(serial_number)
+	}
+	fn burn(
+		owner: &T::AccountId,
+		collection_id: CollectionId,
+		series_id: SeriesId,
+		serial_number: SerialNumber,
+	) -> DispatchResult {
+		Self::do_burn(owner, collection_id, series_id, vec![serial_number])
+	}
+}
@@ -2026,3 +2026,55 @@ fn get_collection_listings_cursor_too_high() {
 		assert_eq!(new_cursor, None);
 	});
 }
+
+#[test]
+fn non_fungible_tokens_interface() {
+	ExtBuilder::default().build().execute_with(|| {
+		let owner = 1_u64;
+		let collection_id = <Nft as NonFungibleTokens>::create_collection(&owner, b"bridged".to_vec()).unwrap();
+		let series_id = <Nft as NonFungibleTokens>::create_series(collection_id).unwrap();
+		assert!(has_event(RawEvent::CreateSeries(collection_id, series_id, 0, owner)));
+		assert_eq!(Nft::next_series_id(collection_id), series_id + 1);
+		assert_noop!(
+			<Nft as NonFungibleTokens>::create_series(collection_id + 1),
+			Error::<Test>::NoCollection
+		);
+
+		// tokens are minted one at a time
+		let beneficiary = 2_u64;
+		assert_eq!(
+			<Nft as NonFungibleTokens>::mint(&beneficiary, collection_id, series_id),
+			Ok(0)
+		);
+		assert_eq!(
+			<Nft as NonFungibleTokens>::mint(&beneficiary, collection_id, series_id),
+			Ok(1)
+		);
+		assert_eq!(Nft::token_owner((collection_id, series_id), 1), beneficiary);
+		assert_eq!(Nft::series_issuance(collection_id, series_id), 2);
+		assert_noop!(
+			<Nft as NonFungibleTokens>::mint(&beneficiary, collection_id, series_id + 1),
+			Error::<Test>::NoToken
+		);
+
+		// only the token owner may burn
+		assert_noop!(
+			<Nft as NonFungibleTokens>::burn(&owner, collection_id, series_id, 0),
+			Error::<Test>::NoPermission
+		);
+		assert_ok!(<Nft as NonFungibleTokens>::burn(
+			&beneficiary,
+			collection_id,
+			series_id,
+			0
+		));
+		assert!(!<TokenOwner<Test>>::contains_key((collection_id, series_id), 0));
+		assert_eq!(Nft::series_issuance(collection_id, series_id), 1);
+
+		// serial numbers are not reused
+		assert_eq!(
+			<Nft as NonFungibleTokens>::mint(&beneficiary, collection_id, series_id),
+			Ok(2)
+		);
+	});
+}
@@ -429,6 +429,10 @@ impl<T: Config> StakingAmount for Module<T> {
 	fn count_nominators() -> u32 {
 		Self::count_nominators()
 	}
+
+	fn stash(controller: &Self::AccountId) -> Option<Self::AccountId> {
+		Self::ledger(controller).map(|ledger| ledger.stash)
+	}
 }
 
 /// The ledger of a (bonded) stash.
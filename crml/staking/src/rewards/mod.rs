@@ -59,6 +59,8 @@ pub trait Config: frame_system::Config {
 	type BlockPayoutInterval: Get<Self::BlockNumber>;
 	/// The number of staking eras in a fiscal era.
 	type FiscalEraLength: Get<u32>;
+	/// Inflation rate % to apply on reward payouts, a new rate takes effect from the next fiscal era
+	type InflationRate: Get<FixedU128>;
 	/// Handles running a scheduled payout
 	type ScheduledPayoutRunner: RunScheduledPayout<AccountId = Self::AccountId, Balance = BalanceOf<Self>>;
 	/// Extrinsic weight info
@@ -84,8 +86,6 @@ decl_event!(
 
 decl_storage! {
 	trait Store for Module<T: Config> as Rewards {
-		/// Inflation rate % to apply on reward payouts
-		pub BaseInflationRate get(fn inflation_rate) config(): FixedU128 = FixedU128::saturating_from_rational(1u64, 100u64);
 		/// Development fund % take for reward payouts, parts-per-billion
 		pub DevelopmentFundTake get(fn development_fund_take) config(): Perbill;
 		/// Accumulated transaction fees for reward payout
@@ -119,17 +119,6 @@ decl_module! {
 
 		fn deposit_event() = default;
 
-		/// Set the per payout inflation rate (`numerator` / `denominator`) (it may be negative)
-		/// Please be advised that a newly set inflation rate would only affect the next fiscal year.
-		#[weight = (10_000, DispatchClass::Operational)]
-		pub fn set_inflation_rate(origin, numerator: u64, denominator: u64) {
-			ensure_root(origin)?;
-			if denominator.is_zero() {
-				return Err("denominator cannot be zero".into());
-			}
-			BaseInflationRate::put(FixedU128::saturating_from_rational(numerator, denominator));
-		}
-
 		/// Set the development fund take %, capped at 100%.
 		#[weight = (10_000, DispatchClass::Operational)]
 		pub fn set_development_fund_take(origin, new_take_percent: u32) {
@@ -508,7 +497,7 @@ impl<T: Config> Module<T> {
 	pub fn new_fiscal_era() {
 		let total_issuance: u128 = T::CurrencyToReward::total_issuance().unique_saturated_into();
 		let target_inflation =
-			<BalanceOf<T>>::unique_saturated_from(Self::inflation_rate().saturating_mul_int(total_issuance));
+			<BalanceOf<T>>::unique_saturated_from(T::InflationRate::get().saturating_mul_int(total_issuance));
 		let target_inflation_per_staking_era = target_inflation
 			.checked_div(&T::FiscalEraLength::get().into())
 			.unwrap_or_else(Zero::zero);
@@ -548,9 +537,7 @@ mod tests {
 	use super::*;
 	use crate::{rewards, IndividualExposure};
 	use crml_generic_asset::impls::TransferDustImbalance;
-	use frame_support::{
-		assert_err, assert_noop, assert_ok, parameter_types, traits::Currency, PalletId, StorageValue,
-	};
+	use frame_support::{assert_err, assert_ok, parameter_types, traits::Currency, PalletId, StorageValue};
 	use pallet_authorship::EventHandler;
 	use sp_core::H256;
 	use sp_runtime::{
@@ -631,6 +618,7 @@ mod tests {
 		pub const HistoricalPayoutEras: u16 = 7;
 		pub const BlockPayoutInterval: <Test as frame_system::Config>::BlockNumber = 3;
 		pub const FiscalEraLength: u32 = 5;
+		pub static InflationRate: FixedU128 = FixedU128::zero();
 	}
 	impl Config for Test {
 		type BlockPayoutInterval = BlockPayoutInterval;
@@ -638,6 +626,7 @@ mod tests {
 		type Event = Event;
 		type FiscalEraLength = FiscalEraLength;
 		type HistoricalPayoutEras = HistoricalPayoutEras;
+		type InflationRate = InflationRate;
 		type ScheduledPayoutRunner = MockPayoutRunner<Self>;
 		type TreasuryPalletId = TreasuryPalletId;
 		type WeightInfo = ();
@@ -676,8 +665,11 @@ mod tests {
 
 			// denominator can't be zero
 			let inflation_denominator = self.inflation_rate.1.max(One::one());
+			InflationRate::set(&FixedU128::saturating_from_rational(
+				self.inflation_rate.0,
+				inflation_denominator,
+			));
 			let _ = rewards::GenesisConfig {
-				inflation_rate: FixedU128::saturating_from_rational(self.inflation_rate.0, inflation_denominator),
 				development_fund_take: Perbill::from_percent(10),
 			}
 			.assimilate_storage(&mut storage);
@@ -806,42 +798,10 @@ mod tests {
 		});
 	}
 
-	#[test]
-	fn set_inflation_rate() {
-		// only root
-		// value is set
-		ExtBuilder::default().build().execute_with(|| {
-			assert_noop!(Rewards::set_inflation_rate(Origin::signed(1), 1, 1_000), BadOrigin);
-			assert_ok!(Rewards::set_inflation_rate(Origin::root(), 1, 1_000));
-			assert_eq!(Rewards::inflation_rate(), FixedU128::saturating_from_rational(1, 1_000))
-		});
-	}
-
-	#[test]
-	fn set_inflation_rate_bounds() {
-		ExtBuilder::default().build().execute_with(|| {
-			assert_noop!(
-				Rewards::set_inflation_rate(Origin::root(), 0, 0),
-				"denominator cannot be zero"
-			);
-			assert_ok!(Rewards::set_inflation_rate(
-				Origin::root(),
-				u64::max_value(),
-				u64::max_value()
-			));
-			assert_ok!(Rewards::set_inflation_rate(
-				Origin::root(),
-				u64::min_value(),
-				u64::max_value()
-			));
-			assert_ok!(Rewards::set_inflation_rate(Origin::root(), 1, u64::max_value()));
-		});
-	}
-
 	#[test]
 	fn emits_new_fiscal_era_event() {
 		ExtBuilder::default().build().execute_with(|| {
-			assert_ok!(Rewards::set_inflation_rate(Origin::root(), 3, 10));
+			InflationRate::set(&FixedU128::saturating_from_rational(3, 10));
 			Rewards::new_fiscal_era();
 
 			let events = System::events();
@@ -853,7 +813,7 @@ mod tests {
 	fn fiscal_era_should_naturally_take_fiscal_era_length_eras() {
 		ExtBuilder::default().build().execute_with(|| {
 			// There should be an event for a new fiscal era on era 0 (due to ext builder setup)
-			assert_ok!(Rewards::set_inflation_rate(Origin::root(), 7, 100));
+			InflationRate::set(&FixedU128::saturating_from_rational(7, 100));
 			Rewards::on_end_era(&vec![], 0, false);
 
 			let era_1_inflation_target = 14;
@@ -870,7 +830,7 @@ mod tests {
 			}));
 
 			// Request inflation rate change, it shouldn't apply until the next fiscal era
-			assert_ok!(Rewards::set_inflation_rate(Origin::root(), 11, 100));
+			InflationRate::set(&FixedU128::saturating_from_rational(11, 100));
 			assert_eq!(Rewards::target_inflation_per_staking_era(), era_1_inflation_target);
 
 			Rewards::on_end_era(&vec![], 3, false);
@@ -892,7 +852,7 @@ mod tests {
 			.build()
 			.execute_with(|| {
 				// set a new annual inflation rate
-				assert_ok!(Rewards::set_inflation_rate(Origin::root(), 7, 100));
+				InflationRate::set(&FixedU128::saturating_from_rational(7, 100));
 				// the default fiscal rate should still be in effect 1%
 				assert_eq!(Rewards::target_inflation_per_staking_era(), 2);
 
@@ -1148,7 +1108,7 @@ mod tests {
 
 			Rewards::reward_by_ids(vec![(1, 30), (2, 50), (3, 20)]);
 
-			assert_ok!(Rewards::set_inflation_rate(Origin::root(), 1, 20));
+			InflationRate::set(&FixedU128::saturating_from_rational(1, 20));
 			Rewards::new_fiscal_era();
 
 			let fee_payout = 1_000_000;
@@ -69,28 +69,77 @@ pub trait NotarizationRewardHandler {
 	fn reward_notary(notary: &Self::AccountId);
 }
 
+/// The EIP-155 chain id of an EVM network e.g. `1` for Ethereum mainnet
+pub type EthChainId = u64;
+
 /// Something that subscribes to bridge event claims
 #[impl_trait_for_tuples::impl_for_tuples(10)]
 pub trait EventClaimSubscriber {
 	/// Notify subscriber about a successful event claim for the given event data
-	fn on_success(event_claim_id: u64, contract_address: &H160, event_signature: &H256, event_data: &[u8]);
+	fn on_success(
+		event_claim_id: u64,
+		chain_id: EthChainId,
+		contract_address: &H160,
+		event_signature: &H256,
+		event_data: &[u8],
+	);
 	/// Notify subscriber about a failed event claim for the given event data
-	fn on_failure(event_claim_id: u64, contract_address: &H160, event_signature: &H256, event_data: &[u8]);
+	fn on_failure(
+		event_claim_id: u64,
+		chain_id: EthChainId,
+		contract_address: &H160,
+		event_signature: &H256,
+		event_data: &[u8],
+	);
 }
 
 /// Something that verifies event claims
 pub trait EventClaimVerifier {
-	/// Submit an event claim to the verifier
+	/// The account type of claim submitters
+	type AccountId;
+	/// Submit an event claim on EVM chain `chain_id` to the verifier on behalf of `submitter`
+	/// The verifier may charge `submitter` a deposit for the claim
 	/// Returns a unique claim Id on success
 	fn submit_event_claim(
+		submitter: &Self::AccountId,
+		chain_id: EthChainId,
 		contract_address: &H160,
 		event_signature: &H256,
 		tx_hash: &H256,
 		event_data: &[u8],
 	) -> Result<u64, DispatchError>;
-	/// Generate proof of the given message
+	/// Generate proof of the given message for EVM chain `chain_id`
 	/// Returns a unique proof Id on success
-	fn generate_event_proof<M: EthAbiCodec>(message: &M) -> Result<u64, DispatchError>;
+	fn generate_event_proof<M: EthAbiCodec>(chain_id: EthChainId, message: &M) -> Result<u64, DispatchError>;
+}
+
+/// Something that mints and burns non-fungible tokens on behalf of another module e.g. a bridge
+pub trait NonFungibleTokens {
+	/// The ID type for an account in the system
+	type AccountId;
+	/// The ID type of a token collection
+	type CollectionId;
+	/// The ID type of a series of tokens within a collection
+	type SeriesId;
+	/// The serial number of a token within a series
+	type SerialNumber;
+	/// Create a collection owned by `owner`, returning its Id
+	fn create_collection(owner: &Self::AccountId, name: Vec<u8>) -> Result<Self::CollectionId, DispatchError>;
+	/// Create an empty series in `collection_id`, returning its Id
+	fn create_series(collection_id: Self::CollectionId) -> Result<Self::SeriesId, DispatchError>;
+	/// Mint one token of `(collection_id, series_id)` to `owner`, returning its serial number
+	fn mint(
+		owner: &Self::AccountId,
+		collection_id: Self::CollectionId,
+		series_id: Self::SeriesId,
+	) -> Result<Self::SerialNumber, DispatchError>;
+	/// Burn the token `(collection_id, series_id, serial_number)` owned by `owner`
+	fn burn(
+		owner: &Self::AccountId,
+		collection_id: Self::CollectionId,
+		series_id: Self::SeriesId,
+		serial_number: Self::SerialNumber,
+	) -> DispatchResult;
 }
 
 /// Something which provides an ID with authority from chain storage
@@ -151,6 +200,9 @@ pub trait MultiCurrency {
 	/// Return the currency Id of the system staking currency
 	fn staking_currency() -> Self::CurrencyId;
 
+	/// The total amount of `currency` in existence.
+	fn total_issuance(currency: Self::CurrencyId) -> Self::Balance;
+
 	/// The combined balance (free + reserved) of `who` for the given `currency`.
 	fn total_balance(who: &Self::AccountId, currency: Self::CurrencyId) -> Self::Balance;
 
@@ -282,4 +334,6 @@ pub trait StakingAmount {
 	fn active_balance(controller: &Self::AccountId) -> Self::Balance;
 	/// Counts the current amount of nominators
 	fn count_nominators() -> u32;
+	/// Gets the stash account bonded by a controller, if any
+	fn stash(controller: &Self::AccountId) -> Option<Self::AccountId>;
 }
@@ -416,6 +416,12 @@ where
 						tag: None,
 						block: block_hash,
 					}),
+					Some(ConsensusLog::TaggedSigningRequest((message, event_id, tag))) => Some(ProofRequest {
+						message,
+						event_id,
+						tag: Some(tag),
+						block: block_hash,
+					}),
 					// Note: we also handle this in `find_authorities_change` to update the validator set
 					// here we want to convert it into an 'OpaqueSigningRequest` to create a proof of the validator set change
 					// we must do this before the validators officially change next session (~10 minutes)
@@ -113,6 +113,10 @@ pub enum ConsensusLog<AuthorityId: Encode + Decode> {
 	/// Signal an `AuthoritiesChange` is scheduled for next session
 	/// Generate a proof that the current validator set has witnessed the new authority set
 	PendingAuthoritiesChange((ValidatorSet<AuthorityId>, EventId)),
+	/// A request to sign some data was logged, the tag is set as the metadata of the proof
+	/// e.g. the chain the proof is destined for
+	#[codec(index = 5)]
+	TaggedSigningRequest((Message, EventId, Vec<u8>)),
 }
 
 /// ETHY witness message.
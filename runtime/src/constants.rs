@@ -33,8 +33,12 @@ pub mod config {
 	// for consistency expect
 	// 4 byte key for prefix and 8 byte key for subkeys
 
-	/// offchain storage config key for eth http URI
+	/// offchain storage config key for eth http URIs
+	/// URIs of other EVM chains are stored under `ETH_HTTP:<chain id>`
 	pub const ETH_HTTP_URI: [u8; 8] = *b"ETH_HTTP";
+	/// offchain storage config key for the number of eth http URIs that must agree on a response
+	/// quorums of other EVM chains are stored under `ETH_QRUM:<chain id>`
+	pub const ETH_HTTP_QUORUM: [u8; 8] = *b"ETH_QRUM";
 }
 
 /// Money matters.
@@ -16,16 +16,20 @@
 
 //! Some configurable implementations as associated type for the substrate runtime.
 
-use crate::{BlockPayoutInterval, EpochDuration, Identity, Rewards, Runtime, SessionsPerEra, Staking, Treasury};
+use crate::{
+	BlockPayoutInterval, EpochDuration, Governance, Identity, Rewards, Runtime, SessionsPerEra, Staking, Treasury,
+};
 use cennznet_primitives::types::{AccountId, Balance};
-use crml_generic_asset::{NegativeImbalance, StakingAssetCurrency};
+use crml_cennzx::{FeeRate, PerMillion};
+use crml_generic_asset::{NegativeImbalance, SpendingAssetCurrency, StakingAssetCurrency};
+use crml_governance::ParameterKey;
 use crml_staking::{rewards::RunScheduledPayout, EraIndex};
 use frame_support::{
-	traits::{Contains, ContainsLengthBound, Currency, Get, Imbalance, OnUnbalanced},
+	traits::{Contains, ContainsLengthBound, Currency, Get, Imbalance, OnUnbalanced, SortedMembers},
 	weights::{Weight, WeightToFeeCoefficient, WeightToFeeCoefficients, WeightToFeePolynomial},
 };
 use smallvec::smallvec;
-use sp_runtime::Perbill;
+use sp_runtime::{PerThing, Perbill, Permill};
 use sp_std::{marker::PhantomData, prelude::*};
 
 /// Runs scheduled payouts for the rewards module.
@@ -57,6 +61,15 @@ impl OnUnbalanced<NegativeImbalance<Runtime>> for DealWithFees {
 	}
 }
 
+/// Reads the CENNZX fee rate from the governance parameter registry
+pub struct CennzxDefaultFeeRate;
+impl Get<FeeRate<PerMillion>> for CennzxDefaultFeeRate {
+	fn get() -> FeeRate<PerMillion> {
+		let fee_rate: Permill = Governance::parameter(ParameterKey::CennzxDefaultFeeRate);
+		FeeRate::<PerMillion>::from(fee_rate.deconstruct() as u128)
+	}
+}
+
 // Move to Substrate identity module eventually
 pub struct RegistrationImplementation<T: crml_governance::Config>(sp_std::marker::PhantomData<T>);
 impl<T: crml_governance::Config> crml_support::RegistrationInfo for RegistrationImplementation<T> {
@@ -128,6 +141,11 @@ impl<T: pallet_sudo::Config> Contains<T::AccountId> for RootMemberOnly<T> {
 		t == (&pallet_sudo::Pallet::<T>::key())
 	}
 }
+impl<T: pallet_sudo::Config> SortedMembers<T::AccountId> for RootMemberOnly<T> {
+	fn sorted_members() -> Vec<T::AccountId> {
+		vec![pallet_sudo::Pallet::<T>::key()]
+	}
+}
 impl<T: pallet_sudo::Config> ContainsLengthBound for RootMemberOnly<T> {
 	fn min_len() -> usize {
 		1
@@ -146,6 +164,15 @@ impl OnUnbalanced<NegativeImbalance<Runtime>> for SlashFundsToTreasury {
 	}
 }
 
+/// An on unbalanced handler which takes an amount in the spending currency
+/// e.g. an eth bridge message fee or invalid claim deposit, and moves it to the system `Treasury` account.
+pub struct SpendingFundsToTreasury;
+impl OnUnbalanced<NegativeImbalance<Runtime>> for SpendingFundsToTreasury {
+	fn on_nonzero_unbalanced(amount: NegativeImbalance<Runtime>) {
+		SpendingAssetCurrency::resolve_creating(&Treasury::account_id(), amount);
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
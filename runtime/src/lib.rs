@@ -65,7 +65,7 @@ pub use frame_support::{
 };
 use frame_system::{
 	limits::{BlockLength, BlockWeights},
-	EnsureRoot,
+	EnsureRoot, EnsureSignedBy,
 };
 pub use pallet_timestamp::Call as TimestampCall;
 #[cfg(any(feature = "std", test))]
@@ -83,7 +83,11 @@ pub use crml_generic_asset::{
 	impls::TransferDustImbalance, AllBalances, AssetInfo, Call as GenericAssetCall, SpendingAssetCurrency,
 	StakingAssetCurrency,
 };
-use crml_governance::{ProposalId, ProposalVoteInfo};
+use crml_governance::{
+	GovernanceParameter, ProposalCallInfo, ProposalDetails, ProposalId, ProposalStatusInfo, ProposalVoteInfo,
+	SpendingProposal,
+};
+pub use crml_governance::{ParameterKey, ParameterValue};
 use crml_nft::{CollectionId, CollectionInfo, Listing, ListingId, SerialNumber, SeriesId, TokenId, TokenInfo};
 use crml_transaction_payment::{FeeDetails, RuntimeDispatchInfo};
 pub use crml_transaction_payment::{Multiplier, TargetedFeeAdjustment};
@@ -94,7 +98,7 @@ use constants::{currency::*, time::*};
 
 // Implementations of some helper traits passed into runtime modules as associated types.
 pub mod impls;
-use impls::{DealWithFees, ScheduledPayoutRunner, SlashFundsToTreasury, WeightToCpayFee};
+use impls::{DealWithFees, ScheduledPayoutRunner, SlashFundsToTreasury, SpendingFundsToTreasury, WeightToCpayFee};
 
 /// Deprecated host functions required for syncing blocks prior to 2.0 upgrade
 pub mod legacy_host_functions;
@@ -122,10 +126,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	// and set `impl_version` to equal spec_version. If only runtime
 	// implementation changes and behavior does not, then leave `spec_version` as
 	// is and increment `impl_version`.
-	spec_version: 45,
-	impl_version: 45,
+	spec_version: 48,
+	impl_version: 48,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 5,
+	transaction_version: 8,
 };
 
 /// The BABE epoch configuration at genesis.
@@ -441,17 +445,35 @@ impl pallet_sudo::Config for Runtime {
 parameter_types! {
 	/// Max. members of the council
 	pub const MaxCouncilSize: u16 = 255;
+	/// Length of a council term, a new council is elected at the end of each term
+	pub const CouncilTermLength: BlockNumber = 28 * DAYS;
+	/// Time the council has to reach a majority on a proposal before it expires
+	pub const DeliberationPeriod: BlockNumber = 14 * DAYS;
+	/// Network parameters read from the governance parameter registry
+	pub const EventConfirmationsParameter: ParameterKey = ParameterKey::EthBridgeEventConfirmations;
+	pub const EventDeadlineSecondsParameter: ParameterKey = ParameterKey::EthBridgeEventDeadlineSeconds;
+	pub const InflationRateParameter: ParameterKey = ParameterKey::StakingInflationRate;
+	/// Deposit reserved per byte of a call noted for a proposal
+	pub const DefaultPreimageByteDeposit: Balance = DOLLARS / 100;
 }
 impl crml_governance::Config for Runtime {
 	type Call = Call;
 	type Currency = SpendingAssetCurrency<Self>;
+	type StakingCurrency = StakingAssetCurrency<Self>;
 	type MaxCouncilSize = MaxCouncilSize;
+	type CouncilTermLength = CouncilTermLength;
+	type DeliberationPeriod = DeliberationPeriod;
 	type Scheduler = Scheduler;
 	type PalletsOrigin = OriginCaller;
 	type Event = Event;
 	type WeightInfo = ();
 	type Registration = impls::RegistrationImplementation<Self>;
 	type StakingAmount = Staking;
+	type MultiCurrency = GenericAsset;
+	type TreasuryPalletId = TreasuryPalletId;
+	type DefaultPreimageByteDeposit = DefaultPreimageByteDeposit;
+	/// The sudo key requests fast-tracks until the network has a technical committee
+	type FastTrackOrigin = EnsureSignedBy<impls::RootMemberOnly<Self>, AccountId>;
 }
 
 impl pallet_utility::Config for Runtime {
@@ -586,6 +608,7 @@ impl crml_staking_rewards::Config for Runtime {
 	type Event = Event;
 	type FiscalEraLength = FiscalEraLength;
 	type HistoricalPayoutEras = HistoricalPayoutEras;
+	type InflationRate = GovernanceParameter<Self, InflationRateParameter>;
 	type ScheduledPayoutRunner = ScheduledPayoutRunner<Self>;
 	type TreasuryPalletId = TreasuryPalletId;
 	type WeightInfo = ();
@@ -597,24 +620,65 @@ impl crml_cennzx::Config for Runtime {
 	type Event = Event;
 	type MultiCurrency = GenericAsset;
 	type ExchangeAddressFor = ExchangeAddressGenerator<Self>;
+	type DefaultFeeRate = impls::CennzxDefaultFeeRate;
 	type WeightInfo = ();
 }
 
 parameter_types! {
 	/// The threshold of notarizations required to approve an Eth deposit
 	pub const NotarizationThreshold: Percent = Percent::from_percent(66_u8);
+	/// The EIP-155 chain id of Ethereum mainnet
+	pub const EthereumChainId: u64 = 1;
+	/// Notaries missing votes on more than this proportion of claims in a session are reported
+	pub const MaxMissedNotarizations: Percent = Percent::from_percent(50_u8);
+	/// Notaries contradicting the result of more than this proportion of claims in a session are reported
+	pub const MaxNotaryDisagreements: Percent = Percent::from_percent(20_u8);
+	/// Deposit reserved from the submitter of an event claim, slashed if the claim is invalid
+	pub const ClaimDeposit: Balance = 10 * DOLLARS;
+	/// Max pending event claims of an account
+	pub const MaxClaimsPerAccount: u64 = 5;
+	/// Max event claims submitted per block
+	pub const MaxClaimsPerBlock: u64 = 5;
+	/// Fee charged to send a message to an EVM chain
+	pub const MessageFee: Balance = 1 * DOLLARS;
 }
 impl crml_eth_bridge::Config for Runtime {
 	/// The identifier type for an offchain worker.
 	type EthyId = EthBridgeId;
 	/// The threshold of positive notarizations to approve an event claim
 	type NotarizationThreshold = NotarizationThreshold;
+	/// The chain id of Ethereum, other EVM chains are registered by governance
+	type EthereumChainId = EthereumChainId;
+	/// Block confirmations required to notarize an Ethereum event
+	type EventConfirmations = GovernanceParameter<Self, EventConfirmationsParameter>;
+	/// Events cannot be notarized after this time (seconds)
+	type EventDeadlineSeconds = GovernanceParameter<Self, EventDeadlineSecondsParameter>;
 	/// Reports the current validator / notary set
 	type AuthoritySet = Historical;
 	/// Handle rewards for notaries
 	type RewardHandler = Rewards;
+	/// Reports notaries missing or contradicting too many claim results for slashing
+	type ReportNotaryOffence = Offences;
+	/// Notaries missing votes on more than this proportion of claims in a session are reported
+	type MaxMissedNotarizations = MaxMissedNotarizations;
+	/// Notaries contradicting the result of more than this proportion of claims in a session are reported
+	type MaxNotaryDisagreements = MaxNotaryDisagreements;
 	/// Tuple of modules subscribed to bridge events
-	type Subscribers = Erc20Peg;
+	type Subscribers = (Erc20Peg, Erc721Peg);
+	/// Claim deposits are paid in CPAY
+	type Currency = SpendingAssetCurrency<Self>;
+	/// Deposit reserved from the submitter of an event claim
+	type ClaimDeposit = ClaimDeposit;
+	/// Deposits of invalid claims are sent to the treasury
+	type OnClaimSlash = SpendingFundsToTreasury;
+	/// Max pending event claims of an account
+	type MaxClaimsPerAccount = MaxClaimsPerAccount;
+	/// Max event claims submitted per block
+	type MaxClaimsPerBlock = MaxClaimsPerBlock;
+	/// Fee charged to send a message to an EVM chain
+	type MessageFee = MessageFee;
+	/// Message fees are sent to the treasury
+	type OnMessageFee = SpendingFundsToTreasury;
 	/// The overarching dispatch call type.
 	type Call = Call;
 	/// The overarching event type.
@@ -629,9 +693,15 @@ impl crml_eth_bridge::Config for Runtime {
 // i.e. keccack256("Deposit(address,address,uint256,bytes32)")
 const DEPOSIT_EVENT_SIGNATURE: [u8; 32] =
 	hex_literal::hex!("76bb911c362d5b1feb3058bc7dc9354703e4b6eb9c61cc845f73da880cf62f61");
+// transaction must have an event/log of the contract reserves
+// i.e. keccack256("Reserves(address,uint256,uint256,uint256)")
+const RESERVES_EVENT_SIGNATURE: [u8; 32] =
+	hex_literal::hex!("2abd2f87904928f6c862edaa5aa4cc38668cf57ac6e6952f0c40445db8b7c7d5");
 parameter_types! {
 	/// The ERC20 bridge contract deposit event
 	pub const DepositEventSignature: [u8; 32] = DEPOSIT_EVENT_SIGNATURE;
+	/// The ERC20 bridge contract reserves event
+	pub const ReservesEventSignature: [u8; 32] = RESERVES_EVENT_SIGNATURE;
 	/// The ERC20 peg address
 	pub const PegPalletId: PalletId = PalletId(*b"erc20peg");
 }
@@ -639,6 +709,11 @@ impl crml_erc20_peg::Config for Runtime {
 	/// Handles Ethereum events
 	type EthBridge = EthBridge;
 	type DepositEventSignature = DepositEventSignature;
+	type ReservesEventSignature = ReservesEventSignature;
+	/// The peg contract is deployed on Ethereum
+	type ChainId = EthereumChainId;
+	/// Validators attest the peg contract's reserves
+	type AuthoritySet = Historical;
 	/// Runtime currency system
 	type MultiCurrency = GenericAsset;
 	/// PalletId/Account for this module
@@ -647,6 +722,30 @@ impl crml_erc20_peg::Config for Runtime {
 	type Event = Event;
 }
 
+// transaction must have an event/log of the ERC721 deposit
+// i.e. keccack256("Erc721Deposit(address,address,uint256,bytes32)")
+const ERC721_DEPOSIT_EVENT_SIGNATURE: [u8; 32] =
+	hex_literal::hex!("8501906af158882859846196390fc225437705be1d0bb6772989face3f1ef267");
+parameter_types! {
+	/// The ERC721 bridge contract deposit event
+	pub const Erc721DepositEventSignature: [u8; 32] = ERC721_DEPOSIT_EVENT_SIGNATURE;
+	/// The ERC721 peg address
+	pub const Erc721PegPalletId: PalletId = PalletId(*b"erc721pg");
+}
+impl crml_erc721_peg::Config for Runtime {
+	/// Handles Ethereum events
+	type EthBridge = EthBridge;
+	type DepositEventSignature = Erc721DepositEventSignature;
+	/// The peg contract is deployed on Ethereum
+	type ChainId = EthereumChainId;
+	/// Mirrored tokens are minted as NFTs
+	type Nft = Nft;
+	/// PalletId/Account for this module
+	type PegPalletId = Erc721PegPalletId;
+	/// The overarching event type.
+	type Event = Event;
+}
+
 parameter_types! {
 	/// lower priority than Staking and ImOnline txs
 	pub const EcdsaUnsignedPriority: TransactionPriority = TransactionPriority::max_value() / 3;
@@ -732,10 +831,11 @@ construct_runtime!(
 		Cennzx: crml_cennzx::{Pallet, Call, Storage, Config<T>, Event<T>} = 21,
 		Rewards: crml_staking_rewards::{Pallet, Call, Storage, Config, Event<T>} = 29,
 		Nft: crml_nft::{Pallet, Call, Storage, Event<T>} = 30,
-		Governance: crml_governance::{Pallet, Call, Storage, Event} = 31,
+		Governance: crml_governance::{Pallet, Call, Storage, Config, Event<T>} = 31,
 		EthBridge: crml_eth_bridge::{Pallet, Call, Storage, Event, ValidateUnsigned} = 32,
 		Erc20Peg: crml_erc20_peg::{Pallet, Call, Storage, Config, Event<T>} = 33,
 		EthWallet: crml_eth_wallet::{Pallet, Call, Event<T>, ValidateUnsigned} = 34,
+		Erc721Peg: crml_erc721_peg::{Pallet, Call, Storage, Event<T>} = 35,
 	}
 );
 
@@ -826,6 +926,31 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl crml_eth_bridge_rpc_runtime_api::EthBridgeApi<Block, BlockNumber, AccountId> for Runtime {
+		fn claim_status(event_claim_id: crml_eth_bridge::EventClaimId) -> Option<crml_eth_bridge::ClaimRecord<BlockNumber>> {
+			EthBridge::claim_status(event_claim_id)
+		}
+		fn claim_status_by_tx_hash(chain_id: crml_eth_bridge::EthChainId, tx_hash: crml_eth_bridge::EthHash) -> Option<crml_eth_bridge::ClaimRecord<BlockNumber>> {
+			EthBridge::claim_status_by_tx_hash(chain_id, &tx_hash)
+		}
+
+		fn message(event_proof_id: crml_eth_bridge::EventProofId) -> Option<(crml_eth_bridge::EthChainId, crml_eth_bridge::Message)> {
+			EthBridge::message(event_proof_id)
+		}
+		fn notary_performance() -> Vec<(AccountId, crml_eth_bridge::NotaryPerformance)> {
+			EthBridge::notary_performance()
+		}
+	}
+
+	impl crml_erc20_peg_rpc_runtime_api::Erc20PegApi<Block> for Runtime {
+		fn remaining_capacity(asset_id: AssetId) -> crml_erc20_peg::PegCapacity {
+			Erc20Peg::remaining_capacity(asset_id)
+		}
+		fn withdrawal_proof(batch_id: u64, index: u32) -> Option<crml_erc20_peg::WithdrawalProof> {
+			Erc20Peg::withdrawal_proof(batch_id, index)
+		}
+	}
+
 	impl crml_eth_wallet_rpc_runtime_api::EthWalletApi<Block> for Runtime {
 		fn address_nonce(eth_address: &crml_support::H160) -> u32 {
 			EthWallet::address_nonce(eth_address)
@@ -954,13 +1079,31 @@ impl_runtime_apis! {
 		}
 	}
 
-	impl crml_governance_rpc_runtime_api::GovernanceRuntimeApi<Block, AccountId> for Runtime {
+	impl crml_governance_rpc_runtime_api::GovernanceRuntimeApi<Block, AccountId, BlockNumber, Hash> for Runtime {
 		fn council() -> Vec<AccountId> {
 			Governance::get_council()
 		}
 		fn proposal_votes() -> Vec<(ProposalId, ProposalVoteInfo)> {
 			Governance::get_proposal_votes()
 		}
+		fn spending_proposals() -> Vec<(ProposalId, SpendingProposal<AccountId, BlockNumber>)> {
+			Governance::get_spending_proposals()
+		}
+		fn milestone_votes() -> Vec<(ProposalId, ProposalVoteInfo)> {
+			Governance::get_milestone_votes()
+		}
+		fn proposal_call(proposal_id: ProposalId) -> Option<ProposalCallInfo<Hash>> {
+			Governance::get_proposal_call(proposal_id)
+		}
+		fn proposal(proposal_id: ProposalId) -> Option<ProposalDetails<AccountId, BlockNumber>> {
+			Governance::get_proposal(proposal_id)
+		}
+		fn proposals(status: Option<ProposalStatusInfo>) -> Vec<ProposalDetails<AccountId, BlockNumber>> {
+			Governance::get_proposals(status)
+		}
+		fn delegations() -> Vec<(AccountId, Vec<AccountId>)> {
+			Governance::get_delegations()
+		}
 	}
 
 	impl crml_transaction_payment_rpc_runtime_api::TransactionPaymentApi<
@@ -1081,6 +1224,7 @@ impl_runtime_apis! {
 			let params = (&config, &whitelist);
 
 			add_benchmark!(params, batches, crml_cennzx, Cennzx);
+			add_benchmark!(params, batches, crml_governance, Governance);
 			add_benchmark!(params, batches, crml_nft, Nft);
 
 			if batches.is_empty() { return Err("Benchmark not found for this pallet.".into()) }
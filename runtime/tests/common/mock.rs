@@ -17,9 +17,9 @@
 
 use cennznet_cli::chain_spec::{session_keys, AuthorityKeys};
 use cennznet_primitives::types::Balance;
-use cennznet_runtime::{constants::asset::*, GenericAsset, Runtime, StakerStatus};
-use core::convert::TryFrom;
-use crml_cennzx::{FeeRate, PerMillion, PerThousand};
+use cennznet_runtime::{
+	constants::asset::*, GenericAsset, ParameterKey, ParameterValue, Permill, Runtime, StakerStatus,
+};
 use crml_support::MultiCurrency;
 use frame_support::traits::GenesisBuild;
 use sp_runtime::{FixedPointNumber, FixedU128, Perbill};
@@ -83,7 +83,6 @@ impl ExtBuilder {
 			.build_storage::<Runtime>()
 			.unwrap();
 		crml_cennzx::GenesisConfig::<Runtime> {
-			fee_rate: FeeRate::<PerMillion>::try_from(FeeRate::<PerThousand>::from(3u128)).unwrap(),
 			core_asset_id: CPAY_ASSET_ID,
 		}
 		.assimilate_storage(&mut t)
@@ -139,7 +138,21 @@ impl ExtBuilder {
 
 		crml_staking::rewards::GenesisConfig {
 			development_fund_take: Perbill::from_percent(10),
-			inflation_rate: FixedU128::saturating_from_rational(8u32, 10u32),
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
+		crml_governance::GenesisConfig {
+			parameters: vec![
+				(
+					ParameterKey::CennzxDefaultFeeRate,
+					ParameterValue::Permill(Permill::from_parts(3_000)),
+				),
+				(
+					ParameterKey::StakingInflationRate,
+					ParameterValue::FixedU128(FixedU128::saturating_from_rational(8u32, 10u32)),
+				),
+			],
 		}
 		.assimilate_storage(&mut t)
 		.unwrap();